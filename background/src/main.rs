@@ -21,13 +21,85 @@ struct QuantumParticle;
 #[derive(Component)]
 struct PointLight1;
 
+#[derive(Component)]
+struct AetherOverlay;
+
+// --- Scene knobs from the main app (launch args, then window properties) ---
+#[derive(Resource, Clone, Copy, PartialEq)]
+struct SceneConfig {
+    particle_count: u32,
+    bloom_intensity: f32,
+    rotation_speed: f32,
+    show_overlay: bool,
+}
+
+impl Default for SceneConfig {
+    fn default() -> Self {
+        Self {
+            particle_count: 800,
+            bloom_intensity: 0.15,
+            rotation_speed: 1.0,
+            show_overlay: true,
+        }
+    }
+}
+
+impl SceneConfig {
+    /// Read the knobs that follow size, position and hwnd on the command line
+    fn from_args(args: &[String]) -> Self {
+        let mut config = Self::default();
+        if let Some(count) = args.first().and_then(|a| a.parse::<u32>().ok()) {
+            config.particle_count = count.clamp(100, 2000);
+        }
+        let finite = |arg: Option<&String>| {
+            arg.and_then(|a| a.parse::<f32>().ok())
+                .filter(|value| value.is_finite())
+        };
+        if let Some(bloom) = finite(args.get(1)) {
+            config.bloom_intensity = bloom.clamp(0.0, 1.0);
+        }
+        if let Some(speed) = finite(args.get(2)) {
+            config.rotation_speed = speed.clamp(0.0, 3.0);
+        }
+        if let Some(overlay) = args.get(3) {
+            config.show_overlay = overlay != "0";
+        }
+        config
+    }
+}
+
+// The orbiting dust, kept so a new particle count can rebuild it
+#[derive(Resource)]
+struct ParticleSystem {
+    root: Entity,
+    count: u32,
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
 #[derive(Resource, Default)]
 struct TrackingState {
     hwnd: isize,
     frames: u32,
     current_rotation: u8,
+    // Our own window once found, and the monitor the parent was last seen on
+    self_hwnd: isize,
+    monitor: isize,
+    // Rect (x, y, width, height) the parent announced before resizing, and
+    // the one we are holding until GetWindowRect catches up (with its age)
+    announced: Option<(i32, i32, i32, i32)>,
+    pending: Option<((i32, i32, i32, i32), u32)>,
+    // Step into FADE_ALPHAS while fading in after showing or a jump in size
+    fade: Option<usize>,
 }
 
+// How long an announced rect is held if the parent never reaches it
+const ANNOUNCE_TIMEOUT_FRAMES: u32 = 30;
+
+// Window opacity over the first frames after showing or resizing, instead
+// of popping in at full strength
+const FADE_ALPHAS: [u8; 2] = [140, 255];
+
 fn main() {
     let args: Vec<String> = std::env::args().collect();
 
@@ -59,7 +131,16 @@ fn main() {
         }
     }
 
-    let position = if use_custom_pos {
+    let scene_config = SceneConfig::from_args(args.get(6..).unwrap_or_default());
+
+    // Parent's monitor at spawn (index into the monitor list). Creating the
+    // window there gets its DPI right; the sync system then moves it onto the
+    // parent's exact rect before it becomes visible.
+    let monitor_index = args.get(10).and_then(|a| a.parse::<usize>().ok());
+
+    let position = if let Some(index) = monitor_index {
+        bevy::window::WindowPosition::Centered(bevy::window::MonitorSelection::Index(index))
+    } else if use_custom_pos {
         bevy::window::WindowPosition::At(IVec2::new(pos_x, pos_y))
     } else {
         bevy::window::WindowPosition::Automatic
@@ -71,7 +152,13 @@ fn main() {
             hwnd: target_hwnd,
             frames: 0,
             current_rotation: 0,
+            self_hwnd: 0,
+            monitor: 0,
+            announced: None,
+            pending: None,
+            fade: None,
         })
+        .insert_resource(scene_config)
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
                 title: "Year 50,000 - Quantum Logo (Pure Rust)".into(),
@@ -87,6 +174,7 @@ fn main() {
         .add_systems(Startup, setup_scene)
         .add_systems(Update, animate_scene)
         .add_systems(Update, sync_window_process)
+        .add_systems(Update, apply_scene_config)
         .run();
 }
 
@@ -95,6 +183,7 @@ fn setup_scene(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<SceneConfig>,
 ) {
     // 1. Camera setup with Bloom (for glitch and ambient glow effect)
     commands.spawn((
@@ -107,7 +196,10 @@ fn setup_scene(
             transform: Transform::from_xyz(0.0, 0.0, 15.0).looking_at(Vec3::ZERO, Vec3::Y),
             ..default()
         },
-        BloomSettings::default(),
+        BloomSettings {
+            intensity: config.bloom_intensity,
+            ..default()
+        },
     ));
 
     // --- Logo Elements (The Quantum Core) ---
@@ -165,7 +257,6 @@ fn setup_scene(
     ));
 
     // 4. Particle System (Orbiting Quantum Dust)
-    let particles_count = 800;
     let particle_mesh = meshes.add(Sphere::new(0.05));
     let particle_material = materials.add(StandardMaterial {
         base_color: Color::rgba(0.0, 1.0, 1.0, 0.8),
@@ -174,26 +265,18 @@ fn setup_scene(
         unlit: true,
         ..default()
     });
-    commands
-        .spawn((SpatialBundle::default(), QuantumParticle))
-        .with_children(|parent| {
-            for _ in 0..particles_count {
-                let radius = 6.0 + rand::random::<f32>() * 4.0;
-                let theta = rand::random::<f32>() * 2.0 * PI;
-                let phi = (rand::random::<f32>() * 2.0 - 1.0).acos();
-
-                let x = radius * phi.sin() * theta.cos();
-                let y = radius * phi.sin() * theta.sin();
-                let z = radius * phi.cos();
-
-                parent.spawn(PbrBundle {
-                    mesh: particle_mesh.clone(),
-                    material: particle_material.clone(),
-                    transform: Transform::from_xyz(x, y, z),
-                    ..default()
-                });
-            }
-        });
+    let root = spawn_particles(
+        &mut commands,
+        &particle_mesh,
+        &particle_material,
+        config.particle_count,
+    );
+    commands.insert_resource(ParticleSystem {
+        root,
+        count: config.particle_count,
+        mesh: particle_mesh,
+        material: particle_material,
+    });
 
     // --- Lighting ---
     commands.insert_resource(AmbientLight {
@@ -229,18 +312,22 @@ fn setup_scene(
     // --- UI Overlay Elements (Equivalent to HTML absolute divs) ---
     // A E T H E R Typography
     commands
-        .spawn(NodeBundle {
-            style: Style {
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
-                flex_direction: FlexDirection::Column,
-                align_items: AlignItems::Center,
-                justify_content: JustifyContent::FlexEnd,
-                padding: UiRect::bottom(Val::Px(64.0)),
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::FlexEnd,
+                    padding: UiRect::bottom(Val::Px(64.0)),
+                    ..default()
+                },
+                visibility: overlay_visibility(config.show_overlay),
                 ..default()
             },
-            ..default()
-        })
+            AetherOverlay,
+        ))
         .with_children(|parent| {
             parent.spawn(TextBundle::from_section(
                 "A E T H E R",
@@ -267,6 +354,76 @@ fn setup_scene(
         });
 }
 
+/// Orbiting dust: `count` spheres on a shell around the core, under one
+/// root the animation rotates
+fn spawn_particles(
+    commands: &mut Commands,
+    mesh: &Handle<Mesh>,
+    material: &Handle<StandardMaterial>,
+    count: u32,
+) -> Entity {
+    commands
+        .spawn((SpatialBundle::default(), QuantumParticle))
+        .with_children(|parent| {
+            for _ in 0..count {
+                let radius = 6.0 + rand::random::<f32>() * 4.0;
+                let theta = rand::random::<f32>() * 2.0 * PI;
+                let phi = (rand::random::<f32>() * 2.0 - 1.0).acos();
+
+                let x = radius * phi.sin() * theta.cos();
+                let y = radius * phi.sin() * theta.sin();
+                let z = radius * phi.cos();
+
+                parent.spawn(PbrBundle {
+                    mesh: mesh.clone(),
+                    material: material.clone(),
+                    transform: Transform::from_xyz(x, y, z),
+                    ..default()
+                });
+            }
+        })
+        .id()
+}
+
+fn overlay_visibility(show: bool) -> Visibility {
+    if show {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+// --- Live config changes from the main app ---
+fn apply_scene_config(
+    mut commands: Commands,
+    config: Res<SceneConfig>,
+    mut particles: ResMut<ParticleSystem>,
+    mut q_bloom: Query<&mut BloomSettings>,
+    mut q_overlay: Query<&mut Visibility, With<AetherOverlay>>,
+) {
+    if !config.is_changed() {
+        return;
+    }
+    for mut bloom in q_bloom.iter_mut() {
+        bloom.intensity = config.bloom_intensity;
+    }
+    for mut visibility in q_overlay.iter_mut() {
+        *visibility = overlay_visibility(config.show_overlay);
+    }
+    // Rebuild the dust rather than restarting the process
+    if particles.count != config.particle_count {
+        commands.entity(particles.root).despawn_recursive();
+        let root = spawn_particles(
+            &mut commands,
+            &particles.mesh,
+            &particles.material,
+            config.particle_count,
+        );
+        particles.root = root;
+        particles.count = config.particle_count;
+    }
+}
+
 // --- Animation Loop (Equivalent to requestAnimationFrame(animate)) ---
 fn animate_scene(
     time: Res<Time>,
@@ -308,8 +465,10 @@ fn animate_scene(
         ),
     >,
     mut q_light: Query<&mut PointLight, With<PointLight1>>,
+    config: Res<SceneConfig>,
 ) {
     let elapsed = time.elapsed_seconds();
+    let speed = config.rotation_speed;
     let window = q_window.single();
 
     // Interaction logic
@@ -328,8 +487,8 @@ fn animate_scene(
 
     // Rotate Torus
     if let Ok(mut transform) = q_torus.get_single_mut() {
-        transform.rotate_x(0.005);
-        transform.rotate_y(0.01);
+        transform.rotate_x(0.005 * speed);
+        transform.rotate_y(0.01 * speed);
 
         // Parallax effect with mouse
         let diff_x = target_y - transform.rotation.x;
@@ -343,22 +502,23 @@ fn animate_scene(
     let scale = Vec3::splat(scale_val);
 
     if let Ok(mut transform) = q_core.get_single_mut() {
-        transform.rotate_x(-0.008);
-        transform.rotate_y(-0.008);
+        transform.rotate_x(-0.008 * speed);
+        transform.rotate_y(-0.008 * speed);
         transform.scale = scale;
     }
 
     // Rotate and Pulse Wireframe
     if let Ok(mut transform) = q_wire.get_single_mut() {
-        transform.rotate_x(-0.008);
-        transform.rotate_y(-0.008);
+        transform.rotate_x(-0.008 * speed);
+        transform.rotate_y(-0.008 * speed);
         transform.scale = scale;
     }
 
     // Rotate Particles
     if let Ok(mut transform) = q_particles.get_single_mut() {
+        let spin = elapsed * speed;
         transform.rotation =
-            Quat::from_rotation_y(elapsed * 0.05) * Quat::from_rotation_z(elapsed * 0.02);
+            Quat::from_rotation_y(spin * 0.05) * Quat::from_rotation_z(spin * 0.02);
     }
 
     // Color morphing for Light 1
@@ -373,7 +533,13 @@ fn sync_window_process(
     mut q_window: Query<&mut Window, With<PrimaryWindow>>,
     mut q_camera: Query<&mut Transform, With<Camera3d>>,
     mut tracking: ResMut<TrackingState>,
+    mut scene_config: ResMut<SceneConfig>,
+    render_device: Option<Res<bevy::render::renderer::RenderDevice>>,
 ) {
+    // The swapchain can't outgrow the GPU's texture limit, which a maximize
+    // onto a bigger monitor than the one we started on could ask for
+    let max_size = render_device.map_or(8192, |device| device.limits().max_texture_dimension_2d);
+
     if let Ok(mut window) = q_window.get_single_mut() {
         tracking.frames += 1;
 
@@ -381,13 +547,17 @@ fn sync_window_process(
         {
             if tracking.hwnd != 0 {
                 use windows::core::s;
-                use windows::Win32::Foundation::HWND;
+                use windows::Win32::Foundation::{COLORREF, HWND};
+                use windows::Win32::Graphics::Gdi::{MonitorFromWindow, MONITOR_DEFAULTTONEAREST};
                 use windows::Win32::System::Com::{
                     CoCreateInstance, CoInitialize, CLSCTX_INPROC_SERVER,
                 };
                 use windows::Win32::UI::Shell::{ITaskbarList, TaskbarList};
                 use windows::Win32::UI::WindowsAndMessaging::{
-                    FindWindowA, GetPropW, GetWindowRect, IsIconic,
+                    FindWindowA, GetPropW, GetWindow, GetWindowLongW, GetWindowRect, IsIconic,
+                    SetLayeredWindowAttributes, SetWindowLongW, SetWindowPos, GWL_EXSTYLE,
+                    GW_HWNDPREV, LWA_ALPHA, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER,
+                    WS_EX_LAYERED,
                 };
 
                 let main_hwnd = HWND(tracking.hwnd);
@@ -398,6 +568,9 @@ fn sync_window_process(
                     if is_minimized {
                         window.visible = false;
                     } else if tracking.frames >= 5 {
+                        if !window.visible {
+                            tracking.fade = Some(0);
+                        }
                         window.visible = true;
                     }
 
@@ -405,6 +578,7 @@ fn sync_window_process(
                         let std_title = s!("Year 50,000 - Quantum Logo (Pure Rust)");
                         let hwnd_self = FindWindowA(windows::core::PCSTR::null(), std_title);
                         if hwnd_self.0 != 0 {
+                            tracking.self_hwnd = hwnd_self.0;
                             // Hide from taskbar using COM ITaskbarList
                             CoInitialize(None).ok();
                             if let Ok(taskbar) = CoCreateInstance::<_, ITaskbarList>(
@@ -415,23 +589,121 @@ fn sync_window_process(
                                 let _ = taskbar.HrInit();
                                 let _ = taskbar.DeleteTab(hwnd_self);
                             }
+                            // Layered, so the fade-ins can set the window's opacity
+                            let ex_style = GetWindowLongW(hwnd_self, GWL_EXSTYLE);
+                            let _ = SetWindowLongW(
+                                hwnd_self,
+                                GWL_EXSTYLE,
+                                ex_style | WS_EX_LAYERED.0 as i32,
+                            );
                         }
                     }
 
                     if !is_minimized {
                         let mut rect = windows::Win32::Foundation::RECT::default();
                         if GetWindowRect(main_hwnd, &mut rect).is_ok() {
-                            let width = (rect.right - rect.left) as f32;
-                            let height = (rect.bottom - rect.top) as f32;
-
-                            let x = rect.left;
-                            let y = rect.top;
-
+                            let polled = (
+                                rect.left,
+                                rect.top,
+                                rect.right - rect.left,
+                                rect.bottom - rect.top,
+                            );
+
+                            // The parent announces maximize/restore and resizes before
+                            // they land (BgRectPos/BgRectSize: two i32 halves each).
+                            // A new announcement is applied this frame and held until
+                            // the parent's real rect catches up.
+                            let read_pair = |name: &str| {
+                                let mut name: Vec<u16> = name.encode_utf16().collect();
+                                name.push(0);
+                                let bits = GetPropW(main_hwnd, windows::core::PCWSTR(name.as_ptr()))
+                                    .0 as u64;
+                                (bits as u32 as i32, (bits >> 32) as u32 as i32)
+                            };
+                            let (w, h) = read_pair("BgRectSize");
+                            let announced = (w > 0 && h > 0).then(|| {
+                                let (x, y) = read_pair("BgRectPos");
+                                (x, y, w, h)
+                            });
+                            let jumped = announced.is_some() && announced != tracking.announced;
+                            if jumped {
+                                tracking.announced = announced;
+                                tracking.pending = announced.map(|rect| (rect, 0));
+                                tracking.fade = Some(0);
+                            }
+                            let (x, y, width, height) = match tracking.pending {
+                                Some((rect, age))
+                                    if rect != polled && age < ANNOUNCE_TIMEOUT_FRAMES =>
+                                {
+                                    tracking.pending = Some((rect, age + 1));
+                                    rect
+                                }
+                                _ => {
+                                    tracking.pending = None;
+                                    polled
+                                }
+                            };
+                            let width = width.clamp(1, max_size as i32);
+                            let height = height.clamp(1, max_size as i32);
+
+                            // The rect is in physical pixels; a logical size would be
+                            // off by the scale factor on a scaled monitor
                             window.position = bevy::window::WindowPosition::At(IVec2::new(x, y));
-                            window.resolution.set(width, height);
+                            window
+                                .resolution
+                                .set_physical_resolution(width as u32, height as u32);
+
+                            // Parent changed monitors (dragged, or Windows moved it off
+                            // an unplugged one), or announced a new rect. Bevy only
+                            // pushes a position that differs from what it last set and
+                            // resizes a frame late, so place the window directly too;
+                            // the render target then follows in this same frame.
+                            let monitor = MonitorFromWindow(main_hwnd, MONITOR_DEFAULTTONEAREST).0;
+                            if monitor != tracking.monitor || jumped {
+                                tracking.monitor = monitor;
+                                if tracking.self_hwnd != 0 {
+                                    let _ = SetWindowPos(
+                                        HWND(tracking.self_hwnd),
+                                        HWND(0),
+                                        x,
+                                        y,
+                                        width,
+                                        height,
+                                        SWP_NOZORDER | SWP_NOACTIVATE,
+                                    );
+                                }
+                            }
+                        }
+
+                        // Stay directly behind the parent in z-order. The parent may
+                        // be always-on-top or a normal window the user stacks under
+                        // other apps; either way we must not float above them.
+                        if tracking.self_hwnd != 0
+                            && GetWindow(HWND(tracking.self_hwnd), GW_HWNDPREV) != main_hwnd
+                        {
+                            let _ = SetWindowPos(
+                                HWND(tracking.self_hwnd),
+                                main_hwnd,
+                                0,
+                                0,
+                                0,
+                                0,
+                                SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+                            );
                         }
                     }
 
+                    // Fade in over FADE_ALPHAS after showing or a jump in size
+                    if let Some(step) = tracking.fade.filter(|_| tracking.self_hwnd != 0) {
+                        let _ = SetLayeredWindowAttributes(
+                            HWND(tracking.self_hwnd),
+                            COLORREF(0),
+                            FADE_ALPHAS[step],
+                            LWA_ALPHA,
+                        );
+                        tracking.fade = Some(step + 1).filter(|&next| next < FADE_ALPHAS.len());
+                    }
+
                     // Check for rotation state property
                     let mut property_name: Vec<u16> = "RotationState".encode_utf16().collect();
                     property_name.push(0);
@@ -446,6 +718,38 @@ fn sync_window_process(
                             cam_transform.rotation = Quat::from_rotation_z(-angle);
                         }
                     }
+
+                    // Scene knobs: value in the low 32 bits, bit 32 marks it as set.
+                    // A missing property (older main app) keeps the launch value.
+                    let read_prop = |name: &str| {
+                        let mut name: Vec<u16> = name.encode_utf16().collect();
+                        name.push(0);
+                        let handle = GetPropW(main_hwnd, windows::core::PCWSTR(name.as_ptr()));
+                        (handle.0 as u64 & (1 << 32) != 0).then_some(handle.0 as u32)
+                    };
+                    let mut live = *scene_config;
+                    if let Some(count) = read_prop("BgParticles") {
+                        live.particle_count = count.clamp(100, 2000);
+                    }
+                    // Same ranges as the command line; a NaN would also never
+                    // compare equal and rebuild the scene every frame
+                    let read_f32 = |name: &str| {
+                        read_prop(name)
+                            .map(f32::from_bits)
+                            .filter(|value| value.is_finite())
+                    };
+                    if let Some(bloom) = read_f32("BgBloom") {
+                        live.bloom_intensity = bloom.clamp(0.0, 1.0);
+                    }
+                    if let Some(speed) = read_f32("BgSpeed") {
+                        live.rotation_speed = speed.clamp(0.0, 3.0);
+                    }
+                    if let Some(overlay) = read_prop("BgOverlay") {
+                        live.show_overlay = overlay != 0;
+                    }
+                    if live != *scene_config {
+                        *scene_config = live;
+                    }
                 }
             } else if tracking.frames == 5 {
                 window.visible = true;
